@@ -9,7 +9,7 @@ use rand_chacha::ChaChaRng;
 use elastic_elgamal::{
     app::{ChoiceParams, EncryptedChoice, QuadraticVotingBallot, QuadraticVotingParams},
     group::{Curve25519Subgroup, Generic, Group, Ristretto},
-    CiphertextWithValue, Keypair, RingProofBuilder, SumOfSquaresProof,
+    Ciphertext, CiphertextWithValue, Keypair, RingProofBuilder, SumOfSquaresProof,
 };
 
 type K256 = Generic<k256::Secp256k1>;
@@ -21,6 +21,36 @@ fn bench_encrypt<G: Group>(b: &mut Bencher<'_>) {
     b.iter(|| keypair.public().encrypt(message, &mut rng));
 }
 
+fn bench_batch_encryption<G: Group>(group: &mut BenchmarkGroup<'_, WallTime>) {
+    const BATCH_SIZES: &[usize] = &[10, 100, 1_000];
+
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    let keypair: Keypair<G> = Keypair::generate(&mut rng);
+    for &size in BATCH_SIZES {
+        let elements: Vec<_> = (0..size)
+            .map(|_| G::mul_generator(&G::generate_scalar(&mut rng)))
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("encrypt_batch/naive", size),
+            &elements,
+            |b, elements| {
+                b.iter(|| {
+                    elements
+                        .iter()
+                        .map(|&element| keypair.public().encrypt_element(element, &mut rng))
+                        .collect::<Vec<_>>()
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("encrypt_batch/batched", size),
+            &elements,
+            |b, elements| b.iter(|| Ciphertext::new_batch(elements, keypair.public(), &mut rng)),
+        );
+    }
+}
+
 fn bench_decrypt<G: Group>(b: &mut Bencher<'_>) {
     let mut rng = ChaChaRng::from_seed([5; 32]);
     let keypair: Keypair<G> = Keypair::generate(&mut rng);
@@ -208,6 +238,9 @@ fn bench_group<G: Group>(group: &mut BenchmarkGroup<'_, WallTime>) {
         .bench_function("bool_verify", bench_bool_encryption_verification::<G>)
         .throughput(Throughput::Elements(1));
 
+    bench_batch_encryption::<G>(group);
+    group.throughput(Throughput::Elements(1));
+
     // Choice encryption.
     const CHOICE_SIZES: &[usize] = &[2, 3, 5, 10, 15];
 
@@ -323,6 +356,42 @@ fn bench_k256_helpers(criterion: &mut Criterion) {
     bench_helpers::<K256>(&mut criterion.benchmark_group("k256"));
 }
 
+/// Compares sequential and parallel creation of `EncryptedChoice`s depending on the number
+/// of options.
+#[cfg(feature = "rayon")]
+fn bench_parallel_choice_creation(criterion: &mut Criterion) {
+    const CHOICE_SIZES: &[usize] = &[5, 10, 25, 50, 100];
+
+    let mut group = criterion.benchmark_group("ristretto");
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+    for &choice_size in CHOICE_SIZES {
+        let params = ChoiceParams::single(pk.clone(), choice_size);
+        group.throughput(Throughput::Elements(choice_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("choice_prove_seq", choice_size),
+            &choice_size,
+            |b, &size| {
+                b.iter(|| {
+                    let choice = rng.gen_range(0..size);
+                    EncryptedChoice::single(&params, choice, &mut rng)
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("choice_prove_par", choice_size),
+            &choice_size,
+            |b, &size| {
+                b.iter(|| {
+                    let mut choices = vec![false; size];
+                    choices[rng.gen_range(0..size)] = true;
+                    EncryptedChoice::new_parallel(&params, &choices, &mut rng)
+                });
+            },
+        );
+    }
+}
+
 criterion_group!(
     benches,
     bench_curve25519_helpers,
@@ -332,4 +401,9 @@ criterion_group!(
     bench_ristretto,
     bench_k256,
 );
+#[cfg(feature = "rayon")]
+criterion_group!(parallel_benches, bench_parallel_choice_creation);
+#[cfg(feature = "rayon")]
+criterion_main!(benches, parallel_benches);
+#[cfg(not(feature = "rayon"))]
 criterion_main!(benches);
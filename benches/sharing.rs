@@ -8,6 +8,7 @@ use rand_core::SeedableRng;
 
 use elastic_elgamal::{
     group::{Curve25519Subgroup, Generic, Group, Ristretto},
+    sharing::{Dealer, Params, PublicKeySet},
     Keypair, ProofOfPossession,
 };
 
@@ -40,6 +41,34 @@ fn bench_proof_of_possession_verification<G: Group>(b: &mut Bencher<'_>, degree:
     );
 }
 
+fn bench_key_set_from_polynomial<G: Group>(b: &mut Bencher<'_>, participants: usize) {
+    let mut rng = ChaChaRng::from_seed([20; 32]);
+    let params = Params::new(participants, participants * 2 / 3 + 1);
+    let dealer = Dealer::<G>::new(params, &mut rng);
+    let (public_poly, proof) = dealer.public_info();
+
+    b.iter_batched(
+        || public_poly.clone(),
+        |public_poly| PublicKeySet::new(params, public_poly, proof).unwrap(),
+        BatchSize::SmallInput,
+    );
+}
+
+fn bench_key_set_from_participants<G: Group>(b: &mut Bencher<'_>, participants: usize) {
+    let mut rng = ChaChaRng::from_seed([20; 32]);
+    let params = Params::new(participants, participants * 2 / 3 + 1);
+    let dealer = Dealer::<G>::new(params, &mut rng);
+    let (public_poly, proof) = dealer.public_info();
+    let key_set = PublicKeySet::new(params, public_poly, proof).unwrap();
+    let participant_keys = key_set.participant_keys().to_vec();
+
+    b.iter_batched(
+        || participant_keys.clone(),
+        |participant_keys| PublicKeySet::from_participants(params, participant_keys).unwrap(),
+        BatchSize::SmallInput,
+    );
+}
+
 fn bench_group<G: Group>(group: &mut BenchmarkGroup<'_, WallTime>) {
     const PARTICIPANTS: &[usize] = &[2, 3, 5, 10, 15, 20];
 
@@ -61,6 +90,25 @@ fn bench_group<G: Group>(group: &mut BenchmarkGroup<'_, WallTime>) {
         );
     }
 
+    // Creating a key set by evaluating the public polynomial at each participant index
+    // (as done on DKG completion; here, this includes verifying the dealer's proof
+    // of possession), vs restoring it from participant keys, which requires checking
+    // their consistency via interpolation.
+    for &participants in PARTICIPANTS {
+        group.bench_with_input(
+            BenchmarkId::new("key_set/from_polynomial", participants),
+            &participants,
+            |b, &participants| bench_key_set_from_polynomial::<G>(b, participants),
+        );
+    }
+    for &participants in PARTICIPANTS {
+        group.bench_with_input(
+            BenchmarkId::new("key_set/from_participants", participants),
+            &participants,
+            |b, &participants| bench_key_set_from_participants::<G>(b, participants),
+        );
+    }
+
     // Helpers: bench different methods to compute polynomials of form
     //
     //     Q(i) = C_0 + [i]C_1 + [i^2]C_2 + ...
@@ -0,0 +1,44 @@
+//! Example demonstrating how distributed key generation detects corrupted participants.
+
+use elastic_elgamal::{
+    group::Ristretto,
+    sharing::Params,
+    testkit::{DkgFault, DkgSimulation, DkgStage},
+};
+
+fn main() {
+    let params = Params::new(5, 3);
+    let faults = [
+        DkgFault::Withhold(DkgStage::Commitments),
+        DkgFault::EquivocateCommitment,
+        DkgFault::MismatchedPolynomial,
+        DkgFault::Withhold(DkgStage::SecretShares),
+        DkgFault::InvalidSecretShares,
+    ];
+
+    for (seed, fault) in (0..).zip(faults) {
+        println!("\nCorrupted participants #1 and #3 commit fault: {fault:?}");
+        let outcome = DkgSimulation::new(params, seed)
+            .corrupt(1, fault)
+            .corrupt(3, fault)
+            .run::<Ristretto>();
+        for complaint in outcome.complaints() {
+            println!(
+                "Participant #{} complains about #{} on stage {:?}: {:?}",
+                complaint.complainant(),
+                complaint.accused(),
+                complaint.stage(),
+                complaint.reason()
+            );
+        }
+        let stage = outcome.aborted_at().expect("DKG should be aborted");
+        println!(
+            "DKG aborted on stage {stage:?}; disqualified participants: {:?}",
+            outcome.disqualified()
+        );
+    }
+
+    let outcome = DkgSimulation::new(params, 0).run::<Ristretto>();
+    let key_set = outcome.participants()[0].key_set();
+    println!("\nWithout faults, DKG completes with shared key {key_set:?}");
+}
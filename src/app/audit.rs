@@ -0,0 +1,615 @@
+//! Audit bundles containing all public artifacts of an election.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+use crate::{
+    alloc::{vec, Vec},
+    app::{
+        ChoiceParams, ChoiceVerificationError, EncryptedChoice, MixAudit, MixError, ProveSum,
+        Shuffle, TallyAudit, TallyError,
+    },
+    group::Group,
+    hash::ObjectHasher,
+    sharing::PublicKeySet,
+    Ciphertext, DiscreteLogSolver, ObjectHash,
+};
+
+/// Integrity hashes of the artifacts in an [`AuditBundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuditHashes {
+    key_set: ObjectHash,
+    ballots: Vec<ObjectHash>,
+    root: ObjectHash,
+}
+
+impl AuditHashes {
+    /// Returns the hash of the key set of talliers.
+    pub fn key_set(&self) -> ObjectHash {
+        self.key_set
+    }
+
+    /// Returns hashes of all ballots in the order they are recorded in the bundle.
+    /// Voters can use these hashes to check that their ballots were recorded.
+    pub fn ballots(&self) -> &[ObjectHash] {
+        &self.ballots
+    }
+
+    /// Returns the root hash committing to the key set, election parameters, ballots,
+    /// encrypted tally, mix-net outputs and all results.
+    pub fn root(&self) -> ObjectHash {
+        self.root
+    }
+}
+
+/// Mix-net section of an [`AuditBundle`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))
+)]
+struct MixRecord<G: Group, P> {
+    audit: MixAudit<G, P>,
+    results: Vec<u64>,
+}
+
+impl<G: Group, P> MixRecord<G, P> {
+    fn outputs(&self) -> &[Ciphertext<G>] {
+        self.audit
+            .stages()
+            .last()
+            .map_or(self.audit.inputs(), |stage| stage.outputs())
+    }
+}
+
+/// Single-file container for all public artifacts of an election: the key set of talliers,
+/// election parameters, [`EncryptedChoice`] ballots, the [`TallyAudit`] with decryption shares
+/// and results, and optionally a [`MixAudit`] with shuffle proofs and decrypted mix-net outputs.
+///
+/// The bundle records [integrity hashes](AuditHashes) of its artifacts. A third-party auditor
+/// can check the entire bundle with a single call to [`Self::verify()`], which recomputes
+/// the hashes, verifies all ballots and proofs, and checks that the encrypted tally
+/// is the sum of the ballots and decrypts to the recorded results. With the `serde` feature,
+/// the bundle can be (de)serialized as a single document.
+///
+/// `P` is the type of shuffle proofs in the mix-net section, which is defined
+/// by the [`Shuffle`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     app::*, group::{Group, Ristretto}, sharing::*, Ciphertext, DiscreteLogTable, PublicKey,
+/// #     VerificationError,
+/// # };
+/// # use rand::{thread_rng, CryptoRng, RngCore};
+/// # use std::error::Error as StdError;
+/// # #[derive(Debug)]
+/// # struct NoOpShuffle;
+/// # impl<G: Group> Shuffle<G> for NoOpShuffle {
+/// #     type Proof = ();
+/// #     fn shuffle<R: CryptoRng + RngCore>(
+/// #         &self, _: &PublicKey<G>, inputs: &[Ciphertext<G>], _: &mut R,
+/// #     ) -> (Vec<Ciphertext<G>>, ()) {
+/// #         (inputs.to_vec(), ())
+/// #     }
+/// #     fn verify(
+/// #         &self, _: &PublicKey<G>, inputs: &[Ciphertext<G>], outputs: &[Ciphertext<G>], _: &(),
+/// #     ) -> Result<(), VerificationError> {
+/// #         assert_eq!(inputs.len(), outputs.len());
+/// #         Ok(())
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..3)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let choice_params = ChoiceParams::single(key_set.shared_key().clone(), 3);
+/// let ballots: Vec<_> = [0, 2, 2]
+///     .into_iter()
+///     .map(|option| EncryptedChoice::single(&choice_params, option, &mut rng))
+///     .collect();
+/// let mut tally = Tally::new(3);
+/// for ballot in &ballots {
+///     tally.add_choice(ballot.verify(&choice_params)?);
+/// }
+/// let lookup_table = DiscreteLogTable::new(0..=3);
+/// let shard = tally.partition(1).pop().unwrap();
+/// let shares = participants[..2]
+///     .iter()
+///     .map(|participant| shard.decryption_shares(participant, &mut rng))
+///     .collect();
+/// let result = shard.decrypt(&key_set, shares, &lookup_table)?;
+/// let tally_audit = tally.merge(vec![result])?;
+///
+/// let bundle = AuditBundle::<_, _, ()>::new(key_set, 3, ballots, tally_audit);
+/// println!("root hash: {}", bundle.hashes().root());
+/// // The bundle is published and verified by an auditor.
+/// let choice_params = ChoiceParams::single(bundle.key_set().shared_key().clone(), 3);
+/// bundle.verify(&choice_params, &NoOpShuffle, &lookup_table)?;
+/// assert_eq!(bundle.tally().values(), [1, 0, 2]);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))
+)]
+pub struct AuditBundle<G: Group, S: ProveSum<G>, P> {
+    key_set: PublicKeySet<G>,
+    options_count: usize,
+    ballots: Vec<EncryptedChoice<G, S>>,
+    tally: TallyAudit<G>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    mix: Option<MixRecord<G, P>>,
+    hashes: AuditHashes,
+}
+
+impl<G, S, P> fmt::Debug for AuditBundle<G, S, P>
+where
+    G: Group + fmt::Debug,
+    S: ProveSum<G> + fmt::Debug,
+    S::Proof: fmt::Debug,
+    P: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AuditBundle")
+            .field("key_set", &self.key_set)
+            .field("options_count", &self.options_count)
+            .field("ballots", &self.ballots)
+            .field("tally", &self.tally)
+            .field("mix", &self.mix)
+            .field("hashes", &self.hashes)
+            .finish()
+    }
+}
+
+impl<G: Group, S: ProveSum<G>, P: Clone> Clone for AuditBundle<G, S, P>
+where
+    S::Proof: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key_set: self.key_set.clone(),
+            options_count: self.options_count,
+            ballots: self.ballots.clone(),
+            tally: self.tally.clone(),
+            mix: self.mix.clone(),
+            hashes: self.hashes.clone(),
+        }
+    }
+}
+
+impl<G: Group, S: ProveSum<G>, P> AuditBundle<G, S, P> {
+    /// Creates a bundle for an election with the specified number of options.
+    /// The artifacts are not verified.
+    pub fn new(
+        key_set: PublicKeySet<G>,
+        options_count: usize,
+        ballots: Vec<EncryptedChoice<G, S>>,
+        tally: TallyAudit<G>,
+    ) -> Self {
+        let mut bundle = Self {
+            key_set,
+            options_count,
+            ballots,
+            tally,
+            mix: None,
+            hashes: AuditHashes {
+                key_set: ObjectHash::from_bytes([0; 32]),
+                ballots: Vec::new(),
+                root: ObjectHash::from_bytes([0; 32]),
+            },
+        };
+        bundle.hashes = bundle.compute_hashes();
+        bundle
+    }
+
+    /// Adds a mix-net section to this bundle: the audit of
+    /// a [`MixPipeline`](crate::app::MixPipeline) and the values decrypted from its outputs.
+    #[must_use]
+    pub fn with_mix(mut self, audit: MixAudit<G, P>, results: Vec<u64>) -> Self {
+        self.mix = Some(MixRecord { audit, results });
+        self.hashes = self.compute_hashes();
+        self
+    }
+
+    /// Returns the key set of talliers.
+    pub fn key_set(&self) -> &PublicKeySet<G> {
+        &self.key_set
+    }
+
+    /// Returns the number of options in the election.
+    pub fn options_count(&self) -> usize {
+        self.options_count
+    }
+
+    /// Returns all ballots **without** checking their validity.
+    pub fn ballots(&self) -> &[EncryptedChoice<G, S>] {
+        &self.ballots
+    }
+
+    /// Returns the decrypted tally together with decryption shares.
+    pub fn tally(&self) -> &TallyAudit<G> {
+        &self.tally
+    }
+
+    /// Returns the mix-net audit and the values decrypted from the mix-net outputs, if any.
+    pub fn mix(&self) -> Option<(&MixAudit<G, P>, &[u64])> {
+        self.mix
+            .as_ref()
+            .map(|record| (&record.audit, record.results.as_slice()))
+    }
+
+    /// Returns integrity hashes recorded in this bundle.
+    pub fn hashes(&self) -> &AuditHashes {
+        &self.hashes
+    }
+
+    fn compute_hashes(&self) -> AuditHashes {
+        let key_set = self.key_set.hash();
+        let ballots: Vec<_> = self.ballots.iter().map(EncryptedChoice::hash).collect();
+
+        let mut hasher = ObjectHasher::new("audit_bundle");
+        hasher.update(key_set.as_bytes());
+        hasher.update_u64(self.options_count as u64);
+        hasher.update_u64(ballots.len() as u64);
+        for ballot in &ballots {
+            hasher.update(ballot.as_bytes());
+        }
+        let tally_totals = self
+            .tally
+            .shards()
+            .iter()
+            .flat_map(|result| result.shard().totals());
+        Self::hash_ciphertexts(&mut hasher, tally_totals);
+        Self::hash_values(&mut hasher, &self.tally.values());
+        if let Some(mix) = &self.mix {
+            Self::hash_ciphertexts(&mut hasher, mix.audit.inputs());
+            Self::hash_ciphertexts(&mut hasher, mix.outputs());
+            Self::hash_values(&mut hasher, &mix.results);
+        }
+
+        AuditHashes {
+            key_set,
+            ballots,
+            root: hasher.finalize(),
+        }
+    }
+
+    fn hash_ciphertexts<'a>(
+        hasher: &mut ObjectHasher,
+        ciphertexts: impl IntoIterator<Item = &'a Ciphertext<G>>,
+    ) {
+        let ciphertexts: Vec<_> = ciphertexts.into_iter().collect();
+        hasher.update_u64(ciphertexts.len() as u64);
+        for ciphertext in ciphertexts {
+            hasher.update(&ciphertext.to_bytes());
+        }
+    }
+
+    fn hash_values(hasher: &mut ObjectHasher, values: &[u64]) {
+        hasher.update_u64(values.len() as u64);
+        for &value in values {
+            hasher.update_u64(value);
+        }
+    }
+
+    /// Verifies this bundle. Checks that:
+    ///
+    /// - The recorded hashes match the artifacts
+    /// - The key set is consistent, and `params` match it and the number of options
+    /// - All ballots are valid
+    /// - The encrypted tally is the sum of all ballots, and all its decryption shares
+    ///   are valid and produce the recorded values
+    /// - The mix-net audit (if any) verifies, and its outputs decrypt to the recorded values.
+    ///
+    /// `solver` is used to decrypt both the tally and the mix-net outputs. `shuffle` is only used
+    /// to verify shuffle proofs in the mix-net section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the checks fails.
+    pub fn verify<Sh, D>(
+        &self,
+        params: &ChoiceParams<G, S>,
+        shuffle: &Sh,
+        solver: &D,
+    ) -> Result<(), AuditError>
+    where
+        Sh: Shuffle<G, Proof = P>,
+        D: DiscreteLogSolver<G> + ?Sized,
+    {
+        if self.compute_hashes() != self.hashes {
+            return Err(AuditError::HashMismatch);
+        }
+
+        let key_set = &self.key_set;
+        let restored_key_set =
+            PublicKeySet::from_participants(key_set.params(), key_set.participant_keys().to_vec())
+                .map_err(|_| AuditError::InconsistentKeySet)?;
+        if restored_key_set.shared_key() != key_set.shared_key() {
+            return Err(AuditError::InconsistentKeySet);
+        }
+        if params.receiver() != key_set.shared_key() || params.options_count() != self.options_count
+        {
+            return Err(AuditError::ParamsMismatch);
+        }
+
+        let mut totals = vec![Ciphertext::zero(); self.options_count];
+        for (index, ballot) in self.ballots.iter().enumerate() {
+            let choices = ballot
+                .verify(params)
+                .map_err(|error| AuditError::Ballot { index, error })?;
+            for (total, &choice) in totals.iter_mut().zip(choices) {
+                *total += choice;
+            }
+        }
+
+        let tally_totals: Vec<_> = self
+            .tally
+            .shards()
+            .iter()
+            .flat_map(|result| result.shard().totals())
+            .collect();
+        let totals_match = tally_totals.len() == totals.len()
+            && tally_totals
+                .iter()
+                .zip(&totals)
+                .all(|(recorded, total)| recorded.to_bytes() == total.to_bytes());
+        if !totals_match {
+            return Err(AuditError::TallyMismatch);
+        }
+        self.tally
+            .verify(key_set, solver)
+            .map_err(AuditError::Tally)?;
+
+        if let Some(mix) = &self.mix {
+            let decryptions = mix
+                .audit
+                .verify(key_set, shuffle)
+                .map_err(AuditError::Mix)?;
+            let results = decryptions
+                .iter()
+                .zip(mix.outputs())
+                .map(|(decryption, &output)| decryption.decrypt(output, solver))
+                .collect::<Option<Vec<_>>>();
+            if results.as_ref() != Some(&mix.results) {
+                return Err(AuditError::MixResultsMismatch);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when verifying an [`AuditBundle`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AuditError {
+    /// Integrity hashes recorded in the bundle do not match its artifacts.
+    HashMismatch,
+    /// Participant keys in the key set do not correspond to its shared key.
+    InconsistentKeySet,
+    /// Choice parameters do not match the key set or the number of options in the bundle.
+    ParamsMismatch,
+    /// A ballot is invalid.
+    Ballot {
+        /// 0-based index of the ballot in the bundle.
+        index: usize,
+        /// Underlying verification error.
+        error: ChoiceVerificationError,
+    },
+    /// The encrypted tally is not the sum of the ballots.
+    TallyMismatch,
+    /// The decrypted tally is invalid.
+    Tally(TallyError),
+    /// The mix-net audit is invalid.
+    Mix(MixError),
+    /// Mix-net outputs do not decrypt to the recorded values.
+    MixResultsMismatch,
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashMismatch => formatter.write_str("integrity hashes do not match artifacts"),
+            Self::InconsistentKeySet => {
+                formatter.write_str("participant keys do not correspond to the shared key")
+            }
+            Self::ParamsMismatch => {
+                formatter.write_str("choice parameters do not match the audit bundle")
+            }
+            Self::Ballot { index, error } => {
+                write!(formatter, "ballot #{index} is invalid: {error}")
+            }
+            Self::TallyMismatch => formatter.write_str("encrypted tally is not the sum of ballots"),
+            Self::Tally(err) => write!(formatter, "tally is invalid: {err}"),
+            Self::Mix(err) => write!(formatter, "mix-net audit is invalid: {err}"),
+            Self::MixResultsMismatch => {
+                formatter.write_str("mix-net outputs do not decrypt to the recorded values")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuditError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Ballot { error, .. } => Some(error),
+            Self::Tally(err) => Some(err),
+            Self::Mix(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, CryptoRng, RngCore};
+
+    use super::*;
+    use crate::{
+        app::{MixDecryptionShares, MixPipeline, SingleChoice, Tally},
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, Params},
+        DiscreteLogTable, PublicKey, VerificationError,
+    };
+
+    /// Placeholder for pipelines only containing re-randomization stages.
+    struct NoShuffle;
+
+    impl Shuffle<Ristretto> for NoShuffle {
+        type Proof = ();
+
+        fn shuffle<R: CryptoRng + RngCore>(
+            &self,
+            _receiver: &PublicKey<Ristretto>,
+            _inputs: &[Ciphertext<Ristretto>],
+            _rng: &mut R,
+        ) -> (Vec<Ciphertext<Ristretto>>, Self::Proof) {
+            unreachable!()
+        }
+
+        fn verify(
+            &self,
+            _receiver: &PublicKey<Ristretto>,
+            _inputs: &[Ciphertext<Ristretto>],
+            _outputs: &[Ciphertext<Ristretto>],
+            _proof: &Self::Proof,
+        ) -> Result<(), VerificationError> {
+            unreachable!()
+        }
+    }
+
+    type Bundle = AuditBundle<Ristretto, SingleChoice, ()>;
+
+    #[test]
+    fn verifying_audit_bundle() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..3)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let choice_params = ChoiceParams::single(key_set.shared_key().clone(), 4);
+        let ballots: Vec<_> = [3, 1, 3, 0, 3]
+            .into_iter()
+            .map(|option| EncryptedChoice::single(&choice_params, option, &mut rng))
+            .collect();
+        let mut tally = Tally::new(4);
+        for ballot in &ballots {
+            tally.add_choice(ballot.verify(&choice_params).unwrap());
+        }
+        let results = tally.partition(2).into_iter().map(|shard| {
+            let shares = participants[1..]
+                .iter()
+                .map(|participant| shard.decryption_shares(participant, &mut rng))
+                .collect();
+            shard.decrypt(&key_set, shares, &DiscreteLogTable::new(0..=5))
+        });
+        let results = results.collect::<Result<Vec<_>, _>>().unwrap();
+        let tally_audit = tally.merge(results).unwrap();
+
+        let mix_inputs: Vec<_> = (0_u64..3)
+            .map(|value| key_set.shared_key().encrypt(value, &mut rng))
+            .collect();
+        let mut pipeline = MixPipeline::new(&key_set, NoShuffle, mix_inputs);
+        pipeline.rerandomize(&mut rng).unwrap();
+        for participant in &participants[..2] {
+            let shares = MixDecryptionShares::new(participant, pipeline.outputs(), &mut rng);
+            pipeline.insert_decryption_shares(shares).unwrap();
+        }
+        let (_, mix_audit) = pipeline.finish().unwrap();
+
+        let bundle = Bundle::new(key_set.clone(), 4, ballots, tally_audit)
+            .with_mix(mix_audit, vec![0, 1, 2]);
+        assert_eq!(bundle.hashes().ballots().len(), 5);
+        let lookup_table = DiscreteLogTable::new(0..=5);
+        bundle
+            .verify(&choice_params, &NoShuffle, &lookup_table)
+            .unwrap();
+        assert_eq!(bundle.tally().values(), [1, 1, 0, 3]);
+
+        let other_params = ChoiceParams::single(key_set.shared_key().clone(), 5);
+        let err = bundle
+            .verify(&other_params, &NoShuffle, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, AuditError::ParamsMismatch), "{err:?}");
+
+        let mut bogus_bundle = bundle.clone();
+        bogus_bundle.ballots.pop();
+        let err = bogus_bundle
+            .verify(&choice_params, &NoShuffle, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, AuditError::HashMismatch), "{err:?}");
+        bogus_bundle.hashes = bogus_bundle.compute_hashes();
+        let err = bogus_bundle
+            .verify(&choice_params, &NoShuffle, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, AuditError::TallyMismatch), "{err:?}");
+
+        let mut bogus_bundle = bundle.clone();
+        bogus_bundle.mix.as_mut().unwrap().results.swap(0, 1);
+        bogus_bundle.hashes = bogus_bundle.compute_hashes();
+        let err = bogus_bundle
+            .verify(&choice_params, &NoShuffle, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, AuditError::MixResultsMismatch), "{err:?}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn audit_bundle_serialization() {
+        let mut rng = thread_rng();
+        let params = Params::new(1, 1);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))
+                .unwrap();
+
+        let choice_params = ChoiceParams::single(key_set.shared_key().clone(), 2);
+        let ballots = vec![EncryptedChoice::single(&choice_params, 1, &mut rng)];
+        let mut tally = Tally::new(2);
+        tally.add_choice(ballots[0].verify(&choice_params).unwrap());
+        let shard = tally.partition(1).pop().unwrap();
+        let shares = vec![shard.decryption_shares(&participant, &mut rng)];
+        let lookup_table = DiscreteLogTable::new(0..=1);
+        let result = shard.decrypt(&key_set, shares, &lookup_table).unwrap();
+        let bundle = Bundle::new(key_set, 2, ballots, tally.merge(vec![result]).unwrap());
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let restored: Bundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.hashes(), bundle.hashes());
+        restored
+            .verify(&choice_params, &NoShuffle, &lookup_table)
+            .unwrap();
+    }
+}
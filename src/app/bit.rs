@@ -0,0 +1,236 @@
+//! Provable encryption of single bits.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::iter;
+
+use crate::{
+    group::Group, proto_labels, Ciphertext, CiphertextWithValue, PublicKey, RingProof,
+    SumOfSquaresProof, VerificationError,
+};
+
+/// Encrypted bit (a Boolean value) together with a zero-knowledge proof that the ciphertext
+/// indeed encrypts 0 or 1.
+///
+/// This is a thin wrapper around [`PublicKey::encrypt_bool()`] and [`PublicKey::verify_bool()`].
+/// It is preferable to using an [`EncryptedChoice`](crate::app::EncryptedChoice) with 2 options,
+/// which produces larger proofs.
+///
+/// Besides encryption, `EncryptedBit` provides homomorphic operations on bits:
+///
+/// - XOR with a public bit via [`Self::xor_public()`]; no proofs are required in this case.
+/// - XOR of two encrypted bits via [`Self::prove_xor()`] / [`Self::verify_xor()`].
+///   This operation relies on the constraint that both operands are bits: for `a, b ∈ {0, 1}`,
+///   `a XOR b = (a - b)^2`, which can be proven via a [`SumOfSquaresProof`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::EncryptedBit, group::Ristretto, DiscreteLogTable, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let pk = receiver.public();
+///
+/// let (lhs, lhs_opening) = EncryptedBit::new_with_opening(true, pk, &mut rng);
+/// let (rhs, rhs_opening) = EncryptedBit::new_with_opening(true, pk, &mut rng);
+/// let (xor, xor_proof) = EncryptedBit::prove_xor(&lhs_opening, &rhs_opening, pk, &mut rng);
+///
+/// // Verify the bits and their XOR.
+/// let xor = EncryptedBit::verify_xor(&lhs, &rhs, xor, &xor_proof, pk)?;
+/// let lookup_table = DiscreteLogTable::new(0..2);
+/// assert_eq!(receiver.secret().decrypt(xor, &lookup_table), Some(0));
+/// // XOR with a public bit does not require proofs.
+/// let negated = lhs.xor_public(true);
+/// assert_eq!(receiver.secret().decrypt(negated, &lookup_table), Some(0));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct EncryptedBit<G: Group> {
+    ciphertext: Ciphertext<G>,
+    proof: RingProof<G>,
+}
+
+impl<G: Group> EncryptedBit<G> {
+    /// Encrypts `value` for the `receiver`.
+    pub fn new<R: CryptoRng + RngCore>(value: bool, receiver: &PublicKey<G>, rng: &mut R) -> Self {
+        Self::new_with_opening(value, receiver, rng).0
+    }
+
+    /// Encrypts `value` for the `receiver`, additionally returning the encrypted value together
+    /// with the randomness used for encryption. The latter can be used
+    /// to [prove XOR](Self::prove_xor()) with other bits; it must be kept secret.
+    pub fn new_with_opening<R: CryptoRng + RngCore>(
+        value: bool,
+        receiver: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (Self, CiphertextWithValue<G>) {
+        let (ciphertext, proof) = receiver.encrypt_bool_extended(value, rng);
+        let ciphertext_with_value = ciphertext.with_value(G::Scalar::from(u64::from(value)));
+        let bit = Self {
+            ciphertext: *ciphertext_with_value.inner(),
+            proof,
+        };
+        (bit, ciphertext_with_value)
+    }
+
+    /// Verifies this bit encryption for the specified `receiver`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not verify.
+    pub fn verify(&self, receiver: &PublicKey<G>) -> Result<&Ciphertext<G>, VerificationError> {
+        receiver
+            .verify_bool(self.ciphertext, &self.proof)
+            .map(|()| &self.ciphertext)
+    }
+
+    /// Splits this bit into the ciphertext and the detached proof that it encrypts a bit.
+    /// The proof can be verified with [`PublicKey::verify_bool()`].
+    pub fn into_parts(self) -> (Ciphertext<G>, RingProof<G>) {
+        (self.ciphertext, self.proof)
+    }
+
+    /// Restores a bit from the parts produced by [`Self::into_parts()`].
+    /// The proof is re-verified against the ciphertext.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not verify.
+    pub fn from_parts(
+        receiver: &PublicKey<G>,
+        ciphertext: Ciphertext<G>,
+        proof: RingProof<G>,
+    ) -> Result<Self, VerificationError> {
+        receiver.verify_bool(ciphertext, &proof)?;
+        Ok(Self { ciphertext, proof })
+    }
+
+    /// Returns the ciphertext **without** checking its validity.
+    pub fn ciphertext_unchecked(&self) -> &Ciphertext<G> {
+        &self.ciphertext
+    }
+
+    /// Returns the proof that the ciphertext encrypts a bit.
+    pub fn proof(&self) -> &RingProof<G> {
+        &self.proof
+    }
+
+    /// Computes XOR of this bit with the public `bit`. The output is a valid bit encryption
+    /// as long as this encryption is valid; thus, it does not require additional proofs.
+    pub fn xor_public(&self, bit: bool) -> Ciphertext<G> {
+        if bit {
+            Ciphertext::non_blinded(1_u64) - self.ciphertext
+        } else {
+            self.ciphertext
+        }
+    }
+
+    /// Encrypts XOR of two bits and proves its correctness. The bits must be specified
+    /// using openings returned by [`Self::new_with_opening()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` or `rhs` do not encrypt a bit (in this case, the proof would not verify
+    /// anyway).
+    pub fn prove_xor<R: CryptoRng + RngCore>(
+        lhs: &CiphertextWithValue<G>,
+        rhs: &CiphertextWithValue<G>,
+        receiver: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, SumOfSquaresProof<G>) {
+        let zero = G::Scalar::from(0_u64);
+        let one = G::Scalar::from(1_u64);
+        let (lhs_value, rhs_value) = (*lhs.value(), *rhs.value());
+        assert!(
+            (lhs_value == zero || lhs_value == one) && (rhs_value == zero || rhs_value == one),
+            "XOR operands do not encrypt bits"
+        );
+
+        let diff_value = lhs_value - rhs_value;
+        let diff = (lhs.extended_ciphertext().clone() - rhs.extended_ciphertext().clone())
+            .with_value(diff_value);
+        let xor_value = diff_value * diff_value;
+        let xor = CiphertextWithValue::new(xor_value, receiver, rng);
+
+        let proof = SumOfSquaresProof::new(
+            iter::once(&diff),
+            &xor,
+            receiver,
+            &mut Transcript::new(proto_labels::BIT_XOR),
+            rng,
+        );
+        (*xor.inner(), proof)
+    }
+
+    /// Verifies a XOR of two bits produced by [`Self::prove_xor()`]. Both bits are verified
+    /// as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the proofs do not verify.
+    pub fn verify_xor(
+        lhs: &Self,
+        rhs: &Self,
+        xor: Ciphertext<G>,
+        proof: &SumOfSquaresProof<G>,
+        receiver: &PublicKey<G>,
+    ) -> Result<Ciphertext<G>, VerificationError> {
+        let diff = *lhs.verify(receiver)? - *rhs.verify(receiver)?;
+        proof.verify(
+            iter::once(&diff),
+            &xor,
+            receiver,
+            &mut Transcript::new(proto_labels::BIT_XOR),
+        )?;
+        Ok(xor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{group::Ristretto, DiscreteLogTable, Keypair};
+
+    #[test]
+    fn xor_of_encrypted_bits() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let pk = receiver.public();
+        let lookup_table = DiscreteLogTable::new(0..2);
+
+        for (lhs_value, rhs_value) in [(false, false), (false, true), (true, false), (true, true)] {
+            let (lhs, lhs_opening) = EncryptedBit::new_with_opening(lhs_value, pk, &mut rng);
+            let (rhs, rhs_opening) = EncryptedBit::new_with_opening(rhs_value, pk, &mut rng);
+            let (xor, proof) = EncryptedBit::prove_xor(&lhs_opening, &rhs_opening, pk, &mut rng);
+            let xor = EncryptedBit::verify_xor(&lhs, &rhs, xor, &proof, pk).unwrap();
+
+            let expected = u64::from(lhs_value ^ rhs_value);
+            assert_eq!(
+                receiver.secret().decrypt(xor, &lookup_table),
+                Some(expected)
+            );
+            assert!(EncryptedBit::verify_xor(&rhs, &lhs, xor, &proof, pk).is_err());
+
+            let (ciphertext, bit_proof) = lhs.clone().into_parts();
+            EncryptedBit::from_parts(pk, ciphertext, bit_proof.clone()).unwrap();
+            assert!(EncryptedBit::from_parts(pk, rhs.clone().into_parts().0, bit_proof).is_err());
+
+            let negated = lhs.xor_public(true);
+            let expected = u64::from(!lhs_value);
+            assert_eq!(
+                receiver.secret().decrypt(negated, &lookup_table),
+                Some(expected)
+            );
+        }
+    }
+}
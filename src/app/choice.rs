@@ -10,9 +10,10 @@ use core::{fmt, iter, ops};
 
 use crate::{
     alloc::{vec, Vec},
+    encryption::ExtendedCiphertext,
     group::Group,
-    Ciphertext, CiphertextWithValue, LogEqualityProof, PublicKey, RingProof, RingProofBuilder,
-    VerificationError,
+    proto_labels, Ciphertext, CiphertextWithValue, LogEqualityProof, PublicKey, RingProof,
+    RingProofBuilder, SecretKey, VerificationError,
 };
 
 /// Encapsulation of functionality for proving and verifying correctness of the sum of option
@@ -32,6 +33,7 @@ pub trait ProveSum<G: Group>: Clone + crate::sealed::Sealed {
         &self,
         ciphertext: &CiphertextWithValue<G, u64>,
         receiver: &PublicKey<G>,
+        version: u32,
         rng: &mut R,
     ) -> Self::Proof;
 
@@ -41,7 +43,11 @@ pub trait ProveSum<G: Group>: Clone + crate::sealed::Sealed {
         ciphertext: &Ciphertext<G>,
         proof: &Self::Proof,
         receiver: &PublicKey<G>,
+        version: u32,
     ) -> Result<(), ChoiceVerificationError>;
+
+    #[doc(hidden)]
+    fn proof_bytes(proof: &Self::Proof) -> Vec<u8>;
 }
 
 /// Single-choice setup for [`EncryptedChoice`], in which it can contain a single selected option.
@@ -61,6 +67,7 @@ impl<G: Group> ProveSum<G> for SingleChoice {
         &self,
         ciphertext: &CiphertextWithValue<G, u64>,
         receiver: &PublicKey<G>,
+        version: u32,
         rng: &mut R,
     ) -> Self::Proof {
         LogEqualityProof::new(
@@ -70,7 +77,7 @@ impl<G: Group> ProveSum<G> for SingleChoice {
                 ciphertext.inner().random_element,
                 ciphertext.inner().blinded_element - G::generator(),
             ),
-            &mut Transcript::new(b"choice_encryption_sum"),
+            &mut versioned_transcript(proto_labels::CHOICE_SUM, version),
             rng,
         )
     }
@@ -80,6 +87,7 @@ impl<G: Group> ProveSum<G> for SingleChoice {
         ciphertext: &Ciphertext<G>,
         proof: &Self::Proof,
         receiver: &PublicKey<G>,
+        version: u32,
     ) -> Result<(), ChoiceVerificationError> {
         let powers = (
             ciphertext.random_element,
@@ -89,10 +97,14 @@ impl<G: Group> ProveSum<G> for SingleChoice {
             .verify(
                 receiver,
                 powers,
-                &mut Transcript::new(b"choice_encryption_sum"),
+                &mut versioned_transcript(proto_labels::CHOICE_SUM, version),
             )
             .map_err(ChoiceVerificationError::Sum)
     }
+
+    fn proof_bytes(proof: &Self::Proof) -> Vec<u8> {
+        proof.to_bytes()
+    }
 }
 
 /// Multi-choice setup for [`EncryptedChoice`], in which it can contain any possible number
@@ -113,6 +125,7 @@ impl<G: Group> ProveSum<G> for MultiChoice {
         &self,
         _ciphertext: &CiphertextWithValue<G, u64>,
         _receiver: &PublicKey<G>,
+        _version: u32,
         _rng: &mut R,
     ) -> Self::Proof {
         // Do nothing.
@@ -123,9 +136,101 @@ impl<G: Group> ProveSum<G> for MultiChoice {
         _ciphertext: &Ciphertext<G>,
         _proof: &Self::Proof,
         _receiver: &PublicKey<G>,
+        _version: u32,
     ) -> Result<(), ChoiceVerificationError> {
         Ok(()) // no failure conditions
     }
+
+    fn proof_bytes((): &Self::Proof) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// Setup for [`EncryptedChoice`] in which exactly `m` options must be selected
+/// (e.g., "rank exactly 3 candidates"). [`SingleChoice`] is a special case of this setup
+/// with `m = 1`.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::{ChoiceParams, EncryptedChoice}, group::Ristretto, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+/// let choice_params = ChoiceParams::exact(pk, 5, 3);
+///
+/// let choices = [true, false, true, true, false];
+/// let enc = EncryptedChoice::new(&choice_params, &choices, &mut rng);
+/// assert_eq!(enc.verify(&choice_params)?.len(), 5);
+///
+/// // A choice with another number of selected options does not verify.
+/// let choices = [true, false, true, false, false];
+/// let enc = EncryptedChoice::new(&choice_params, &choices, &mut rng);
+/// assert!(enc.verify(&choice_params).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ExactChoice {
+    selected_count: u64,
+}
+
+impl ExactChoice {
+    /// Returns the number of options that must be selected.
+    pub fn selected_count(self) -> u64 {
+        self.selected_count
+    }
+
+    fn transcript(self, version: u32) -> Transcript {
+        let mut transcript = versioned_transcript(proto_labels::CHOICE_EXACT_SUM, version);
+        transcript.append_u64(b"m", self.selected_count);
+        transcript
+    }
+}
+
+impl crate::sealed::Sealed for ExactChoice {}
+
+impl<G: Group> ProveSum<G> for ExactChoice {
+    type Proof = LogEqualityProof<G>;
+
+    fn prove<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: &CiphertextWithValue<G, u64>,
+        receiver: &PublicKey<G>,
+        version: u32,
+        rng: &mut R,
+    ) -> Self::Proof {
+        let sum = G::vartime_mul_generator(&self.selected_count.into());
+        LogEqualityProof::new(
+            receiver,
+            ciphertext.randomness(),
+            (
+                ciphertext.inner().random_element,
+                ciphertext.inner().blinded_element - sum,
+            ),
+            &mut self.transcript(version),
+            rng,
+        )
+    }
+
+    fn verify(
+        &self,
+        ciphertext: &Ciphertext<G>,
+        proof: &Self::Proof,
+        receiver: &PublicKey<G>,
+        version: u32,
+    ) -> Result<(), ChoiceVerificationError> {
+        let sum = G::vartime_mul_generator(&self.selected_count.into());
+        let powers = (ciphertext.random_element, ciphertext.blinded_element - sum);
+        proof
+            .verify(receiver, powers, &mut self.transcript(version))
+            .map_err(ChoiceVerificationError::Sum)
+    }
+
+    fn proof_bytes(proof: &Self::Proof) -> Vec<u8> {
+        proof.to_bytes()
+    }
 }
 
 /// Parameters of an [`EncryptedChoice`] polling.
@@ -146,8 +251,29 @@ impl<G: Group, S: ProveSum<G>> Clone for ChoiceParams<G, S> {
     }
 }
 
+/// Checks that choice ciphertexts are not degenerate. Ciphertexts with the identity
+/// random element are not blinded; while they can be accompanied by valid range proofs,
+/// they leak the encrypted value and are never produced by honest provers.
+pub(super) fn check_ciphertexts<G: Group>(
+    ciphertexts: &[Ciphertext<G>],
+    offset: usize,
+) -> Result<(), ChoiceVerificationError> {
+    let malformed_idx = ciphertexts
+        .iter()
+        .position(|ciphertext| G::is_identity(ciphertext.random_element()));
+    match malformed_idx {
+        Some(idx) => Err(ChoiceVerificationError::MalformedCiphertext {
+            option: offset + idx,
+        }),
+        None => Ok(()),
+    }
+}
+
 impl<G: Group, S: ProveSum<G>> ChoiceParams<G, S> {
-    fn check_options_count(&self, actual_count: usize) -> Result<(), ChoiceVerificationError> {
+    pub(super) fn check_options_count(
+        &self,
+        actual_count: usize,
+    ) -> Result<(), ChoiceVerificationError> {
         if self.options_count == actual_count {
             Ok(())
         } else {
@@ -158,6 +284,10 @@ impl<G: Group, S: ProveSum<G>> ChoiceParams<G, S> {
         }
     }
 
+    pub(super) fn sum_prover(&self) -> &S {
+        &self.sum_prover
+    }
+
     /// Returns the public key for which the [`EncryptedChoice`] are encrypted.
     pub fn receiver(&self) -> &PublicKey<G> {
         &self.receiver
@@ -201,6 +331,29 @@ impl<G: Group> ChoiceParams<G, MultiChoice> {
     }
 }
 
+impl<G: Group> ChoiceParams<G, ExactChoice> {
+    /// Creates parameters for a polling in which exactly `selected_count` options
+    /// must be selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if provided `options_count` is zero, or if `selected_count` exceeds `options_count`.
+    pub fn exact(receiver: PublicKey<G>, options_count: usize, selected_count: usize) -> Self {
+        assert!(options_count > 0, "Number of options must be positive");
+        assert!(
+            selected_count <= options_count,
+            "Number of selected options must not exceed the number of options"
+        );
+        Self {
+            options_count,
+            sum_prover: ExactChoice {
+                selected_count: selected_count as u64,
+            },
+            receiver,
+        }
+    }
+}
+
 /// Zero or more encrypted choices from `n` options (`n >= 1`) together with zero-knowledge
 /// proofs of correctness.
 ///
@@ -216,8 +369,28 @@ impl<G: Group> ChoiceParams<G, MultiChoice> {
 ///   This proof can be obtained via [`Self::range_proof()`].
 /// - A [`LogEqualityProof`] attesting that the encrypted values sum up to 1. Combined with
 ///   the range proof, this means that exactly one of encrypted values is 1, and all others are 0.
-///   This proof can be obtained via [`Self::sum_proof()`]. This proof is absent for
-///   a [`MultiChoice`] setup (`sum_proof()` just returns `()`).
+///   This proof can be obtained via [`Self::sum_proof()`]. For an [`ExactChoice`] setup,
+///   the proof attests that the values sum up to the required number of selected options `m`,
+///   so that exactly `m` values are 1. This proof is absent for a [`MultiChoice`] setup
+///   (`sum_proof()` just returns `()`).
+///
+/// Each choice ciphertext uses independent randomness. Sharing a single random element
+/// among choice ciphertexts (as in multi-recipient ElGamal) would almost halve their size,
+/// but is insecure here since all ciphertexts are encrypted for the same receiver: the difference
+/// of blinded elements `B_i - B_j = [m_i - m_j]G` would reveal whether options `i` and `j`
+/// are both selected / unselected, and thus the choice itself for a [`SingleChoice`] setup.
+///
+/// # Versioning
+///
+/// A choice records the [version](Self::version()) of the proof system used to create it,
+/// which is embedded into the serialized choice. New choices are always created with
+/// the latest [`Self::VERSION`], while [verification](Self::verify()) supports all versions
+/// starting from [`Self::MIN_SUPPORTED_VERSION`]. This allows accepting choices
+/// produced by older clients while the proof system is being upgraded. Choices serialized
+/// without a version are treated as having version 1.
+///
+/// Starting from version 2, the version is committed to in the range and sum proofs,
+/// so it cannot be changed without invalidating the choice.
 ///
 /// # Examples
 ///
@@ -277,11 +450,36 @@ impl<G: Group> ChoiceParams<G, MultiChoice> {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct EncryptedChoice<G: Group, S: ProveSum<G>> {
+    #[cfg_attr(feature = "serde", serde(default = "legacy_version"))]
+    version: u32,
     choices: Vec<Ciphertext<G>>,
     range_proof: RingProof<G>,
     sum_proof: S::Proof,
 }
 
+/// Latest version of the proof system for choices.
+const VERSION: u32 = 2;
+/// Minimum supported version of the proof system for choices.
+const MIN_SUPPORTED_VERSION: u32 = 1;
+/// Version of choices created before the version was embedded into them. Proofs
+/// of this version do not commit to the version.
+pub(super) const LEGACY_VERSION: u32 = 1;
+
+/// Version of choices serialized before the version was embedded into them.
+#[cfg(feature = "serde")]
+const fn legacy_version() -> u32 {
+    LEGACY_VERSION
+}
+
+/// Creates a transcript for a choice proof committing to the proof system `version`.
+fn versioned_transcript(label: &'static [u8], version: u32) -> Transcript {
+    let mut transcript = Transcript::new(label);
+    if version != LEGACY_VERSION {
+        transcript.append_u64(b"version", version.into());
+    }
+    transcript
+}
+
 impl<G: Group> EncryptedChoice<G, SingleChoice> {
     /// Creates a new encrypted choice.
     ///
@@ -305,10 +503,17 @@ impl<G: Group> EncryptedChoice<G, SingleChoice> {
 
 #[allow(clippy::len_without_is_empty)] // `is_empty()` would always be false
 impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
+    /// Latest version of the proof system, with which new choices are created.
+    pub const VERSION: u32 = VERSION;
+    /// Minimum version of the proof system supported during verification.
+    pub const MIN_SUPPORTED_VERSION: u32 = MIN_SUPPORTED_VERSION;
+
     /// Creates an encrypted multi-choice.
     ///
     /// For a [`SingleChoice`] polling, it is caller's responsibility to ensure that `choices`
     /// contains exactly one `true` value; otherwise, the produced proof will not verify.
+    /// Likewise, for an [`ExactChoice`] polling, `choices` must contain exactly the required
+    /// number of `true` values.
     ///
     /// # Panics
     ///
@@ -318,6 +523,34 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
         choices: &[bool],
         rng: &mut R,
     ) -> Self {
+        Self::new_with_randomness(params, choices, rng).0
+    }
+
+    /// Same as [`Self::new()`], but also returns the randomness used to encrypt the choice.
+    ///
+    /// The randomness allows to spoil the choice instead of casting it (aka the Benaloh
+    /// challenge): the voter reveals the randomness, and anyone can check via [`Self::open()`]
+    /// that the choice encrypts the options the voter has selected. This establishes that
+    /// the voting client encrypts choices as intended. A spoiled choice reveals the selected
+    /// options, so it must never be cast; the voter should create a new choice instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`Self::new()`].
+    pub fn new_with_randomness<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        choices: &[bool],
+        rng: &mut R,
+    ) -> (Self, ChoiceRandomness<G>) {
+        Self::new_with_version(params, choices, Self::VERSION, rng)
+    }
+
+    fn new_with_version<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        choices: &[bool],
+        version: u32,
+        rng: &mut R,
+    ) -> (Self, ChoiceRandomness<G>) {
         assert!(!choices.is_empty(), "No choices provided");
         assert_eq!(
             choices.len(),
@@ -327,7 +560,7 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
 
         let admissible_values = [G::identity(), G::generator()];
         let mut ring_responses = vec![G::Scalar::default(); 2 * params.options_count];
-        let mut transcript = Transcript::new(b"encrypted_choice_ranges");
+        let mut transcript = versioned_transcript(proto_labels::CHOICE_RANGES, version);
         let mut proof_builder = RingProofBuilder::new(
             &params.receiver,
             params.options_count,
@@ -342,17 +575,96 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
             .map(|&flag| proof_builder.add_value(&admissible_values, usize::from(flag)))
             .collect();
         let range_proof = RingProof::new(proof_builder.build(), ring_responses);
+        Self::with_sum_proof(params, version, choices, sum, range_proof, rng)
+    }
 
+    fn with_sum_proof<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        version: u32,
+        choices: Vec<ExtendedCiphertext<G>>,
+        sum: u64,
+        range_proof: RingProof<G>,
+        rng: &mut R,
+    ) -> (Self, ChoiceRandomness<G>) {
         let sum_ciphertext = choices.iter().cloned().reduce(ops::Add::add).unwrap();
         let sum_ciphertext = sum_ciphertext.with_value(sum);
         let sum_proof = params
             .sum_prover
-            .prove(&sum_ciphertext, &params.receiver, rng);
-        Self {
-            choices: choices.into_iter().map(|choice| choice.inner).collect(),
+            .prove(&sum_ciphertext, &params.receiver, version, rng);
+
+        let (choices, scalars) = choices
+            .into_iter()
+            .map(|choice| (choice.inner, choice.random_scalar))
+            .unzip();
+        let choice = Self {
+            version,
+            choices,
             range_proof,
             sum_proof,
-        }
+        };
+        (choice, ChoiceRandomness { scalars })
+    }
+
+    /// Same as [`Self::new()`], but creates range proofs for choice ciphertexts in parallel
+    /// using the [`rayon`] thread pool. The output is indistinguishable from that of `new()`.
+    ///
+    /// Range proofs dominate the cost of creating a choice, so this may significantly speed up
+    /// creation for a large number of options on multi-core devices.
+    ///
+    /// [`rayon`]: https://docs.rs/rayon/
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`Self::new()`].
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn new_parallel<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        choices: &[bool],
+        rng: &mut R,
+    ) -> Self
+    where
+        G::Scalar: Send + Sync,
+        G::Element: Send + Sync,
+    {
+        Self::new_parallel_with_randomness(params, choices, rng).0
+    }
+
+    /// Same as [`Self::new_parallel()`], but also returns the randomness used to encrypt
+    /// the choice. See [`Self::new_with_randomness()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`Self::new()`].
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn new_parallel_with_randomness<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        choices: &[bool],
+        rng: &mut R,
+    ) -> (Self, ChoiceRandomness<G>)
+    where
+        G::Scalar: Send + Sync,
+        G::Element: Send + Sync,
+    {
+        assert!(!choices.is_empty(), "No choices provided");
+        assert_eq!(
+            choices.len(),
+            params.options_count,
+            "Mismatch between expected and actual number of choices"
+        );
+
+        let admissible_values = [G::identity(), G::generator()];
+        let values: Vec<_> = choices
+            .iter()
+            .map(|&flag| (&admissible_values as &[_], usize::from(flag)))
+            .collect();
+        let mut transcript = versioned_transcript(proto_labels::CHOICE_RANGES, Self::VERSION);
+        let (range_proof, ciphertexts) =
+            RingProof::build_parallel(&params.receiver, &values, &mut transcript, rng);
+
+        let sum = choices.iter().map(|&flag| u64::from(flag)).sum::<u64>();
+        Self::with_sum_proof(params, Self::VERSION, ciphertexts, sum, range_proof, rng)
     }
 
     /// Verifies the zero-knowledge proofs in this choice and returns Boolean ciphertexts
@@ -361,29 +673,137 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
     /// # Errors
     ///
     /// Returns an error if the `choice` is malformed or its proofs fail verification.
-    #[allow(clippy::missing_panics_doc)]
+    /// Checks are performed in the following order, and the first failing check
+    /// determines the returned [`ChoiceVerificationError`] variant:
+    ///
+    /// 1. The proof system version ([`UnsupportedVersion`]).
+    /// 2. The number of options ([`OptionsLenMismatch`]).
+    /// 3. Well-formedness of ciphertexts for each option ([`MalformedCiphertext`]).
+    /// 4. The sum proof ([`Sum`]).
+    /// 5. The range proof ([`Range`]). Since the range proof is a single proof for all options
+    ///    with a common challenge, it is impossible to pinpoint the option for which
+    ///    the proof fails.
+    ///
+    /// [`UnsupportedVersion`]: ChoiceVerificationError::UnsupportedVersion
+    /// [`OptionsLenMismatch`]: ChoiceVerificationError::OptionsLenMismatch
+    /// [`MalformedCiphertext`]: ChoiceVerificationError::MalformedCiphertext
+    /// [`Sum`]: ChoiceVerificationError::Sum
+    /// [`Range`]: ChoiceVerificationError::Range
     pub fn verify(
         &self,
         params: &ChoiceParams<G, S>,
     ) -> Result<&[Ciphertext<G>], ChoiceVerificationError> {
-        params.check_options_count(self.choices.len())?;
-        let sum_of_ciphertexts = self.choices.iter().copied().reduce(ops::Add::add);
-        let sum_of_ciphertexts = sum_of_ciphertexts.unwrap();
-        // ^ `unwrap()` is safe; `params` cannot have 0 options by construction
-        params
-            .sum_prover
-            .verify(&sum_of_ciphertexts, &self.sum_proof, &params.receiver)?;
+        verify_choice(
+            params,
+            self.version,
+            &self.choices,
+            &self.range_proof,
+            &self.sum_proof,
+        )?;
+        Ok(&self.choices)
+    }
 
-        let admissible_values = [G::identity(), G::generator()];
-        self.range_proof
-            .verify(
-                &params.receiver,
-                iter::repeat(&admissible_values as &[_]).take(self.choices.len()),
-                self.choices.iter().copied(),
-                &mut Transcript::new(b"encrypted_choice_ranges"),
-            )
-            .map(|()| self.choices.as_slice())
-            .map_err(ChoiceVerificationError::Range)
+    /// Opens this choice using the `randomness` revealed by the voter when spoiling the choice,
+    /// and returns the selected options. See [`Self::new_with_randomness()`] for details.
+    ///
+    /// This method only checks the encrypted values; use [`Self::verify()`] to check proofs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `randomness` does not correspond to this choice, or if
+    /// a ciphertext for any option does not encrypt a Boolean value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{app::{ChoiceParams, EncryptedChoice}, group::Ristretto, Keypair};
+    /// # use rand::thread_rng;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut rng = thread_rng();
+    /// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+    /// let params = ChoiceParams::multi(receiver.public().clone(), 3);
+    /// let choices = [true, false, true];
+    /// let (choice, randomness) =
+    ///     EncryptedChoice::new_with_randomness(&params, &choices, &mut rng);
+    ///
+    /// // The voter decides to spoil the choice and reveals the randomness.
+    /// let opened_choices = choice.open(&params, &randomness)?;
+    /// assert_eq!(opened_choices, choices);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open(
+        &self,
+        params: &ChoiceParams<G, S>,
+        randomness: &ChoiceRandomness<G>,
+    ) -> Result<Vec<bool>, ChoiceOpeningError> {
+        if self.choices.len() != params.options_count {
+            return Err(ChoiceOpeningError::OptionsLenMismatch {
+                expected: params.options_count,
+                actual: self.choices.len(),
+            });
+        }
+        if randomness.scalars.len() != self.choices.len() {
+            return Err(ChoiceOpeningError::RandomnessLenMismatch {
+                expected: self.choices.len(),
+                actual: randomness.scalars.len(),
+            });
+        }
+
+        let receiver = params.receiver.as_element();
+        let zipped = self.choices.iter().zip(&randomness.scalars);
+        let opened = zipped.enumerate().map(|(option, (ciphertext, scalar))| {
+            let scalar = scalar.expose_scalar();
+            if ciphertext.random_element != G::mul_generator(scalar) {
+                return Err(ChoiceOpeningError::RandomnessMismatch { option });
+            }
+            let value = ciphertext.blinded_element - receiver * scalar;
+            if G::is_identity(&value) {
+                Ok(false)
+            } else if value == G::generator() {
+                Ok(true)
+            } else {
+                Err(ChoiceOpeningError::NonBooleanValue { option })
+            }
+        });
+        opened.collect()
+    }
+
+    /// Splits this choice into ciphertexts for all options and a detached [`ChoiceProofs`]
+    /// object, which can still be verified against the ciphertexts. This allows,
+    /// e.g., storing ciphertexts in per-option tables.
+    pub fn into_parts(self) -> (Vec<Ciphertext<G>>, ChoiceProofs<G, S>) {
+        let proofs = ChoiceProofs {
+            version: self.version,
+            range_proof: self.range_proof,
+            sum_proof: self.sum_proof,
+        };
+        (self.choices, proofs)
+    }
+
+    /// Restores a choice from the parts produced by [`Self::into_parts()`].
+    /// The proofs are re-verified against the ciphertexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proofs do not verify; see [`Self::verify()`] for details.
+    pub fn from_parts(
+        params: &ChoiceParams<G, S>,
+        choices: Vec<Ciphertext<G>>,
+        proofs: ChoiceProofs<G, S>,
+    ) -> Result<Self, ChoiceVerificationError> {
+        proofs.verify(params, &choices)?;
+        Ok(Self {
+            version: proofs.version,
+            choices,
+            range_proof: proofs.range_proof,
+            sum_proof: proofs.sum_proof,
+        })
+    }
+
+    /// Returns the version of the proof system used to create this choice.
+    pub fn version(&self) -> u32 {
+        self.version
     }
 
     /// Returns the number of encrypted choices. This value is equal to
@@ -397,6 +817,173 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
         &self.choices
     }
 
+    #[cfg(feature = "testkit")]
+    pub(crate) fn choices_mut(&mut self) -> &mut [Ciphertext<G>] {
+        &mut self.choices
+    }
+
+    /// Iterates over ciphertexts for all options together with 0-based option indexes
+    /// **without** checking the validity of this choice. Options are yielded in the increasing
+    /// index order.
+    pub fn indexed_choices_unchecked(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, &Ciphertext<G>)> + '_ {
+        self.choices.iter().enumerate()
+    }
+
+    /// Returns the range proof for the choice ciphertexts.
+    pub fn range_proof(&self) -> &RingProof<G> {
+        &self.range_proof
+    }
+
+    /// Returns the sum proof for the choice ciphertexts.
+    pub fn sum_proof(&self) -> &S::Proof {
+        &self.sum_proof
+    }
+}
+
+fn verify_choice<G: Group, S: ProveSum<G>>(
+    params: &ChoiceParams<G, S>,
+    version: u32,
+    choices: &[Ciphertext<G>],
+    range_proof: &RingProof<G>,
+    sum_proof: &S::Proof,
+) -> Result<(), ChoiceVerificationError> {
+    // When the proof system is upgraded, verification logic for older versions
+    // must be retained here until they are no longer supported.
+    if !(MIN_SUPPORTED_VERSION..=VERSION).contains(&version) {
+        return Err(ChoiceVerificationError::UnsupportedVersion(version));
+    }
+
+    params.check_options_count(choices.len())?;
+    check_ciphertexts(choices, 0)?;
+    let sum_of_ciphertexts = choices.iter().copied().reduce(ops::Add::add);
+    let sum_of_ciphertexts = sum_of_ciphertexts.unwrap();
+    // ^ `unwrap()` is safe; `params` cannot have 0 options by construction
+    params
+        .sum_prover
+        .verify(&sum_of_ciphertexts, sum_proof, &params.receiver, version)?;
+
+    let admissible_values = [G::identity(), G::generator()];
+    range_proof
+        .verify(
+            &params.receiver,
+            iter::repeat(&admissible_values as &[_]).take(choices.len()),
+            choices.iter().copied(),
+            &mut versioned_transcript(proto_labels::CHOICE_RANGES, version),
+        )
+        .map_err(ChoiceVerificationError::Range)
+}
+
+/// Randomness used to encrypt an [`EncryptedChoice`], which allows to open the choice
+/// via [`EncryptedChoice::open()`].
+///
+/// Obtained via [`EncryptedChoice::new_with_randomness()`] or its parallel counterpart.
+/// The randomness reveals the selected options, so it must be kept secret unless the choice
+/// is spoiled. The randomness scalars are zeroized on drop.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "", transparent))]
+pub struct ChoiceRandomness<G: Group> {
+    scalars: Vec<SecretKey<G>>,
+}
+
+impl<G: Group> fmt::Debug for ChoiceRandomness<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ChoiceRandomness")
+            .field("len", &self.scalars.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> ChoiceRandomness<G> {
+    /// Serializes this randomness as a concatenation of scalars for all options.
+    /// The returned bytes are zeroized on drop.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(vec![0_u8; G::SCALAR_SIZE * self.scalars.len()]);
+        let chunks = bytes.chunks_exact_mut(G::SCALAR_SIZE);
+        for (scalar, chunk) in self.scalars.iter().zip(chunks) {
+            G::serialize_scalar(scalar.expose_scalar(), chunk);
+        }
+        bytes
+    }
+
+    /// Attempts to deserialize randomness from `bytes`. Returns `None` if `bytes` do not
+    /// represent a non-empty sequence of valid scalars.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() || bytes.len() % G::SCALAR_SIZE != 0 {
+            return None;
+        }
+        let scalars = bytes
+            .chunks_exact(G::SCALAR_SIZE)
+            .map(SecretKey::from_bytes);
+        let scalars: Option<Vec<_>> = scalars.collect();
+        Some(Self { scalars: scalars? })
+    }
+}
+
+/// Zero-knowledge proofs of an [`EncryptedChoice`] detached from the choice ciphertexts.
+///
+/// Produced by [`EncryptedChoice::into_parts()`]. The proofs remain verifiable against
+/// the ciphertexts, and can be combined with them back into a choice
+/// via [`EncryptedChoice::from_parts()`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::{ChoiceParams, EncryptedChoice}, group::Ristretto, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let params = ChoiceParams::single(receiver.public().clone(), 3);
+/// let choice = EncryptedChoice::single(&params, 1, &mut rng);
+///
+/// let (ciphertexts, proofs) = choice.into_parts();
+/// // Ciphertexts and proofs may be stored separately, e.g. in per-option tables.
+/// proofs.verify(&params, &ciphertexts)?;
+/// let choice = EncryptedChoice::from_parts(&params, ciphertexts, proofs)?;
+/// assert_eq!(choice.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ChoiceProofs<G: Group, S: ProveSum<G>> {
+    #[cfg_attr(feature = "serde", serde(default = "legacy_version"))]
+    version: u32,
+    range_proof: RingProof<G>,
+    sum_proof: S::Proof,
+}
+
+impl<G: Group, S: ProveSum<G>> ChoiceProofs<G, S> {
+    /// Verifies these proofs against the ciphertexts for all options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ciphertexts are malformed or the proofs fail verification;
+    /// see [`EncryptedChoice::verify()`] for details.
+    pub fn verify(
+        &self,
+        params: &ChoiceParams<G, S>,
+        choices: &[Ciphertext<G>],
+    ) -> Result<(), ChoiceVerificationError> {
+        verify_choice(
+            params,
+            self.version,
+            choices,
+            &self.range_proof,
+            &self.sum_proof,
+        )
+    }
+
+    /// Returns the version of the proof system used to create these proofs.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     /// Returns the range proof for the choice ciphertexts.
     pub fn range_proof(&self) -> &RingProof<G> {
         &self.range_proof
@@ -412,6 +999,8 @@ impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ChoiceVerificationError {
+    /// Choice was created with an unsupported version of the proof system.
+    UnsupportedVersion(u32),
     /// Mismatch between expected and actual number of options in the `EncryptedChoice`.
     OptionsLenMismatch {
         /// Expected number of options.
@@ -419,21 +1008,38 @@ pub enum ChoiceVerificationError {
         /// Actual number of options.
         actual: usize,
     },
+    /// Ciphertext for an option is malformed (its random element is the group identity,
+    /// i.e., the ciphertext is not blinded).
+    MalformedCiphertext {
+        /// 0-based index of the option.
+        option: usize,
+    },
     /// Error verifying [`EncryptedChoice::sum_proof()`].
     Sum(VerificationError),
     /// Error verifying [`EncryptedChoice::range_proof()`].
     Range(VerificationError),
+    /// Error verifying [`EligibleChoice::eligibility_proof()`](crate::app::EligibleChoice::eligibility_proof()).
+    Eligibility(VerificationError),
 }
 
 impl fmt::Display for ChoiceVerificationError {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::UnsupportedVersion(version) => write!(
+                formatter,
+                "unsupported proof system version {version}; expected a version \
+                 in {MIN_SUPPORTED_VERSION}..={VERSION}"
+            ),
             Self::OptionsLenMismatch { expected, actual } => write!(
                 formatter,
                 "number of options in the ballot ({actual}) differs from expected ({expected})",
             ),
+            Self::MalformedCiphertext { option } => {
+                write!(formatter, "ciphertext for option #{option} is not blinded")
+            }
             Self::Sum(err) => write!(formatter, "cannot verify sum proof: {err}"),
             Self::Range(err) => write!(formatter, "cannot verify range proofs: {err}"),
+            Self::Eligibility(err) => write!(formatter, "cannot verify eligibility proof: {err}"),
         }
     }
 }
@@ -442,19 +1048,77 @@ impl fmt::Display for ChoiceVerificationError {
 impl std::error::Error for ChoiceVerificationError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Sum(err) | Self::Range(err) => Some(err),
+            Self::Sum(err) | Self::Range(err) | Self::Eligibility(err) => Some(err),
             _ => None,
         }
     }
 }
 
+/// Error opening an [`EncryptedChoice`] via [`EncryptedChoice::open()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ChoiceOpeningError {
+    /// Mismatch between expected and actual number of options in the `EncryptedChoice`.
+    OptionsLenMismatch {
+        /// Expected number of options.
+        expected: usize,
+        /// Actual number of options.
+        actual: usize,
+    },
+    /// Mismatch between the number of options and the number of randomness scalars.
+    RandomnessLenMismatch {
+        /// Expected number of scalars (i.e., the number of options).
+        expected: usize,
+        /// Actual number of scalars.
+        actual: usize,
+    },
+    /// Randomness does not correspond to the ciphertext for an option.
+    RandomnessMismatch {
+        /// 0-based index of the option.
+        option: usize,
+    },
+    /// Ciphertext for an option encrypts a value other than 0 or 1.
+    NonBooleanValue {
+        /// 0-based index of the option.
+        option: usize,
+    },
+}
+
+impl fmt::Display for ChoiceOpeningError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OptionsLenMismatch { expected, actual } => write!(
+                formatter,
+                "number of options in the ballot ({actual}) differs from expected ({expected})",
+            ),
+            Self::RandomnessLenMismatch { expected, actual } => write!(
+                formatter,
+                "number of randomness scalars ({actual}) differs from the number \
+                 of options ({expected})"
+            ),
+            Self::RandomnessMismatch { option } => write!(
+                formatter,
+                "randomness does not correspond to the ciphertext for option #{option}"
+            ),
+            Self::NonBooleanValue { option } => write!(
+                formatter,
+                "ciphertext for option #{option} does not encrypt a Boolean value"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChoiceOpeningError {}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
 
     use super::*;
     use crate::{
-        group::{Generic, Ristretto},
+        alloc::ToString,
+        group::{ElementOps, Generic, Ristretto, ScalarOps},
         Keypair,
     };
 
@@ -483,6 +1147,216 @@ mod tests {
         assert!(choice.verify(&params).is_err());
     }
 
+    #[test]
+    fn choice_ciphertexts_use_independent_randomness() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(receiver, 5);
+        let choice = EncryptedChoice::single(&params, 3, &mut rng);
+
+        let random_elements: Vec<_> = choice
+            .choices_unchecked()
+            .iter()
+            .map(|ciphertext| ciphertext.random_element)
+            .collect();
+        for (i, element) in random_elements.iter().enumerate() {
+            assert!(random_elements[..i].iter().all(|other| other != element));
+        }
+    }
+
+    #[test]
+    fn choice_roundtrip_via_parts() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::multi(receiver, 4);
+        let choice = EncryptedChoice::new(&params, &[true, false, true, false], &mut rng);
+        let expected: Vec<_> = choice.choices.iter().map(|ct| ct.to_bytes()).collect();
+
+        let (mut ciphertexts, proofs) = choice.into_parts();
+        proofs.verify(&params, &ciphertexts).unwrap();
+        ciphertexts.swap(0, 1);
+        let err = proofs.verify(&params, &ciphertexts).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Range(_)), "{err:?}");
+        let err =
+            EncryptedChoice::from_parts(&params, ciphertexts.clone(), proofs.clone()).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Range(_)), "{err:?}");
+
+        ciphertexts.swap(0, 1);
+        let choice = EncryptedChoice::from_parts(&params, ciphertexts, proofs).unwrap();
+        let actual: Vec<_> = choice
+            .verify(&params)
+            .unwrap()
+            .iter()
+            .map(|ct| ct.to_bytes())
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn spoiled_choice_can_be_opened() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(receiver, 4);
+        let choices = [false, false, true, false];
+        let (choice, randomness) =
+            EncryptedChoice::new_with_randomness(&params, &choices, &mut rng);
+        choice.verify(&params).unwrap();
+        assert_eq!(choice.open(&params, &randomness).unwrap(), choices);
+
+        let (other_choice, other_randomness) =
+            EncryptedChoice::new_with_randomness(&params, &choices, &mut rng);
+        let err = choice.open(&params, &other_randomness).unwrap_err();
+        assert!(
+            matches!(err, ChoiceOpeningError::RandomnessMismatch { option: 0 }),
+            "{err:?}"
+        );
+
+        let mut mixed_randomness = randomness.clone();
+        mixed_randomness.scalars[2] = other_randomness.scalars[2].clone();
+        let mut mixed_choice = choice.clone();
+        mixed_choice.choices[2] = other_choice.choices[2];
+        assert_eq!(
+            mixed_choice.open(&params, &mixed_randomness).unwrap(),
+            choices
+        );
+
+        let mut bogus_choice = choice.clone();
+        bogus_choice.choices[2].blinded_element += Ristretto::generator();
+        let err = bogus_choice.open(&params, &randomness).unwrap_err();
+        assert!(
+            matches!(err, ChoiceOpeningError::NonBooleanValue { option: 2 }),
+            "{err:?}"
+        );
+
+        let randomness_bytes = randomness.to_bytes();
+        assert_eq!(randomness_bytes.len(), 4 * Ristretto::SCALAR_SIZE);
+        let restored_randomness = ChoiceRandomness::from_bytes(&randomness_bytes).unwrap();
+        assert_eq!(choice.open(&params, &restored_randomness).unwrap(), choices);
+        assert!(ChoiceRandomness::<Ristretto>::from_bytes(&[]).is_none());
+        assert!(ChoiceRandomness::<Ristretto>::from_bytes(&randomness_bytes[1..]).is_none());
+
+        let mut short_randomness = randomness;
+        short_randomness.scalars.pop();
+        let err = choice.open(&params, &short_randomness).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ChoiceOpeningError::RandomnessLenMismatch {
+                    expected: 4,
+                    actual: 3
+                }
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn choice_version_is_checked() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(receiver, 3);
+        let mut choice = EncryptedChoice::single(&params, 1, &mut rng);
+        assert_eq!(choice.version(), VERSION);
+
+        choice.version = VERSION + 1;
+        let err = choice.verify(&params).unwrap_err();
+        assert!(
+            matches!(err, ChoiceVerificationError::UnsupportedVersion(v) if v == VERSION + 1),
+            "{err:?}"
+        );
+        let (ciphertexts, proofs) = choice.into_parts();
+        assert_eq!(proofs.version(), VERSION + 1);
+        let err = EncryptedChoice::from_parts(&params, ciphertexts, proofs).unwrap_err();
+        assert!(
+            matches!(err, ChoiceVerificationError::UnsupportedVersion(_)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn choice_proofs_commit_to_version() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(receiver, 3);
+        let mut choice = EncryptedChoice::single(&params, 1, &mut rng);
+        choice.verify(&params).unwrap();
+
+        choice.version = LEGACY_VERSION;
+        let err = choice.verify(&params).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Sum(_)), "{err:?}");
+
+        let params = ChoiceParams::multi(params.receiver().clone(), 3);
+        let (mut choice, _) =
+            EncryptedChoice::new_with_version(&params, &[true, false, true], 1, &mut rng);
+        choice.verify(&params).unwrap();
+        choice.version = VERSION;
+        let err = choice.verify(&params).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Range(_)), "{err:?}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn legacy_choices_without_version_are_accepted() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::multi(receiver, 3);
+        let (choice, _) =
+            EncryptedChoice::new_with_version(&params, &[true, false, true], 1, &mut rng);
+
+        let mut json = serde_json::to_value(&choice).unwrap();
+        assert_eq!(json["version"], 1);
+        json.as_object_mut().unwrap().remove("version");
+        let legacy_choice: EncryptedChoice<Ristretto, MultiChoice> =
+            serde_json::from_value(json).unwrap();
+        assert_eq!(legacy_choice.version(), 1);
+        legacy_choice.verify(&params).unwrap();
+    }
+
+    #[test]
+    fn exact_choice_enforces_cardinality() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::exact(receiver, 4, 2);
+
+        let choice = EncryptedChoice::new(&params, &[false, true, false, true], &mut rng);
+        choice.verify(&params).unwrap();
+        for choices in [[false; 4], [true, true, true, false], [true; 4]] {
+            let choice = EncryptedChoice::new(&params, &choices, &mut rng);
+            let err = choice.verify(&params).unwrap_err();
+            assert!(matches!(err, ChoiceVerificationError::Sum(_)), "{err:?}");
+        }
+
+        // The sum proof is bound to the required number of selected options.
+        let other_params = ChoiceParams::exact(params.receiver().clone(), 4, 1);
+        let err = choice.verify(&other_params).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Sum(_)), "{err:?}");
+    }
+
+    #[test]
+    fn choice_verification_errors_identify_failing_component() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(receiver, 3);
+        let choice = EncryptedChoice::single(&params, 1, &mut rng);
+
+        let mut malformed_choice = choice.clone();
+        malformed_choice.choices[2] = Ciphertext::non_blinded(1_u64);
+        let err = malformed_choice.verify(&params).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ChoiceVerificationError::MalformedCiphertext { option: 2 }
+            ),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("option #2"), "{err}");
+
+        let mut swapped_choice = choice;
+        swapped_choice.choices.swap(0, 1);
+        let err = swapped_choice.verify(&params).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Range(_)), "{err:?}");
+    }
+
     #[test]
     fn bogus_encrypted_choice_does_not_work_for_edwards() {
         test_bogus_encrypted_choice_does_not_work::<Ristretto>();
@@ -492,4 +1366,36 @@ mod tests {
     fn bogus_encrypted_choice_does_not_work_for_k256() {
         test_bogus_encrypted_choice_does_not_work::<Generic<k256::Secp256k1>>();
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_choice_creation() {
+        use crate::DiscreteLogTable;
+
+        let mut rng = thread_rng();
+        let (receiver, secret) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::multi(receiver, 7);
+        let choices = [true, false, false, true, true, false, true];
+        let choice = EncryptedChoice::new_parallel(&params, &choices, &mut rng);
+
+        let lookup_table = DiscreteLogTable::new(0..=1);
+        let ciphertexts = choice.verify(&params).unwrap();
+        for (&ciphertext, &flag) in ciphertexts.iter().zip(&choices) {
+            assert_eq!(
+                secret.decrypt(ciphertext, &lookup_table),
+                Some(u64::from(flag))
+            );
+        }
+
+        let params = ChoiceParams::single(params.receiver().clone(), 7);
+        let mut choices = [false; 7];
+        choices[4] = true;
+        let choice = EncryptedChoice::new_parallel(&params, &choices, &mut rng);
+        choice.verify(&params).unwrap();
+
+        let (choice, randomness) =
+            EncryptedChoice::new_parallel_with_randomness(&params, &choices, &mut rng);
+        choice.verify(&params).unwrap();
+        assert_eq!(choice.open(&params, &randomness).unwrap(), choices);
+    }
 }
@@ -0,0 +1,415 @@
+//! Chunked construction and verification of encrypted choices.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::{fmt, iter, ops};
+
+use super::{
+    choice::{check_ciphertexts, LEGACY_VERSION},
+    ChoiceParams, ChoiceVerificationError, ProveSum,
+};
+use crate::{
+    alloc::{vec, Vec},
+    encryption::ExtendedCiphertext,
+    group::Group,
+    proto_labels, Ciphertext, RingProof, RingProofBuilder,
+};
+
+fn initialize_transcript(options_count: usize) -> Transcript {
+    let mut transcript = Transcript::new(proto_labels::CHOICE_CHUNKS);
+    transcript.append_u64(b"n", options_count as u64);
+    transcript
+}
+
+/// Chunk of an encrypted choice produced by [`ChunkedChoiceEncryptor`].
+///
+/// A chunk contains Boolean ciphertexts for a contiguous range of options together
+/// with a [`RingProof`] that all of them encrypt 0 or 1. The proof in each chunk is bound
+/// to all preceding chunks, so chunks must be verified in the order of their creation
+/// using [`ChunkedChoiceVerifier`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ChoiceChunk<G: Group> {
+    choices: Vec<Ciphertext<G>>,
+    range_proof: RingProof<G>,
+}
+
+#[allow(clippy::len_without_is_empty)] // `is_empty()` would always be false
+impl<G: Group> ChoiceChunk<G> {
+    /// Returns the number of options in this chunk.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Returns ciphertexts for the options in this chunk **without** checking their validity.
+    pub fn choices_unchecked(&self) -> &[Ciphertext<G>] {
+        &self.choices
+    }
+
+    /// Returns the range proof for the choice ciphertexts in this chunk.
+    pub fn range_proof(&self) -> &RingProof<G> {
+        &self.range_proof
+    }
+}
+
+/// Streaming alternative to [`EncryptedChoice::new()`] for choices with many options.
+///
+/// The encryptor consumes choices in chunks, producing a [`ChoiceChunk`] for each of them.
+/// Thus, memory usage is bounded by the chunk size rather than by the total number of options.
+/// After all options are processed, [`Self::finish()`] produces the proof for the sum
+/// of all choices (same as [`EncryptedChoice::sum_proof()`]).
+///
+/// The produced chunks are **not** compatible with [`EncryptedChoice`]; they must be verified
+/// with [`ChunkedChoiceVerifier`].
+///
+/// [`EncryptedChoice`]: super::EncryptedChoice
+/// [`EncryptedChoice::new()`]: super::EncryptedChoice::new()
+/// [`EncryptedChoice::sum_proof()`]: super::EncryptedChoice::sum_proof()
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     app::{ChoiceParams, ChunkedChoiceEncryptor, ChunkedChoiceVerifier},
+/// #     group::Ristretto, DiscreteLogTable, Keypair,
+/// # };
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let (pk, sk) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+/// let choice_params = ChoiceParams::single(pk, 1_000);
+/// let choice = 777;
+///
+/// let mut encryptor = ChunkedChoiceEncryptor::new(&choice_params);
+/// let mut verifier = ChunkedChoiceVerifier::new(&choice_params);
+/// let lookup_table = DiscreteLogTable::new(0..=1);
+/// for start in (0..1_000).step_by(100) {
+///     let flags: Vec<_> = (start..start + 100).map(|i| i == choice).collect();
+///     let chunk = encryptor.push_chunk(&flags, &mut rng);
+///     // Chunks can be sent to the verifier as they are produced.
+///     let ciphertexts = verifier.verify_chunk(&chunk)?;
+///     for (i, &ciphertext) in (start..).zip(ciphertexts) {
+///         let value = sk.decrypt(ciphertext, &lookup_table);
+///         assert_eq!(value, Some((i == choice) as u64));
+///     }
+/// }
+/// let sum_proof = encryptor.finish(&mut rng);
+/// verifier.finish(&sum_proof)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChunkedChoiceEncryptor<'a, G: Group, S: ProveSum<G>> {
+    params: &'a ChoiceParams<G, S>,
+    transcript: Transcript,
+    processed_count: usize,
+    sum: Option<ExtendedCiphertext<G>>,
+    sum_value: u64,
+}
+
+impl<G: Group, S: ProveSum<G>> fmt::Debug for ChunkedChoiceEncryptor<'_, G, S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ChunkedChoiceEncryptor")
+            .field("options_count", &self.params.options_count())
+            .field("receiver", self.params.receiver())
+            .field("processed_count", &self.processed_count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, G: Group, S: ProveSum<G>> ChunkedChoiceEncryptor<'a, G, S> {
+    /// Creates an encryptor with the specified parameters.
+    pub fn new(params: &'a ChoiceParams<G, S>) -> Self {
+        Self {
+            params,
+            transcript: initialize_transcript(params.options_count()),
+            processed_count: 0,
+            sum: None,
+            sum_value: 0,
+        }
+    }
+
+    /// Returns the number of options processed so far.
+    pub fn processed_count(&self) -> usize {
+        self.processed_count
+    }
+
+    /// Encrypts the next chunk of `choices`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `choices` is empty, or if the total number of processed choices
+    /// would exceed the number of options specified in the parameters.
+    pub fn push_chunk<R: CryptoRng + RngCore>(
+        &mut self,
+        choices: &[bool],
+        rng: &mut R,
+    ) -> ChoiceChunk<G> {
+        assert!(!choices.is_empty(), "No choices provided");
+        assert!(
+            self.processed_count + choices.len() <= self.params.options_count(),
+            "Number of choices exceeds the number of options"
+        );
+        self.transcript
+            .append_u64(b"offset", self.processed_count as u64);
+
+        let receiver = self.params.receiver();
+        let admissible_values = [G::identity(), G::generator()];
+        let mut ring_responses = vec![G::Scalar::default(); 2 * choices.len()];
+        let mut proof_builder = RingProofBuilder::new(
+            receiver,
+            choices.len(),
+            &mut ring_responses,
+            &mut self.transcript,
+            rng,
+        );
+        let ciphertexts: Vec<_> = choices
+            .iter()
+            .map(|&flag| proof_builder.add_value(&admissible_values, usize::from(flag)))
+            .collect();
+        let range_proof = RingProof::new(proof_builder.build(), ring_responses);
+
+        self.processed_count += choices.len();
+        self.sum_value += choices.iter().map(|&flag| u64::from(flag)).sum::<u64>();
+        let chunk_sum = ciphertexts.iter().cloned().reduce(ops::Add::add).unwrap();
+        // ^ `unwrap()` is safe; `choices` is non-empty
+        self.sum = Some(match self.sum.take() {
+            Some(sum) => sum + chunk_sum,
+            None => chunk_sum,
+        });
+
+        ChoiceChunk {
+            choices: ciphertexts.into_iter().map(|choice| choice.inner).collect(),
+            range_proof,
+        }
+    }
+
+    /// Finishes encryption and returns the proof for the sum of all choices.
+    ///
+    /// For a [`SingleChoice`](super::SingleChoice) polling, it is caller's responsibility
+    /// to ensure that exactly one `true` value was supplied across all chunks; otherwise,
+    /// the produced proof will not verify.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not all options were processed.
+    pub fn finish<R: CryptoRng + RngCore>(self, rng: &mut R) -> S::Proof {
+        assert_eq!(
+            self.processed_count,
+            self.params.options_count(),
+            "Not all options were processed"
+        );
+        let sum = self.sum.unwrap().with_value(self.sum_value);
+        // ^ `unwrap()` is safe; there is at least one option by construction of `params`
+        // Chunked choices are not versioned, so the sum proof does not commit to a version.
+        self.params
+            .sum_prover()
+            .prove(&sum, self.params.receiver(), LEGACY_VERSION, rng)
+    }
+}
+
+/// Verifier for [`ChoiceChunk`]s produced by [`ChunkedChoiceEncryptor`].
+///
+/// Chunks must be supplied to [`Self::verify_chunk()`] in the order of their creation.
+/// The verifier only retains a constant amount of data between chunks.
+///
+/// # Examples
+///
+/// See [`ChunkedChoiceEncryptor`] for an example of usage.
+pub struct ChunkedChoiceVerifier<'a, G: Group, S: ProveSum<G>> {
+    params: &'a ChoiceParams<G, S>,
+    transcript: Transcript,
+    processed_count: usize,
+    sum: Ciphertext<G>,
+}
+
+impl<G: Group, S: ProveSum<G>> fmt::Debug for ChunkedChoiceVerifier<'_, G, S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ChunkedChoiceVerifier")
+            .field("options_count", &self.params.options_count())
+            .field("receiver", self.params.receiver())
+            .field("processed_count", &self.processed_count)
+            .field("sum", &self.sum)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, G: Group, S: ProveSum<G>> ChunkedChoiceVerifier<'a, G, S> {
+    /// Creates a verifier with the specified parameters.
+    pub fn new(params: &'a ChoiceParams<G, S>) -> Self {
+        Self {
+            params,
+            transcript: initialize_transcript(params.options_count()),
+            processed_count: 0,
+            sum: Ciphertext::zero(),
+        }
+    }
+
+    /// Returns the number of options verified so far.
+    pub fn processed_count(&self) -> usize {
+        self.processed_count
+    }
+
+    /// Verifies the next chunk and returns Boolean ciphertexts for the options in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk range proof does not verify, or if the total number
+    /// of options in the verified chunks exceeds the expected number of options.
+    pub fn verify_chunk<'c>(
+        &mut self,
+        chunk: &'c ChoiceChunk<G>,
+    ) -> Result<&'c [Ciphertext<G>], ChoiceVerificationError> {
+        let options_count = self.params.options_count();
+        let new_count = self.processed_count + chunk.choices.len();
+        if new_count > options_count {
+            return Err(ChoiceVerificationError::OptionsLenMismatch {
+                expected: options_count,
+                actual: new_count,
+            });
+        }
+
+        check_ciphertexts(&chunk.choices, self.processed_count)?;
+
+        // The transcript is modified during verification; thus, it must be reverted
+        // on failure in order to not brick the verifier.
+        let mut transcript = self.transcript.clone();
+        transcript.append_u64(b"offset", self.processed_count as u64);
+        let admissible_values = [G::identity(), G::generator()];
+        chunk
+            .range_proof
+            .verify(
+                self.params.receiver(),
+                iter::repeat(&admissible_values as &[_]).take(chunk.choices.len()),
+                chunk.choices.iter().copied(),
+                &mut transcript,
+            )
+            .map_err(ChoiceVerificationError::Range)?;
+
+        self.transcript = transcript;
+        self.processed_count = new_count;
+        for &choice in &chunk.choices {
+            self.sum += choice;
+        }
+        Ok(&chunk.choices)
+    }
+
+    /// Finishes verification by checking the proof for the sum of all choices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not all options were verified, or if the sum proof does not verify.
+    pub fn finish(self, sum_proof: &S::Proof) -> Result<(), ChoiceVerificationError> {
+        self.params.check_options_count(self.processed_count)?;
+        self.params.sum_prover().verify(
+            &self.sum,
+            sum_proof,
+            self.params.receiver(),
+            LEGACY_VERSION,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{group::Ristretto, Keypair};
+
+    #[test]
+    fn chunked_single_choice() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(pk, 10);
+
+        let mut encryptor = ChunkedChoiceEncryptor::new(&params);
+        let chunks = [
+            encryptor.push_chunk(&[false; 4], &mut rng),
+            encryptor.push_chunk(&[false, true, false, false], &mut rng),
+            encryptor.push_chunk(&[false; 2], &mut rng),
+        ];
+        let sum_proof = encryptor.finish(&mut rng);
+
+        let mut verifier = ChunkedChoiceVerifier::new(&params);
+        for chunk in &chunks {
+            verifier.verify_chunk(chunk).unwrap();
+        }
+        verifier.finish(&sum_proof).unwrap();
+
+        // Reordering chunks breaks verification.
+        let mut verifier = ChunkedChoiceVerifier::new(&params);
+        let err = verifier.verify_chunk(&chunks[1]).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Range(_)));
+        // ...but the verifier can continue after the error.
+        for chunk in &chunks {
+            verifier.verify_chunk(chunk).unwrap();
+        }
+
+        // Incomplete choices are not accepted.
+        let mut verifier = ChunkedChoiceVerifier::new(&params);
+        verifier.verify_chunk(&chunks[0]).unwrap();
+        let err = verifier.finish(&sum_proof).unwrap_err();
+        assert!(matches!(
+            err,
+            ChoiceVerificationError::OptionsLenMismatch {
+                expected: 10,
+                actual: 4
+            }
+        ));
+    }
+
+    #[test]
+    fn chunked_choice_with_invalid_sum() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::single(pk, 6);
+
+        let mut encryptor = ChunkedChoiceEncryptor::new(&params);
+        let chunks = [
+            encryptor.push_chunk(&[true, false, false], &mut rng),
+            encryptor.push_chunk(&[false, false, true], &mut rng),
+        ];
+        let sum_proof = encryptor.finish(&mut rng);
+
+        let mut verifier = ChunkedChoiceVerifier::new(&params);
+        for chunk in &chunks {
+            verifier.verify_chunk(chunk).unwrap();
+        }
+        let err = verifier.finish(&sum_proof).unwrap_err();
+        assert!(matches!(err, ChoiceVerificationError::Sum(_)));
+    }
+
+    #[test]
+    fn chunked_multi_choice() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::multi(pk, 5);
+
+        let mut encryptor = ChunkedChoiceEncryptor::new(&params);
+        let chunks = [
+            encryptor.push_chunk(&[true, false, true], &mut rng),
+            encryptor.push_chunk(&[true, true], &mut rng),
+        ];
+        encryptor.finish(&mut rng);
+
+        let mut verifier = ChunkedChoiceVerifier::new(&params);
+        for chunk in &chunks {
+            verifier.verify_chunk(chunk).unwrap();
+        }
+        let err = verifier.verify_chunk(&chunks[1]).unwrap_err();
+        assert!(matches!(
+            err,
+            ChoiceVerificationError::OptionsLenMismatch {
+                expected: 5,
+                actual: 7
+            }
+        ));
+        verifier.finish(&()).unwrap();
+    }
+}
@@ -0,0 +1,341 @@
+//! Encrypted choice bound to an eligibility credential.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+use crate::{
+    alloc::{vec, Vec},
+    app::{ChoiceParams, ChoiceVerificationError, EncryptedChoice, ProveSum},
+    group::Group,
+    proofs::TranscriptForGroup,
+    proto_labels, Ciphertext, Keypair, PublicKey, SecretKey, VerificationError,
+};
+
+#[cfg(feature = "serde")]
+use crate::serde::{ScalarHelper, VecHelper};
+
+/// Zero-knowledge proof of knowledge of a secret key corresponding to one of public keys
+/// in an eligibility list, without revealing which one.
+///
+/// # Construction
+///
+/// The proof is a ring signature in the form of [Abe, Ohkubo and Suzuki][AOS]. For the list
+/// of public keys `K_0, …, K_{n-1}`, it consists of the initial challenge `c_0` and responses
+/// `s_0, …, s_{n-1}`, such that the chain of challenges
+/// `c_{i+1} = H(i, [s_i]G - [c_i]K_i)` computed over all keys loops back, i.e.,
+/// `c_n = c_0`. The hash function `H` is bound to the list of keys and to the proof context
+/// (e.g., ballot ciphertexts in [`EligibleChoice`]). The proof size is linear
+/// in the size of the list.
+///
+/// The proof does not prevent the same credential from being used several times;
+/// if this is a concern, it should be addressed on the application level.
+///
+/// [AOS]: https://link.springer.com/content/pdf/10.1007/3-540-36178-2_26.pdf
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct EligibilityProof<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))]
+    challenge: G::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ScalarHelper<G>, 1>"))]
+    responses: Vec<G::Scalar>,
+}
+
+impl<G: Group> EligibilityProof<G> {
+    fn initialize_transcript(transcript: &mut Transcript, eligibility_list: &[PublicKey<G>]) {
+        transcript.start_proof(proto_labels::ELIGIBILITY);
+        transcript.append_u64(b"n", eligibility_list.len() as u64);
+        for key in eligibility_list {
+            transcript.append_element_bytes(b"K", key.as_bytes());
+        }
+    }
+
+    fn link_challenge(transcript: &Transcript, index: usize, commitment: &G::Element) -> G::Scalar {
+        let mut transcript = transcript.clone();
+        transcript.append_u64(b"i", index as u64);
+        transcript.append_element::<G>(b"R", commitment);
+        transcript.challenge_scalar::<G>(b"c")
+    }
+
+    /// Creates a proof that the `credential` is contained in the `eligibility_list`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the public key of `credential` is not in the `eligibility_list`.
+    pub fn new<R: CryptoRng + RngCore>(
+        credential: &Keypair<G>,
+        eligibility_list: &[PublicKey<G>],
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Self {
+        let signer_index = eligibility_list
+            .iter()
+            .position(|key| key == credential.public())
+            .expect("credential is not in the eligibility list");
+        Self::initialize_transcript(transcript, eligibility_list);
+
+        let n = eligibility_list.len();
+        let mut challenges = vec![G::Scalar::default(); n];
+        let mut responses = vec![G::Scalar::default(); n];
+
+        let randomness = SecretKey::<G>::generate(rng);
+        let commitment = G::mul_generator(randomness.expose_scalar());
+        let mut challenge = Self::link_challenge(transcript, signer_index, &commitment);
+        for offset in 1..n {
+            let i = (signer_index + offset) % n;
+            challenges[i] = challenge;
+            responses[i] = *SecretKey::<G>::generate(rng).expose_scalar();
+            let commitment = G::vartime_double_mul_generator(
+                &-challenge,
+                eligibility_list[i].as_element(),
+                &responses[i],
+            );
+            challenge = Self::link_challenge(transcript, i, &commitment);
+        }
+        challenges[signer_index] = challenge;
+        let response = randomness + credential.secret() * &challenge;
+        responses[signer_index] = *response.expose_scalar();
+
+        Self {
+            challenge: challenges[0],
+            responses,
+        }
+    }
+
+    /// Verifies this proof against the `eligibility_list`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this proof does not verify.
+    pub fn verify(
+        &self,
+        eligibility_list: &[PublicKey<G>],
+        transcript: &mut Transcript,
+    ) -> Result<(), VerificationError> {
+        VerificationError::check_lengths(
+            "eligibility list",
+            self.responses.len(),
+            eligibility_list.len(),
+        )?;
+        Self::initialize_transcript(transcript, eligibility_list);
+
+        let mut challenge = self.challenge;
+        for (i, (key, response)) in eligibility_list.iter().zip(&self.responses).enumerate() {
+            let commitment =
+                G::vartime_double_mul_generator(&-challenge, key.as_element(), response);
+            challenge = Self::link_challenge(transcript, i, &commitment);
+        }
+
+        if challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(VerificationError::ChallengeMismatch)
+        }
+    }
+}
+
+/// [`EncryptedChoice`] together with an [`EligibilityProof`] that the voter holds a credential
+/// from a published eligibility list.
+///
+/// The eligibility proof is bound to the choice ciphertexts, so it cannot be transplanted
+/// to another choice. Eligibility and well-formedness of the choice are verified together
+/// via [`Self::verify()`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     app::{ChoiceParams, EligibleChoice}, group::Ristretto, Keypair,
+/// # };
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+/// let choice_params = ChoiceParams::single(pk, 3);
+/// let credentials: Vec<_> = (0..5)
+///     .map(|_| Keypair::<Ristretto>::generate(&mut rng))
+///     .collect();
+/// let eligibility_list: Vec<_> =
+///     credentials.iter().map(|keypair| keypair.public().clone()).collect();
+///
+/// let choice = EligibleChoice::new(
+///     &choice_params,
+///     &[false, true, false],
+///     &credentials[3],
+///     &eligibility_list,
+///     &mut rng,
+/// );
+/// let choices = choice.verify(&choice_params, &eligibility_list)?;
+/// assert_eq!(choices.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct EligibleChoice<G: Group, S: ProveSum<G>> {
+    choice: EncryptedChoice<G, S>,
+    eligibility_proof: EligibilityProof<G>,
+}
+
+impl<G: Group, S: ProveSum<G>> fmt::Debug for EligibleChoice<G, S>
+where
+    EncryptedChoice<G, S>: fmt::Debug,
+    EligibilityProof<G>: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("EligibleChoice")
+            .field("choice", &self.choice)
+            .field("eligibility_proof", &self.eligibility_proof)
+            .finish()
+    }
+}
+
+impl<G: Group, S: ProveSum<G>> Clone for EligibleChoice<G, S>
+where
+    EncryptedChoice<G, S>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            choice: self.choice.clone(),
+            eligibility_proof: self.eligibility_proof.clone(),
+        }
+    }
+}
+
+impl<G: Group, S: ProveSum<G>> EligibleChoice<G, S> {
+    fn transcript(receiver: &PublicKey<G>, choices: &[Ciphertext<G>]) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::CHOICE_ELIGIBILITY);
+        transcript.append_element_bytes(b"K", receiver.as_bytes());
+        transcript.append_u64(b"n", choices.len() as u64);
+        for choice in choices {
+            transcript.append_element::<G>(b"R", &choice.random_element);
+            transcript.append_element::<G>(b"B", &choice.blinded_element);
+        }
+        transcript
+    }
+
+    /// Creates an encrypted choice (see [`EncryptedChoice::new()`]) and proves that
+    /// the `credential` is contained in the `eligibility_list`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`EncryptedChoice::new()`], or if the public key
+    /// of `credential` is not in the `eligibility_list`.
+    pub fn new<R: CryptoRng + RngCore>(
+        params: &ChoiceParams<G, S>,
+        choices: &[bool],
+        credential: &Keypair<G>,
+        eligibility_list: &[PublicKey<G>],
+        rng: &mut R,
+    ) -> Self {
+        let choice = EncryptedChoice::new(params, choices, rng);
+        let mut transcript = Self::transcript(params.receiver(), choice.choices_unchecked());
+        let eligibility_proof =
+            EligibilityProof::new(credential, eligibility_list, &mut transcript, rng);
+        Self {
+            choice,
+            eligibility_proof,
+        }
+    }
+
+    /// Verifies both the encrypted choice and the eligibility proof, returning Boolean
+    /// ciphertexts for all options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the proofs do not verify.
+    pub fn verify(
+        &self,
+        params: &ChoiceParams<G, S>,
+        eligibility_list: &[PublicKey<G>],
+    ) -> Result<&[Ciphertext<G>], ChoiceVerificationError> {
+        let choices = self.choice.verify(params)?;
+        let mut transcript = Self::transcript(params.receiver(), choices);
+        self.eligibility_proof
+            .verify(eligibility_list, &mut transcript)
+            .map_err(ChoiceVerificationError::Eligibility)?;
+        Ok(choices)
+    }
+
+    /// Returns the encrypted choice **without** checking eligibility.
+    pub fn choice_unchecked(&self) -> &EncryptedChoice<G, S> {
+        &self.choice
+    }
+
+    /// Returns the eligibility proof.
+    pub fn eligibility_proof(&self) -> &EligibilityProof<G> {
+        &self.eligibility_proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::group::Ristretto;
+
+    #[test]
+    fn eligibility_proof_is_bound_to_choice_and_list() {
+        let mut rng = thread_rng();
+        let (receiver, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let params = ChoiceParams::multi(receiver, 3);
+        let credentials: Vec<_> = (0..4)
+            .map(|_| Keypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let eligibility_list: Vec<_> = credentials
+            .iter()
+            .map(|keypair| keypair.public().clone())
+            .collect();
+
+        for credential in &credentials {
+            let choices = [true, false, true];
+            let choice =
+                EligibleChoice::new(&params, &choices, credential, &eligibility_list, &mut rng);
+            choice.verify(&params, &eligibility_list).unwrap();
+
+            let mut other_list = eligibility_list.clone();
+            other_list.swap(0, 1);
+            let err = choice.verify(&params, &other_list).unwrap_err();
+            assert!(
+                matches!(err, ChoiceVerificationError::Eligibility(_)),
+                "{err:?}"
+            );
+            let err = choice.verify(&params, &eligibility_list[..3]).unwrap_err();
+            assert!(
+                matches!(err, ChoiceVerificationError::Eligibility(_)),
+                "{err:?}"
+            );
+
+            let other_choice = EncryptedChoice::new(&params, &choices, &mut rng);
+            let transplanted = EligibleChoice {
+                choice: other_choice,
+                eligibility_proof: choice.eligibility_proof.clone(),
+            };
+            let err = transplanted.verify(&params, &eligibility_list).unwrap_err();
+            assert!(
+                matches!(err, ChoiceVerificationError::Eligibility(_)),
+                "{err:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "credential is not in the eligibility list")]
+    fn eligibility_proof_panics_for_unknown_credential() {
+        let mut rng = thread_rng();
+        let credential = Keypair::<Ristretto>::generate(&mut rng);
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        EligibilityProof::new(
+            &credential,
+            &[other.public().clone()],
+            &mut Transcript::new(b"test"),
+            &mut rng,
+        );
+    }
+}
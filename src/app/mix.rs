@@ -0,0 +1,726 @@
+//! Mix-net pipeline combining shuffling, re-randomization and threshold decryption.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use core::fmt;
+
+use crate::{
+    alloc::Vec,
+    group::Group,
+    proto_labels,
+    sharing::{ActiveParticipant, PublicKeySet},
+    CandidateDecryption, Ciphertext, LogEqualityProof, PublicKey, VerifiableDecryption,
+    VerificationError,
+};
+
+/// Verifiable shuffle of ciphertexts used in a [`MixPipeline`].
+///
+/// A shuffle permutes and re-randomizes input ciphertexts and provides a zero-knowledge proof
+/// that outputs correspond to inputs. The crate does not provide shuffle implementations;
+/// they should be supplied by the caller.
+pub trait Shuffle<G: Group> {
+    /// Proof of shuffle correctness.
+    type Proof: Clone + fmt::Debug;
+
+    /// Shuffles `inputs` encrypted for the `receiver`.
+    fn shuffle<R: CryptoRng + RngCore>(
+        &self,
+        receiver: &PublicKey<G>,
+        inputs: &[Ciphertext<G>],
+        rng: &mut R,
+    ) -> (Vec<Ciphertext<G>>, Self::Proof);
+
+    /// Verifies that `outputs` are a shuffle of `inputs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    fn verify(
+        &self,
+        receiver: &PublicKey<G>,
+        inputs: &[Ciphertext<G>],
+        outputs: &[Ciphertext<G>],
+        proof: &Self::Proof,
+    ) -> Result<(), VerificationError>;
+}
+
+/// Proof for a single stage of a [`MixPipeline`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))
+)]
+#[non_exhaustive]
+pub enum MixStageProof<G: Group, P> {
+    /// Proof of a [`Shuffle`].
+    Shuffle(P),
+    /// Re-randomization: each output ciphertext is obtained by adding an encryption of zero
+    /// to the corresponding input ciphertext. The proofs are zero encryption proofs
+    /// (see [`PublicKey::verify_zero()`]) for differences between outputs and inputs.
+    ReRandomization(Vec<LogEqualityProof<G>>),
+}
+
+/// Record of a single stage of a [`MixPipeline`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))
+)]
+pub struct MixStage<G: Group, P> {
+    input_hash: [u8; 32],
+    outputs: Vec<Ciphertext<G>>,
+    output_hash: [u8; 32],
+    proof: MixStageProof<G, P>,
+}
+
+impl<G: Group, P> MixStage<G, P> {
+    /// Returns the SHA-256 hash of input ciphertexts for this stage.
+    pub fn input_hash(&self) -> &[u8; 32] {
+        &self.input_hash
+    }
+
+    /// Returns output ciphertexts of this stage.
+    pub fn outputs(&self) -> &[Ciphertext<G>] {
+        &self.outputs
+    }
+
+    /// Returns the SHA-256 hash of output ciphertexts of this stage.
+    pub fn output_hash(&self) -> &[u8; 32] {
+        &self.output_hash
+    }
+
+    /// Returns the proof for this stage.
+    pub fn proof(&self) -> &MixStageProof<G, P> {
+        &self.proof
+    }
+}
+
+/// Decryption shares of a single participant for all ciphertexts output by a [`MixPipeline`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct MixDecryptionShares<G: Group> {
+    index: usize,
+    shares: Vec<(CandidateDecryption<G>, LogEqualityProof<G>)>,
+}
+
+impl<G: Group> MixDecryptionShares<G> {
+    /// Creates decryption shares of the specified `participant` for `ciphertexts`
+    /// (usually, [`MixPipeline::outputs()`]).
+    pub fn new<R: CryptoRng + RngCore>(
+        participant: &ActiveParticipant<G>,
+        ciphertexts: &[Ciphertext<G>],
+        rng: &mut R,
+    ) -> Self {
+        let shares = ciphertexts
+            .iter()
+            .map(|&ciphertext| {
+                let (share, proof) = participant.decrypt_share(ciphertext, rng);
+                (share.into(), proof)
+            })
+            .collect();
+        Self {
+            index: participant.index(),
+            shares,
+        }
+    }
+
+    /// Returns the 0-based index of the participant that has produced these shares.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn verify(
+        &self,
+        key_set: &PublicKeySet<G>,
+        ciphertexts: &[Ciphertext<G>],
+    ) -> Result<Vec<VerifiableDecryption<G>>, VerificationError> {
+        if self.index >= key_set.params().shares {
+            return Err(VerificationError::UnknownParticipant { index: self.index });
+        }
+        VerificationError::check_lengths(
+            "decryption shares",
+            self.shares.len(),
+            ciphertexts.len(),
+        )?;
+        self.shares
+            .iter()
+            .zip(ciphertexts)
+            .map(|((share, proof), &ciphertext)| {
+                key_set.verify_share(*share, ciphertext, self.index, proof)
+            })
+            .collect()
+    }
+}
+
+/// Errors that can occur in a [`MixPipeline`] or when verifying a [`MixAudit`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MixError {
+    /// Input hash of a stage does not match the output hash of the previous stage,
+    /// or the output hash does not match the stage outputs.
+    HashMismatch {
+        /// 0-based index of the stage.
+        stage: usize,
+    },
+    /// Proof for a stage does not verify.
+    InvalidStage {
+        /// 0-based index of the stage.
+        stage: usize,
+        /// Underlying verification error.
+        err: VerificationError,
+    },
+    /// Decryption shares provided by a participant do not verify.
+    InvalidShares {
+        /// 0-based index of the participant.
+        participant: usize,
+        /// Underlying verification error.
+        err: VerificationError,
+    },
+    /// Decryption shares from the participant were already provided.
+    DuplicateShares(usize),
+    /// Not enough decryption shares were provided.
+    InsufficientShares,
+    /// A stage was added after decryption shares have been provided.
+    DecryptionStarted,
+}
+
+impl fmt::Display for MixError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashMismatch { stage } => {
+                write!(
+                    formatter,
+                    "ciphertext hashes do not match for stage #{stage}"
+                )
+            }
+            Self::InvalidStage { stage, err } => {
+                write!(formatter, "proof for stage #{stage} is invalid: {err}")
+            }
+            Self::InvalidShares { participant, err } => write!(
+                formatter,
+                "decryption shares from participant #{participant} are invalid: {err}"
+            ),
+            Self::DuplicateShares(participant) => write!(
+                formatter,
+                "decryption shares from participant #{participant} were already provided"
+            ),
+            Self::InsufficientShares => formatter.write_str("not enough decryption shares"),
+            Self::DecryptionStarted => {
+                formatter.write_str("cannot add a stage after decryption has started")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidStage { err, .. } | Self::InvalidShares { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn hash_ciphertexts<G: Group>(ciphertexts: &[Ciphertext<G>]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(proto_labels::MIX);
+    hasher.update((ciphertexts.len() as u64).to_le_bytes());
+    for ciphertext in ciphertexts {
+        hasher.update(ciphertext.to_bytes());
+    }
+    hasher.finalize().into()
+}
+
+fn verify_rerandomization<G: Group>(
+    receiver: &PublicKey<G>,
+    inputs: &[Ciphertext<G>],
+    outputs: &[Ciphertext<G>],
+    proofs: &[LogEqualityProof<G>],
+) -> Result<(), VerificationError> {
+    VerificationError::check_lengths("outputs", outputs.len(), inputs.len())?;
+    VerificationError::check_lengths("proofs", proofs.len(), inputs.len())?;
+    for ((&input, &output), proof) in inputs.iter().zip(outputs).zip(proofs) {
+        receiver.verify_zero(output - input, proof)?;
+    }
+    Ok(())
+}
+
+/// Audit bundle produced by a [`MixPipeline`]. Contains all information necessary
+/// to independently verify the pipeline: input ciphertexts, records for all stages,
+/// and decryption shares.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))
+)]
+pub struct MixAudit<G: Group, P> {
+    inputs: Vec<Ciphertext<G>>,
+    stages: Vec<MixStage<G, P>>,
+    decryption_shares: Vec<MixDecryptionShares<G>>,
+}
+
+impl<G: Group, P> MixAudit<G, P> {
+    /// Returns input ciphertexts of the pipeline.
+    pub fn inputs(&self) -> &[Ciphertext<G>] {
+        &self.inputs
+    }
+
+    /// Returns records for all pipeline stages.
+    pub fn stages(&self) -> &[MixStage<G, P>] {
+        &self.stages
+    }
+
+    /// Returns decryption shares for the pipeline outputs.
+    pub fn decryption_shares(&self) -> &[MixDecryptionShares<G>] {
+        &self.decryption_shares
+    }
+
+    /// Verifies this audit bundle, returning decryptions of the pipeline outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the stage hashes or proofs do not verify.
+    pub fn verify<S>(
+        &self,
+        key_set: &PublicKeySet<G>,
+        shuffle: &S,
+    ) -> Result<Vec<VerifiableDecryption<G>>, MixError>
+    where
+        S: Shuffle<G, Proof = P>,
+    {
+        let receiver = key_set.shared_key();
+        let mut inputs = self.inputs.as_slice();
+        let mut input_hash = hash_ciphertexts(inputs);
+        for (i, stage) in self.stages.iter().enumerate() {
+            if stage.input_hash != input_hash
+                || stage.output_hash != hash_ciphertexts(&stage.outputs)
+            {
+                return Err(MixError::HashMismatch { stage: i });
+            }
+
+            match &stage.proof {
+                MixStageProof::Shuffle(proof) => {
+                    shuffle.verify(receiver, inputs, &stage.outputs, proof)
+                }
+                MixStageProof::ReRandomization(proofs) => {
+                    verify_rerandomization(receiver, inputs, &stage.outputs, proofs)
+                }
+            }
+            .map_err(|err| MixError::InvalidStage { stage: i, err })?;
+
+            inputs = &stage.outputs;
+            input_hash = stage.output_hash;
+        }
+
+        let verified_shares = self
+            .decryption_shares
+            .iter()
+            .map(|shares| {
+                shares
+                    .verify(key_set, inputs)
+                    .map(|verified| (shares.index, verified))
+                    .map_err(|err| MixError::InvalidShares {
+                        participant: shares.index,
+                        err,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        combine_decryptions(key_set, inputs.len(), &verified_shares)
+    }
+}
+
+fn combine_decryptions<G: Group>(
+    key_set: &PublicKeySet<G>,
+    ciphertext_count: usize,
+    verified_shares: &[(usize, Vec<VerifiableDecryption<G>>)],
+) -> Result<Vec<VerifiableDecryption<G>>, MixError> {
+    let params = key_set.params();
+    (0..ciphertext_count)
+        .map(|i| {
+            let shares = verified_shares
+                .iter()
+                .map(|(index, shares)| (*index, shares[i]));
+            params
+                .combine_shares(shares)
+                .ok_or(MixError::InsufficientShares)
+        })
+        .collect()
+}
+
+/// Orchestrator for a mix-net pipeline: a sequence of [`Shuffle`]s and re-randomizations
+/// followed by threshold decryption.
+///
+/// The pipeline tracks proofs for each stage and links stages via hashes of their input
+/// and output ciphertexts. Stages can be performed locally (e.g., [`Self::rerandomize()`])
+/// or by external operators, in which case their outputs are checked before being added
+/// to the pipeline (e.g., [`Self::insert_shuffle()`]). Once the pipeline is finished,
+/// it emits a [`MixAudit`] that can be independently verified.
+///
+/// # Examples
+///
+/// See [`Shuffle`] for an example of a shuffle implementation; here, we assume
+/// that one is available.
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     app::{MixDecryptionShares, MixPipeline, Shuffle},
+/// #     group::{Group, Ristretto}, sharing::*, Ciphertext, DiscreteLogTable, PublicKey,
+/// #     VerificationError,
+/// # };
+/// # use rand::{CryptoRng, RngCore, thread_rng};
+/// # struct NoOpShuffle;
+/// # impl<G: Group> Shuffle<G> for NoOpShuffle {
+/// #     type Proof = ();
+/// #     fn shuffle<R: CryptoRng + RngCore>(
+/// #         &self, _: &PublicKey<G>, inputs: &[Ciphertext<G>], _: &mut R,
+/// #     ) -> (Vec<Ciphertext<G>>, ()) {
+/// #         (inputs.to_vec(), ())
+/// #     }
+/// #     fn verify(
+/// #         &self, _: &PublicKey<G>, inputs: &[Ciphertext<G>], outputs: &[Ciphertext<G>], _: &(),
+/// #     ) -> Result<(), VerificationError> {
+/// #         assert_eq!(inputs.len(), outputs.len());
+/// #         Ok(())
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..3)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let inputs: Vec<_> = (0_u64..5)
+///     .map(|i| key_set.shared_key().encrypt(i, &mut rng))
+///     .collect();
+/// let mut pipeline = MixPipeline::new(&key_set, NoOpShuffle, inputs);
+/// pipeline.shuffle(&mut rng)?;
+/// pipeline.rerandomize(&mut rng)?;
+/// for participant in &participants[..2] {
+///     let shares = MixDecryptionShares::new(participant, pipeline.outputs(), &mut rng);
+///     pipeline.insert_decryption_shares(shares)?;
+/// }
+/// let (decryptions, audit) = pipeline.finish()?;
+///
+/// // The audit bundle can be verified independently.
+/// let verified_decryptions = audit.verify(&key_set, &NoOpShuffle)?;
+/// let lookup_table = DiscreteLogTable::new(0..5);
+/// let outputs = audit.stages().last().unwrap().outputs();
+/// for (decryption, &output) in verified_decryptions.iter().zip(outputs) {
+///     assert!(decryption.decrypt(output, &lookup_table).is_some());
+/// }
+/// # assert_eq!(decryptions.len(), verified_decryptions.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct MixPipeline<'a, G: Group, S: Shuffle<G>> {
+    key_set: &'a PublicKeySet<G>,
+    shuffle: S,
+    inputs: Vec<Ciphertext<G>>,
+    stages: Vec<MixStage<G, S::Proof>>,
+    decryption_shares: Vec<MixDecryptionShares<G>>,
+    verified_shares: Vec<(usize, Vec<VerifiableDecryption<G>>)>,
+}
+
+impl<G: Group + fmt::Debug, S: Shuffle<G>> fmt::Debug for MixPipeline<'_, G, S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MixPipeline")
+            .field("key_set", self.key_set)
+            .field("inputs", &self.inputs)
+            .field("stages", &self.stages)
+            .field("decryption_shares", &self.decryption_shares)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, G: Group, S: Shuffle<G>> MixPipeline<'a, G, S> {
+    /// Creates a pipeline for the specified `inputs` encrypted for the shared key
+    /// of the `key_set`.
+    pub fn new(key_set: &'a PublicKeySet<G>, shuffle: S, inputs: Vec<Ciphertext<G>>) -> Self {
+        Self {
+            key_set,
+            shuffle,
+            inputs,
+            stages: Vec::new(),
+            decryption_shares: Vec::new(),
+            verified_shares: Vec::new(),
+        }
+    }
+
+    /// Returns the output ciphertexts of the last stage (or the inputs if no stages
+    /// were performed yet).
+    pub fn outputs(&self) -> &[Ciphertext<G>] {
+        self.stages
+            .last()
+            .map_or(self.inputs.as_slice(), |stage| &stage.outputs)
+    }
+
+    /// Returns records for all stages performed so far.
+    pub fn stages(&self) -> &[MixStage<G, S::Proof>] {
+        &self.stages
+    }
+
+    fn push_stage(
+        &mut self,
+        outputs: Vec<Ciphertext<G>>,
+        proof: MixStageProof<G, S::Proof>,
+    ) -> Result<(), MixError> {
+        if !self.decryption_shares.is_empty() {
+            return Err(MixError::DecryptionStarted);
+        }
+        let input_hash = hash_ciphertexts(self.outputs());
+        let output_hash = hash_ciphertexts(&outputs);
+        self.stages.push(MixStage {
+            input_hash,
+            outputs,
+            output_hash,
+            proof,
+        });
+        Ok(())
+    }
+
+    /// Shuffles the current outputs of the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decryption shares have already been provided.
+    pub fn shuffle<R: CryptoRng + RngCore>(&mut self, rng: &mut R) -> Result<(), MixError> {
+        let receiver = self.key_set.shared_key();
+        let (outputs, proof) = self.shuffle.shuffle(receiver, self.outputs(), rng);
+        self.push_stage(outputs, MixStageProof::Shuffle(proof))
+    }
+
+    /// Inserts a shuffle performed by an external operator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify, or if decryption shares have already
+    /// been provided.
+    pub fn insert_shuffle(
+        &mut self,
+        outputs: Vec<Ciphertext<G>>,
+        proof: S::Proof,
+    ) -> Result<(), MixError> {
+        let receiver = self.key_set.shared_key();
+        self.shuffle
+            .verify(receiver, self.outputs(), &outputs, &proof)
+            .map_err(|err| MixError::InvalidStage {
+                stage: self.stages.len(),
+                err,
+            })?;
+        self.push_stage(outputs, MixStageProof::Shuffle(proof))
+    }
+
+    /// Re-randomizes the current outputs of the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decryption shares have already been provided.
+    pub fn rerandomize<R: CryptoRng + RngCore>(&mut self, rng: &mut R) -> Result<(), MixError> {
+        let receiver = self.key_set.shared_key();
+        let (outputs, proofs) = self
+            .outputs()
+            .iter()
+            .map(|&input| {
+                let (zero, proof) = receiver.encrypt_zero(rng);
+                (input + zero, proof)
+            })
+            .unzip();
+        self.push_stage(outputs, MixStageProof::ReRandomization(proofs))
+    }
+
+    /// Inserts decryption shares from a participant for the current outputs of the pipeline.
+    /// After this, no more stages can be added to the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shares do not verify, or if shares from the same participant
+    /// were already provided.
+    pub fn insert_decryption_shares(
+        &mut self,
+        shares: MixDecryptionShares<G>,
+    ) -> Result<(), MixError> {
+        if self
+            .decryption_shares
+            .iter()
+            .any(|existing| existing.index == shares.index)
+        {
+            return Err(MixError::DuplicateShares(shares.index));
+        }
+        let verified =
+            shares
+                .verify(self.key_set, self.outputs())
+                .map_err(|err| MixError::InvalidShares {
+                    participant: shares.index,
+                    err,
+                })?;
+        self.verified_shares.push((shares.index, verified));
+        self.decryption_shares.push(shares);
+        Ok(())
+    }
+
+    /// Finishes the pipeline, returning decryptions of its outputs together with
+    /// the audit bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not enough decryption shares were provided.
+    #[allow(clippy::type_complexity)] // not that complex
+    pub fn finish(self) -> Result<(Vec<VerifiableDecryption<G>>, MixAudit<G, S::Proof>), MixError> {
+        let decryptions =
+            combine_decryptions(self.key_set, self.outputs().len(), &self.verified_shares)?;
+        let audit = MixAudit {
+            inputs: self.inputs,
+            stages: self.stages,
+            decryption_shares: self.decryption_shares,
+        };
+        Ok((decryptions, audit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{seq::SliceRandom, thread_rng};
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{Dealer, Params},
+        DiscreteLogTable,
+    };
+
+    /// Shuffle with a public permutation, which is only useful for testing.
+    struct PublicPermutation;
+
+    impl Shuffle<Ristretto> for PublicPermutation {
+        type Proof = (Vec<usize>, Vec<LogEqualityProof<Ristretto>>);
+
+        fn shuffle<R: CryptoRng + RngCore>(
+            &self,
+            receiver: &PublicKey<Ristretto>,
+            inputs: &[Ciphertext<Ristretto>],
+            rng: &mut R,
+        ) -> (Vec<Ciphertext<Ristretto>>, Self::Proof) {
+            let mut permutation: Vec<_> = (0..inputs.len()).collect();
+            permutation.shuffle(rng);
+            let (outputs, proofs) = permutation
+                .iter()
+                .map(|&i| {
+                    let (zero, proof) = receiver.encrypt_zero(rng);
+                    (inputs[i] + zero, proof)
+                })
+                .unzip();
+            (outputs, (permutation, proofs))
+        }
+
+        fn verify(
+            &self,
+            receiver: &PublicKey<Ristretto>,
+            inputs: &[Ciphertext<Ristretto>],
+            outputs: &[Ciphertext<Ristretto>],
+            (permutation, proofs): &Self::Proof,
+        ) -> Result<(), VerificationError> {
+            let permuted: Vec<_> = permutation.iter().map(|&i| inputs[i]).collect();
+            verify_rerandomization(receiver, &permuted, outputs, proofs)
+        }
+    }
+
+    #[test]
+    fn mix_pipeline_with_external_stages() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..3)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let inputs: Vec<_> = (0_u64..8)
+            .map(|i| key_set.shared_key().encrypt(i, &mut rng))
+            .collect();
+        let mut pipeline = MixPipeline::new(&key_set, PublicPermutation, inputs);
+        pipeline.shuffle(&mut rng).unwrap();
+
+        // Shuffle by an external operator.
+        let (outputs, proof) =
+            PublicPermutation.shuffle(key_set.shared_key(), pipeline.outputs(), &mut rng);
+        let mut bogus_outputs = outputs.clone();
+        bogus_outputs.swap(0, 1);
+        let err = pipeline
+            .insert_shuffle(bogus_outputs, proof.clone())
+            .unwrap_err();
+        assert!(
+            matches!(err, MixError::InvalidStage { stage: 1, .. }),
+            "{err:?}"
+        );
+        pipeline.insert_shuffle(outputs, proof).unwrap();
+        pipeline.rerandomize(&mut rng).unwrap();
+        assert_eq!(pipeline.stages().len(), 3);
+
+        let shares = MixDecryptionShares::new(&participants[2], pipeline.outputs(), &mut rng);
+        let mut out_of_bounds_shares = shares.clone();
+        out_of_bounds_shares.index = 3;
+        let err = pipeline
+            .insert_decryption_shares(out_of_bounds_shares)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                MixError::InvalidShares {
+                    participant: 3,
+                    err: VerificationError::UnknownParticipant { index: 3 },
+                }
+            ),
+            "{err:?}"
+        );
+        pipeline.insert_decryption_shares(shares.clone()).unwrap();
+        let err = pipeline.insert_decryption_shares(shares).unwrap_err();
+        assert!(matches!(err, MixError::DuplicateShares(2)), "{err:?}");
+        let err = pipeline.rerandomize(&mut rng).unwrap_err();
+        assert!(matches!(err, MixError::DecryptionStarted), "{err:?}");
+
+        let shares = MixDecryptionShares::new(&participants[0], pipeline.outputs(), &mut rng);
+        pipeline.insert_decryption_shares(shares).unwrap();
+        let (decryptions, mut audit) = pipeline.finish().unwrap();
+
+        let lookup_table = DiscreteLogTable::new(0..8);
+        let outputs = audit.stages.last().unwrap().outputs();
+        let mut values: Vec<_> = decryptions
+            .iter()
+            .zip(outputs)
+            .map(|(decryption, &output)| decryption.decrypt(output, &lookup_table).unwrap())
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..8).collect::<Vec<_>>());
+
+        let verified = audit.verify(&key_set, &PublicPermutation).unwrap();
+        assert_eq!(verified.len(), decryptions.len());
+
+        audit.stages[1].outputs.swap(0, 1);
+        let err = audit.verify(&key_set, &PublicPermutation).unwrap_err();
+        assert!(
+            matches!(err, MixError::HashMismatch { stage: 1 }),
+            "{err:?}"
+        );
+    }
+}
@@ -2,19 +2,52 @@
 //!
 //! For now, the applications are:
 //!
+//! - [`AuditBundle`]. Single-file container for all public artifacts of an election
+//!   (keys, ballots, mix-net and tally proofs, results) with integrity hashes.
+//! - [`EncryptedBit`]. Encryption of a single bit with a proof of validity,
+//!   together with provable homomorphic XOR.
 //! - [`EncryptedChoice`]. Single-choice or multi-choice selection from a predefined
-//!   list of options, with summable selection ciphertexts.
+//!   list of options, with summable selection ciphertexts. For choices with many options,
+//!   [`ChunkedChoiceEncryptor`] and [`ChunkedChoiceVerifier`] allow to process options in chunks.
+//!   [`EligibleChoice`] additionally proves that the voter holds a credential
+//!   from an eligibility list.
+//! - [`MixPipeline`]. Orchestration of a mix-net: a sequence of verifiable shuffles
+//!   and re-randomizations followed by threshold decryption, with an auditable record
+//!   of all stages.
 //! - [`QuadraticVotingBallot`]. [Quadratic voting] on a predefined list of options,
 //!   with summable selection ciphertexts.
+//! - [`Tally`]. Per-option encrypted totals that can be partitioned into shards
+//!   decrypted independently (e.g., in parallel or by different committees).
+//!   [`DigitTally`] splits totals into digits, which are decrypted progressively.
+//! - [`RunningTally`]. Stream of running encrypted totals, each update of which
+//!   can be checked incrementally by a [`TallyObserver`].
 //!
 //! [Quadratic voting]: https://en.wikipedia.org/wiki/Quadratic_voting
 
+mod audit;
+mod bit;
 mod choice;
+mod chunked_choice;
+mod eligibility;
+mod mix;
 mod quadratic_voting;
+mod tally;
 
 pub use self::{
+    audit::{AuditBundle, AuditError, AuditHashes},
+    bit::EncryptedBit,
     choice::{
-        ChoiceParams, ChoiceVerificationError, EncryptedChoice, MultiChoice, ProveSum, SingleChoice,
+        ChoiceOpeningError, ChoiceParams, ChoiceProofs, ChoiceRandomness, ChoiceVerificationError,
+        EncryptedChoice, ExactChoice, MultiChoice, ProveSum, SingleChoice,
+    },
+    chunked_choice::{ChoiceChunk, ChunkedChoiceEncryptor, ChunkedChoiceVerifier},
+    eligibility::{EligibilityProof, EligibleChoice},
+    mix::{MixAudit, MixDecryptionShares, MixError, MixPipeline, MixStage, MixStageProof, Shuffle},
+    quadratic_voting::{
+        QuadraticVotingBallot, QuadraticVotingError, QuadraticVotingParams, QuadraticVotingProofs,
+    },
+    tally::{
+        DigitTally, RunningTally, ShardDecryptionShares, ShardResult, Tally, TallyAudit,
+        TallyError, TallyObserver, TallyShard, TallyUpdate, TallyUpdateError,
     },
-    quadratic_voting::{QuadraticVotingBallot, QuadraticVotingError, QuadraticVotingParams},
 };
@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use core::fmt;
 
 use crate::{
-    alloc::Vec, group::Group, Ciphertext, PreparedRange, PublicKey, RangeDecomposition, RangeProof,
-    SumOfSquaresProof, VerificationError,
+    alloc::Vec, group::Group, math::isqrt, proto_labels, Ciphertext, PreparedRange, PublicKey,
+    RangeDecomposition, RangeProof, SumOfSquaresProof, VerificationError,
 };
 
 /// [Quadratic voting] parameters prepared for a certain [`Group`].
@@ -123,26 +123,6 @@ impl<G: Group> QuadraticVotingParams<G> {
     }
 }
 
-/// Integer square root of a `u64` number. Uses the digit-by-digit calculation method in base 2;
-/// see https://en.wikipedia.org/wiki/Methods_of_computing_square_roots#Binary_numeral_system_(base_2)
-fn isqrt(mut x: u64) -> u64 {
-    let mut root = 0_u64;
-    let mut power_of_4 = 1_u64 << 62;
-    while power_of_4 > x {
-        power_of_4 /= 4;
-    }
-    while power_of_4 > 0 {
-        if x >= root + power_of_4 {
-            x -= root + power_of_4;
-            root = root / 2 + power_of_4;
-        } else {
-            root /= 2;
-        }
-        power_of_4 /= 4;
-    }
-    root
-}
-
 /// Encrypted ballot for [quadratic voting] together with zero-knowledge proofs of correctness.
 ///
 /// # Overview
@@ -252,7 +232,7 @@ impl<G: Group> QuadraticVotingBallot<G> {
                     &params.receiver,
                     &params.vote_count_range,
                     vote_count,
-                    &mut Transcript::new(b"quadratic_voting_variant"),
+                    &mut Transcript::new(proto_labels::QUADRATIC_VOTING_VARIANT),
                     rng,
                 );
                 (ciphertext.generalize(), proof)
@@ -262,7 +242,7 @@ impl<G: Group> QuadraticVotingBallot<G> {
             &params.receiver,
             &params.credit_range,
             credit,
-            &mut Transcript::new(b"quadratic_voting_credit_range"),
+            &mut Transcript::new(proto_labels::QUADRATIC_VOTING_CREDIT_RANGE),
             rng,
         );
         let credit = credit.generalize();
@@ -271,7 +251,7 @@ impl<G: Group> QuadraticVotingBallot<G> {
             votes.iter().map(|(ciphertext, _)| ciphertext),
             &credit,
             &params.receiver,
-            &mut Transcript::new(b"quadratic_voting_credit_equiv"),
+            &mut Transcript::new(proto_labels::QUADRATIC_VOTING_CREDIT_EQUIVALENCE),
             rng,
         );
 
@@ -294,40 +274,172 @@ impl<G: Group> QuadraticVotingBallot<G> {
         &self,
         params: &QuadraticVotingParams<G>,
     ) -> Result<impl Iterator<Item = Ciphertext<G>> + '_, QuadraticVotingError> {
-        params.check_options_count(self.votes.len())?;
+        verify_ballot(
+            params,
+            self.votes.iter().map(|c| (&c.ciphertext, &c.range_proof)),
+            (&self.credit.ciphertext, &self.credit.range_proof),
+            &self.credit_equivalence_proof,
+        )?;
+        Ok(self.votes.iter().map(|c| c.ciphertext))
+    }
 
-        for (i, vote_count) in self.votes.iter().enumerate() {
-            vote_count
-                .range_proof
-                .verify(
-                    &params.receiver,
-                    &params.vote_count_range,
-                    vote_count.ciphertext,
-                    &mut Transcript::new(b"quadratic_voting_variant"),
-                )
-                .map_err(|error| QuadraticVotingError::Variant { index: i, error })?;
-        }
+    /// Splits this ballot into vote ciphertexts for all options, the credit ciphertext
+    /// and a detached [`QuadraticVotingProofs`] object, which can still be verified against
+    /// the ciphertexts.
+    pub fn into_parts(self) -> (Vec<Ciphertext<G>>, Ciphertext<G>, QuadraticVotingProofs<G>) {
+        let (votes, vote_range_proofs) = self
+            .votes
+            .into_iter()
+            .map(|c| (c.ciphertext, c.range_proof))
+            .unzip();
+        let proofs = QuadraticVotingProofs {
+            vote_range_proofs,
+            credit_range_proof: self.credit.range_proof,
+            credit_equivalence_proof: self.credit_equivalence_proof,
+        };
+        (votes, self.credit.ciphertext, proofs)
+    }
 
-        self.credit
-            .range_proof
-            .verify(
-                &params.receiver,
-                &params.credit_range,
-                self.credit.ciphertext,
-                &mut Transcript::new(b"quadratic_voting_credit_range"),
-            )
-            .map_err(QuadraticVotingError::CreditRange)?;
+    /// Restores a ballot from the parts produced by [`Self::into_parts()`].
+    /// The proofs are re-verified against the ciphertexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proofs do not verify.
+    pub fn from_parts(
+        params: &QuadraticVotingParams<G>,
+        votes: Vec<Ciphertext<G>>,
+        credit: Ciphertext<G>,
+        proofs: QuadraticVotingProofs<G>,
+    ) -> Result<Self, QuadraticVotingError> {
+        proofs.verify(params, &votes, &credit)?;
+        let votes = votes
+            .into_iter()
+            .zip(proofs.vote_range_proofs)
+            .map(|(ciphertext, proof)| CiphertextWithRangeProof::new(ciphertext, proof))
+            .collect();
+        Ok(Self {
+            votes,
+            credit: CiphertextWithRangeProof::new(credit, proofs.credit_range_proof),
+            credit_equivalence_proof: proofs.credit_equivalence_proof,
+        })
+    }
+}
+
+fn verify_ballot<'a, G: Group>(
+    params: &QuadraticVotingParams<G>,
+    votes: impl ExactSizeIterator<Item = (&'a Ciphertext<G>, &'a RangeProof<G>)> + Clone,
+    credit: (&Ciphertext<G>, &RangeProof<G>),
+    credit_equivalence_proof: &SumOfSquaresProof<G>,
+) -> Result<(), QuadraticVotingError> {
+    params.check_options_count(votes.len())?;
 
-        self.credit_equivalence_proof
+    for (i, (&ciphertext, range_proof)) in votes.clone().enumerate() {
+        range_proof
             .verify(
-                self.votes.iter().map(|c| &c.ciphertext),
-                &self.credit.ciphertext,
                 &params.receiver,
-                &mut Transcript::new(b"quadratic_voting_credit_equiv"),
+                &params.vote_count_range,
+                ciphertext,
+                &mut Transcript::new(proto_labels::QUADRATIC_VOTING_VARIANT),
             )
-            .map_err(QuadraticVotingError::CreditEquivalence)?;
+            .map_err(|error| QuadraticVotingError::Variant { index: i, error })?;
+    }
 
-        Ok(self.votes.iter().map(|c| c.ciphertext))
+    let (credit, credit_range_proof) = credit;
+    credit_range_proof
+        .verify(
+            &params.receiver,
+            &params.credit_range,
+            *credit,
+            &mut Transcript::new(proto_labels::QUADRATIC_VOTING_CREDIT_RANGE),
+        )
+        .map_err(QuadraticVotingError::CreditRange)?;
+
+    credit_equivalence_proof
+        .verify(
+            votes.map(|(ciphertext, _)| ciphertext),
+            credit,
+            &params.receiver,
+            &mut Transcript::new(proto_labels::QUADRATIC_VOTING_CREDIT_EQUIVALENCE),
+        )
+        .map_err(QuadraticVotingError::CreditEquivalence)
+}
+
+/// Zero-knowledge proofs of a [`QuadraticVotingBallot`] detached from the ballot ciphertexts.
+///
+/// Produced by [`QuadraticVotingBallot::into_parts()`]. The proofs remain verifiable against
+/// the ciphertexts, and can be combined with them back into a ballot
+/// via [`QuadraticVotingBallot::from_parts()`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::*, group::Ristretto, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let params = QuadraticVotingParams::new(receiver.public().clone(), 3, 10);
+/// let ballot = QuadraticVotingBallot::new(&params, &[2, 0, 1], &mut rng);
+///
+/// let (votes, credit, proofs) = ballot.into_parts();
+/// // Proofs may be archived separately from the ciphertexts.
+/// proofs.verify(&params, &votes, &credit)?;
+/// let ballot = QuadraticVotingBallot::from_parts(&params, votes, credit, proofs)?;
+/// assert_eq!(ballot.verify(&params)?.count(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct QuadraticVotingProofs<G: Group> {
+    vote_range_proofs: Vec<RangeProof<G>>,
+    credit_range_proof: RangeProof<G>,
+    credit_equivalence_proof: SumOfSquaresProof<G>,
+}
+
+impl<G: Group> QuadraticVotingProofs<G> {
+    /// Verifies these proofs against vote ciphertexts for all options and the credit
+    /// ciphertext.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if verification fails, or if the number of vote ciphertexts differs
+    /// from the number of options in `params` or from the number of proofs.
+    pub fn verify(
+        &self,
+        params: &QuadraticVotingParams<G>,
+        votes: &[Ciphertext<G>],
+        credit: &Ciphertext<G>,
+    ) -> Result<(), QuadraticVotingError> {
+        if votes.len() != self.vote_range_proofs.len() {
+            return Err(QuadraticVotingError::OptionsLenMismatch {
+                expected: self.vote_range_proofs.len(),
+                actual: votes.len(),
+            });
+        }
+        verify_ballot(
+            params,
+            votes.iter().zip(&self.vote_range_proofs),
+            (credit, &self.credit_range_proof),
+            &self.credit_equivalence_proof,
+        )
+    }
+
+    /// Returns range proofs for vote ciphertexts.
+    pub fn vote_range_proofs(&self) -> &[RangeProof<G>] {
+        &self.vote_range_proofs
+    }
+
+    /// Returns the range proof for the credit ciphertext.
+    pub fn credit_range_proof(&self) -> &RangeProof<G> {
+        &self.credit_range_proof
+    }
+
+    /// Returns the proof of equivalence between votes and credits.
+    pub fn credit_equivalence_proof(&self) -> &SumOfSquaresProof<G> {
+        &self.credit_equivalence_proof
     }
 }
 
@@ -399,27 +511,6 @@ mod tests {
 
     use rand::thread_rng;
 
-    #[test]
-    fn isqrt_is_correct() {
-        let samples = (0..1_000).chain((0..1_000).map(|x| x * 1_000)).chain([
-            u64::MAX,
-            u64::MAX - 1,
-            1 << 63,
-            1 << 62,
-            (1 << 62) - 1,
-        ]);
-        for sample in samples {
-            let sqrt = isqrt(sample);
-            assert!(sqrt * sqrt <= sample, "sqrt({sample}) ?= {sqrt}");
-
-            let next_square = (sqrt + 1).checked_mul(sqrt + 1);
-            assert!(
-                next_square.map_or(true, |sq| sq > sample),
-                "sqrt({sample}) ?= {sqrt}"
-            );
-        }
-    }
-
     #[test]
     fn quadratic_voting() {
         let mut rng = thread_rng();
@@ -467,4 +558,33 @@ mod tests {
         let err = bogus_ballot.verify(&params).map(drop).unwrap_err();
         assert!(matches!(err, QuadraticVotingError::CreditEquivalence(_)));
     }
+
+    #[test]
+    fn detached_quadratic_voting_proofs() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::generate(&mut rng).into_tuple();
+        let params = QuadraticVotingParams::<Ristretto>::new(pk, 3, 10);
+        let ballot = QuadraticVotingBallot::new(&params, &[2, 0, 1], &mut rng);
+        let (votes, credit, proofs) = ballot.into_parts();
+        proofs.verify(&params, &votes, &credit).unwrap();
+
+        let err = proofs.verify(&params, &votes[..2], &credit).unwrap_err();
+        assert!(matches!(
+            err,
+            QuadraticVotingError::OptionsLenMismatch {
+                expected: 3,
+                actual: 2
+            }
+        ));
+        let mut swapped_votes = votes.clone();
+        swapped_votes.swap(0, 2);
+        let err = proofs.verify(&params, &swapped_votes, &credit).unwrap_err();
+        assert!(matches!(
+            err,
+            QuadraticVotingError::Variant { index: 0, .. }
+        ));
+
+        let ballot = QuadraticVotingBallot::from_parts(&params, votes, credit, proofs).unwrap();
+        assert_eq!(ballot.verify(&params).unwrap().count(), 3);
+    }
 }
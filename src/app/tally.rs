@@ -0,0 +1,1353 @@
+//! Encrypted tallies that can be partitioned into independently decrypted shards,
+//! and verifiable streams of running totals.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use core::{fmt, ops};
+
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    proto_labels,
+    sharing::{ActiveParticipant, PublicKeySet},
+    CandidateDecryption, Ciphertext, DiscreteLogSolver, LogEqualityProof, VerifiableDecryption,
+    VerificationError,
+};
+
+/// Encrypted tally: per-option sums of selection ciphertexts (e.g., obtained from verified
+/// [`EncryptedChoice`](crate::app::EncryptedChoice)s) encrypted for a threshold key.
+///
+/// For elections with many contests / options, decrypting the entire tally by a single
+/// committee may be impractical. A tally can be [partitioned](Self::partition()) into
+/// [`TallyShard`]s containing contiguous ranges of options. Shards are independent:
+/// decryption shares for a shard can be produced, verified and combined in parallel
+/// with other shards, possibly by different subsets of participants of the same
+/// [`PublicKeySet`]. Results for all shards are then [merged](Self::merge()) into
+/// a [`TallyAudit`], which contains the decrypted values together with all decryption shares
+/// and can be re-verified independently.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::Tally, group::Ristretto, sharing::*, DiscreteLogTable};
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..3)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let mut tally = Tally::new(5);
+/// for choice in [[1_u64, 0, 0, 1, 0], [1, 1, 0, 0, 0]] {
+///     let ciphertexts: Vec<_> = choice
+///         .iter()
+///         .map(|&value| key_set.shared_key().encrypt(value, &mut rng))
+///         .collect();
+///     tally.add_choice(&ciphertexts);
+/// }
+///
+/// let lookup_table = DiscreteLogTable::new(0..=2);
+/// let shards = tally.partition(2);
+/// assert_eq!(shards.len(), 2);
+/// // Each shard may be decrypted by a different subset of participants.
+/// let results = shards.into_iter().zip([&participants[..2], &participants[1..]]);
+/// let results = results.map(|(shard, committee)| {
+///     let shares = committee
+///         .iter()
+///         .map(|participant| shard.decryption_shares(participant, &mut rng))
+///         .collect();
+///     shard.decrypt(&key_set, shares, &lookup_table)
+/// });
+/// let results = results.collect::<Result<Vec<_>, _>>()?;
+/// let audit = tally.merge(results)?;
+/// assert_eq!(audit.values(), [2, 1, 0, 1, 0]);
+/// audit.verify(&key_set, &lookup_table)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Tally<G: Group> {
+    totals: Vec<Ciphertext<G>>,
+}
+
+impl<G: Group> Tally<G> {
+    /// Creates an empty tally (i.e., with all totals being encryptions of zero)
+    /// for the specified number of options.
+    pub fn new(options_count: usize) -> Self {
+        Self {
+            totals: vec![Ciphertext::zero(); options_count],
+        }
+    }
+
+    /// Creates a tally from the provided per-option totals.
+    pub fn from_totals(totals: Vec<Ciphertext<G>>) -> Self {
+        Self { totals }
+    }
+
+    /// Returns the per-option totals.
+    pub fn totals(&self) -> &[Ciphertext<G>] {
+        &self.totals
+    }
+
+    /// Adds per-option ciphertexts of a choice to this tally. The ciphertexts should be
+    /// verified beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of ciphertexts differs from the number of options in the tally.
+    pub fn add_choice(&mut self, ciphertexts: &[Ciphertext<G>]) {
+        assert_eq!(
+            ciphertexts.len(),
+            self.totals.len(),
+            "number of ciphertexts differs from the number of options"
+        );
+        for (total, &ciphertext) in self.totals.iter_mut().zip(ciphertexts) {
+            *total += ciphertext;
+        }
+    }
+
+    /// Partitions this tally into at most `shards_count` shards of contiguous options.
+    /// All shards except for the last one have the same number of options. An empty tally
+    /// has no shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards_count` is zero.
+    pub fn partition(&self, shards_count: usize) -> Vec<TallyShard<G>> {
+        assert!(shards_count > 0, "number of shards must be positive");
+        let shard_len = (self.totals.len() + shards_count - 1) / shards_count;
+        if shard_len == 0 {
+            return Vec::new();
+        }
+        self.totals
+            .chunks(shard_len)
+            .enumerate()
+            .map(|(i, totals)| TallyShard {
+                offset: i * shard_len,
+                totals: totals.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Merges decryption results for shards of this tally into a [`TallyAudit`].
+    /// Results may be supplied in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TallyError::ShardMismatch`] if the shards do not cover the options
+    /// of this tally exactly once, or if their ciphertexts differ from the tally totals.
+    pub fn merge(
+        &self,
+        results: impl IntoIterator<Item = ShardResult<G>>,
+    ) -> Result<TallyAudit<G>, TallyError> {
+        let mut shards: Vec<_> = results.into_iter().collect();
+        shards.sort_unstable_by_key(|result| result.shard.offset);
+
+        let mut expected_offset = 0;
+        for result in &shards {
+            let shard = &result.shard;
+            let end = shard.offset + shard.len();
+            if shard.offset != expected_offset || end > self.totals.len() {
+                return Err(TallyError::ShardMismatch);
+            }
+            let totals = &self.totals[shard.offset..end];
+            let totals_match = totals
+                .iter()
+                .zip(&shard.totals)
+                .all(|(total, shard_total)| total.to_bytes() == shard_total.to_bytes());
+            if !totals_match {
+                return Err(TallyError::ShardMismatch);
+            }
+            expected_offset = end;
+        }
+        if expected_offset != self.totals.len() {
+            return Err(TallyError::ShardMismatch);
+        }
+        Ok(TallyAudit { shards })
+    }
+}
+
+/// Contiguous range of options of a [`Tally`] that can be decrypted independently
+/// of other options.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TallyShard<G: Group> {
+    offset: usize,
+    totals: Vec<Ciphertext<G>>,
+}
+
+impl<G: Group> TallyShard<G> {
+    /// Returns the 0-based index of the first option in this shard.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the totals for options in this shard.
+    pub fn totals(&self) -> &[Ciphertext<G>] {
+        &self.totals
+    }
+
+    /// Returns the number of options in this shard.
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    /// Checks whether this shard is empty.
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    /// Creates decryption shares of the specified `participant` for all options in this shard.
+    pub fn decryption_shares<R: CryptoRng + RngCore>(
+        &self,
+        participant: &ActiveParticipant<G>,
+        rng: &mut R,
+    ) -> ShardDecryptionShares<G> {
+        let shares = self
+            .totals
+            .iter()
+            .map(|&ciphertext| {
+                let (share, proof) = participant.decrypt_share(ciphertext, rng);
+                (share.into(), proof)
+            })
+            .collect();
+        ShardDecryptionShares {
+            index: participant.index(),
+            offset: self.offset,
+            shares,
+        }
+    }
+
+    /// Verifies decryption `shares` for this shard, combines them and decrypts the totals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the shares is invalid or does not correspond to this shard,
+    /// if there are not enough shares, or if a decrypted value cannot be found by `solver`.
+    pub fn decrypt<D: DiscreteLogSolver<G> + ?Sized>(
+        self,
+        key_set: &PublicKeySet<G>,
+        shares: Vec<ShardDecryptionShares<G>>,
+        solver: &D,
+    ) -> Result<ShardResult<G>, TallyError> {
+        let values = self.decrypt_values(key_set, &shares, solver)?;
+        Ok(ShardResult {
+            shard: self,
+            values,
+            shares,
+        })
+    }
+
+    fn decrypt_values<D: DiscreteLogSolver<G> + ?Sized>(
+        &self,
+        key_set: &PublicKeySet<G>,
+        shares: &[ShardDecryptionShares<G>],
+        solver: &D,
+    ) -> Result<Vec<u64>, TallyError> {
+        let mut verified_shares = Vec::with_capacity(shares.len());
+        for participant_shares in shares {
+            let index = participant_shares.index;
+            if verified_shares.iter().any(|(i, _)| *i == index) {
+                return Err(TallyError::DuplicateShares(index));
+            }
+            let verified = participant_shares.verify(key_set, self)?;
+            verified_shares.push((index, verified));
+        }
+
+        let params = key_set.params();
+        self.totals
+            .iter()
+            .enumerate()
+            .map(|(i, &ciphertext)| {
+                let shares = verified_shares
+                    .iter()
+                    .map(|(index, shares)| (*index, shares[i]));
+                let decryption = params
+                    .combine_shares(shares)
+                    .ok_or(TallyError::InsufficientShares)?;
+                decryption
+                    .decrypt(ciphertext, solver)
+                    .ok_or(TallyError::UnknownPlaintext {
+                        option: self.offset + i,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Decryption shares of a single participant for all options in a [`TallyShard`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ShardDecryptionShares<G: Group> {
+    index: usize,
+    offset: usize,
+    shares: Vec<(CandidateDecryption<G>, LogEqualityProof<G>)>,
+}
+
+impl<G: Group> ShardDecryptionShares<G> {
+    /// Returns the 0-based index of the participant that has produced these shares.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the [offset](TallyShard::offset()) of the shard these shares are produced for.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn verify(
+        &self,
+        key_set: &PublicKeySet<G>,
+        shard: &TallyShard<G>,
+    ) -> Result<Vec<VerifiableDecryption<G>>, TallyError> {
+        // Checked before anything else since `PublicKeySet::verify_share()` panics
+        // for out-of-bounds indexes.
+        if self.index >= key_set.params().shares {
+            return Err(TallyError::InvalidParticipantIndex(self.index));
+        }
+        if self.offset != shard.offset || self.shares.len() != shard.len() {
+            return Err(TallyError::ShardMismatch);
+        }
+        self.shares
+            .iter()
+            .zip(&shard.totals)
+            .enumerate()
+            .map(|(i, ((share, proof), &ciphertext))| {
+                key_set
+                    .verify_share(*share, ciphertext, self.index, proof)
+                    .map_err(|err| TallyError::InvalidShare {
+                        participant: self.index,
+                        option: shard.offset + i,
+                        err,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Decryption result for a [`TallyShard`] together with the decryption shares
+/// it was obtained from.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ShardResult<G: Group> {
+    shard: TallyShard<G>,
+    values: Vec<u64>,
+    shares: Vec<ShardDecryptionShares<G>>,
+}
+
+impl<G: Group> ShardResult<G> {
+    /// Returns the decrypted shard.
+    pub fn shard(&self) -> &TallyShard<G> {
+        &self.shard
+    }
+
+    /// Returns the decrypted values for options in the shard.
+    pub fn values(&self) -> &[u64] {
+        &self.values
+    }
+
+    /// Returns the decryption shares the values were obtained from.
+    pub fn decryption_shares(&self) -> &[ShardDecryptionShares<G>] {
+        &self.shares
+    }
+}
+
+/// Decrypted [`Tally`] together with the decryption shares for all its shards.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TallyAudit<G: Group> {
+    shards: Vec<ShardResult<G>>,
+}
+
+impl<G: Group> TallyAudit<G> {
+    /// Returns the decrypted values for all options of the tally.
+    pub fn values(&self) -> Vec<u64> {
+        self.shards
+            .iter()
+            .flat_map(|result| result.values.iter().copied())
+            .collect()
+    }
+
+    /// Returns results for all shards, ordered by their offset.
+    pub fn shards(&self) -> &[ShardResult<G>] {
+        &self.shards
+    }
+
+    /// Verifies all decryption shares and checks that they produce the recorded values.
+    /// This is useful to check audits obtained from an untrusted source (e.g., deserialized).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the shards fails verification, or if decrypted values
+    /// do not match the recorded ones.
+    pub fn verify<D: DiscreteLogSolver<G> + ?Sized>(
+        &self,
+        key_set: &PublicKeySet<G>,
+        solver: &D,
+    ) -> Result<(), TallyError> {
+        for result in &self.shards {
+            let values = result
+                .shard
+                .decrypt_values(key_set, &result.shares, solver)?;
+            if values != result.values {
+                return Err(TallyError::ValueMismatch {
+                    offset: result.shard.offset,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encrypted tally in which totals are split into digits in a fixed base, so that the totals
+/// can be decrypted progressively, starting from the most significant digit.
+///
+/// Decrypting a [`Tally`] requires a discrete log search over the entire range of totals,
+/// which may be impractical for large elections or large contributed values (e.g., weighted
+/// votes). In a `DigitTally`, each contributed value is encrypted as several ciphertexts
+/// of its digits in base `b`, each accompanied by a range proof for `0..b`
+/// (e.g., via [`PublicKey::encrypt_range()`](crate::PublicKey::encrypt_range())).
+/// Digits at each position are summed up without carries into a separate [`Tally`],
+/// the [digit tally](Self::digit()). Hence, totals in a digit tally do not exceed
+/// [`Self::max_digit_sum()`], which is linear in the number of contributions rather than
+/// in the maximum total.
+///
+/// Digit tallies are decrypted in the same way as ordinary tallies (e.g., via sharding),
+/// starting from the most significant position. After each decrypted position,
+/// [`Self::bounds()`] narrows down the range of each total; it may be possible to stop
+/// decryption early, e.g., once the winner is determined. [`Self::totals()`] computes
+/// the exact totals with all carries once all positions are decrypted.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     app::{DigitTally, Tally, TallyAudit}, group::Ristretto, sharing::*, DiscreteLogTable,
+/// #     PreparedRange, RangeDecomposition,
+/// # };
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..2)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// // Weighted votes for 2 options encrypted as 3 decimal digits each.
+/// let mut tally = DigitTally::new(2, 10, 3);
+/// let digit_range: PreparedRange<_> = RangeDecomposition::optimal(10).into();
+/// for weights in [[250_u64, 17], [999, 0], [3, 402]] {
+///     let ballot: Vec<Vec<_>> = weights
+///         .iter()
+///         .map(|&weight| {
+///             let digits = [weight % 10, weight / 10 % 10, weight / 100];
+///             digits.iter().map(|&digit| {
+///                 let (ciphertext, _proof) =
+///                     key_set.shared_key().encrypt_range(&digit_range, digit, &mut rng);
+///                 // `_proof` should be published and verified by the tallier.
+///                 ciphertext
+///             })
+///             .collect()
+///         })
+///         .collect();
+///     tally.add_choice(&ballot);
+/// }
+///
+/// let lookup_table = DiscreteLogTable::new(0..=tally.max_digit_sum());
+/// let decrypt = |digit_tally: &Tally<_>| -> Result<TallyAudit<_>, Box<dyn StdError>> {
+///     let shard = digit_tally.partition(1).pop().unwrap();
+///     let shares = participants
+///         .iter()
+///         .map(|participant| shard.decryption_shares(participant, &mut thread_rng()))
+///         .collect();
+///     let result = shard.decrypt(&key_set, shares, &lookup_table)?;
+///     Ok(digit_tally.merge([result])?)
+/// };
+///
+/// // Decrypt the most significant digits first.
+/// let mut audits = vec![decrypt(tally.digit(2))?];
+/// let bounds = tally.bounds(&audits)?;
+/// assert_eq!(bounds, [1_100..=1_397, 400..=697]);
+/// // The first option has won regardless of the remaining digits.
+/// audits.push(decrypt(tally.digit(1))?);
+/// audits.push(decrypt(tally.digit(0))?);
+/// assert_eq!(tally.totals(&audits)?, [1_252, 419]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "", try_from = "DigitTallyRepr<G>"))]
+pub struct DigitTally<G: Group> {
+    base: u64,
+    contributions: u64,
+    digits: Vec<Tally<G>>,
+}
+
+/// Serialized form of a [`DigitTally`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct DigitTallyRepr<G: Group> {
+    base: u64,
+    contributions: u64,
+    digits: Vec<Tally<G>>,
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> TryFrom<DigitTallyRepr<G>> for DigitTally<G> {
+    type Error = &'static str;
+
+    fn try_from(repr: DigitTallyRepr<G>) -> Result<Self, Self::Error> {
+        Self::check_params(repr.base, repr.digits.len())?;
+        let options_count = repr.digits[0].totals.len();
+        if repr
+            .digits
+            .iter()
+            .any(|digit| digit.totals.len() != options_count)
+        {
+            return Err("digit tallies have different numbers of options");
+        }
+        Ok(Self {
+            base: repr.base,
+            contributions: repr.contributions,
+            digits: repr.digits,
+        })
+    }
+}
+
+impl<G: Group> DigitTally<G> {
+    fn check_params(base: u64, digits_count: usize) -> Result<(), &'static str> {
+        if base < 2 {
+            return Err("digit base must be greater than 1");
+        }
+        if digits_count == 0 {
+            return Err("number of digits must be positive");
+        }
+        let max_power = u32::try_from(digits_count)
+            .ok()
+            .and_then(|exp| u128::from(base).checked_pow(exp));
+        if max_power.map_or(false, |power| power <= 1 << 64) {
+            Ok(())
+        } else {
+            Err("contributed values must fit into `u64`")
+        }
+    }
+
+    /// Creates an empty tally for the specified number of options, with each total
+    /// split into `digits_count` digits in the specified `base`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is less than 2, `digits_count` is zero, or if `base^digits_count`
+    /// exceeds `2^64`.
+    pub fn new(options_count: usize, base: u64, digits_count: usize) -> Self {
+        if let Err(err) = Self::check_params(base, digits_count) {
+            panic!("{err}");
+        }
+
+        Self {
+            base,
+            contributions: 0,
+            digits: vec![Tally::new(options_count); digits_count],
+        }
+    }
+
+    /// Returns the base of digits.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the number of digits in each total.
+    pub fn digits_count(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Returns the number of choices added to this tally.
+    pub fn contributions(&self) -> u64 {
+        self.contributions
+    }
+
+    /// Returns the tally of digits at the specified 0-based `position`, with position 0
+    /// corresponding to the least significant digit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is out of bounds.
+    pub fn digit(&self, position: usize) -> &Tally<G> {
+        &self.digits[position]
+    }
+
+    /// Returns the maximum possible total in a [digit tally](Self::digit()). This value
+    /// can be used to bound a discrete log search during decryption.
+    pub fn max_digit_sum(&self) -> u64 {
+        self.contributions.saturating_mul(self.base - 1)
+    }
+
+    /// Adds digit ciphertexts of a choice to this tally. `ciphertexts` must contain
+    /// a vector for each option, with digits ordered from the least significant one.
+    /// The ciphertexts should be verified beforehand; in particular, each of them
+    /// must encrypt a value in `0..base`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of ciphertexts differs from the number of options in the tally,
+    /// or the number of digits for an option differs from [`Self::digits_count()`].
+    pub fn add_choice(&mut self, ciphertexts: &[Vec<Ciphertext<G>>]) {
+        assert_eq!(
+            ciphertexts.len(),
+            self.digits[0].totals.len(),
+            "number of ciphertexts differs from the number of options"
+        );
+        for digits in ciphertexts {
+            assert_eq!(
+                digits.len(),
+                self.digits.len(),
+                "number of digit ciphertexts differs from the number of digits"
+            );
+        }
+
+        for (position, tally) in self.digits.iter_mut().enumerate() {
+            for (total, digits) in tally.totals.iter_mut().zip(ciphertexts) {
+                *total += digits[position];
+            }
+        }
+        self.contributions += 1;
+    }
+
+    /// Computes inclusive bounds for totals of all options based on the decrypted
+    /// most significant digits. `audits` must contain decryptions of [digit tallies](Self::digit())
+    /// starting from the most significant position; e.g., `audits[0]` must decrypt
+    /// the digit tally at position `digits_count - 1`. If `audits` covers all positions,
+    /// the bounds are exact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are more audits than digits, or if an audit does not match
+    /// the digit tally at the corresponding position.
+    pub fn bounds(
+        &self,
+        audits: &[TallyAudit<G>],
+    ) -> Result<Vec<ops::RangeInclusive<u128>>, TallyError> {
+        if audits.len() > self.digits.len() {
+            return Err(TallyError::DigitsMismatch {
+                expected: self.digits.len(),
+                actual: audits.len(),
+            });
+        }
+
+        let base = u128::from(self.base);
+        let mut lower_bounds = vec![0_u128; self.digits[0].totals.len()];
+        let positions = self.digits.iter().enumerate().rev();
+        for ((position, digit_tally), audit) in positions.zip(audits) {
+            digit_tally.merge(audit.shards.iter().cloned())?;
+            let weight = base.saturating_pow(u32::try_from(position).unwrap_or(u32::MAX));
+            for (bound, value) in lower_bounds.iter_mut().zip(audit.values()) {
+                *bound = bound.saturating_add(u128::from(value).saturating_mul(weight));
+            }
+        }
+
+        // Each of undecrypted digit totals is at most `contributions * (base - 1)`, thus
+        // all of them contribute at most `contributions * (base^undecrypted - 1)`.
+        let undecrypted = self.digits.len() - audits.len();
+        let undecrypted_weight =
+            base.saturating_pow(u32::try_from(undecrypted).unwrap_or(u32::MAX));
+        let max_remainder = u128::from(self.contributions).saturating_mul(undecrypted_weight - 1);
+        let bounds = lower_bounds
+            .into_iter()
+            .map(|lower| lower..=lower.saturating_add(max_remainder));
+        Ok(bounds.collect())
+    }
+
+    /// Computes exact totals for all options based on decryptions of all [digit tallies]
+    /// ordered from the most significant position, as in [`Self::bounds()`].
+    ///
+    /// [digit tallies]: Self::digit()
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `audits` do not cover all positions, or if an audit does not match
+    /// the digit tally at the corresponding position.
+    pub fn totals(&self, audits: &[TallyAudit<G>]) -> Result<Vec<u128>, TallyError> {
+        if audits.len() != self.digits.len() {
+            return Err(TallyError::DigitsMismatch {
+                expected: self.digits.len(),
+                actual: audits.len(),
+            });
+        }
+        let bounds = self.bounds(audits)?;
+        Ok(bounds.into_iter().map(|bounds| *bounds.start()).collect())
+    }
+}
+
+fn initial_tally_digest(options_count: usize) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(proto_labels::RUNNING_TALLY)
+        .chain_update((options_count as u64).to_le_bytes())
+        .finalize()
+        .into()
+}
+
+fn tally_update_digest<G: Group>(
+    previous_digest: &[u8; 32],
+    sequence: u64,
+    ballot: &[Ciphertext<G>],
+    totals: &[Ciphertext<G>],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new()
+        .chain_update(proto_labels::TALLY_UPDATE)
+        .chain_update(previous_digest)
+        .chain_update(sequence.to_le_bytes());
+    for ciphertext in ballot.iter().chain(totals) {
+        hasher.update(ciphertext.to_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// [`Tally`] that publishes a [`TallyUpdate`] after each accepted ballot, e.g., to feed
+/// a live public dashboard.
+///
+/// Updates form a hash chain: each update commits to the digest of the previous update,
+/// the ballot ciphertexts and the resulting totals. A [`TallyObserver`] checks each update
+/// incrementally; since ElGamal encryption is additively homomorphic, checking that
+/// the new totals equal the previous totals plus the ballot requires no zero-knowledge proofs.
+/// Ballots themselves (e.g., [`EncryptedChoice`](crate::app::EncryptedChoice)s with their
+/// proofs) should be published and verified separately.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::{RunningTally, TallyObserver}, group::Ristretto, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let mut tally = RunningTally::new(3);
+/// let mut observer = TallyObserver::new(3);
+///
+/// for choice in [[1_u64, 0, 0], [0, 0, 1], [1, 0, 0]] {
+///     let ballot: Vec<_> = choice
+///         .iter()
+///         .map(|&value| receiver.public().encrypt(value, &mut rng))
+///         .collect();
+///     let update = tally.accept(&ballot);
+///     observer.apply(&update)?;
+/// }
+/// assert_eq!(observer.sequence(), 3);
+/// assert_eq!(observer.digest(), tally.digest());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct RunningTally<G: Group> {
+    tally: Tally<G>,
+    sequence: u64,
+    digest: [u8; 32],
+}
+
+impl<G: Group> RunningTally<G> {
+    /// Creates an empty running tally for the specified number of options.
+    pub fn new(options_count: usize) -> Self {
+        Self {
+            tally: Tally::new(options_count),
+            sequence: 0,
+            digest: initial_tally_digest(options_count),
+        }
+    }
+
+    /// Returns the current tally.
+    pub fn tally(&self) -> &Tally<G> {
+        &self.tally
+    }
+
+    /// Returns the number of accepted ballots.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the digest of the last published update (or the initial digest if no ballots
+    /// were accepted yet).
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    /// Adds per-option ciphertexts of a ballot to the tally and returns the update
+    /// to be published. The ballot should be verified beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of ciphertexts differs from the number of options in the tally.
+    pub fn accept(&mut self, ballot: &[Ciphertext<G>]) -> TallyUpdate<G> {
+        self.tally.add_choice(ballot);
+        let previous_digest = self.digest;
+        self.sequence += 1;
+        self.digest =
+            tally_update_digest(&previous_digest, self.sequence, ballot, &self.tally.totals);
+        TallyUpdate {
+            sequence: self.sequence,
+            previous_digest,
+            ballot: ballot.to_vec(),
+            totals: self.tally.totals.clone(),
+            digest: self.digest,
+        }
+    }
+}
+
+/// Update of a [`RunningTally`] published after accepting a ballot.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TallyUpdate<G: Group> {
+    sequence: u64,
+    previous_digest: [u8; 32],
+    ballot: Vec<Ciphertext<G>>,
+    totals: Vec<Ciphertext<G>>,
+    digest: [u8; 32],
+}
+
+impl<G: Group> TallyUpdate<G> {
+    /// Returns the 1-based sequence number of the accepted ballot.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the digest of the previous update.
+    pub fn previous_digest(&self) -> &[u8; 32] {
+        &self.previous_digest
+    }
+
+    /// Returns per-option ciphertexts of the accepted ballot.
+    pub fn ballot(&self) -> &[Ciphertext<G>] {
+        &self.ballot
+    }
+
+    /// Returns the running totals after accepting the ballot.
+    pub fn totals(&self) -> &[Ciphertext<G>] {
+        &self.totals
+    }
+
+    /// Returns the digest of this update.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+}
+
+/// Observer of [`TallyUpdate`]s checking that the published running totals are consistent
+/// with the published ballots. See [`RunningTally`] for details.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TallyObserver<G: Group> {
+    totals: Vec<Ciphertext<G>>,
+    sequence: u64,
+    digest: [u8; 32],
+}
+
+impl<G: Group> TallyObserver<G> {
+    /// Creates an observer starting from an empty tally with the specified number of options.
+    pub fn new(options_count: usize) -> Self {
+        Self {
+            totals: vec![Ciphertext::zero(); options_count],
+            sequence: 0,
+            digest: initial_tally_digest(options_count),
+        }
+    }
+
+    /// Creates an observer starting from the specified `update`, e.g., if the observer joins
+    /// an ongoing stream. The update is trusted, but its digest is checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the digest of the update is invalid.
+    pub fn resume(update: &TallyUpdate<G>) -> Result<Self, TallyUpdateError> {
+        let digest = tally_update_digest(
+            &update.previous_digest,
+            update.sequence,
+            &update.ballot,
+            &update.totals,
+        );
+        if digest != update.digest {
+            return Err(TallyUpdateError::DigestMismatch);
+        }
+        Ok(Self {
+            totals: update.totals.clone(),
+            sequence: update.sequence,
+            digest,
+        })
+    }
+
+    /// Returns the current running totals.
+    pub fn totals(&self) -> &[Ciphertext<G>] {
+        &self.totals
+    }
+
+    /// Returns the number of ballots accounted for in the current totals.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the digest of the last applied update.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    /// Checks the next `update` in the stream and applies it to the observer state.
+    /// If the check fails, the state is not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update does not follow the last applied update, or if its
+    /// totals are not equal to the previous totals plus the ballot.
+    pub fn apply(&mut self, update: &TallyUpdate<G>) -> Result<(), TallyUpdateError> {
+        if update.sequence != self.sequence + 1 || update.previous_digest != self.digest {
+            return Err(TallyUpdateError::OutOfOrder {
+                expected_sequence: self.sequence + 1,
+            });
+        }
+        if update.ballot.len() != self.totals.len() || update.totals.len() != self.totals.len() {
+            return Err(TallyUpdateError::OptionsCountMismatch);
+        }
+
+        let totals = self.totals.iter().zip(&update.ballot);
+        let totals: Vec<_> = totals
+            .map(|(&total, &ciphertext)| total + ciphertext)
+            .collect();
+        let totals_match = totals
+            .iter()
+            .zip(&update.totals)
+            .all(|(expected, actual)| expected.to_bytes() == actual.to_bytes());
+        if !totals_match {
+            return Err(TallyUpdateError::TotalsMismatch);
+        }
+
+        let digest = tally_update_digest(&self.digest, update.sequence, &update.ballot, &totals);
+        if digest != update.digest {
+            return Err(TallyUpdateError::DigestMismatch);
+        }
+        self.totals = totals;
+        self.sequence = update.sequence;
+        self.digest = digest;
+        Ok(())
+    }
+}
+
+/// Errors that can occur when checking a [`TallyUpdate`] with a [`TallyObserver`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TallyUpdateError {
+    /// The update does not follow the last applied update.
+    OutOfOrder {
+        /// Expected sequence number of the update.
+        expected_sequence: u64,
+    },
+    /// The number of ballot ciphertexts or totals differs from the number of options.
+    OptionsCountMismatch,
+    /// Updated totals are not equal to the previous totals plus the ballot.
+    TotalsMismatch,
+    /// The digest of the update is invalid.
+    DigestMismatch,
+}
+
+impl fmt::Display for TallyUpdateError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfOrder { expected_sequence } => write!(
+                formatter,
+                "update does not follow the last applied update; expected update #{expected_sequence}"
+            ),
+            Self::OptionsCountMismatch => {
+                formatter.write_str("number of ciphertexts differs from the number of options")
+            }
+            Self::TotalsMismatch => {
+                formatter.write_str("updated totals are not equal to previous totals plus ballot")
+            }
+            Self::DigestMismatch => formatter.write_str("update digest is invalid"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TallyUpdateError {}
+
+/// Errors that can occur when decrypting a [`Tally`] or verifying a [`TallyAudit`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TallyError {
+    /// Decryption shares refer to a non-existing participant.
+    InvalidParticipantIndex(usize),
+    /// Decryption shares from the participant were provided more than once.
+    DuplicateShares(usize),
+    /// A decryption share does not verify.
+    InvalidShare {
+        /// 0-based index of the participant.
+        participant: usize,
+        /// 0-based index of the option in the tally.
+        option: usize,
+        /// Underlying verification error.
+        err: VerificationError,
+    },
+    /// Not enough decryption shares were provided.
+    InsufficientShares,
+    /// The decrypted value for an option is not found by the discrete log solver.
+    UnknownPlaintext {
+        /// 0-based index of the option in the tally.
+        option: usize,
+    },
+    /// Shards or decryption shares do not match the tally or shard they are used with.
+    ShardMismatch,
+    /// Values recorded for a shard in a [`TallyAudit`] differ from the decrypted values.
+    ValueMismatch {
+        /// Offset of the shard.
+        offset: usize,
+    },
+    /// Number of decrypted digit tallies does not match the number of digits
+    /// in a [`DigitTally`].
+    DigitsMismatch {
+        /// Expected number of digits.
+        expected: usize,
+        /// Actual number of decrypted digit tallies.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for TallyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParticipantIndex(participant) => {
+                write!(formatter, "invalid participant index {participant}")
+            }
+            Self::DuplicateShares(participant) => write!(
+                formatter,
+                "decryption shares from participant #{participant} were provided more than once"
+            ),
+            Self::InvalidShare {
+                participant,
+                option,
+                err,
+            } => write!(
+                formatter,
+                "decryption share from participant #{participant} for option #{option} \
+                 is invalid: {err}"
+            ),
+            Self::InsufficientShares => formatter.write_str("not enough decryption shares"),
+            Self::UnknownPlaintext { option } => {
+                write!(formatter, "cannot decrypt total for option #{option}")
+            }
+            Self::ShardMismatch => formatter.write_str("shards do not match the tally"),
+            Self::ValueMismatch { offset } => write!(
+                formatter,
+                "recorded values for shard at offset {offset} do not match decrypted values"
+            ),
+            Self::DigitsMismatch { expected, actual } => write!(
+                formatter,
+                "number of decrypted digit tallies ({actual}) does not match the number \
+                 of digits ({expected})"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TallyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidShare { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{Dealer, Params},
+        DiscreteLogTable, Keypair,
+    };
+
+    #[test]
+    fn tally_shards_are_decrypted_independently() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..3)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let totals: Vec<_> = (0_u64..7)
+            .map(|i| key_set.shared_key().encrypt(i, &mut rng))
+            .collect();
+        let tally = Tally::from_totals(totals);
+        let tally_shards = tally.partition(3);
+        let lens: Vec<_> = tally_shards.iter().map(TallyShard::len).collect();
+        assert_eq!(lens, [3, 3, 1]);
+        assert!(tally.partition(10).iter().all(|shard| shard.len() == 1));
+
+        let lookup_table = DiscreteLogTable::new(0..7);
+        let mut results: Vec<_> = tally_shards
+            .iter()
+            .enumerate()
+            .map(|(i, shard)| {
+                let committee = [&participants[i % 3], &participants[(i + 1) % 3]];
+                let shares = committee
+                    .iter()
+                    .map(|participant| shard.decryption_shares(participant, &mut rng))
+                    .collect();
+                shard
+                    .clone()
+                    .decrypt(&key_set, shares, &lookup_table)
+                    .unwrap()
+            })
+            .collect();
+        results.reverse();
+
+        let err = tally.merge(results[1..].to_vec()).unwrap_err();
+        assert!(matches!(err, TallyError::ShardMismatch), "{err:?}");
+        let audit = tally.merge(results).unwrap();
+        assert_eq!(audit.values(), (0..7).collect::<Vec<_>>());
+        audit.verify(&key_set, &lookup_table).unwrap();
+
+        let shard = &tally_shards[1];
+        let mut shares = shard.decryption_shares(&participants[0], &mut rng);
+        shares.shares.swap(0, 1);
+        let err = shard
+            .clone()
+            .decrypt(&key_set, vec![shares], &lookup_table)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                TallyError::InvalidShare {
+                    participant: 0,
+                    option: 3,
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+
+        let shares = vec![shard.decryption_shares(&participants[2], &mut rng)];
+        let err = shard
+            .clone()
+            .decrypt(&key_set, shares, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, TallyError::InsufficientShares), "{err:?}");
+
+        let mut shares = shard.decryption_shares(&participants[2], &mut rng);
+        shares.index = 5;
+        let err = shares.verify(&key_set, shard).unwrap_err();
+        assert!(
+            matches!(err, TallyError::InvalidParticipantIndex(5)),
+            "{err:?}"
+        );
+        let err = shard
+            .clone()
+            .decrypt(&key_set, vec![shares], &lookup_table)
+            .unwrap_err();
+        assert!(
+            matches!(err, TallyError::InvalidParticipantIndex(5)),
+            "{err:?}"
+        );
+
+        let shares = vec![tally_shards[0].decryption_shares(&participants[2], &mut rng)];
+        let err = shard
+            .clone()
+            .decrypt(&key_set, shares, &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, TallyError::ShardMismatch), "{err:?}");
+    }
+
+    #[test]
+    fn digit_tally_is_decrypted_progressively() {
+        let mut rng = thread_rng();
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..2)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        // Values in base 4 with 3 digits (i.e., in `0..64`).
+        let mut tally = DigitTally::new(3, 4, 3);
+        let values = [[63_u64, 0, 5], [63, 1, 16], [63, 2, 47], [63, 3, 0]];
+        for choice in values {
+            let ciphertexts: Vec<Vec<_>> = choice
+                .iter()
+                .map(|&value| {
+                    [value % 4, value / 4 % 4, value / 16]
+                        .iter()
+                        .map(|&digit| key_set.shared_key().encrypt(digit, &mut rng))
+                        .collect()
+                })
+                .collect();
+            tally.add_choice(&ciphertexts);
+        }
+        assert_eq!(tally.contributions(), 4);
+        assert_eq!(tally.max_digit_sum(), 12);
+
+        let lookup_table = DiscreteLogTable::new(0..=tally.max_digit_sum());
+        let audits: Vec<_> = (0..3)
+            .rev()
+            .map(|position| {
+                let digit_tally = tally.digit(position);
+                let shard = digit_tally.partition(1).pop().unwrap();
+                let shares = participants
+                    .iter()
+                    .map(|participant| shard.decryption_shares(participant, &mut rng))
+                    .collect();
+                let result = shard.decrypt(&key_set, shares, &lookup_table).unwrap();
+                digit_tally.merge([result]).unwrap()
+            })
+            .collect();
+
+        let bounds = tally.bounds(&[]).unwrap();
+        assert_eq!(bounds, [0..=252, 0..=252, 0..=252]);
+        let bounds = tally.bounds(&audits[..1]).unwrap();
+        assert_eq!(bounds, [192..=252, 0..=60, 48..=108]);
+        let bounds = tally.bounds(&audits[..2]).unwrap();
+        assert_eq!(bounds, [240..=252, 0..=12, 64..=76]);
+        // Digit sums in the least significant position (12, 6, 4) require carries.
+        assert_eq!(tally.totals(&audits).unwrap(), [252, 6, 68]);
+
+        let err = tally.totals(&audits[..2]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                TallyError::DigitsMismatch {
+                    expected: 3,
+                    actual: 2
+                }
+            ),
+            "{err:?}"
+        );
+        let reordered_audits = [audits[1].clone(), audits[0].clone()];
+        let err = tally.bounds(&reordered_audits).unwrap_err();
+        assert!(matches!(err, TallyError::ShardMismatch), "{err:?}");
+    }
+
+    #[test]
+    #[should_panic(expected = "contributed values must fit into `u64`")]
+    fn digit_tally_with_excessive_digits() {
+        DigitTally::<Ristretto>::new(1, 10, 20);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn digit_tally_deserialization_is_validated() {
+        let tally = DigitTally::<Ristretto>::new(2, 10, 3);
+        let json = serde_json::to_value(&tally).unwrap();
+        let restored: DigitTally<Ristretto> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.base(), 10);
+        assert_eq!(restored.digits_count(), 3);
+
+        let mut invalid_json = json.clone();
+        invalid_json["digits"] = serde_json::json!([]);
+        let err = serde_json::from_value::<DigitTally<Ristretto>>(invalid_json).unwrap_err();
+        assert!(err.to_string().contains("number of digits"), "{err}");
+
+        for base in [0, 1] {
+            let mut invalid_json = json.clone();
+            invalid_json["base"] = base.into();
+            let err = serde_json::from_value::<DigitTally<Ristretto>>(invalid_json).unwrap_err();
+            assert!(err.to_string().contains("digit base"), "{err}");
+        }
+
+        let mut invalid_json = json.clone();
+        invalid_json["base"] = u64::MAX.into();
+        let err = serde_json::from_value::<DigitTally<Ristretto>>(invalid_json).unwrap_err();
+        assert!(err.to_string().contains("fit into `u64`"), "{err}");
+
+        let mut invalid_json = json;
+        invalid_json["digits"][1]["totals"] = serde_json::json!([]);
+        let err = serde_json::from_value::<DigitTally<Ristretto>>(invalid_json).unwrap_err();
+        assert!(err.to_string().contains("numbers of options"), "{err}");
+    }
+
+    #[test]
+    fn running_tally_updates_are_verified_incrementally() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let mut tally = RunningTally::new(2);
+        let mut observer = TallyObserver::new(2);
+        let ballots: Vec<Vec<_>> = [[1_u64, 0], [0, 1], [1, 0]]
+            .iter()
+            .map(|choice| {
+                choice
+                    .iter()
+                    .map(|&value| receiver.public().encrypt(value, &mut rng))
+                    .collect()
+            })
+            .collect();
+        let updates: Vec<_> = ballots.iter().map(|ballot| tally.accept(ballot)).collect();
+
+        observer.apply(&updates[0]).unwrap();
+        let err = observer.apply(&updates[2]).unwrap_err();
+        assert!(matches!(
+            err,
+            TallyUpdateError::OutOfOrder {
+                expected_sequence: 2
+            }
+        ));
+
+        let mut forged = updates[1].clone();
+        forged.totals[0] += receiver.public().encrypt(1_u64, &mut rng);
+        let err = observer.apply(&forged).unwrap_err();
+        assert!(matches!(err, TallyUpdateError::TotalsMismatch));
+        assert_eq!(observer.sequence(), 1);
+
+        observer.apply(&updates[1]).unwrap();
+        observer.apply(&updates[2]).unwrap();
+        assert_eq!(observer.digest(), tally.digest());
+        let lookup_table = DiscreteLogTable::new(0..=3);
+        let values: Vec<_> = observer
+            .totals()
+            .iter()
+            .map(|&total| receiver.secret().decrypt(total, &lookup_table))
+            .collect();
+        assert_eq!(values, [Some(2), Some(1)]);
+
+        let mut resumed = TallyObserver::resume(&updates[1]).unwrap();
+        resumed.apply(&updates[2]).unwrap();
+        assert_eq!(resumed.digest(), tally.digest());
+    }
+}
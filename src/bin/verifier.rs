@@ -0,0 +1,430 @@
+//! Independent offline verifier for election artifacts produced with `elastic-elgamal`.
+//!
+//! The verifier reads a JSON file with serialized election artifacts (the public key set
+//! of talliers, encrypted ballots, decryption shares for the tally, and optionally
+//! an audit bundle of a mix-net) and checks all proofs using the library verification APIs.
+//! The `sample` subcommand outputs artifacts for a simulated election, which is useful
+//! to see the expected file format.
+//!
+//! Mix-net shuffle proofs are not supported, since the library does not provide
+//! a shuffle argument; a mix audit may only contain re-randomization stages.
+
+use clap::{Parser, Subcommand};
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use std::{error::Error as StdError, fmt::Write as _, fs, path::PathBuf};
+
+use elastic_elgamal::{
+    app::{
+        ChoiceParams, EncryptedChoice, MixAudit, MixDecryptionShares, MixPipeline, Shuffle,
+        SingleChoice,
+    },
+    group::Ristretto,
+    sharing::{ActiveParticipant, Dealer, Params, PublicKeySet},
+    CandidateDecryption, Ciphertext, DiscreteLogTable, LogEqualityProof, PublicKey,
+    VerificationError,
+};
+
+type Error = Box<dyn StdError>;
+
+/// Independent offline verifier for election artifacts.
+#[derive(Debug, Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Verifies election artifacts from a JSON file.
+    Verify {
+        /// Path to the artifacts file.
+        path: PathBuf,
+    },
+    /// Outputs artifacts for a simulated election to stdout. Not suitable for real elections.
+    Sample {
+        /// Number of options in the poll.
+        #[arg(long, default_value = "3")]
+        options: usize,
+        /// Number of votes to be cast.
+        #[arg(long, default_value = "20")]
+        votes: usize,
+        /// Tallier configuration specified as a `$threshold/$number`.
+        #[arg(long, default_value = "2/3", value_parser = parse_talliers)]
+        talliers: Params,
+        /// Seed for the deterministic RNG used in the simulation.
+        #[arg(long, default_value = "0")]
+        seed: u64,
+    },
+}
+
+fn parse_talliers(s: &str) -> Result<Params, Box<dyn StdError + Send + Sync>> {
+    let (threshold, count) = s
+        .split_once('/')
+        .ok_or("talliers specification must contain `/` char")?;
+    let params = Params {
+        threshold: threshold.parse()?,
+        shares: count.parse()?,
+    };
+    if params.threshold == 0 || params.threshold > params.shares {
+        return Err("threshold must be in 1..=number of talliers".into());
+    }
+    Ok(params)
+}
+
+/// Shuffle placeholder; audits with shuffle stages cannot be deserialized.
+#[derive(Debug)]
+struct NoShuffle;
+
+/// Uninhabited shuffle proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum NoShuffleProof {}
+
+impl Shuffle<Ristretto> for NoShuffle {
+    type Proof = NoShuffleProof;
+
+    fn shuffle<R: rand_core::CryptoRng + RngCore>(
+        &self,
+        _receiver: &PublicKey<Ristretto>,
+        _inputs: &[Ciphertext<Ristretto>],
+        _rng: &mut R,
+    ) -> (Vec<Ciphertext<Ristretto>>, Self::Proof) {
+        unreachable!("shuffles are not supported by the verifier")
+    }
+
+    fn verify(
+        &self,
+        _receiver: &PublicKey<Ristretto>,
+        _inputs: &[Ciphertext<Ristretto>],
+        _outputs: &[Ciphertext<Ristretto>],
+        proof: &Self::Proof,
+    ) -> Result<(), VerificationError> {
+        match *proof {}
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Artifacts {
+    key_set: PublicKeySet<Ristretto>,
+    options_count: usize,
+    ballots: Vec<EncryptedChoice<Ristretto, SingleChoice>>,
+    /// Hex-encoded commitment to the ballot box; checked if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ballot_box_commitment: Option<String>,
+    tally: TallyArtifacts,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mix: Option<MixArtifacts>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TallyArtifacts {
+    shares: Vec<TallierShares>,
+    results: Vec<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TallierShares {
+    index: usize,
+    /// Decryption shares for each option total.
+    shares: Vec<ShareWithProof>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ShareWithProof {
+    share: CandidateDecryption<Ristretto>,
+    proof: LogEqualityProof<Ristretto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MixArtifacts {
+    audit: MixAudit<Ristretto, NoShuffleProof>,
+    /// Upper bound (inclusive) on the values decrypted by the mix-net.
+    max_value: u64,
+    results: Vec<u64>,
+}
+
+/// Computes a commitment to the ballot box: a SHA-256 digest of all ballot ciphertexts.
+fn ballot_box_commitment(ballots: &[EncryptedChoice<Ristretto, SingleChoice>]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(elastic_elgamal::proto_labels::BALLOT_BOX);
+    hasher.update((ballots.len() as u64).to_le_bytes());
+    for ballot in ballots {
+        hasher.update((ballot.len() as u64).to_le_bytes());
+        for ciphertext in ballot.choices_unchecked() {
+            hasher.update(ciphertext.to_bytes());
+        }
+    }
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            write!(hex, "{byte:02x}").unwrap();
+            hex
+        })
+}
+
+fn verify(artifacts: &Artifacts) -> Result<(), Error> {
+    let key_set = &artifacts.key_set;
+    let params = key_set.params();
+    let restored_key_set =
+        PublicKeySet::from_participants(params, key_set.participant_keys().to_vec())
+            .map_err(|err| format!("key set is inconsistent: {err}"))?;
+    if restored_key_set.shared_key() != key_set.shared_key() {
+        return Err("shared key does not correspond to participant keys".into());
+    }
+    println!(
+        "key set: OK ({}-of-{} talliers)",
+        params.threshold, params.shares
+    );
+
+    let commitment = ballot_box_commitment(&artifacts.ballots);
+    if let Some(expected) = &artifacts.ballot_box_commitment {
+        if !expected.eq_ignore_ascii_case(&commitment) {
+            return Err(format!(
+                "ballot box commitment mismatch: expected {expected}, computed {commitment}"
+            )
+            .into());
+        }
+        println!("ballot box commitment: OK ({commitment})");
+    } else {
+        println!("ballot box commitment: {commitment}");
+    }
+
+    let choice_params = ChoiceParams::single(key_set.shared_key().clone(), artifacts.options_count);
+    let mut totals = vec![Ciphertext::zero(); artifacts.options_count];
+    for (i, ballot) in artifacts.ballots.iter().enumerate() {
+        let choices = ballot
+            .verify(&choice_params)
+            .map_err(|err| format!("ballot #{i} is invalid: {err}"))?;
+        for (total, &choice) in totals.iter_mut().zip(choices) {
+            *total += choice;
+        }
+    }
+    println!("ballots: OK ({} ballots)", artifacts.ballots.len());
+
+    let tally = &artifacts.tally;
+    let mut verified_shares = vec![vec![]; artifacts.options_count];
+    for tallier in &tally.shares {
+        if tallier.index >= params.shares {
+            return Err(format!("tallier index {} is out of bounds", tallier.index).into());
+        }
+        if tallier.shares.len() != artifacts.options_count {
+            return Err(format!(
+                "unexpected number of shares from tallier #{}",
+                tallier.index
+            )
+            .into());
+        }
+        for (option, share) in tallier.shares.iter().enumerate() {
+            let verified = key_set
+                .verify_share(share.share, totals[option], tallier.index, &share.proof)
+                .map_err(|err| {
+                    format!(
+                        "share from tallier #{} for option #{option} is invalid: {err}",
+                        tallier.index
+                    )
+                })?;
+            verified_shares[option].push((tallier.index, verified));
+        }
+    }
+
+    let lookup_table = DiscreteLogTable::new(0..=artifacts.ballots.len() as u64);
+    let mut results = Vec::with_capacity(artifacts.options_count);
+    for (option, shares) in verified_shares.into_iter().enumerate() {
+        let decryption = params
+            .combine_shares(shares)
+            .ok_or_else(|| format!("not enough decryption shares for option #{option}"))?;
+        let result = decryption
+            .decrypt(totals[option], &lookup_table)
+            .ok_or_else(|| format!("cannot decrypt total for option #{option}"))?;
+        results.push(result);
+    }
+    if results != tally.results {
+        return Err(format!(
+            "tally mismatch: expected {:?}, decrypted {results:?}",
+            tally.results
+        )
+        .into());
+    }
+    println!("tally: OK ({results:?})");
+
+    if let Some(mix) = &artifacts.mix {
+        verify_mix(key_set, mix)?;
+    }
+    Ok(())
+}
+
+fn verify_mix(key_set: &PublicKeySet<Ristretto>, mix: &MixArtifacts) -> Result<(), Error> {
+    let audit = &mix.audit;
+    let decryptions = audit
+        .verify(key_set, &NoShuffle)
+        .map_err(|err| format!("mix audit is invalid: {err}"))?;
+    let outputs = audit
+        .stages()
+        .last()
+        .map_or(audit.inputs(), |stage| stage.outputs());
+
+    let lookup_table = DiscreteLogTable::new(0..=mix.max_value);
+    let results = decryptions
+        .iter()
+        .zip(outputs)
+        .map(|(decryption, &output)| decryption.decrypt(output, &lookup_table))
+        .collect::<Option<Vec<_>>>()
+        .ok_or("cannot decrypt mix-net outputs")?;
+    if results != mix.results {
+        return Err(format!(
+            "mix-net results mismatch: expected {:?}, decrypted {results:?}",
+            mix.results
+        )
+        .into());
+    }
+    println!(
+        "mix-net: OK ({} stages, {} outputs)",
+        audit.stages().len(),
+        results.len()
+    );
+    Ok(())
+}
+
+fn sample(options_count: usize, votes_count: usize, talliers: Params, seed: u64) -> Artifacts {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    let dealer = Dealer::<Ristretto>::new(talliers, &mut rng);
+    let (public_poly, public_poly_proof) = dealer.public_info();
+    let key_set = PublicKeySet::new(talliers, public_poly, public_poly_proof).unwrap();
+    let participants: Vec<_> = (0..talliers.shares)
+        .map(|i| {
+            let secret_share = dealer.secret_share_for_participant(i);
+            ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+        })
+        .collect();
+
+    let choice_params = ChoiceParams::single(key_set.shared_key().clone(), options_count);
+    let mut results = vec![0; options_count];
+    let mut totals = vec![Ciphertext::zero(); options_count];
+    let ballots: Vec<_> = (0..votes_count)
+        .map(|_| {
+            let choice = (rng.next_u64() % options_count as u64) as usize;
+            results[choice] += 1;
+            let ballot = EncryptedChoice::single(&choice_params, choice, &mut rng);
+            for (total, &ciphertext) in totals.iter_mut().zip(ballot.choices_unchecked()) {
+                *total += ciphertext;
+            }
+            ballot
+        })
+        .collect();
+
+    let shares = participants
+        .iter()
+        .take(talliers.threshold)
+        .map(|participant| TallierShares {
+            index: participant.index(),
+            shares: totals
+                .iter()
+                .map(|&total| {
+                    let (share, proof) = participant.decrypt_share(total, &mut rng);
+                    ShareWithProof {
+                        share: share.into(),
+                        proof,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mix_values: Vec<u64> = (0..5).map(|i| i * 2).collect();
+    let mix_inputs = mix_values
+        .iter()
+        .map(|&value| key_set.shared_key().encrypt(value, &mut rng))
+        .collect();
+    let mut pipeline = MixPipeline::new(&key_set, NoShuffle, mix_inputs);
+    pipeline.rerandomize(&mut rng).unwrap();
+    pipeline.rerandomize(&mut rng).unwrap();
+    for participant in participants.iter().take(talliers.threshold) {
+        let shares = MixDecryptionShares::new(participant, pipeline.outputs(), &mut rng);
+        pipeline.insert_decryption_shares(shares).unwrap();
+    }
+    let (_, audit) = pipeline.finish().unwrap();
+
+    Artifacts {
+        ballot_box_commitment: Some(ballot_box_commitment(&ballots)),
+        key_set,
+        options_count,
+        ballots,
+        tally: TallyArtifacts { shares, results },
+        mix: Some(MixArtifacts {
+            audit,
+            max_value: 10,
+            results: mix_values,
+        }),
+    }
+}
+
+fn main() -> Result<(), Error> {
+    match Args::parse().command {
+        Command::Verify { path } => {
+            let artifacts = fs::read_to_string(&path)
+                .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+            let artifacts: Artifacts = serde_json::from_str(&artifacts)
+                .map_err(|err| format!("cannot parse artifacts: {err}"))?;
+            verify(&artifacts)?;
+            println!("All checks passed");
+        }
+        Command::Sample {
+            options,
+            votes,
+            talliers,
+            seed,
+        } => {
+            if options == 0 {
+                return Err("number of options must be positive".into());
+            }
+            let artifacts = sample(options, votes, talliers, seed);
+            println!("{}", serde_json::to_string_pretty(&artifacts)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_artifacts() -> Artifacts {
+        let artifacts = sample(3, 10, Params::new(3, 2), 123);
+        let json = serde_json::to_string(&artifacts).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn verifying_sample_artifacts() {
+        verify(&sample_artifacts()).unwrap();
+    }
+
+    #[test]
+    fn verifying_tampered_artifacts() {
+        let mut artifacts = sample_artifacts();
+        artifacts.tally.results[0] += 1;
+        let err = verify(&artifacts).unwrap_err().to_string();
+        assert!(err.contains("tally mismatch"), "{err}");
+
+        let mut artifacts = sample_artifacts();
+        artifacts.ballots.pop();
+        let err = verify(&artifacts).unwrap_err().to_string();
+        assert!(err.contains("ballot box commitment mismatch"), "{err}");
+
+        let mut artifacts = sample_artifacts();
+        artifacts.tally.shares.swap(0, 1);
+        artifacts.tally.shares[0].index = 0;
+        let err = verify(&artifacts).unwrap_err().to_string();
+        assert!(err.contains("share from tallier #0"), "{err}");
+
+        let mut artifacts = sample_artifacts();
+        artifacts.mix.as_mut().unwrap().results.reverse();
+        let err = verify(&artifacts).unwrap_err().to_string();
+        assert!(err.contains("mix-net results mismatch"), "{err}");
+    }
+}
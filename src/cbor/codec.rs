@@ -0,0 +1,336 @@
+//! Minimal deterministic CBOR encoder / decoder.
+//!
+//! Only the subset of CBOR necessary for the crate types is supported: unsigned and negative
+//! integers, byte and text strings, arrays, maps and tags. Decoding is strict: it rejects
+//! indefinite-length items, non-shortest integer encodings and unordered map keys.
+
+use core::str;
+
+use super::CborError;
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+};
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_NEGATIVE_INT: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+/// Additional info values signalling that the argument is stored in the following bytes.
+const INFO_U8: u8 = 0x18;
+const INFO_U16: u8 = 0x19;
+const INFO_U32: u8 = 0x1a;
+const INFO_U64: u8 = 0x1b;
+
+/// Deterministic CBOR encoder.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    buffer: Vec<u8>,
+}
+
+impl Encoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    fn head(&mut self, major: u8, value: u64) {
+        let major = major << 5;
+        if let Ok(value @ 0..=23) = u8::try_from(value) {
+            self.buffer.push(major | value);
+        } else if let Ok(value) = u8::try_from(value) {
+            self.buffer.extend_from_slice(&[major | INFO_U8, value]);
+        } else if let Ok(value) = u16::try_from(value) {
+            self.buffer.push(major | INFO_U16);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        } else if let Ok(value) = u32::try_from(value) {
+            self.buffer.push(major | INFO_U32);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        } else {
+            self.buffer.push(major | INFO_U64);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    pub(crate) fn int(&mut self, value: i64) {
+        if let Ok(value) = u64::try_from(value) {
+            self.head(MAJOR_UINT, value);
+        } else {
+            // `value` is negative, so the encoded argument `-1 - value` is equal to `|value| - 1`.
+            self.head(MAJOR_NEGATIVE_INT, value.unsigned_abs() - 1);
+        }
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.head(MAJOR_BYTES, bytes.len() as u64);
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn text(&mut self, text: &str) {
+        self.head(MAJOR_TEXT, text.len() as u64);
+        self.buffer.extend_from_slice(text.as_bytes());
+    }
+
+    pub(crate) fn array(&mut self, len: usize) {
+        self.head(MAJOR_ARRAY, len as u64);
+    }
+
+    pub(crate) fn map(&mut self, len: usize) {
+        self.head(MAJOR_MAP, len as u64);
+    }
+
+    pub(crate) fn tag(&mut self, tag: u64) {
+        self.head(MAJOR_TAG, tag);
+    }
+
+    pub(crate) fn scalar<G: Group>(&mut self, scalar: &G::Scalar) {
+        let mut bytes = vec![0_u8; G::SCALAR_SIZE];
+        G::serialize_scalar(scalar, &mut bytes);
+        self.bytes(&bytes);
+    }
+
+    pub(crate) fn scalars<G: Group>(&mut self, scalars: &[G::Scalar]) {
+        self.array(scalars.len());
+        for scalar in scalars {
+            self.scalar::<G>(scalar);
+        }
+    }
+
+    pub(crate) fn element<G: Group>(&mut self, element: &G::Element) {
+        let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(element, &mut bytes);
+        self.bytes(&bytes);
+    }
+}
+
+/// Strict CBOR decoder accepting only deterministically encoded data.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), CborError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(CborError::TrailingBytes)
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CborError> {
+        if self.bytes.len() < len {
+            return Err(CborError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn head(&mut self) -> Result<(u8, u64), CborError> {
+        let initial_byte = self.take(1)?[0];
+        let major = initial_byte >> 5;
+        let (value, min_value) = match initial_byte & 0x1f {
+            info @ 0..=23 => (u64::from(info), 0),
+            INFO_U8 => (u64::from(self.take(1)?[0]), 24),
+            INFO_U16 => {
+                let bytes = self.take(2)?.try_into().unwrap();
+                (u64::from(u16::from_be_bytes(bytes)), 0x100)
+            }
+            INFO_U32 => {
+                let bytes = self.take(4)?.try_into().unwrap();
+                (u64::from(u32::from_be_bytes(bytes)), 0x1_0000)
+            }
+            INFO_U64 => {
+                let bytes = self.take(8)?.try_into().unwrap();
+                (u64::from_be_bytes(bytes), 0x1_0000_0000)
+            }
+            // Reserved values and indefinite-length items.
+            _ => return Err(CborError::NonCanonical),
+        };
+
+        if value < min_value {
+            Err(CborError::NonCanonical)
+        } else {
+            Ok((major, value))
+        }
+    }
+
+    fn expect_head(&mut self, major: u8, expected: &'static str) -> Result<u64, CborError> {
+        let (actual_major, value) = self.head()?;
+        if actual_major == major {
+            Ok(value)
+        } else {
+            Err(CborError::UnexpectedType { expected })
+        }
+    }
+
+    fn expect_len(&mut self, major: u8, expected: &'static str) -> Result<usize, CborError> {
+        let len = self.expect_head(major, expected)?;
+        // Each item takes at least 1 byte, which allows to discard obviously invalid lengths
+        // before allocating anything.
+        match usize::try_from(len) {
+            Ok(len) if len <= self.bytes.len() => Ok(len),
+            _ => Err(CborError::UnexpectedEnd),
+        }
+    }
+
+    /// Peeks at the encoded representation of the next integer (e.g., a map key)
+    /// without consuming it.
+    pub(crate) fn peek_int_bytes(&self) -> Result<&'a [u8], CborError> {
+        let mut lookahead = Decoder { bytes: self.bytes };
+        lookahead.int()?;
+        Ok(&self.bytes[..self.bytes.len() - lookahead.bytes.len()])
+    }
+
+    pub(crate) fn int(&mut self) -> Result<i64, CborError> {
+        let (major, value) = self.head()?;
+        let value = i64::try_from(value).map_err(|_| CborError::UnexpectedType {
+            expected: "64-bit signed integer",
+        })?;
+        match major {
+            MAJOR_UINT => Ok(value),
+            MAJOR_NEGATIVE_INT => Ok(-1 - value),
+            _ => Err(CborError::UnexpectedType {
+                expected: "integer",
+            }),
+        }
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], CborError> {
+        let len = self.expect_len(MAJOR_BYTES, "byte string")?;
+        self.take(len)
+    }
+
+    pub(crate) fn text(&mut self) -> Result<&'a str, CborError> {
+        let len = self.expect_len(MAJOR_TEXT, "text string")?;
+        str::from_utf8(self.take(len)?).map_err(|_| CborError::UnexpectedType {
+            expected: "UTF-8 text string",
+        })
+    }
+
+    pub(crate) fn array(&mut self) -> Result<usize, CborError> {
+        self.expect_len(MAJOR_ARRAY, "array")
+    }
+
+    pub(crate) fn array_of_len(&mut self, expected: usize) -> Result<(), CborError> {
+        let actual = self.array()?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(CborError::LenMismatch { expected, actual })
+        }
+    }
+
+    pub(crate) fn map(&mut self) -> Result<usize, CborError> {
+        self.expect_len(MAJOR_MAP, "map")
+    }
+
+    pub(crate) fn tag(&mut self) -> Result<u64, CborError> {
+        self.expect_head(MAJOR_TAG, "tag")
+    }
+
+    pub(crate) fn scalar<G: Group>(&mut self) -> Result<G::Scalar, CborError> {
+        let bytes = self.bytes()?;
+        if bytes.len() != G::SCALAR_SIZE {
+            return Err(CborError::LenMismatch {
+                expected: G::SCALAR_SIZE,
+                actual: bytes.len(),
+            });
+        }
+        G::deserialize_scalar(bytes).ok_or(CborError::InvalidScalar)
+    }
+
+    pub(crate) fn scalars<G: Group>(&mut self) -> Result<Vec<G::Scalar>, CborError> {
+        let len = self.array()?;
+        (0..len).map(|_| self.scalar::<G>()).collect()
+    }
+
+    pub(crate) fn element<G: Group>(&mut self) -> Result<G::Element, CborError> {
+        let bytes = self.bytes()?;
+        if bytes.len() != G::ELEMENT_SIZE {
+            return Err(CborError::LenMismatch {
+                expected: G::ELEMENT_SIZE,
+                actual: bytes.len(),
+            });
+        }
+        let element = G::deserialize_element(bytes).ok_or(CborError::InvalidElement)?;
+
+        // Ensure that the element encoding is canonical, so that it is hashed
+        // into proof transcripts in the same way as by the prover.
+        let mut canonical_bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(&element, &mut canonical_bytes);
+        if canonical_bytes == bytes {
+            Ok(element)
+        } else {
+            Err(CborError::NonCanonical)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_are_encoded_in_shortest_form() {
+        let samples: &[(i64, &[u8])] = &[
+            (0, &[0x00]),
+            (23, &[0x17]),
+            (24, &[0x18, 0x18]),
+            (256, &[0x19, 0x01, 0x00]),
+            (-1, &[0x20]),
+            (-2, &[0x21]),
+            (-25, &[0x38, 0x18]),
+            (1 << 32, &[0x1b, 0, 0, 0, 1, 0, 0, 0, 0]),
+        ];
+        for &(value, expected) in samples {
+            let mut encoder = Encoder::new();
+            encoder.int(value);
+            assert_eq!(encoder.into_bytes(), expected);
+
+            let mut decoder = Decoder::new(expected);
+            assert_eq!(decoder.int().unwrap(), value);
+            decoder.finish().unwrap();
+        }
+    }
+
+    #[test]
+    fn non_canonical_integers_are_rejected() {
+        let samples: &[&[u8]] = &[
+            &[0x18, 0x17],
+            &[0x19, 0x00, 0xff],
+            &[0x1a, 0, 0, 0xff, 0xff],
+        ];
+        for &sample in samples {
+            let err = Decoder::new(sample).int().unwrap_err();
+            assert!(matches!(err, CborError::NonCanonical), "{err:?}");
+        }
+    }
+
+    #[test]
+    fn indefinite_length_items_are_rejected() {
+        let err = Decoder::new(&[0x5f, 0x41, 0x00, 0xff]).bytes().unwrap_err();
+        assert!(matches!(err, CborError::NonCanonical), "{err:?}");
+    }
+
+    #[test]
+    fn overly_large_lengths_are_rejected_without_allocation() {
+        let mut encoder = Encoder::new();
+        encoder.array(1 << 40);
+        let bytes = encoder.into_bytes();
+        let err = Decoder::new(&bytes).array().unwrap_err();
+        assert!(matches!(err, CborError::UnexpectedEnd), "{err:?}");
+    }
+}
@@ -0,0 +1,652 @@
+//! CBOR encoding for keys, ciphertexts and proofs, together with COSE-style wrappers.
+//!
+//! All types implementing [`CborEncoding`] are encoded using the *deterministic* CBOR profile
+//! ([RFC 8949, section 4.2.1]): integers and lengths use the shortest possible form,
+//! indefinite-length items are not used, and map keys are sorted by their encoded form.
+//! Decoding is strict and rejects any other encoding of the same data, as well as trailing bytes.
+//! Group elements must additionally be encoded canonically. As a result, each value has
+//! a single valid encoding, which is important for data fed into Fiat–Shamir transcripts
+//! (e.g., when a proof is verified against a ciphertext received from another party).
+//!
+//! Group scalars and elements are encoded as CBOR byte strings of the fixed size
+//! ([`ScalarOps::SCALAR_SIZE`] and [`ElementOps::ELEMENT_SIZE`], respectively);
+//! composite values (ciphertexts, proofs) are encoded as arrays of their components.
+//!
+//! [`CoseKey`] and [`CoseEnvelope`] follow the [COSE] structure for keys and single-signer
+//! signed messages, so that the encoded artifacts can be processed by existing
+//! COSE toolchains. Since COSE does not register key types for ElGamal encryption,
+//! private-use text labels are used where necessary.
+//!
+//! [RFC 8949, section 4.2.1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+//! [COSE]: https://www.rfc-editor.org/rfc/rfc9052.html
+//! [`ScalarOps::SCALAR_SIZE`]: crate::group::ScalarOps::SCALAR_SIZE
+//! [`ElementOps::ELEMENT_SIZE`]: crate::group::ElementOps::ELEMENT_SIZE
+//!
+//! # Examples
+//!
+//! ```
+//! # use elastic_elgamal::{cbor::{CborEncoding, CoseEnvelope}, group::Ristretto, Ciphertext, Keypair};
+//! # use rand::thread_rng;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut rng = thread_rng();
+//! let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+//! let ciphertext = pk.encrypt(42_u64, &mut rng);
+//!
+//! let bytes = ciphertext.to_cbor();
+//! let restored = Ciphertext::<Ristretto>::from_cbor(&bytes)?;
+//! assert_eq!(restored.to_bytes(), ciphertext.to_bytes());
+//!
+//! // Wrap the ciphertext into a COSE_Sign1-compatible envelope.
+//! let envelope = CoseEnvelope::new(&ciphertext);
+//! let data_to_sign = envelope.to_be_signed(b"");
+//! // ...sign `data_to_sign` with an external tool...
+//! # let signature = [0_u8; 64];
+//! let message = envelope.to_sign1(&signature);
+//!
+//! let (envelope, _signature) = CoseEnvelope::from_sign1(&message)?;
+//! let restored: Ciphertext<Ristretto> = envelope.open()?;
+//! # assert_eq!(restored.to_bytes(), ciphertext.to_bytes());
+//! # Ok(())
+//! # }
+//! ```
+
+use core::fmt;
+
+use crate::{
+    alloc::{String, ToString, Vec},
+    group::Group,
+    CandidateDecryption, Ciphertext, PublicKey, PublicKeyConversionError, SecretKey,
+    TaggedCiphertext, VerifiableDecryption,
+};
+
+mod codec;
+
+pub(crate) use self::codec::{Decoder, Encoder};
+
+/// Types that can be encoded to and decoded from deterministic CBOR.
+///
+/// This trait is sealed; it cannot be implemented outside the crate.
+pub trait CborEncoding: crate::sealed::Sealed + Sized {
+    /// Content type used in the protected header of [`CoseEnvelope`]s wrapping this type.
+    const CONTENT_TYPE: &'static str;
+
+    #[doc(hidden)] // implementation detail
+    fn encode(&self, encoder: &mut Encoder);
+
+    #[doc(hidden)] // implementation detail
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError>;
+
+    /// Encodes this value into deterministic CBOR.
+    fn to_cbor(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        self.encode(&mut encoder);
+        encoder.into_bytes()
+    }
+
+    /// Decodes a value from CBOR.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` are not a deterministic CBOR encoding of a value
+    /// of this type.
+    fn from_cbor(bytes: &[u8]) -> Result<Self, CborError> {
+        let mut decoder = Decoder::new(bytes);
+        let value = Self::decode(&mut decoder)?;
+        decoder.finish()?;
+        Ok(value)
+    }
+}
+
+/// Errors that can occur when decoding CBOR data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CborError {
+    /// Data ended unexpectedly.
+    UnexpectedEnd,
+    /// Data contains bytes after the decoded value.
+    TrailingBytes,
+    /// Data is valid CBOR, but it is not encoded deterministically (e.g., uses non-shortest
+    /// integer encoding, indefinite-length items, or unordered map keys), or a group element
+    /// is not encoded canonically.
+    NonCanonical,
+    /// Unexpected CBOR data type.
+    UnexpectedType {
+        /// Human-readable description of the expected type.
+        expected: &'static str,
+    },
+    /// Array or byte string has an unexpected length.
+    LenMismatch {
+        /// Expected length.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+    /// Unexpected key in a CBOR map, or a missing mandatory key.
+    UnexpectedMapKey(i64),
+    /// Unexpected CBOR tag.
+    UnexpectedTag(u64),
+    /// Content type of a [`CoseEnvelope`] differs from the expected one.
+    UnexpectedContentType,
+    /// Byte string does not represent a valid group scalar.
+    InvalidScalar,
+    /// Byte string does not represent a valid group element.
+    InvalidElement,
+    /// Invalid public key.
+    InvalidPublicKey(PublicKeyConversionError),
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => formatter.write_str("unexpected end of data"),
+            Self::TrailingBytes => formatter.write_str("trailing bytes after the decoded value"),
+            Self::NonCanonical => formatter.write_str("data is not encoded deterministically"),
+            Self::UnexpectedType { expected } => write!(formatter, "expected {expected}"),
+            Self::LenMismatch { expected, actual } => write!(
+                formatter,
+                "unexpected length ({actual}), expected {expected}"
+            ),
+            Self::UnexpectedMapKey(key) => write!(formatter, "unexpected or missing map key {key}"),
+            Self::UnexpectedTag(tag) => write!(formatter, "unexpected tag {tag}"),
+            Self::UnexpectedContentType => formatter.write_str("unexpected content type"),
+            Self::InvalidScalar => formatter.write_str("invalid group scalar"),
+            Self::InvalidElement => formatter.write_str("invalid group element"),
+            Self::InvalidPublicKey(err) => write!(formatter, "invalid public key: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CborError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPublicKey(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for PublicKey<G> {}
+
+impl<G: Group> CborEncoding for PublicKey<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.public-key+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.bytes(self.as_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        let element = decoder.element::<G>()?;
+        if G::is_identity(&element) {
+            Err(CborError::InvalidPublicKey(
+                PublicKeyConversionError::IdentityKey,
+            ))
+        } else {
+            Ok(Self::from_element(element))
+        }
+    }
+}
+
+impl<G: Group> CborEncoding for SecretKey<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.secret-key+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.scalar::<G>(self.expose_scalar());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.scalar::<G>().map(Self::new)
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for Ciphertext<G> {}
+
+impl<G: Group> CborEncoding for Ciphertext<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.ciphertext+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.element::<G>(&self.random_element);
+        encoder.element::<G>(&self.blinded_element);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        Ok(Self {
+            random_element: decoder.element::<G>()?,
+            blinded_element: decoder.element::<G>()?,
+        })
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for TaggedCiphertext<G> {}
+
+impl<G: Group> CborEncoding for TaggedCiphertext<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.tagged-ciphertext+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.bytes(self.tag());
+        self.ciphertext().encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        let tag = decoder.bytes()?;
+        let ciphertext = Ciphertext::decode(decoder)?;
+        Ok(Self::new(tag, ciphertext))
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for CandidateDecryption<G> {}
+
+impl<G: Group> CborEncoding for CandidateDecryption<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.decryption+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.element::<G>(&self.dh_element());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        let element = decoder.element::<G>()?;
+        Ok(VerifiableDecryption::from_element(element).into())
+    }
+}
+
+/// Checks that map keys are strictly increasing in their encoded form, as required
+/// by deterministic CBOR.
+#[derive(Debug, Default)]
+struct MapKeys<'a> {
+    last_key: Option<&'a [u8]>,
+}
+
+impl<'a> MapKeys<'a> {
+    fn next(&mut self, decoder: &mut Decoder<'a>) -> Result<i64, CborError> {
+        let key_bytes = decoder.peek_int_bytes()?;
+        if self
+            .last_key
+            .map_or(false, |last_key| last_key >= key_bytes)
+        {
+            return Err(CborError::NonCanonical);
+        }
+        self.last_key = Some(key_bytes);
+        decoder.int()
+    }
+}
+
+/// [COSE_Key] representation of a [`PublicKey`].
+///
+/// The key is encoded as a map with the following entries:
+///
+/// - Key type (label 1) set to [`Self::KEY_TYPE`]
+/// - Optional key ID (label 2) as a byte string
+/// - Public key bytes (label -2) as a byte string
+///
+/// [COSE_Key]: https://www.rfc-editor.org/rfc/rfc9052.html#section-7
+#[derive(Debug, Clone)]
+pub struct CoseKey<G: Group> {
+    /// Public key.
+    pub key: PublicKey<G>,
+    /// Optional key identifier.
+    pub key_id: Option<Vec<u8>>,
+}
+
+impl<G: Group> CoseKey<G> {
+    /// Private-use key type label.
+    pub const KEY_TYPE: &'static str = "elastic-elgamal";
+
+    const KTY_LABEL: i64 = 1;
+    const KID_LABEL: i64 = 2;
+    const X_LABEL: i64 = -2;
+
+    /// Creates a key without an identifier.
+    pub fn new(key: PublicKey<G>) -> Self {
+        Self { key, key_id: None }
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for CoseKey<G> {}
+
+impl<G: Group> CborEncoding for CoseKey<G> {
+    const CONTENT_TYPE: &'static str = "application/cose-key";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        // Keys are listed in the order of their encoded form (1, 2, -2).
+        encoder.map(if self.key_id.is_some() { 3 } else { 2 });
+        encoder.int(Self::KTY_LABEL);
+        encoder.text(Self::KEY_TYPE);
+        if let Some(key_id) = &self.key_id {
+            encoder.int(Self::KID_LABEL);
+            encoder.bytes(key_id);
+        }
+        encoder.int(Self::X_LABEL);
+        self.key.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        let len = decoder.map()?;
+        let mut keys = MapKeys::default();
+        let mut has_key_type = false;
+        let mut key_id = None;
+        let mut key = None;
+        for _ in 0..len {
+            match keys.next(decoder)? {
+                Self::KTY_LABEL => {
+                    if decoder.text()? != Self::KEY_TYPE {
+                        return Err(CborError::UnexpectedType {
+                            expected: "elastic-elgamal key type",
+                        });
+                    }
+                    has_key_type = true;
+                }
+                Self::KID_LABEL => key_id = Some(decoder.bytes()?.to_vec()),
+                Self::X_LABEL => key = Some(PublicKey::decode(decoder)?),
+                other => return Err(CborError::UnexpectedMapKey(other)),
+            }
+        }
+
+        if !has_key_type {
+            return Err(CborError::UnexpectedMapKey(Self::KTY_LABEL));
+        }
+        let key = key.ok_or(CborError::UnexpectedMapKey(Self::X_LABEL))?;
+        Ok(Self { key, key_id })
+    }
+}
+
+/// Payload together with protected headers in the [COSE_Sign1] format.
+///
+/// The envelope does not sign data by itself. Instead, [`Self::to_be_signed()`] returns
+/// the `Sig_structure` to be signed by an external signer, and [`Self::to_sign1()`]
+/// combines the envelope with the produced signature into a tagged COSE_Sign1 message.
+/// The protected header contains a single entry: the content type (label 3)
+/// equal to [`CborEncoding::CONTENT_TYPE`] of the wrapped value. The unprotected header
+/// is always empty.
+///
+/// [COSE_Sign1]: https://www.rfc-editor.org/rfc/rfc9052.html#section-4.2
+#[derive(Debug, Clone)]
+pub struct CoseEnvelope {
+    content_type: String,
+    protected_header: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+impl CoseEnvelope {
+    const CONTENT_TYPE_LABEL: i64 = 3;
+    const SIGN1_TAG: u64 = 18;
+
+    /// Wraps the provided value into an envelope.
+    pub fn new<T: CborEncoding>(value: &T) -> Self {
+        let mut encoder = Encoder::new();
+        encoder.map(1);
+        encoder.int(Self::CONTENT_TYPE_LABEL);
+        encoder.text(T::CONTENT_TYPE);
+
+        Self {
+            content_type: T::CONTENT_TYPE.to_string(),
+            protected_header: encoder.into_bytes(),
+            payload: value.to_cbor(),
+        }
+    }
+
+    fn parse_protected_header(protected_header: &[u8]) -> Result<String, CborError> {
+        let mut decoder = Decoder::new(protected_header);
+        let len = decoder.map()?;
+        let mut keys = MapKeys::default();
+        let mut content_type = None;
+        for _ in 0..len {
+            match keys.next(&mut decoder)? {
+                Self::CONTENT_TYPE_LABEL => content_type = Some(decoder.text()?.to_string()),
+                other => return Err(CborError::UnexpectedMapKey(other)),
+            }
+        }
+        decoder.finish()?;
+        content_type.ok_or(CborError::UnexpectedMapKey(Self::CONTENT_TYPE_LABEL))
+    }
+
+    /// Returns the content type of the payload.
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// Returns the CBOR-encoded payload.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Decodes the wrapped value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content type of the envelope does not match `T`,
+    /// or if the payload cannot be decoded.
+    pub fn open<T: CborEncoding>(&self) -> Result<T, CborError> {
+        if self.content_type != T::CONTENT_TYPE {
+            return Err(CborError::UnexpectedContentType);
+        }
+        T::from_cbor(&self.payload)
+    }
+
+    /// Returns the COSE `Sig_structure` for this envelope, which should be signed
+    /// by the signer. `external_aad` is externally supplied data authenticated
+    /// together with the envelope; it may be empty.
+    pub fn to_be_signed(&self, external_aad: &[u8]) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.array(4);
+        encoder.text("Signature1");
+        encoder.bytes(&self.protected_header);
+        encoder.bytes(external_aad);
+        encoder.bytes(&self.payload);
+        encoder.into_bytes()
+    }
+
+    /// Creates a tagged COSE_Sign1 message from this envelope and the provided signature
+    /// over [`Self::to_be_signed()`].
+    pub fn to_sign1(&self, signature: &[u8]) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.tag(Self::SIGN1_TAG);
+        encoder.array(4);
+        encoder.bytes(&self.protected_header);
+        encoder.map(0);
+        encoder.bytes(&self.payload);
+        encoder.bytes(signature);
+        encoder.into_bytes()
+    }
+
+    /// Parses a tagged COSE_Sign1 message, returning the envelope and the signature.
+    /// The signature is **not** verified; this is the responsibility of the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `message` is not a deterministically encoded COSE_Sign1 message
+    /// with the header structure described [above](Self).
+    pub fn from_sign1(message: &[u8]) -> Result<(Self, Vec<u8>), CborError> {
+        let mut decoder = Decoder::new(message);
+        let tag = decoder.tag()?;
+        if tag != Self::SIGN1_TAG {
+            return Err(CborError::UnexpectedTag(tag));
+        }
+        decoder.array_of_len(4)?;
+        let protected_header = decoder.bytes()?.to_vec();
+        let content_type = Self::parse_protected_header(&protected_header)?;
+        let unprotected_len = decoder.map()?;
+        if unprotected_len != 0 {
+            return Err(CborError::LenMismatch {
+                expected: 0,
+                actual: unprotected_len,
+            });
+        }
+        let payload = decoder.bytes()?.to_vec();
+        let signature = decoder.bytes()?.to_vec();
+        decoder.finish()?;
+
+        let envelope = Self {
+            content_type,
+            protected_header,
+            payload,
+        };
+        Ok((envelope, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merlin::Transcript;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::{ElementOps, Generic, Ristretto},
+        Keypair, LinearCombinationProof, LogEqualityProof, RangeDecomposition, RangeProof,
+        RingProof,
+    };
+
+    type K256 = Generic<k256::Secp256k1>;
+
+    fn test_ciphertext_roundtrip<G: Group>() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<G>::generate(&mut rng).into_tuple();
+        let ciphertext = pk.encrypt(5_u64, &mut rng);
+
+        let bytes = ciphertext.to_cbor();
+        assert_eq!(bytes.len(), 1 + 2 * (2 + G::ELEMENT_SIZE));
+        let restored = Ciphertext::<G>::from_cbor(&bytes).unwrap();
+        assert_eq!(restored.to_bytes(), ciphertext.to_bytes());
+
+        let mut bytes_with_trailer = bytes.clone();
+        bytes_with_trailer.push(0);
+        let err = Ciphertext::<G>::from_cbor(&bytes_with_trailer).unwrap_err();
+        assert!(matches!(err, CborError::TrailingBytes));
+
+        let err = Ciphertext::<G>::from_cbor(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(matches!(err, CborError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn ciphertext_roundtrip_for_ristretto() {
+        test_ciphertext_roundtrip::<Ristretto>();
+    }
+
+    #[test]
+    fn ciphertext_roundtrip_for_k256() {
+        test_ciphertext_roundtrip::<K256>();
+    }
+
+    #[test]
+    fn non_canonical_element_encoding_is_rejected() {
+        let mut encoder = Encoder::new();
+        encoder.array(2);
+        encoder.element::<Ristretto>(&Ristretto::generator());
+        // The field element encoding is not reduced modulo `2^255 - 19`.
+        encoder.bytes(&[0xff; 32]);
+
+        let err = Ciphertext::<Ristretto>::from_cbor(&encoder.into_bytes()).unwrap_err();
+        assert!(matches!(err, CborError::InvalidElement), "{err:?}");
+    }
+
+    #[test]
+    fn proofs_roundtrip() {
+        let mut rng = thread_rng();
+        let (pk, sk) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let range = RangeDecomposition::optimal(10).into();
+        let (ciphertext, proof) = pk.encrypt_range(&range, 7, &mut rng);
+        let bytes = proof.to_cbor();
+        let proof = RangeProof::from_cbor(&bytes).unwrap();
+        pk.verify_range(&range, ciphertext, &proof).unwrap();
+        assert_eq!(proof.to_cbor(), bytes);
+
+        let (ciphertext, proof) = pk.encrypt_bool(true, &mut rng);
+        let proof = RingProof::from_cbor(&proof.to_cbor()).unwrap();
+        pk.verify_bool(ciphertext, &proof).unwrap();
+
+        let bases = [Ristretto::generator(), pk.as_element()];
+        let coefficients = [SecretKey::generate(&mut rng), sk.clone()];
+        let point = Ristretto::multi_mul(
+            coefficients.iter().map(SecretKey::expose_scalar),
+            bases.iter().copied(),
+        );
+        let proof = LinearCombinationProof::new(
+            &bases,
+            &coefficients,
+            point,
+            &mut Transcript::new(b"test"),
+            &mut rng,
+        );
+        let proof = LinearCombinationProof::<Ristretto>::from_cbor(&proof.to_cbor()).unwrap();
+        proof
+            .verify(&bases, point, &mut Transcript::new(b"test"))
+            .unwrap();
+
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        let power = other.public().as_element() * sk.expose_scalar();
+        let proof = LogEqualityProof::new(
+            other.public(),
+            &sk,
+            (pk.as_element(), power),
+            &mut Transcript::new(b"test"),
+            &mut rng,
+        );
+        let proof = LogEqualityProof::<Ristretto>::from_cbor(&proof.to_cbor()).unwrap();
+        proof
+            .verify(
+                other.public(),
+                (pk.as_element(), power),
+                &mut Transcript::new(b"test"),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn cose_key_roundtrip() {
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut thread_rng()).into_tuple();
+        let mut key = CoseKey::new(pk.clone());
+        let bytes = key.to_cbor();
+        let restored = CoseKey::<Ristretto>::from_cbor(&bytes).unwrap();
+        assert_eq!(restored.key, pk);
+        assert!(restored.key_id.is_none());
+
+        key.key_id = Some(b"talliers".to_vec());
+        let bytes = key.to_cbor();
+        let restored = CoseKey::<Ristretto>::from_cbor(&bytes).unwrap();
+        assert_eq!(restored.key, pk);
+        assert_eq!(restored.key_id.as_deref(), Some(&b"talliers"[..]));
+    }
+
+    #[test]
+    fn cose_key_with_unordered_map_keys_is_rejected() {
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut thread_rng()).into_tuple();
+        let mut encoder = Encoder::new();
+        encoder.map(2);
+        encoder.int(-2);
+        encoder.bytes(pk.as_bytes());
+        encoder.int(1);
+        encoder.text(CoseKey::<Ristretto>::KEY_TYPE);
+
+        let err = CoseKey::<Ristretto>::from_cbor(&encoder.into_bytes()).unwrap_err();
+        assert!(matches!(err, CborError::NonCanonical), "{err:?}");
+    }
+
+    #[test]
+    fn cose_envelope_roundtrip() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let ciphertext = pk.encrypt(1_u64, &mut rng);
+        let envelope = CoseEnvelope::new(&ciphertext);
+        assert_eq!(
+            envelope.content_type(),
+            Ciphertext::<Ristretto>::CONTENT_TYPE
+        );
+
+        let to_be_signed = envelope.to_be_signed(b"aad");
+        assert!(to_be_signed.ends_with(&ciphertext.to_cbor()));
+
+        let message = envelope.to_sign1(b"signature");
+        let (restored, signature) = CoseEnvelope::from_sign1(&message).unwrap();
+        assert_eq!(signature, b"signature");
+        assert_eq!(restored.to_be_signed(b"aad"), to_be_signed);
+        let restored_ciphertext: Ciphertext<Ristretto> = restored.open().unwrap();
+        assert_eq!(restored_ciphertext.to_bytes(), ciphertext.to_bytes());
+
+        let err = restored.open::<PublicKey<Ristretto>>().unwrap_err();
+        assert!(matches!(err, CborError::UnexpectedContentType));
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     alloc::{vec, Vec},
     group::Group,
     proofs::{LogEqualityProof, TranscriptForGroup},
-    Ciphertext, DiscreteLogTable, Keypair, PublicKey, VerificationError,
+    proto_labels, Ciphertext, DiscreteLogSolver, Keypair, PublicKey, VerificationError,
 };
 
 /// Verifiable decryption for a certain [`Ciphertext`] in the ElGamal encryption scheme.
@@ -96,7 +96,7 @@ impl<G: Group> VerifiableDecryption<G> {
         // All inputs except from `ciphertext.blinded_element` are committed in the `proof`,
         // and it is not necessary to commit in order to allow iteratively recomputing
         // the ciphertext.
-        transcript.start_proof(b"decryption_with_custom_key");
+        transcript.start_proof(proto_labels::CUSTOM_KEY_DECRYPTION);
 
         let dh_element = ciphertext.random_element * keys.secret().expose_scalar();
         let proof = LogEqualityProof::new(
@@ -133,15 +133,15 @@ impl<G: Group> VerifiableDecryption<G> {
 
     /// Decrypts the provided ciphertext and returns the original encrypted value.
     ///
-    /// `lookup_table` is used to find encrypted values based on the original decrypted
-    /// group element. That is, it must contain all valid plaintext values. If the value
-    /// is not in the table, this method will return `None`.
-    pub fn decrypt(
+    /// `lookup_table` (e.g., a [`DiscreteLogTable`](crate::DiscreteLogTable)) is used to find encrypted values based
+    /// on the original decrypted group element. That is, it must contain all valid plaintext
+    /// values. If the value is not in the table, this method will return `None`.
+    pub fn decrypt<D: DiscreteLogSolver<G> + ?Sized>(
         &self,
         encrypted: Ciphertext<G>,
-        lookup_table: &DiscreteLogTable<G>,
+        lookup_table: &D,
     ) -> Option<u64> {
-        lookup_table.get(&self.decrypt_to_element(encrypted))
+        lookup_table.solve(&self.decrypt_to_element(encrypted))
     }
 }
 
@@ -177,7 +177,7 @@ impl<G: Group> CandidateDecryption<G> {
         }
     }
 
-    pub(super) fn dh_element(self) -> G::Element {
+    pub(crate) fn dh_element(self) -> G::Element {
         self.inner.dh_element
     }
 
@@ -194,7 +194,7 @@ impl<G: Group> CandidateDecryption<G> {
         proof: &LogEqualityProof<G>,
         transcript: &mut Transcript,
     ) -> Result<VerifiableDecryption<G>, VerificationError> {
-        transcript.start_proof(b"decryption_with_custom_key");
+        transcript.start_proof(proto_labels::CUSTOM_KEY_DECRYPTION);
 
         let dh_element = self.dh_element();
         proof.verify(
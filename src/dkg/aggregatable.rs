@@ -0,0 +1,513 @@
+//! Single-round aggregatable DKG.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+#[cfg(feature = "serde")]
+use crate::serde::{ElementHelper, ScalarHelper, VecHelper};
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    proofs::{LogEqualityProof, ProofOfPossession, TranscriptForGroup},
+    proto_labels,
+    sharing::{self, ActiveParticipant, Dealer, Params, PublicKeySet, PublicPolynomial},
+    PublicKey, SecretKey,
+};
+
+/// Single-round distributed key generation, in which each participant posts a single
+/// [`Dealing`] to a public bulletin board instead of exchanging private messages
+/// with every other participant.
+///
+/// Each participant has a long-term encryption key known to all participants. A dealing
+/// contains the dealer's public polynomial with a proof of possession (same as
+/// [`PublicInfo`](super::PublicInfo) in the interactive protocol) and the secret shares
+/// for all participants, each encrypted for the participant's key. Each participant
+/// decrypts and checks the shares addressed to it; if a share is invalid, the participant
+/// publishes a [`DealingComplaint`], which can be verified by anyone without trusting
+/// the complainant. Dealers with invalid dealings or justified complaints are excluded,
+/// and the remaining (*qualified*) dealings are aggregated into the shared key.
+///
+/// Dealings have size `O(n)`, and checking them takes `O(n²)` operations overall, so
+/// this protocol is meant for small committees (up to a few dozen participants).
+///
+/// # Security considerations
+///
+/// Unlike the [committed protocol](super::ParticipantCollectingCommitments), there is
+/// no commitment round. Thus, the last dealer to post its dealing can choose the dealing
+/// based on dealings of other participants, e.g., to bias the shared key. Additionally,
+/// shares are encrypted with a one-time pad derived from a Diffie–Hellman exchange with
+/// the participant's encryption key, so compromising this key reveals all shares
+/// addressed to the participant. Encryption keys should thus not be reused outside
+/// of key generation.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{dkg::AggregatableDkg, group::Ristretto, sharing::Params, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let keypairs: Vec<_> = (0..3)
+///     .map(|_| Keypair::<Ristretto>::generate(&mut rng))
+///     .collect();
+/// let keys = keypairs.iter().map(|keypair| keypair.public().clone());
+/// let dkg = AggregatableDkg::new(Params::new(3, 2), keys.collect())?;
+///
+/// // Each participant posts a single dealing.
+/// let dealings: Vec<_> = (0..3).map(|i| dkg.deal(i, &mut rng)).collect();
+/// // Dealings are checked by all participants. A participant that cannot decrypt
+/// // a valid share would publish a complaint via `AggregatableDkg::complain()`.
+/// for (i, dealing) in dealings.iter().enumerate() {
+///     dkg.verify_dealing(dealing)?;
+///     for (j, keypair) in keypairs.iter().enumerate() {
+///         dkg.decrypt_share(i, dealing, j, keypair.secret())?;
+///     }
+/// }
+///
+/// // All dealings are qualified; aggregate them.
+/// let qualified: Vec<_> = dealings.iter().enumerate().collect();
+/// let key_set = dkg.key_set(&qualified)?;
+/// let participant = dkg.complete(&qualified, 1, keypairs[1].secret())?;
+/// assert_eq!(participant.key_set().shared_key(), key_set.shared_key());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AggregatableDkg<G: Group> {
+    params: Params,
+    encryption_keys: Vec<PublicKey<G>>,
+}
+
+impl<G: Group> AggregatableDkg<G> {
+    /// Creates a protocol instance with the specified `params` and encryption keys
+    /// of all participants, ordered by the participant index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of keys does not match the number of participants
+    /// in `params`, or if `params` are [not supported](Params::check_group()) by the group.
+    pub fn new(params: Params, encryption_keys: Vec<PublicKey<G>>) -> Result<Self, sharing::Error> {
+        params.check_group::<G>()?;
+        if encryption_keys.len() != params.shares {
+            return Err(sharing::Error::ParticipantCountMismatch);
+        }
+        Ok(Self {
+            params,
+            encryption_keys,
+        })
+    }
+
+    /// Returns the parameters of the generated key.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns encryption keys of all participants.
+    pub fn encryption_keys(&self) -> &[PublicKey<G>] {
+        &self.encryption_keys
+    }
+
+    /// Creates a dealing for the participant with the specified 0-based `dealer_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dealer_index` is out of bounds.
+    pub fn deal<R: CryptoRng + RngCore>(&self, dealer_index: usize, rng: &mut R) -> Dealing<G> {
+        assert!(
+            dealer_index < self.params.shares,
+            "dealer index {dealer_index} is out of bounds"
+        );
+        let dealer = Dealer::<G>::new(self.params, rng);
+        let ephemeral_secret = SecretKey::<G>::generate(rng);
+        let ephemeral_key = PublicKey::from(&ephemeral_secret);
+
+        let encrypted_shares = self
+            .encryption_keys
+            .iter()
+            .enumerate()
+            .map(|(recipient_index, key)| {
+                let dh_element = key.as_element() * ephemeral_secret.expose_scalar();
+                let pad = self.pad(dealer_index, recipient_index, &ephemeral_key, dh_element);
+                *dealer
+                    .secret_share_for_participant(recipient_index)
+                    .expose_scalar()
+                    + pad
+            })
+            .collect();
+        let (polynomial, proof_of_possession) = dealer.public_info();
+        Dealing {
+            polynomial,
+            proof_of_possession: proof_of_possession.clone(),
+            ephemeral_key,
+            encrypted_shares,
+        }
+    }
+
+    /// Derives the one-time pad for the share from `dealer_index` to `recipient_index`.
+    fn pad(
+        &self,
+        dealer_index: usize,
+        recipient_index: usize,
+        ephemeral_key: &PublicKey<G>,
+        dh_element: G::Element,
+    ) -> G::Scalar {
+        let mut transcript = Transcript::new(proto_labels::AGGREGATABLE_DKG);
+        transcript.append_u64(b"n", self.params.shares as u64);
+        transcript.append_u64(b"t", self.params.threshold as u64);
+        transcript.append_u64(b"dealer", dealer_index as u64);
+        transcript.append_u64(b"recipient", recipient_index as u64);
+        transcript.append_element_bytes(b"R", ephemeral_key.as_bytes());
+        transcript.append_element_bytes(b"K", self.encryption_keys[recipient_index].as_bytes());
+        transcript.append_element::<G>(b"[r]K", &dh_element);
+        transcript.challenge_scalar::<G>(b"pad")
+    }
+
+    fn complaint_transcript(&self, dealer_index: usize, recipient_index: usize) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::DKG_COMPLAINT);
+        transcript.append_u64(b"n", self.params.shares as u64);
+        transcript.append_u64(b"t", self.params.threshold as u64);
+        transcript.append_u64(b"dealer", dealer_index as u64);
+        transcript.append_u64(b"recipient", recipient_index as u64);
+        transcript
+    }
+
+    /// Performs public checks of a `dealing`: checks the number of encrypted shares
+    /// and the proof of possession for the public polynomial. These checks can be performed
+    /// by anyone, e.g., by the bulletin board.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the checks fail.
+    pub fn verify_dealing(&self, dealing: &Dealing<G>) -> Result<(), Error> {
+        if dealing.encrypted_shares.len() != self.params.shares {
+            return Err(Error::MalformedDealing);
+        }
+        PublicKeySet::validate(
+            self.params,
+            &dealing.polynomial,
+            &dealing.proof_of_possession,
+        )
+        .map_err(Error::MalformedParticipantProof)
+    }
+
+    /// Decrypts the secret share addressed to the participant with the specified 0-based
+    /// `recipient_index` and checks it against the public polynomial from the `dealing`.
+    /// The dealing should be [publicly verified](Self::verify_dealing()) beforehand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSecret`] if the decrypted share is invalid. In this case,
+    /// the participant should [complain](Self::complain()) against the dealer.
+    /// Returns [`Error::MalformedDealing`] if the dealing has an unexpected number of shares.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dealer_index` or `recipient_index` is out of bounds.
+    pub fn decrypt_share(
+        &self,
+        dealer_index: usize,
+        dealing: &Dealing<G>,
+        recipient_index: usize,
+        secret: &SecretKey<G>,
+    ) -> Result<SecretKey<G>, Error> {
+        let dh_element = dealing.ephemeral_key.as_element() * secret.expose_scalar();
+        self.open_share(dealer_index, dealing, recipient_index, dh_element)
+    }
+
+    fn open_share(
+        &self,
+        dealer_index: usize,
+        dealing: &Dealing<G>,
+        recipient_index: usize,
+        dh_element: G::Element,
+    ) -> Result<SecretKey<G>, Error> {
+        assert!(
+            dealer_index < self.params.shares,
+            "dealer index {dealer_index} is out of bounds"
+        );
+        let encrypted_share = dealing
+            .encrypted_shares
+            .get(recipient_index)
+            .ok_or(Error::MalformedDealing)?;
+        let pad = self.pad(
+            dealer_index,
+            recipient_index,
+            &dealing.ephemeral_key,
+            dh_element,
+        );
+        let share = SecretKey::new(*encrypted_share - pad);
+
+        let polynomial = PublicPolynomial::<G>::new(dealing.polynomial.clone());
+        let expected_element = polynomial.value_at((recipient_index as u64 + 1).into());
+        if G::mul_generator(share.expose_scalar()) == expected_element {
+            Ok(share)
+        } else {
+            Err(Error::InvalidSecret)
+        }
+    }
+
+    /// Creates a complaint against the dealer with the specified 0-based `dealer_index`
+    /// on behalf of the participant with the specified 0-based `recipient_index`.
+    /// The complaint reveals the share addressed to the participant, but not
+    /// the participant's encryption `secret`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `recipient_index` is out of bounds.
+    pub fn complain<R: CryptoRng + RngCore>(
+        &self,
+        dealer_index: usize,
+        dealing: &Dealing<G>,
+        recipient_index: usize,
+        secret: &SecretKey<G>,
+        rng: &mut R,
+    ) -> DealingComplaint<G> {
+        let encryption_key = &self.encryption_keys[recipient_index];
+        let dh_element = dealing.ephemeral_key.as_element() * secret.expose_scalar();
+        let mut transcript = self.complaint_transcript(dealer_index, recipient_index);
+        let proof = LogEqualityProof::new(
+            &dealing.ephemeral_key,
+            secret,
+            (encryption_key.as_element(), dh_element),
+            &mut transcript,
+            rng,
+        );
+        DealingComplaint {
+            recipient_index,
+            dh_element,
+            proof,
+        }
+    }
+
+    /// Verifies a `complaint` against the dealer with the specified 0-based `dealer_index`.
+    /// If this method returns `Ok(())`, the complaint is justified, and the dealer
+    /// must be excluded from the qualified dealers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnfoundedComplaint`] if the complaint is not justified, i.e.,
+    /// the complaint proof does not verify, or the share addressed to the complainant
+    /// is valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dealer_index` or the recipient index in the complaint is out of bounds.
+    pub fn verify_complaint(
+        &self,
+        dealer_index: usize,
+        dealing: &Dealing<G>,
+        complaint: &DealingComplaint<G>,
+    ) -> Result<(), Error> {
+        let recipient_index = complaint.recipient_index;
+        let encryption_key = &self.encryption_keys[recipient_index];
+        let mut transcript = self.complaint_transcript(dealer_index, recipient_index);
+        complaint
+            .proof
+            .verify(
+                &dealing.ephemeral_key,
+                (encryption_key.as_element(), complaint.dh_element),
+                &mut transcript,
+            )
+            .map_err(|_| Error::UnfoundedComplaint)?;
+
+        match self.open_share(dealer_index, dealing, recipient_index, complaint.dh_element) {
+            Ok(_) => Err(Error::UnfoundedComplaint),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn aggregate_polynomial(
+        &self,
+        qualified: &[(usize, &Dealing<G>)],
+    ) -> Result<PublicPolynomial<G>, Error> {
+        assert!(!qualified.is_empty(), "no qualified dealings");
+        let mut is_included = vec![false; self.params.shares];
+        let mut polynomial = PublicPolynomial::new(vec![G::identity(); self.params.threshold]);
+        for &(dealer_index, dealing) in qualified {
+            if is_included[dealer_index] {
+                return Err(Error::Equivocation(dealer_index));
+            }
+            is_included[dealer_index] = true;
+            if dealing.polynomial.len() != self.params.threshold {
+                return Err(Error::MalformedDealing);
+            }
+            polynomial += &PublicPolynomial::new(dealing.polynomial.clone());
+        }
+        Ok(polynomial)
+    }
+
+    /// Aggregates `qualified` dealings (provided together with 0-based dealer indexes)
+    /// into the public key set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Equivocation`] if a dealer index is repeated, and
+    /// [`Error::MalformedDealing`] if a dealing is malformed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qualified` is empty or if a dealer index is out of bounds.
+    pub fn key_set(&self, qualified: &[(usize, &Dealing<G>)]) -> Result<PublicKeySet<G>, Error> {
+        let polynomial = self.aggregate_polynomial(qualified)?;
+        Ok(PublicKeySet::from_polynomial(self.params, &polynomial))
+    }
+
+    /// Aggregates `qualified` dealings into the key set and the secret share
+    /// of the participant with the specified 0-based `recipient_index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same situations as [`Self::key_set()`], or if any share
+    /// addressed to the participant cannot be [decrypted](Self::decrypt_share()).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same situations as [`Self::key_set()`], or if `recipient_index`
+    /// is out of bounds.
+    pub fn complete(
+        &self,
+        qualified: &[(usize, &Dealing<G>)],
+        recipient_index: usize,
+        secret: &SecretKey<G>,
+    ) -> Result<ActiveParticipant<G>, Error> {
+        let key_set = self.key_set(qualified)?;
+        let mut secret_share = SecretKey::new(G::Scalar::from(0));
+        for &(dealer_index, dealing) in qualified {
+            secret_share += self.decrypt_share(dealer_index, dealing, recipient_index, secret)?;
+        }
+        ActiveParticipant::new(key_set, recipient_index, secret_share)
+            .map_err(Error::InconsistentPublicShares)
+    }
+}
+
+/// Single message posted by a dealer in the [`AggregatableDkg`] protocol.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Dealing<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ElementHelper<G>, 1>"))]
+    polynomial: Vec<G::Element>,
+    proof_of_possession: ProofOfPossession<G>,
+    ephemeral_key: PublicKey<G>,
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ScalarHelper<G>, 1>"))]
+    encrypted_shares: Vec<G::Scalar>,
+}
+
+impl<G: Group> Dealing<G> {
+    /// Returns the dealer's public polynomial.
+    pub fn polynomial(&self) -> &[G::Element] {
+        &self.polynomial
+    }
+
+    /// Returns the proof of possession for the public polynomial.
+    pub fn proof_of_possession(&self) -> &ProofOfPossession<G> {
+        &self.proof_of_possession
+    }
+}
+
+/// Publicly verifiable complaint against a dealer in the [`AggregatableDkg`] protocol.
+///
+/// The complaint reveals the Diffie–Hellman element used to encrypt the share addressed
+/// to the complainant, together with a proof of its correctness. Thus, anyone can decrypt
+/// the share and check that it is invalid.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct DealingComplaint<G: Group> {
+    recipient_index: usize,
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    dh_element: G::Element,
+    proof: LogEqualityProof<G>,
+}
+
+impl<G: Group> DealingComplaint<G> {
+    /// Returns the 0-based index of the complainant.
+    pub fn recipient_index(&self) -> usize {
+        self.recipient_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::{ElementOps, Ristretto, ScalarOps},
+        DiscreteLogTable, Keypair,
+    };
+
+    #[test]
+    fn aggregatable_dkg_with_faulty_dealer() {
+        let mut rng = thread_rng();
+        let params = Params::new(4, 3);
+        let keypairs: Vec<_> = (0..4)
+            .map(|_| Keypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let keys = keypairs.iter().map(|keypair| keypair.public().clone());
+        let dkg = AggregatableDkg::new(params, keys.collect()).unwrap();
+
+        let mut dealings: Vec<_> = (0..4).map(|i| dkg.deal(i, &mut rng)).collect();
+        // Dealer #2 sends an invalid share to participant #0.
+        dealings[2].encrypted_shares[0] += Ristretto::generate_scalar(&mut rng);
+
+        for (i, dealing) in dealings.iter().enumerate() {
+            dkg.verify_dealing(dealing).unwrap();
+            for (j, keypair) in keypairs.iter().enumerate() {
+                let result = dkg.decrypt_share(i, dealing, j, keypair.secret());
+                if (i, j) == (2, 0) {
+                    assert!(matches!(result, Err(Error::InvalidSecret)));
+                } else {
+                    result.unwrap();
+                }
+            }
+        }
+
+        let complaint = dkg.complain(2, &dealings[2], 0, keypairs[0].secret(), &mut rng);
+        dkg.verify_complaint(2, &dealings[2], &complaint).unwrap();
+        // A complaint against an honest dealer is not justified.
+        let complaint = dkg.complain(1, &dealings[1], 0, keypairs[0].secret(), &mut rng);
+        let err = dkg
+            .verify_complaint(1, &dealings[1], &complaint)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnfoundedComplaint));
+        // A complaint with a forged DH element does not verify.
+        let mut complaint = dkg.complain(2, &dealings[2], 0, keypairs[1].secret(), &mut rng);
+        let err = dkg
+            .verify_complaint(2, &dealings[2], &complaint)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnfoundedComplaint));
+        complaint.dh_element += Ristretto::generator();
+        let err = dkg
+            .verify_complaint(2, &dealings[2], &complaint)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnfoundedComplaint));
+
+        let qualified: Vec<_> = dealings
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 2)
+            .collect();
+        let participants: Vec<_> = keypairs
+            .iter()
+            .enumerate()
+            .map(|(j, keypair)| dkg.complete(&qualified, j, keypair.secret()).unwrap())
+            .collect();
+        let key_set = participants[0].key_set();
+        let ciphertext = key_set.shared_key().encrypt(2_u64, &mut rng);
+        let shares = participants.iter().take(3).map(|participant| {
+            let (share, _) = participant.decrypt_share(ciphertext, &mut rng);
+            (participant.index(), share)
+        });
+        let decrypted = params.combine_and_decode(ciphertext, shares, &DiscreteLogTable::new(0..5));
+        assert_eq!(decrypted.unwrap(), 2);
+
+        let mut repeated = qualified.clone();
+        repeated.push(qualified[0]);
+        let err = dkg.key_set(&repeated).unwrap_err();
+        assert!(matches!(err, Error::Equivocation(0)));
+    }
+}
@@ -0,0 +1,1745 @@
+//! Committed Pedersen's distributed key generation (DKG).
+//!
+//! DKG allows to securely generate shared secret without a need for a trusted
+//! dealer. Compare with Feldman's verifiable secret sharing implemented in the [`sharing`] module
+//! which requires a trusted dealer.
+//!
+//! This implementation is based on [Pedersen's DKG], which was shown by [Gennaro et al.]
+//! to contain a flaw allowing an adversary to bias distribution of the shared public key.
+//! We try to prevent this kind of possible attacks by forcing the parties to
+//! commit to their public key shares before receiving public shares from other
+//! parties.
+//!
+//! All `insert_*` methods of participant states are idempotent: re-delivery of an identical
+//! message is a no-op, while a conflicting message from the same sender is reported
+//! as [`Error::Equivocation`]. Thus, messages can be delivered via transports with
+//! at-least-once semantics, such as gossip networks.
+//!
+//! As an additional safeguard (e.g., for a small number of participants, where withholding
+//! by the last participant is a practical concern), the resulting shared key can be bound
+//! to an evaluation of a [verifiable delay function](Vdf) over all public polynomials;
+//! see [`ParticipantExchangingSecrets::complete_with_vdf()`]. Since no participant can compute
+//! the VDF output before the polynomials are fixed, the shared key cannot be ground.
+//!
+//! Participant states do not authenticate senders of messages; this is delegated
+//! to the transport. If participants have long-term identity keys listed in a committee
+//! [`Config`], a [`DkgSession`] can [sign](DkgSession::sign()) emitted messages
+//! and [check signatures](DkgSession::verify()) of received ones, so that a participant
+//! cannot impersonate another one.
+//!
+//! For small committees with access to a public bulletin board, the module also provides
+//! a single-round [`AggregatableDkg`], in which each participant posts a single message
+//! with secret shares encrypted for other participants.
+//!
+//! [Pedersen's DKG]: https://link.springer.com/content/pdf/10.1007/3-540-46416-6_47.pdf
+//! [Gennaro et al.]: https://link.springer.com/content/pdf/10.1007/3-540-48910-X_21.pdf
+//!
+//! # Examples
+//!
+//! Decentralized key generation for 2-of-3 threshold encryption.
+//!
+//! ```
+//! # use elastic_elgamal::{
+//! #     group::Ristretto, dkg::*, sharing::Params,
+//! # };
+//! # use rand::thread_rng;
+//! # use std::error::Error as StdError;
+//! # fn main() -> Result<(), Box<dyn StdError>> {
+//! let mut rng = thread_rng();
+//! let params = Params::new(3, 2);
+//!
+//! // Initialize participants.
+//! let participants = (0..3).map(|i| {
+//!     ParticipantCollectingCommitments::<Ristretto>::new(params, i, &mut rng)
+//! });
+//! let mut participants: Vec<_> = participants.collect();
+//!
+//! // Publish commitments from all participants...
+//! let commitments: Vec<_> = participants
+//!     .iter()
+//!     .map(|participant| participant.commitment())
+//!     .collect();
+//! // ...and consume them from each participant's perspective.
+//! for (i, participant) in participants.iter_mut().enumerate() {
+//!     for (j, &commitment) in commitments.iter().enumerate() {
+//!         if i != j {
+//!             participant.insert_commitment(j, commitment)?;
+//!         }
+//!     }
+//! }
+//!
+//! // Transition all participants to the next stage: exchanging polynomials.
+//! let mut participants: Vec<_> = participants
+//!     .into_iter()
+//!     .map(|participant| participant.finish_commitment_phase())
+//!     .collect();
+//! // Publish each participant's polynomial...
+//! let infos: Vec<_> = participants
+//!     .iter()
+//!     .map(|participant| participant.public_info().into_owned())
+//!     .collect();
+//! // ...and consume them from each participant's perspective.
+//! for (i, participant) in participants.iter_mut().enumerate() {
+//!     for (j, info) in infos.iter().enumerate() {
+//!         if i != j {
+//!             participant.insert_public_polynomial(j, info.clone())?;
+//!         }
+//!     }
+//! }
+//!
+//! // Transition all participants to the final phase: exchanging secrets.
+//! let mut participants: Vec<_> = participants
+//!     .into_iter()
+//!     .map(|participant| participant.finish_polynomials_phase())
+//!     .collect();
+//! // Exchange shares (this should happen over secure peer-to-peer channels).
+//! for i in 0..3 {
+//!     for j in 0..3 {
+//!         if i == j { continue; }
+//!         let share = participants[i].secret_share_for_participant(j);
+//!         participants[j].insert_secret_share(i, share)?;
+//!     }
+//! }
+//!
+//! // Finalize all participants.
+//! let participants = participants
+//!     .into_iter()
+//!     .map(|participant| participant.complete())
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! // Check that the shared key is the same for all participants.
+//! let expected_key = participants[0].key_set().shared_key();
+//! for participant in &participants {
+//!     assert_eq!(participant.key_set().shared_key(), expected_key);
+//! }
+//!
+//! // Participants can then jointly decrypt messages as showcased
+//! // in the example for the `sharing` module.
+//! # Ok(())
+//! # }
+//! ```
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use core::fmt;
+
+use merlin::Transcript;
+
+#[cfg(feature = "serde")]
+use crate::serde::{BytesHelper, ElementHelper, VecHelper};
+use crate::{
+    alloc::{vec, Cow, Vec},
+    group::Group,
+    proofs::{ProofOfPossession, TranscriptForGroup, VerificationError},
+    proto_labels,
+    sharing::{
+        self, ActiveParticipant, Config, ConfigError, Dealer, MessageLog, Params, PublicKeySet,
+        PublicPolynomial,
+    },
+    CertificateVerifier, IdentityKeypair, PublicKey, SecretKey,
+};
+
+mod aggregatable;
+mod signed;
+
+pub use self::{
+    aggregatable::{AggregatableDkg, Dealing, DealingComplaint},
+    signed::{DkgMessage, SignedDkgMessage},
+};
+
+/// Errors that can occur during the distributed key generation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Secret received from the party does not correspond to their commitment via
+    /// the public polynomial.
+    InvalidSecret,
+    /// Provided commitment does not correspond to the party's public key share.
+    InvalidCommitment,
+    /// Participant with the specified 0-based index has sent conflicting messages
+    /// for the same protocol stage (e.g., two different commitments).
+    Equivocation(usize),
+    /// Provided proof of possession or public polynomial is malformed.
+    MalformedParticipantProof(sharing::Error),
+    /// Public shares obtained from accumulated public polynomial are inconsistent.
+    InconsistentPublicShares(sharing::Error),
+    /// Provided [`VdfEvaluation`] does not verify for the VDF input of the protocol.
+    InvalidVdfEvaluation,
+    /// Some secret shares inserted on the [optimistic path](ParticipantExchangingSecrets#optimistic-path)
+    /// are invalid. Contains 0-based indexes of participants that have sent invalid shares.
+    InvalidSecrets(Vec<usize>),
+    /// [`Dealing`] in the aggregatable DKG has an unexpected number of encrypted shares
+    /// or polynomial coefficients.
+    MalformedDealing,
+    /// [`DealingComplaint`] is not justified: either its proof does not verify, or the share
+    /// addressed to the complainant is valid.
+    UnfoundedComplaint,
+    /// [`SignedDkgMessage`] has an unexpected sender, recipient or kind (e.g., a commitment
+    /// is provided instead of a secret share).
+    UnexpectedMessage,
+    /// Signature of a [`SignedDkgMessage`] does not verify.
+    InvalidSignature(VerificationError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSecret => formatter.write_str(
+                "secret received from the party does not correspond to their commitment via \
+                public polynomial",
+            ),
+            Self::InvalidCommitment => formatter.write_str(
+                "public polynomial received from one of the parties does not correspond \
+                to their commitment",
+            ),
+            Self::Equivocation(participant) => write!(
+                formatter,
+                "participant #{participant} has sent conflicting messages for the same \
+                 protocol stage"
+            ),
+            Self::MalformedParticipantProof(err) => write!(
+                formatter,
+                "provided proof of possession or public polynomial is malformed: {err}"
+            ),
+            Self::InconsistentPublicShares(err) => write!(
+                formatter,
+                "public shares obtained from accumulated public polynomial \
+                 are inconsistent: {err}"
+            ),
+            Self::InvalidVdfEvaluation => {
+                formatter.write_str("VDF evaluation does not verify for the protocol input")
+            }
+            Self::InvalidSecrets(participants) => write!(
+                formatter,
+                "secrets received from participants {participants:?} do not correspond \
+                 to their commitments via public polynomials"
+            ),
+            Self::MalformedDealing => formatter.write_str(
+                "dealing has an unexpected number of encrypted shares or polynomial coefficients",
+            ),
+            Self::UnfoundedComplaint => {
+                formatter.write_str("complaint against dealer is unfounded")
+            }
+            Self::UnexpectedMessage => {
+                formatter.write_str("signed message has unexpected sender, recipient or kind")
+            }
+            Self::InvalidSignature(err) => write!(formatter, "invalid message signature: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InconsistentPublicShares(err) | Self::MalformedParticipantProof(err) => Some(err),
+            Self::InvalidSignature(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn create_commitment<G: Group>(element: &G::Element, opening: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+    G::serialize_element(element, &mut bytes);
+    hasher.update(&bytes);
+    hasher.update(opening);
+    hasher.finalize().into()
+}
+
+/// Verifiable delay function (VDF) that can be used to bind the output of the distributed
+/// key generation to all participants' contributions.
+///
+/// A VDF requires a prescribed amount of sequential computation to evaluate, while
+/// the evaluation result can be verified efficiently. The crate does not provide VDF
+/// implementations; they should be supplied by the caller (e.g., a Wesolowski or Pietrzak VDF
+/// over a group of unknown order).
+///
+/// See [`ParticipantExchangingSecrets::complete_with_vdf()`] for details on how the VDF is used.
+pub trait Vdf {
+    /// Evaluates the function on the provided `input`.
+    fn evaluate(&self, input: &[u8]) -> VdfEvaluation;
+
+    /// Checks whether the `evaluation` is valid for the provided `input`.
+    fn verify(&self, input: &[u8], evaluation: &VdfEvaluation) -> bool;
+}
+
+/// Output of a [`Vdf`] together with the proof of its correctness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VdfEvaluation {
+    /// Function output.
+    #[cfg_attr(feature = "serde", serde(with = "BytesHelper"))]
+    pub output: Vec<u8>,
+    /// Proof that the output is correct.
+    #[cfg_attr(feature = "serde", serde(with = "BytesHelper"))]
+    pub proof: Vec<u8>,
+}
+
+/// Opening for a hash commitment used in Pedersen's distributed key generation.
+#[derive(Debug, Clone)]
+pub struct Opening(pub(crate) Zeroizing<[u8; 32]>);
+
+/// Participant state during the first stage of the committed Pedersen's distributed key generation.
+///
+/// During this stage, participants exchange commitments to their public keys via
+/// a public bulletin board (e.g., a blockchain).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ParticipantCollectingCommitments<G: Group> {
+    params: Params,
+    index: usize,
+    dealer: Dealer<G>,
+    commitments: Vec<Option<[u8; 32]>>,
+    opening: Opening,
+}
+
+impl<G: Group> ParticipantCollectingCommitments<G> {
+    /// Instantiates a distributed key generation participant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater or equal to the number of shares, or if `params`
+    /// are [not supported](Params::check_group()) by the group.
+    pub fn new<R: CryptoRng + RngCore>(params: Params, index: usize, rng: &mut R) -> Self {
+        assert!(index < params.shares);
+
+        let dealer = Dealer::new(params, rng);
+        let mut opening = Zeroizing::new([0_u8; 32]);
+        rng.fill_bytes(&mut *opening);
+
+        let mut commitments = vec![None; params.shares];
+        let (public_poly, _) = dealer.public_info();
+        commitments[index] = Some(create_commitment::<G>(&public_poly[0], opening.as_slice()));
+        Self {
+            params,
+            index,
+            dealer,
+            commitments,
+            opening: Opening(opening),
+        }
+    }
+
+    /// Returns params of this threshold ElGamal encryption scheme.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns 0-based index of this participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the commitment of participant's share of the joint public key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the commitment is missing which can only happen if this struct got corrupted
+    /// (e.g., after deserialization).
+    pub fn commitment(&self) -> [u8; 32] {
+        self.commitments[self.index].unwrap()
+    }
+
+    /// Inserts a commitment from the participant with index `participant_index`.
+    /// Re-inserting the same commitment is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Equivocation`] if another commitment for the participant
+    /// was already provided.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant_index` is out of bounds.
+    pub fn insert_commitment(
+        &mut self,
+        participant_index: usize,
+        commitment: [u8; 32],
+    ) -> Result<(), Error> {
+        match &self.commitments[participant_index] {
+            Some(existing) if *existing == commitment => Ok(()),
+            Some(_) => Err(Error::Equivocation(participant_index)),
+            None => {
+                self.commitments[participant_index] = Some(commitment);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns indices of parties whose commitments were not provided.
+    pub fn missing_commitments(&self) -> impl Iterator<Item = usize> + '_ {
+        self.commitments
+            .iter()
+            .enumerate()
+            .filter_map(|(i, commitment)| commitment.is_none().then_some(i))
+    }
+
+    /// Proceeds to the next step of the DKG protocol, in which participants exchange public
+    /// polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any commitments are missing. If this is not known statically, check
+    /// with [`Self::missing_commitments()`] before calling this method.
+    pub fn finish_commitment_phase(self) -> ParticipantCollectingPolynomials<G> {
+        if let Some(missing_idx) = self.missing_commitments().next() {
+            panic!("Missing commitment for participant {missing_idx}");
+        }
+
+        let (public_polynomial, _) = self.dealer.public_info();
+        let mut public_polynomials = vec![None; self.params.shares];
+        public_polynomials[self.index] = Some(PublicPolynomial::new(public_polynomial));
+        ParticipantCollectingPolynomials {
+            params: self.params,
+            index: self.index,
+            dealer: self.dealer,
+            opening: self.opening,
+            commitments: self.commitments.into_iter().map(Option::unwrap).collect(),
+            // ^ `unwrap()` is safe due to the above checks
+            public_polynomials,
+        }
+    }
+}
+
+/// Public participant information in the distributed key generation protocol. Returned by
+/// [`ParticipantCollectingPolynomials::public_info()`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct PublicInfo<'a, G: Group> {
+    /// Participant's public polynomial.
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ElementHelper<G>, 1>"))]
+    pub polynomial: Vec<G::Element>,
+    /// Proof of possession for the secret polynomial that corresponds to `polynomial`.
+    pub proof_of_possession: Cow<'a, ProofOfPossession<G>>,
+    /// Opening for the participant's key commitment.
+    pub opening: Opening,
+}
+
+impl<G: Group> PublicInfo<'_, G> {
+    /// Converts this information to the owned form.
+    pub fn into_owned(self) -> PublicInfo<'static, G> {
+        PublicInfo {
+            polynomial: self.polynomial,
+            proof_of_possession: Cow::Owned(self.proof_of_possession.into_owned()),
+            opening: self.opening,
+        }
+    }
+}
+
+/// Participant state during the second stage of the committed Pedersen's distributed key generation.
+///
+/// During this stage, participants exchange public polynomials and openings for the commitments
+/// exchanged on the previous stage. The exchange happens using a public bulletin board
+/// (e.g., a blockchain).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ParticipantCollectingPolynomials<G: Group> {
+    params: Params,
+    index: usize,
+    dealer: Dealer<G>,
+    opening: Opening,
+    commitments: Vec<[u8; 32]>,
+    public_polynomials: Vec<Option<PublicPolynomial<G>>>,
+}
+
+impl<G: Group> ParticipantCollectingPolynomials<G> {
+    /// Returns params of this threshold ElGamal encryption scheme.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns 0-based index of this participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns public participant information: participant's public polynomial,
+    /// proof of possession for the corresponding secret polynomial and the opening of
+    /// the participant's public key share commitment.
+    pub fn public_info(&self) -> PublicInfo<'_, G> {
+        let (polynomial, proof) = self.dealer.public_info();
+        PublicInfo {
+            polynomial,
+            proof_of_possession: Cow::Borrowed(proof),
+            opening: self.opening.clone(),
+        }
+    }
+
+    /// Returns the indices of parties whose public polynomials were not provided.
+    pub fn missing_public_polynomials(&self) -> impl Iterator<Item = usize> + '_ {
+        self.public_polynomials
+            .iter()
+            .enumerate()
+            .filter_map(|(i, poly)| poly.is_none().then_some(i))
+    }
+
+    /// Inserts public polynomial from participant with index `participant_index`
+    /// their proof of possession of the public polynomial and opening of
+    /// their previously provided commitment. Re-inserting the same polynomial is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if provided polynomial doesn't correspond to the previous
+    /// commitment or the proof of possession is not valid. Returns [`Error::Equivocation`]
+    /// if another polynomial for the participant was already provided.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant_index` is out of bounds.
+    pub fn insert_public_polynomial(
+        &mut self,
+        participant_index: usize,
+        info: PublicInfo<'_, G>,
+    ) -> Result<(), Error> {
+        if let Some(existing) = &self.public_polynomials[participant_index] {
+            return if existing.coefficients() == info.polynomial.as_slice() {
+                Ok(())
+            } else {
+                Err(Error::Equivocation(participant_index))
+            };
+        }
+
+        let verifier = ShareVerifier::new(
+            self.params,
+            participant_index,
+            &self.commitments[participant_index],
+            info,
+        )?;
+        self.public_polynomials[participant_index] = Some(verifier.polynomial);
+        Ok(())
+    }
+
+    /// Proceeds to the next step of the DKG protocol, in which participants exchange
+    /// secret shares.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any public polynomials are missing. If this is not known statically, check
+    /// with [`Self::missing_public_polynomials()`] before calling this method.
+    pub fn finish_polynomials_phase(self) -> ParticipantExchangingSecrets<G> {
+        if let Some(missing_idx) = self.missing_public_polynomials().next() {
+            panic!("Missing public polynomial for participant {missing_idx}");
+        }
+
+        let mut shares_received = vec![false; self.params.shares];
+        shares_received[self.index] = true;
+        ParticipantExchangingSecrets {
+            params: self.params,
+            index: self.index,
+            public_polynomials: self.public_polynomials.into_iter().flatten().collect(),
+            accumulated_share: self.dealer.secret_share_for_participant(self.index),
+            dealer: self.dealer,
+            shares_received,
+            unverified_shares: vec![None; self.params.shares],
+        }
+    }
+}
+
+/// Verifier of secret shares sent by a single participant of the distributed key generation.
+///
+/// A verifier is created from the participant's commitment and [`PublicInfo`] published
+/// during the first two stages of the protocol. Unlike [`ParticipantExchangingSecrets`],
+/// it does not require information about other participants, which is useful for
+/// streaming DKG implementations that verify each pairwise share as soon as it arrives,
+/// while the full state of the protocol is assembled elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, dkg::*, sharing::Params};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+/// let commitment = alice.commitment();
+/// # let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+/// # let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+/// # alice.insert_commitment(1, bob.commitment())?;
+/// # alice.insert_commitment(2, carol.commitment())?;
+/// # bob.insert_commitment(0, commitment)?;
+/// # bob.insert_commitment(2, carol.commitment())?;
+/// # carol.insert_commitment(0, commitment)?;
+/// # carol.insert_commitment(1, bob.commitment())?;
+/// let mut alice = alice.finish_commitment_phase();
+///
+/// // Alice's commitment and public info are published on a bulletin board.
+/// let verifier = ShareVerifier::new(params, 0, &commitment, alice.public_info())?;
+/// // Later, Alice sends a share to Bob (participant #1). For the sake of example,
+/// // we assume that Alice has received all polynomials from other participants.
+/// # let (bob, carol) = (bob.finish_commitment_phase(), carol.finish_commitment_phase());
+/// # alice.insert_public_polynomial(1, bob.public_info())?;
+/// # alice.insert_public_polynomial(2, carol.public_info())?;
+/// let alice = alice.finish_polynomials_phase();
+/// let share = alice.secret_share_for_participant(1);
+/// verifier.verify_share(1, &share)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ShareVerifier<G: Group> {
+    params: Params,
+    dealer_index: usize,
+    polynomial: PublicPolynomial<G>,
+}
+
+impl<G: Group> ShareVerifier<G> {
+    /// Creates a verifier for shares sent by the participant with the specified 0-based
+    /// `dealer_index`, given their `commitment` and public `info`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public polynomial in `info` doesn't correspond
+    /// to the commitment, or if the proof of possession is not valid.
+    pub fn new(
+        params: Params,
+        dealer_index: usize,
+        commitment: &[u8; 32],
+        info: PublicInfo<'_, G>,
+    ) -> Result<Self, Error> {
+        let opening = info.opening.0.as_slice();
+        let expected_commitment = create_commitment::<G>(&info.polynomial[0], opening);
+        if *commitment != expected_commitment {
+            // provided commitment doesn't match the given public key share
+            return Err(Error::InvalidCommitment);
+        }
+
+        PublicKeySet::validate(params, &info.polynomial, &info.proof_of_possession)
+            .map_err(Error::MalformedParticipantProof)?;
+        Ok(Self {
+            params,
+            dealer_index,
+            polynomial: PublicPolynomial::new(info.polynomial),
+        })
+    }
+
+    /// Returns the 0-based index of the participant whose shares are verified.
+    pub fn dealer_index(&self) -> usize {
+        self.dealer_index
+    }
+
+    /// Returns the public key corresponding to the share that the participant must send
+    /// to the recipient with the specified 0-based index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `recipient_index` is out of bounds.
+    pub fn expected_public_share(&self, recipient_index: usize) -> PublicKey<G> {
+        assert!(
+            recipient_index < self.params.shares,
+            "recipient index {recipient_index} is out of bounds"
+        );
+        let idx = (recipient_index as u64 + 1).into();
+        PublicKey::from_element(self.polynomial.value_at(idx))
+    }
+
+    /// Verifies a `secret_share` sent by the participant to the recipient with
+    /// the specified 0-based index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSecret`] if the share doesn't correspond to the participant's
+    /// public polynomial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `recipient_index` is out of bounds.
+    pub fn verify_share(
+        &self,
+        recipient_index: usize,
+        secret_share: &SecretKey<G>,
+    ) -> Result<(), Error> {
+        let public_share = self.expected_public_share(recipient_index);
+        if public_share.as_element() == G::mul_generator(secret_share.expose_scalar()) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSecret)
+        }
+    }
+}
+
+/// Participant state during the third and final stage of the committed Pedersen's
+/// distributed key generation.
+///
+/// During this stage, participants exchange secret shares corresponding to the polynomials
+/// exchanged on the previous stage. The exchange happens using secure peer-to-peer channels
+/// established between pairs of participants.
+///
+/// # Optimistic path
+///
+/// Checking each share via [`Self::insert_secret_share()`] requires evaluating
+/// the sender's public polynomial. If most participants are expected to be honest,
+/// shares can be inserted with [`Self::insert_secret_share_optimistically()`] instead.
+/// Such shares are checked all at once by [`Self::verify_optimistic_shares()`]
+/// (which is also called on completion) by comparing their sum with the sum
+/// of public polynomials, which requires a single polynomial evaluation. Only if
+/// this aggregate check fails, the shares are checked one by one to find out
+/// which participants should be complained about.
+///
+/// The aggregate check is sufficient for correctness since the participant's secret share
+/// is the sum of received shares. However, it does not catch colluding participants
+/// whose share errors cancel each other out.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ParticipantExchangingSecrets<G: Group> {
+    params: Params,
+    index: usize,
+    dealer: Dealer<G>,
+    public_polynomials: Vec<PublicPolynomial<G>>,
+    accumulated_share: SecretKey<G>,
+    shares_received: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    unverified_shares: Vec<Option<SecretKey<G>>>,
+}
+
+impl<G: Group> ParticipantExchangingSecrets<G> {
+    /// Returns params of this threshold ElGamal encryption scheme.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns 0-based index of this participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the secret share for a participant with the specified `participant_index`.
+    pub fn secret_share_for_participant(&self, participant_index: usize) -> SecretKey<G> {
+        self.dealer.secret_share_for_participant(participant_index)
+    }
+
+    /// Returns indices of parties whose secret shares were not provided.
+    pub fn missing_shares(&self) -> impl Iterator<Item = usize> + '_ {
+        self.shares_received
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &is_received)| {
+                let is_unverified = self.unverified_shares.get(i).map_or(false, Option::is_some);
+                (!is_received && !is_unverified).then_some(i)
+            })
+    }
+
+    /// Inserts a secret share from participant with index `participant_index` and
+    /// checks that the share is valid. Re-inserting the same share is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if provided secret share doesn't correspond to the participant's
+    /// public polynomial collected on the previous step of the DKG protocol.
+    /// If a valid share from the participant was already provided, such a share
+    /// is reported as [`Error::Equivocation`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant_index` is out of bounds.
+    pub fn insert_secret_share(
+        &mut self,
+        participant_index: usize,
+        secret_share: SecretKey<G>,
+    ) -> Result<(), Error> {
+        let polynomial = &self.public_polynomials[participant_index];
+        let idx = (self.index as u64 + 1).into();
+        let public_share = PublicKey::<G>::from_element(polynomial.value_at(idx));
+
+        if public_share.as_element() != G::mul_generator(secret_share.expose_scalar()) {
+            // point corresponding to the received secret share doesn't lie
+            // on the public polynomial
+            return Err(if self.shares_received[participant_index] {
+                Error::Equivocation(participant_index)
+            } else {
+                Error::InvalidSecret
+            });
+        }
+        if self.shares_received[participant_index] {
+            // A valid share is uniquely determined by the public polynomial,
+            // so this is a re-delivery of the same share.
+            return Ok(());
+        }
+
+        self.accumulated_share += secret_share;
+        self.shares_received[participant_index] = true;
+        Ok(())
+    }
+
+    /// Inserts a secret share from participant with index `participant_index` without
+    /// checking it; the share will be checked in [`Self::verify_optimistic_shares()`].
+    /// See [the type-level docs](#optimistic-path) for details.
+    ///
+    /// If a share from the participant was already provided, both shares are checked
+    /// immediately in the same way as with [`Self::insert_secret_share()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if a share from the participant was already provided
+    /// and the shares were checked, as described above.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant_index` is out of bounds.
+    pub fn insert_secret_share_optimistically(
+        &mut self,
+        participant_index: usize,
+        secret_share: SecretKey<G>,
+    ) -> Result<(), Error> {
+        if self.unverified_shares.len() != self.params.shares {
+            // The state may have been deserialized from an older format.
+            self.unverified_shares.resize(self.params.shares, None);
+        }
+        if self.shares_received[participant_index] {
+            return self.insert_secret_share(participant_index, secret_share);
+        }
+        if let Some(existing_share) = self.unverified_shares[participant_index].take() {
+            // If the existing share is invalid, it is discarded, and the new share
+            // is treated as the first one.
+            self.insert_secret_share(participant_index, existing_share)
+                .ok();
+            return self.insert_secret_share(participant_index, secret_share);
+        }
+        self.unverified_shares[participant_index] = Some(secret_share);
+        Ok(())
+    }
+
+    /// Checks all secret shares inserted with [`Self::insert_secret_share_optimistically()`]
+    /// that were not checked yet. Valid shares are accepted as if they were inserted with
+    /// [`Self::insert_secret_share()`]; invalid shares are discarded, so that
+    /// the corresponding participants are again reported by [`Self::missing_shares()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSecrets`] with the indexes of participants that have sent
+    /// invalid shares.
+    pub fn verify_optimistic_shares(&mut self) -> Result<(), Error> {
+        let unverified_shares: Vec<_> = self
+            .unverified_shares
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, share)| share.take().map(|share| (i, share)))
+            .collect();
+        if unverified_shares.is_empty() {
+            return Ok(());
+        }
+
+        // Aggregate check: the sum of shares must lie on the sum of the public polynomials.
+        let mut share_sum = SecretKey::new(G::Scalar::from(0_u64));
+        let mut polynomial_sum = self.public_polynomials[unverified_shares[0].0].clone();
+        for (_, share) in &unverified_shares {
+            share_sum += share.clone();
+        }
+        for (i, _) in &unverified_shares[1..] {
+            polynomial_sum += &self.public_polynomials[*i];
+        }
+        let idx = (self.index as u64 + 1).into();
+        if polynomial_sum.value_at(idx) == G::mul_generator(share_sum.expose_scalar()) {
+            for (i, share) in unverified_shares {
+                self.accumulated_share += share;
+                self.shares_received[i] = true;
+            }
+            return Ok(());
+        }
+
+        // Fallback: check shares one by one.
+        let mut invalid_senders = vec![];
+        for (i, share) in unverified_shares {
+            if self.insert_secret_share(i, share).is_err() {
+                invalid_senders.push(i);
+            }
+        }
+        Err(Error::InvalidSecrets(invalid_senders))
+    }
+
+    /// Returns the input for a [`Vdf`] used in [`Self::complete_with_vdf()`].
+    ///
+    /// The input is a SHA-256 digest of the protocol params and public polynomials of
+    /// all participants (in the order of participant indices). Thus, it is the same
+    /// for all participants.
+    pub fn vdf_input(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(proto_labels::DKG_VDF_INPUT);
+        hasher.update((self.params.shares as u64).to_le_bytes());
+        hasher.update((self.params.threshold as u64).to_le_bytes());
+
+        let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+        for polynomial in &self.public_polynomials {
+            for coefficient in polynomial.coefficients() {
+                G::serialize_element(coefficient, &mut bytes);
+                hasher.update(&bytes);
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    /// Completes the distributed key generation protocol returning an [`ActiveParticipant`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if secret shares from some parties were not provided,
+    /// if shares inserted [optimistically](Self::insert_secret_share_optimistically())
+    /// are invalid, or if the [`PublicKeySet`] cannot be created from participants' keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if shares from any participants are missing. If this is not known statically, check
+    /// with [`Self::missing_shares()`] before calling this method.
+    pub fn complete(self) -> Result<ActiveParticipant<G>, Error> {
+        self.complete_with_offset(None)
+    }
+
+    /// Completes the distributed key generation protocol, binding the shared key
+    /// to the provided evaluation of a verifiable delay function.
+    ///
+    /// The `evaluation` of `vdf` on [`Self::vdf_input()`] is hashed to a scalar `d`,
+    /// which is added to the shared secret; i.e., the shared key becomes `K + [d]G`, where `K`
+    /// is the key [`Self::complete()`] would output. Correspondingly, `d` is added to
+    /// all secret shares, which preserves their consistency with the shared key.
+    ///
+    /// The VDF needs to be evaluated only once (e.g., by one of participants or
+    /// by an untrusted third party); other participants just verify the evaluation.
+    /// The delay parameter of the VDF should be chosen so that evaluation takes longer
+    /// than the timeout for publishing public polynomials. In this case, a participant publishing
+    /// their polynomial last cannot predict the shared key and thus cannot bias it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`Self::complete()`], and additionally
+    /// if the `evaluation` does not verify.
+    ///
+    /// # Panics
+    ///
+    /// Panics if shares from any participants are missing.
+    pub fn complete_with_vdf<V: Vdf + ?Sized>(
+        self,
+        vdf: &V,
+        evaluation: &VdfEvaluation,
+    ) -> Result<ActiveParticipant<G>, Error> {
+        if !vdf.verify(&self.vdf_input(), evaluation) {
+            return Err(Error::InvalidVdfEvaluation);
+        }
+
+        let mut transcript = Transcript::new(proto_labels::DKG_VDF);
+        transcript.append_message(b"y", &evaluation.output);
+        let offset = transcript.challenge_scalar::<G>(b"d");
+        self.complete_with_offset(Some(offset))
+    }
+
+    fn complete_with_offset(
+        mut self,
+        offset: Option<G::Scalar>,
+    ) -> Result<ActiveParticipant<G>, Error> {
+        if let Some(missing_idx) = self.missing_shares().next() {
+            panic!("Missing secret share from participant {missing_idx}");
+        }
+        self.verify_optimistic_shares()?;
+
+        let mut accumulated_polynomial = self
+            .public_polynomials
+            .into_iter()
+            .reduce(|mut acc, poly| {
+                acc += &poly;
+                acc
+            })
+            .unwrap(); // safe: we have at least ourselves as a participant
+        if let Some(offset) = &offset {
+            accumulated_polynomial.shift(G::mul_generator(offset));
+        }
+        // Participant keys are evaluated from the accumulated polynomial once; since they are
+        // consistent by construction, there is no need to re-check them via interpolation.
+        let key_set = PublicKeySet::from_polynomial(self.params, &accumulated_polynomial);
+
+        let mut secret_share = self.accumulated_share;
+        if let Some(offset) = offset {
+            secret_share += SecretKey::new(offset);
+        }
+        let active_participant = ActiveParticipant::new(key_set, self.index, secret_share)
+            .map_err(Error::InconsistentPublicShares)?;
+        Ok(active_participant)
+    }
+}
+
+/// DKG session of a local participant configured via a committee [`Config`].
+///
+/// The session locates the local participant in the config by its identity key
+/// and provides helpers to start the protocol, log protocol messages, and bind the resulting
+/// [`PublicKeySet`] to the identity keys of all participants.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     dkg::DkgSession, group::Ristretto, sharing::{Config, ParticipantConfig, Params},
+/// #     IdentityKeypair,
+/// # };
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let identities: Vec<_> = (0..3)
+///     .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+///     .collect();
+/// let participants = identities.iter().enumerate().map(|(i, identity)| ParticipantConfig {
+///     identity_key: identity.public(),
+///     transport: format!("node-{i}"),
+///     certificate: None,
+/// });
+/// let config = Config {
+///     params: Params::new(3, 2),
+///     context: "committee #1".to_owned(),
+///     participants: participants.collect(),
+/// };
+///
+/// let session = DkgSession::from_config(config, identities[1].clone())?;
+/// assert_eq!(session.index(), 1);
+/// let peers: Vec<_> = session.peers().collect();
+/// assert_eq!(peers, [(0, "node-0"), (2, "node-2")]);
+/// let participant = session.start(&mut rng);
+/// // Proceed with the DKG as usual, e.g., by broadcasting `participant.commitment()`
+/// // to `peers`, and finally complete it with `session.complete(_)`.
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DkgSession<G: Group> {
+    config: Config<G>,
+    index: usize,
+    identity: IdentityKeypair<G>,
+}
+
+impl<G: Group> DkgSession<G> {
+    /// Creates a session from the committee `config` for the local participant with
+    /// the specified `identity`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `config` is [inconsistent](Config::validate()), or if
+    /// the `identity` is not present in it.
+    pub fn from_config(
+        config: Config<G>,
+        identity: IdentityKeypair<G>,
+    ) -> Result<Self, ConfigError> {
+        config.validate()?;
+        let index = config
+            .index_of(&identity.public())
+            .ok_or(ConfigError::UnknownIdentity)?;
+        Ok(Self {
+            config,
+            index,
+            identity,
+        })
+    }
+
+    /// Creates a session from the committee `config` requiring that identity keys
+    /// of all participants are certified by the `root` authority. Certificates are checked
+    /// with the provided `verifier` and attached to the key set on [completion](Self::complete()).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`Self::from_config()`], and additionally
+    /// [`ConfigError::Certification`] if any participant certificate is missing or does not verify.
+    pub fn from_certified_config<V: CertificateVerifier + ?Sized>(
+        config: Config<G>,
+        identity: IdentityKeypair<G>,
+        root: &[u8],
+        verifier: &V,
+    ) -> Result<Self, ConfigError> {
+        let session = Self::from_config(config, identity)?;
+        session
+            .config
+            .verify_certificates(root, verifier)
+            .map_err(ConfigError::Certification)?;
+        Ok(session)
+    }
+
+    fn assert_local(&self, participant_index: usize) {
+        assert_eq!(
+            participant_index, self.index,
+            "participant does not correspond to the local participant of the session"
+        );
+    }
+
+    /// Returns the committee configuration.
+    pub fn config(&self) -> &Config<G> {
+        &self.config
+    }
+
+    /// Returns the 0-based index of the local participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Iterates over 0-based indexes and transport labels of other participants.
+    pub fn peers(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.config
+            .participants
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| *i != self.index)
+            .map(|(i, participant)| (i, participant.transport.as_str()))
+    }
+
+    /// Creates an empty [`MessageLog`] for the ceremony context specified in the config.
+    pub fn message_log(&self) -> MessageLog {
+        MessageLog::new(self.config.context.as_bytes())
+    }
+
+    /// Checks that the `log` (e.g., obtained from another participant) is a valid hash chain
+    /// for the ceremony context specified in the config.
+    pub fn verify_message_log(&self, log: &MessageLog) -> bool {
+        log.verify_chain(self.config.context.as_bytes())
+    }
+
+    /// Starts the DKG protocol for the local participant.
+    pub fn start<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> ParticipantCollectingCommitments<G> {
+        ParticipantCollectingCommitments::new(self.config.params, self.index, rng)
+    }
+
+    /// Completes the DKG protocol for the local participant. The resulting key set has
+    /// identity keys of all participants registered, and the participant has the local identity
+    /// attached to it. If all participants in the config have certificates, they are attached
+    /// to the key set as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`ParticipantExchangingSecrets::complete()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant` does not correspond to the local participant of this session,
+    /// or if shares from any participants are missing.
+    pub fn complete(
+        &self,
+        participant: ParticipantExchangingSecrets<G>,
+    ) -> Result<ActiveParticipant<G>, Error> {
+        self.assert_local(participant.index);
+        let participant = participant.complete()?;
+        let key_set = participant
+            .key_set()
+            .clone()
+            .with_identity_keys(self.config.identity_keys())
+            .map_err(Error::InconsistentPublicShares)?;
+        let key_set = match self.config.certificates() {
+            Some(certificates) => key_set
+                .with_certificates(certificates)
+                .map_err(Error::InconsistentPublicShares)?,
+            None => key_set,
+        };
+        ActiveParticipant::new(key_set, self.index, participant.secret_share().clone())
+            .and_then(|participant| participant.with_identity(self.identity.clone()))
+            .map_err(Error::InconsistentPublicShares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        encryption::DiscreteLogTable,
+        group::{ElementOps, Ristretto},
+        sharing::Params,
+        Certificate, CertificateChain, CertificationError,
+    };
+
+    #[test]
+    fn share_verifier_for_single_participant() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        let alice_commitment = alice.commitment();
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        let err = ShareVerifier::new(params, 0, &alice_commitment, bob.public_info()).unwrap_err();
+        assert!(matches!(err, Error::InvalidCommitment));
+        let verifier =
+            ShareVerifier::new(params, 0, &alice_commitment, alice.public_info()).unwrap();
+        assert_eq!(verifier.dealer_index(), 0);
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let alice = alice.finish_polynomials_phase();
+        for recipient in 1..3 {
+            let share = alice.secret_share_for_participant(recipient);
+            verifier.verify_share(recipient, &share).unwrap();
+            let other_recipient = 3 - recipient;
+            let err = verifier.verify_share(other_recipient, &share).unwrap_err();
+            assert!(matches!(err, Error::InvalidSecret));
+        }
+    }
+
+    #[test]
+    fn dkg_session_from_config() {
+        let mut rng = thread_rng();
+        let identities: Vec<_> = (0..3)
+            .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let participants = identities.iter().zip(["a", "b", "c"]);
+        let participants = participants.map(|(identity, transport)| sharing::ParticipantConfig {
+            identity_key: identity.public(),
+            transport: transport.into(),
+            certificate: None,
+        });
+        let config = Config {
+            params: Params::new(3, 2),
+            context: "test".into(),
+            participants: participants.collect(),
+        };
+
+        let mut invalid_config = config.clone();
+        invalid_config.participants[2].identity_key = identities[0].public();
+        let err = DkgSession::from_config(invalid_config, identities[0].clone()).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::DuplicateIdentityKey(2)),
+            "{err:?}"
+        );
+        let mut invalid_config = config.clone();
+        invalid_config.participants.pop();
+        let err = DkgSession::from_config(invalid_config, identities[0].clone()).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::ParticipantCountMismatch),
+            "{err:?}"
+        );
+        let stranger = IdentityKeypair::generate(&mut rng);
+        let err = DkgSession::from_config(config.clone(), stranger).unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownIdentity), "{err:?}");
+
+        let mut changed_config = config.clone();
+        changed_config.participants.swap(0, 1);
+        assert_ne!(changed_config.digest(), config.digest());
+
+        let sessions: Vec<_> = identities
+            .into_iter()
+            .map(|identity| DkgSession::from_config(config.clone(), identity).unwrap())
+            .collect();
+        let mut alice = sessions[0].start(&mut rng);
+        let mut bob = sessions[1].start(&mut rng);
+        let mut carol = sessions[2].start(&mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let mut carol = carol.finish_polynomials_phase();
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let bob = sessions[1].complete(bob).unwrap();
+        let key_set = bob.key_set();
+        assert_eq!(key_set.indexed_identity_keys().len(), 3);
+        for (i, key) in key_set.indexed_identity_keys() {
+            assert_eq!(*key, config.participants[i].identity_key);
+        }
+        let ciphertext = key_set.shared_key().encrypt(1_u64, &mut rng);
+        let share = bob.decrypt_share_signed(ciphertext, &mut rng);
+        key_set.verify_signed_share(&share, ciphertext, 1).unwrap();
+
+        let mut log = sessions[0].message_log();
+        log.record(
+            sharing::MessageDirection::Sent,
+            None,
+            "dkg_commitment",
+            b"a",
+        );
+        assert!(sessions[1].verify_message_log(&log));
+        let mut foreign_log = sharing::MessageLog::new(b"other");
+        foreign_log.record(
+            sharing::MessageDirection::Sent,
+            None,
+            "dkg_commitment",
+            b"a",
+        );
+        assert!(!sessions[1].verify_message_log(&foreign_log));
+    }
+
+    #[test]
+    fn dkg_session_with_certified_config() {
+        struct ConcatVerifier;
+
+        impl CertificateVerifier for ConcatVerifier {
+            fn verify(&self, issuer: &[u8], message: &[u8], signature: &[u8]) -> bool {
+                signature == [issuer, message].concat()
+            }
+        }
+
+        let mut rng = thread_rng();
+        let identities: Vec<_> = (0..3)
+            .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let participants = identities.iter().zip(["a", "b", "c"]);
+        let participants = participants.map(|(identity, transport)| {
+            let subject = identity.public().as_bytes().to_vec();
+            let signature = [b"root", &Certificate::signed_message(&subject)[..]].concat();
+            sharing::ParticipantConfig {
+                identity_key: identity.public(),
+                transport: transport.into(),
+                certificate: Some(CertificateChain::new(vec![Certificate {
+                    subject,
+                    signature,
+                }])),
+            }
+        });
+        let config = Config {
+            params: Params::new(3, 2),
+            context: "test".into(),
+            participants: participants.collect(),
+        };
+        let mut uncertified_config = config.clone();
+        uncertified_config.participants[2].certificate = None;
+        assert_ne!(uncertified_config.digest(), config.digest());
+
+        let identity = identities[0].clone();
+        let err = DkgSession::from_certified_config(
+            uncertified_config,
+            identity.clone(),
+            b"root",
+            &ConcatVerifier,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConfigError::Certification(CertificationError::MissingCertificate {
+                    participant: 2
+                })
+            ),
+            "{err:?}"
+        );
+        let err =
+            DkgSession::from_certified_config(config.clone(), identity, b"other", &ConcatVerifier)
+                .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConfigError::Certification(CertificationError::Participant { index: 0, .. })
+            ),
+            "{err:?}"
+        );
+
+        let sessions: Vec<_> = identities
+            .into_iter()
+            .map(|identity| {
+                DkgSession::from_certified_config(
+                    config.clone(),
+                    identity,
+                    b"root",
+                    &ConcatVerifier,
+                )
+                .unwrap()
+            })
+            .collect();
+        let mut alice = sessions[0].start(&mut rng);
+        let mut bob = sessions[1].start(&mut rng);
+        let mut carol = sessions[2].start(&mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let mut carol = carol.finish_polynomials_phase();
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let bob = sessions[1].complete(bob).unwrap();
+        let key_set = bob.key_set();
+        assert_eq!(
+            key_set.certificate(2),
+            config.participants[2].certificate.as_ref()
+        );
+        key_set
+            .verify_certificates(b"root", &ConcatVerifier)
+            .unwrap();
+        let err = key_set
+            .clone()
+            .with_identity_keys(config.identity_keys())
+            .unwrap()
+            .verify_certificates(b"root", &ConcatVerifier)
+            .unwrap_err();
+        assert!(matches!(err, CertificationError::MissingCertificate { .. }));
+
+        #[cfg(feature = "serde")]
+        check_certified_key_set_deserialization(key_set, &ConcatVerifier);
+    }
+
+    #[cfg(feature = "serde")]
+    fn check_certified_key_set_deserialization(
+        key_set: &PublicKeySet<Ristretto>,
+        verifier: &impl CertificateVerifier,
+    ) {
+        let json = serde_json::to_value(key_set).unwrap();
+        let restored: PublicKeySet<Ristretto> = serde_json::from_value(json.clone()).unwrap();
+        restored.verify_certificates(b"root", verifier).unwrap();
+
+        // Certificates for only some of participants.
+        let mut truncated = json.clone();
+        truncated["certificates"]
+            .as_array_mut()
+            .unwrap()
+            .truncate(1);
+        serde_json::from_value::<PublicKeySet<Ristretto>>(truncated).unwrap_err();
+        // Invalid params.
+        let mut invalid = json.clone();
+        invalid["params"]["threshold"] = serde_json::json!(0);
+        serde_json::from_value::<PublicKeySet<Ristretto>>(invalid).unwrap_err();
+        // Certificates without identity keys.
+        let mut truncated = json;
+        truncated["identity_keys"] = serde_json::json!([]);
+        serde_json::from_value::<PublicKeySet<Ristretto>>(truncated).unwrap_err();
+    }
+
+    #[test]
+    fn optimistic_share_exchange() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let carol = carol.finish_polynomials_phase();
+
+        // Carol sends an invalid share.
+        let bob_share = bob.secret_share_for_participant(0);
+        alice
+            .insert_secret_share_optimistically(1, bob_share.clone())
+            .unwrap();
+        alice
+            .insert_secret_share_optimistically(2, carol.secret_share_for_participant(1))
+            .unwrap();
+        assert_eq!(alice.missing_shares().count(), 0);
+        let err = alice.verify_optimistic_shares().unwrap_err();
+        assert!(matches!(err, Error::InvalidSecrets(ref senders) if *senders == [2]));
+        assert_eq!(alice.missing_shares().collect::<Vec<_>>(), [2]);
+
+        // Bob's share was accepted; re-delivering it is a no-op.
+        alice
+            .insert_secret_share_optimistically(1, bob_share)
+            .unwrap();
+        alice
+            .insert_secret_share_optimistically(2, carol.secret_share_for_participant(0))
+            .unwrap();
+        bob.insert_secret_share_optimistically(0, alice.secret_share_for_participant(1))
+            .unwrap();
+        bob.insert_secret_share_optimistically(2, carol.secret_share_for_participant(1))
+            .unwrap();
+        let alice = alice.complete().unwrap();
+        let bob = bob.complete().unwrap();
+        assert_eq!(alice.key_set().shared_key(), bob.key_set().shared_key());
+    }
+
+    #[test]
+    fn dkg_key_set_matches_key_set_from_participants() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let mut carol = carol.finish_polynomials_phase();
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let alice = alice.complete().unwrap();
+        let key_set = alice.key_set();
+        let participant_keys = key_set.participant_keys().to_vec();
+        let restored_key_set = PublicKeySet::from_participants(params, participant_keys).unwrap();
+        assert_eq!(restored_key_set.shared_key(), key_set.shared_key());
+        assert_eq!(
+            restored_key_set.participant_keys(),
+            key_set.participant_keys()
+        );
+        assert_eq!(restored_key_set.generation(), key_set.generation());
+        for participant in [bob.complete().unwrap(), carol.complete().unwrap()] {
+            assert_eq!(participant.key_set().shared_key(), key_set.shared_key());
+            assert_eq!(
+                participant.key_set().participant_keys(),
+                key_set.participant_keys()
+            );
+        }
+    }
+
+    #[test]
+    fn dkg_shared_2_of_3_key() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        assert_eq!(alice.params().shares, params.shares);
+        assert_eq!(alice.params().threshold, params.threshold);
+        assert_eq!(alice.index(), 0);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        assert_eq!(bob.index(), 1);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        assert_eq!(carol.index(), 2);
+
+        assert_eq!(
+            alice.missing_commitments().collect::<Vec<_>>(),
+            [bob.index(), carol.index()]
+        );
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+
+        let mut alice = alice.finish_commitment_phase();
+        assert_eq!(alice.params().shares, params.shares);
+        assert_eq!(alice.params().threshold, params.threshold);
+        assert_eq!(alice.index(), 0);
+        let mut bob = bob.finish_commitment_phase();
+        assert_eq!(bob.index(), 1);
+        let mut carol = carol.finish_commitment_phase();
+        assert_eq!(carol.index(), 2);
+
+        assert_eq!(
+            alice.missing_public_polynomials().collect::<Vec<_>>(),
+            [bob.index(), carol.index()]
+        );
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let mut alice = alice.finish_polynomials_phase();
+        assert_eq!(alice.params().shares, params.shares);
+        assert_eq!(alice.params().threshold, params.threshold);
+        assert_eq!(alice.index(), 0);
+        let mut bob = bob.finish_polynomials_phase();
+        assert_eq!(bob.index(), 1);
+        let mut carol = carol.finish_polynomials_phase();
+        assert_eq!(carol.index(), 2);
+
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let alice = alice.complete().unwrap();
+        let bob = bob.complete().unwrap();
+        carol.complete().unwrap();
+        let key_set = alice.key_set();
+
+        let ciphertext = key_set.shared_key().encrypt(15_u64, &mut rng);
+        let (alice_share, proof) = alice.decrypt_share(ciphertext, &mut rng);
+        key_set
+            .verify_share(alice_share.into(), ciphertext, alice.index(), &proof)
+            .unwrap();
+
+        let (bob_share, proof) = bob.decrypt_share(ciphertext, &mut rng);
+        key_set
+            .verify_share(bob_share.into(), ciphertext, bob.index(), &proof)
+            .unwrap();
+
+        let combined = params
+            .combine_shares([(alice.index(), alice_share), (bob.index(), bob_share)])
+            .unwrap();
+        let lookup_table = DiscreteLogTable::<Ristretto>::new(0..20);
+
+        assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(15));
+    }
+
+    struct IteratedHash(usize);
+
+    impl Vdf for IteratedHash {
+        fn evaluate(&self, input: &[u8]) -> VdfEvaluation {
+            let mut output = input.to_vec();
+            for _ in 0..self.0 {
+                output = Sha256::digest(&output).to_vec();
+            }
+            VdfEvaluation {
+                output,
+                proof: Vec::new(),
+            }
+        }
+
+        fn verify(&self, input: &[u8], evaluation: &VdfEvaluation) -> bool {
+            self.evaluate(input) == *evaluation
+        }
+    }
+
+    #[test]
+    fn dkg_with_vdf() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let vdf = IteratedHash(100);
+
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let mut carol = carol.finish_polynomials_phase();
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+
+        let vdf_input = alice.vdf_input();
+        assert_eq!(bob.vdf_input(), vdf_input);
+        assert_eq!(carol.vdf_input(), vdf_input);
+        let evaluation = vdf.evaluate(&vdf_input);
+
+        let mut bad_evaluation = evaluation.clone();
+        bad_evaluation.output[0] ^= 1;
+        let err = carol
+            .clone()
+            .complete_with_vdf(&vdf, &bad_evaluation)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidVdfEvaluation), "{err:?}");
+
+        let unbound_key = alice
+            .clone()
+            .complete()
+            .unwrap()
+            .key_set()
+            .shared_key()
+            .clone();
+        let alice = alice.complete_with_vdf(&vdf, &evaluation).unwrap();
+        let bob = bob.complete_with_vdf(&vdf, &evaluation).unwrap();
+        let carol = carol.complete_with_vdf(&vdf, &evaluation).unwrap();
+        let key_set = alice.key_set();
+        assert_ne!(key_set.shared_key(), &unbound_key);
+        assert_eq!(bob.key_set().shared_key(), key_set.shared_key());
+        assert_eq!(carol.key_set().shared_key(), key_set.shared_key());
+
+        let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+        let (bob_share, _) = bob.decrypt_share(ciphertext, &mut rng);
+        let (carol_share, _) = carol.decrypt_share(ciphertext, &mut rng);
+        let combined = params
+            .combine_shares([(bob.index(), bob_share), (carol.index(), carol_share)])
+            .unwrap();
+        let lookup_table = DiscreteLogTable::<Ristretto>::new(0..10);
+        assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(5));
+    }
+
+    #[test]
+    fn redelivered_messages_are_idempotent() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let mut alice = ParticipantCollectingCommitments::<Ristretto>::new(params, 0, &mut rng);
+        let mut bob = ParticipantCollectingCommitments::<Ristretto>::new(params, 1, &mut rng);
+        let mut carol = ParticipantCollectingCommitments::<Ristretto>::new(params, 2, &mut rng);
+        exchange_commitments(&mut alice, &mut bob, &mut carol);
+        alice.insert_commitment(1, bob.commitment()).unwrap();
+        let err = alice.insert_commitment(1, carol.commitment()).unwrap_err();
+        assert!(matches!(err, Error::Equivocation(1)), "{err:?}");
+
+        let mut alice = alice.finish_commitment_phase();
+        let mut bob = bob.finish_commitment_phase();
+        let mut carol = carol.finish_commitment_phase();
+        exchange_polynomials(&mut alice, &mut bob, &mut carol).unwrap();
+        let bob_info = bob.public_info().into_owned();
+        alice.insert_public_polynomial(1, bob_info.clone()).unwrap();
+        let mut conflicting_info = bob_info;
+        conflicting_info.polynomial[1] = Ristretto::generator();
+        let err = alice
+            .insert_public_polynomial(1, conflicting_info)
+            .unwrap_err();
+        assert!(matches!(err, Error::Equivocation(1)), "{err:?}");
+
+        let mut alice = alice.finish_polynomials_phase();
+        let mut bob = bob.finish_polynomials_phase();
+        let mut carol = carol.finish_polynomials_phase();
+        exchange_secret_shares(&mut alice, &mut bob, &mut carol).unwrap();
+        let bob_share = bob.secret_share_for_participant(0);
+        alice.insert_secret_share(1, bob_share).unwrap();
+        let err = alice
+            .insert_secret_share(1, carol.secret_share_for_participant(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::Equivocation(1)), "{err:?}");
+
+        let alice = alice.complete().unwrap();
+        let bob = bob.complete().unwrap();
+        assert_eq!(alice.key_set().shared_key(), bob.key_set().shared_key());
+    }
+
+    fn exchange_commitments(
+        alice: &mut ParticipantCollectingCommitments<Ristretto>,
+        bob: &mut ParticipantCollectingCommitments<Ristretto>,
+        carol: &mut ParticipantCollectingCommitments<Ristretto>,
+    ) {
+        let alice_commitment = alice.commitment();
+        let bob_commitment = bob.commitment();
+        let carol_commitment = carol.commitment();
+
+        alice
+            .insert_commitment(bob.index(), bob_commitment)
+            .unwrap();
+        alice
+            .insert_commitment(carol.index(), carol_commitment)
+            .unwrap();
+        bob.insert_commitment(alice.index(), alice_commitment)
+            .unwrap();
+        bob.insert_commitment(carol.index(), carol_commitment)
+            .unwrap();
+        carol
+            .insert_commitment(alice.index(), alice_commitment)
+            .unwrap();
+        carol
+            .insert_commitment(bob.index(), bob_commitment)
+            .unwrap();
+    }
+
+    fn exchange_polynomials(
+        alice: &mut ParticipantCollectingPolynomials<Ristretto>,
+        bob: &mut ParticipantCollectingPolynomials<Ristretto>,
+        carol: &mut ParticipantCollectingPolynomials<Ristretto>,
+    ) -> Result<(), Error> {
+        let alice_info = alice.public_info().into_owned();
+        let bob_info = bob.public_info().into_owned();
+        let carol_info = carol.public_info().into_owned();
+
+        alice.insert_public_polynomial(bob.index(), bob_info.clone())?;
+        alice.insert_public_polynomial(carol.index(), carol_info.clone())?;
+        bob.insert_public_polynomial(alice.index(), alice_info.clone())?;
+        bob.insert_public_polynomial(carol.index(), carol_info)?;
+        carol.insert_public_polynomial(alice.index(), alice_info)?;
+        carol.insert_public_polynomial(bob.index(), bob_info)?;
+        Ok(())
+    }
+
+    fn exchange_secret_shares(
+        alice: &mut ParticipantExchangingSecrets<Ristretto>,
+        bob: &mut ParticipantExchangingSecrets<Ristretto>,
+        carol: &mut ParticipantExchangingSecrets<Ristretto>,
+    ) -> Result<(), Error> {
+        alice.insert_secret_share(bob.index(), bob.secret_share_for_participant(alice.index()))?;
+        alice.insert_secret_share(
+            carol.index(),
+            carol.secret_share_for_participant(alice.index()),
+        )?;
+
+        bob.insert_secret_share(
+            alice.index(),
+            alice.secret_share_for_participant(bob.index()),
+        )?;
+        bob.insert_secret_share(
+            carol.index(),
+            carol.secret_share_for_participant(bob.index()),
+        )?;
+
+        carol.insert_secret_share(
+            alice.index(),
+            alice.secret_share_for_participant(carol.index()),
+        )?;
+        carol.insert_secret_share(bob.index(), bob.secret_share_for_participant(carol.index()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+//! Signed messages of the committed DKG.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use core::iter;
+
+use super::{
+    DkgSession, Error, ParticipantCollectingCommitments, ParticipantCollectingPolynomials,
+    ParticipantExchangingSecrets, PublicInfo,
+};
+use crate::{
+    alloc::vec,
+    group::Group,
+    proofs::{ProofOfPossession, TranscriptForGroup},
+    proto_labels, SecretKey,
+};
+
+/// Message emitted by a participant of the committed DKG.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "", rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum DkgMessage<G: Group> {
+    /// [Commitment](ParticipantCollectingCommitments::commitment()) to the public key share
+    /// of the sender.
+    Commitment([u8; 32]),
+    /// [Public information](ParticipantCollectingPolynomials::public_info()) of the sender.
+    PublicInfo(PublicInfo<'static, G>),
+    /// [Secret share](ParticipantExchangingSecrets::secret_share_for_participant())
+    /// sent to a single recipient.
+    SecretShare {
+        /// 0-based index of the recipient.
+        recipient: usize,
+        /// Secret share for the recipient.
+        share: SecretKey<G>,
+    },
+}
+
+impl<G: Group> DkgMessage<G> {
+    fn commit(&self, transcript: &mut Transcript) {
+        match self {
+            Self::Commitment(commitment) => {
+                transcript.append_message(b"kind", b"commitment");
+                transcript.append_message(b"c", commitment);
+            }
+            Self::PublicInfo(info) => {
+                transcript.append_message(b"kind", b"public_info");
+                transcript.append_u64(b"len", info.polynomial.len() as u64);
+                for coefficient in &info.polynomial {
+                    transcript.append_element::<G>(b"coeff", coefficient);
+                }
+                transcript.append_message(b"opening", info.opening.0.as_slice());
+            }
+            Self::SecretShare { recipient, share } => {
+                transcript.append_message(b"kind", b"secret_share");
+                transcript.append_u64(b"recipient", *recipient as u64);
+                let mut bytes = Zeroizing::new(vec![0_u8; G::SCALAR_SIZE]);
+                G::serialize_scalar(share.expose_scalar(), &mut bytes);
+                transcript.append_message(b"share", &bytes);
+            }
+        }
+    }
+}
+
+/// [`DkgMessage`] signed by the identity key of its sender. Created by [`DkgSession::sign()`]
+/// and checked by [`DkgSession::verify()`].
+///
+/// The signature is bound to the [digest](crate::sharing::Config::digest()) of the committee
+/// config, so a message cannot be replayed in another ceremony.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SignedDkgMessage<G: Group> {
+    sender: usize,
+    message: DkgMessage<G>,
+    signature: ProofOfPossession<G>,
+}
+
+impl<G: Group> SignedDkgMessage<G> {
+    fn transcript(session: &DkgSession<G>, sender: usize, message: &DkgMessage<G>) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::DKG_MESSAGE);
+        transcript.append_message(b"config", &session.config.digest());
+        transcript.append_u64(b"sender", sender as u64);
+        message.commit(&mut transcript);
+        transcript
+    }
+
+    /// Returns the 0-based index of the sender.
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    /// Returns the unverified message.
+    pub fn message(&self) -> &DkgMessage<G> {
+        &self.message
+    }
+}
+
+impl<G: Group> DkgSession<G> {
+    /// Signs a `message` emitted by the local participant with its identity key.
+    pub fn sign<R: CryptoRng + RngCore>(
+        &self,
+        message: DkgMessage<G>,
+        rng: &mut R,
+    ) -> SignedDkgMessage<G> {
+        let mut transcript = SignedDkgMessage::transcript(self, self.index, &message);
+        let identity = self.identity.as_untyped();
+        let signature = ProofOfPossession::from_keys(
+            iter::once(identity.secret()),
+            iter::once(identity.public()),
+            &mut transcript,
+            rng,
+        );
+        SignedDkgMessage {
+            sender: self.index,
+            message,
+            signature,
+        }
+    }
+
+    /// Verifies a `message` received from another participant and returns its payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnexpectedMessage`] if the sender is not another participant
+    /// from the config, or if a secret share is addressed to another participant.
+    /// Returns [`Error::InvalidSignature`] if the signature does not verify.
+    pub fn verify<'a>(&self, message: &'a SignedDkgMessage<G>) -> Result<&'a DkgMessage<G>, Error> {
+        let sender = message.sender;
+        if sender == self.index {
+            return Err(Error::UnexpectedMessage);
+        }
+        let identity_key = self
+            .config
+            .participants
+            .get(sender)
+            .ok_or(Error::UnexpectedMessage)?
+            .identity_key
+            .as_untyped();
+        if let DkgMessage::SecretShare { recipient, .. } = &message.message {
+            if *recipient != self.index {
+                return Err(Error::UnexpectedMessage);
+            }
+        }
+
+        let mut transcript = SignedDkgMessage::transcript(self, sender, &message.message);
+        message
+            .signature
+            .verify(iter::once(identity_key), &mut transcript)
+            .map_err(Error::InvalidSignature)?;
+        Ok(&message.message)
+    }
+
+    /// Verifies a signed commitment and inserts it into the `participant` state
+    /// similar to [`ParticipantCollectingCommitments::insert_commitment()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message does not [verify](Self::verify()), is not
+    /// a commitment, or cannot be inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant` does not correspond to the local participant of this session.
+    pub fn insert_commitment(
+        &self,
+        participant: &mut ParticipantCollectingCommitments<G>,
+        message: &SignedDkgMessage<G>,
+    ) -> Result<(), Error> {
+        self.assert_local(participant.index());
+        match self.verify(message)? {
+            DkgMessage::Commitment(commitment) => {
+                participant.insert_commitment(message.sender, *commitment)
+            }
+            _ => Err(Error::UnexpectedMessage),
+        }
+    }
+
+    /// Verifies signed public information and inserts it into the `participant` state
+    /// similar to [`ParticipantCollectingPolynomials::insert_public_polynomial()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message does not [verify](Self::verify()), is not
+    /// public information, or cannot be inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant` does not correspond to the local participant of this session.
+    pub fn insert_public_polynomial(
+        &self,
+        participant: &mut ParticipantCollectingPolynomials<G>,
+        message: &SignedDkgMessage<G>,
+    ) -> Result<(), Error> {
+        self.assert_local(participant.index());
+        match self.verify(message)? {
+            DkgMessage::PublicInfo(info) => {
+                participant.insert_public_polynomial(message.sender, info.clone())
+            }
+            _ => Err(Error::UnexpectedMessage),
+        }
+    }
+
+    /// Verifies a signed secret share and inserts it into the `participant` state
+    /// similar to [`ParticipantExchangingSecrets::insert_secret_share()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message does not [verify](Self::verify()), is not
+    /// a secret share, or cannot be inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `participant` does not correspond to the local participant of this session.
+    pub fn insert_secret_share(
+        &self,
+        participant: &mut ParticipantExchangingSecrets<G>,
+        message: &SignedDkgMessage<G>,
+    ) -> Result<(), Error> {
+        self.assert_local(participant.index());
+        match self.verify(message)? {
+            DkgMessage::SecretShare { share, .. } => {
+                participant.insert_secret_share(message.sender, share.clone())
+            }
+            _ => Err(Error::UnexpectedMessage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        alloc::Vec,
+        group::Ristretto,
+        sharing::{Config, Params, ParticipantConfig},
+        IdentityKeypair,
+    };
+
+    fn create_sessions(n: usize) -> Vec<DkgSession<Ristretto>> {
+        let mut rng = thread_rng();
+        let identities: Vec<_> = (0..n)
+            .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let participants = identities.iter().enumerate();
+        let participants = participants.map(|(i, identity)| ParticipantConfig {
+            identity_key: identity.public(),
+            transport: format!("node-{i}"),
+            certificate: None,
+        });
+        let config = Config {
+            params: Params::new(n, 2),
+            context: "test".into(),
+            participants: participants.collect(),
+        };
+        identities
+            .into_iter()
+            .map(|identity| DkgSession::from_config(config.clone(), identity).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn dkg_with_signed_messages() {
+        let mut rng = thread_rng();
+        let sessions = create_sessions(3);
+        let mut participants: Vec<_> = sessions.iter().map(|s| s.start(&mut rng)).collect();
+
+        let messages: Vec<_> = sessions
+            .iter()
+            .zip(&participants)
+            .map(|(session, participant)| {
+                session.sign(DkgMessage::Commitment(participant.commitment()), &mut rng)
+            })
+            .collect();
+        for (i, participant) in participants.iter_mut().enumerate() {
+            for message in messages.iter().filter(|message| message.sender() != i) {
+                sessions[i].insert_commitment(participant, message).unwrap();
+            }
+        }
+        let mut participants: Vec<_> = participants
+            .into_iter()
+            .map(ParticipantCollectingCommitments::finish_commitment_phase)
+            .collect();
+
+        let messages: Vec<_> = sessions
+            .iter()
+            .zip(&participants)
+            .map(|(session, participant)| {
+                let info = participant.public_info().into_owned();
+                session.sign(DkgMessage::PublicInfo(info), &mut rng)
+            })
+            .collect();
+        for (i, participant) in participants.iter_mut().enumerate() {
+            for message in messages.iter().filter(|message| message.sender() != i) {
+                sessions[i]
+                    .insert_public_polynomial(participant, message)
+                    .unwrap();
+            }
+        }
+        let mut participants: Vec<_> = participants
+            .into_iter()
+            .map(ParticipantCollectingPolynomials::finish_polynomials_phase)
+            .collect();
+
+        for sender in 0..3 {
+            for recipient in (0..3).filter(|&j| j != sender) {
+                let share = participants[sender].secret_share_for_participant(recipient);
+                let message = DkgMessage::SecretShare { recipient, share };
+                let message = sessions[sender].sign(message, &mut rng);
+                sessions[recipient]
+                    .insert_secret_share(&mut participants[recipient], &message)
+                    .unwrap();
+            }
+        }
+        let participants: Vec<_> = sessions
+            .iter()
+            .zip(participants)
+            .map(|(session, participant)| session.complete(participant).unwrap())
+            .collect();
+        let shared_key = participants[0].key_set().shared_key();
+        assert!(participants
+            .iter()
+            .all(|participant| participant.key_set().shared_key() == shared_key));
+    }
+
+    #[test]
+    fn invalid_signed_messages() {
+        let mut rng = thread_rng();
+        let sessions = create_sessions(3);
+        let mut alice = sessions[0].start(&mut rng);
+        let carol = sessions[2].start(&mut rng);
+
+        // Carol impersonates Bob.
+        let mut message = sessions[2].sign(DkgMessage::Commitment(carol.commitment()), &mut rng);
+        message.sender = 1;
+        let err = sessions[0]
+            .insert_commitment(&mut alice, &message)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSignature(_)), "{err:?}");
+        assert_eq!(alice.missing_commitments().count(), 2);
+
+        message.sender = 5;
+        let err = sessions[0].verify(&message).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedMessage), "{err:?}");
+        let message = sessions[0].sign(DkgMessage::Commitment(alice.commitment()), &mut rng);
+        let err = sessions[0].verify(&message).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedMessage), "{err:?}");
+
+        // The message is valid, but has an unexpected kind.
+        let share = SecretKey::generate(&mut rng);
+        let message = DkgMessage::SecretShare {
+            recipient: 0,
+            share,
+        };
+        let message = sessions[2].sign(message, &mut rng);
+        sessions[0].verify(&message).unwrap();
+        let err = sessions[0]
+            .insert_commitment(&mut alice, &message)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnexpectedMessage), "{err:?}");
+        // The share is addressed to Alice, not Bob.
+        let err = sessions[1].verify(&message).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedMessage), "{err:?}");
+    }
+}
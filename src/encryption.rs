@@ -1,18 +1,22 @@
 //! `Ciphertext` and closely related types.
 
+use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::{Zeroize, Zeroizing};
 
 use core::{fmt, marker::PhantomData, ops};
 
 #[cfg(feature = "serde")]
-use crate::serde::ElementHelper;
+use crate::serde::{BytesHelper, ElementHelper};
 use crate::{
     alloc::{vec, HashMap, Vec},
     group::{Group, ScalarOps},
-    PublicKey, SecretKey,
+    proofs::TranscriptForGroup,
+    proto_labels, KeyFingerprint, Keypair, ProofOfPossession, PublicKey, SecretKey,
+    VerifiableDecryption, VerificationError,
 };
 
 /// Ciphertext for ElGamal encryption.
@@ -133,6 +137,36 @@ impl<G: Group> Ciphertext<G> {
         }
     }
 
+    /// Encrypts a batch of group elements for the specified `receiver`. This is equivalent
+    /// to calling [`PublicKey::encrypt_element()`] for each element, but is more efficient
+    /// for large batches.
+    ///
+    /// All random scalars are drawn up front; then, Diffie–Hellman elements are computed
+    /// with [`Group::batch_mul()`], which can share precomputations for the receiver key
+    /// among all ciphertexts. Random elements are computed with [`Group::mul_generator()`]
+    /// for each scalar; there is no batched counterpart since the Curve25519-based groups
+    /// already use a static precomputed table for the generator.
+    pub fn new_batch<R: CryptoRng + RngCore>(
+        values: &[G::Element],
+        receiver: &PublicKey<G>,
+        rng: &mut R,
+    ) -> Vec<Self> {
+        let random_scalars: Zeroizing<Vec<_>> =
+            Zeroizing::new(values.iter().map(|_| G::generate_scalar(rng)).collect());
+        let random_elements = random_scalars.iter().map(G::mul_generator);
+        let dh_elements = G::batch_mul(receiver.as_element(), &random_scalars);
+
+        values
+            .iter()
+            .zip(random_elements)
+            .zip(dh_elements)
+            .map(|((&value, random_element), dh_element)| Self {
+                random_element,
+                blinded_element: value + dh_element,
+            })
+            .collect()
+    }
+
     /// Returns a reference to the random element.
     pub fn random_element(&self) -> &G::Element {
         &self.random_element
@@ -143,6 +177,68 @@ impl<G: Group> Ciphertext<G> {
         &self.blinded_element
     }
 
+    /// Computes a linear combination of ciphertexts with the specified coefficients.
+    ///
+    /// This is equivalent to multiplying each ciphertext by its coefficient and summing
+    /// the products, but uses a single multi-scalar multiplication per ciphertext component,
+    /// which is significantly faster for many terms. Use [`Self::linear_combination_bound()`]
+    /// to determine the range of values for the decryption lookup table.
+    ///
+    /// # Implementation notes
+    ///
+    /// The computation is **not** constant-time w.r.t. coefficients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{group::Ristretto, Ciphertext, DiscreteLogTable, Keypair};
+    /// # use rand::thread_rng;
+    /// let mut rng = thread_rng();
+    /// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+    /// // Weighted sum of encrypted Boolean values.
+    /// let terms = [(true, 3), (false, 5), (true, 2)].map(|(value, weight)| {
+    ///     (receiver.public().encrypt(u64::from(value), &mut rng), weight)
+    /// });
+    /// let weighted_sum = Ciphertext::linear_combination(&terms);
+    ///
+    /// let max_value = Ciphertext::<Ristretto>::linear_combination_bound(
+    ///     terms.iter().map(|&(_, weight)| (1, weight)),
+    /// );
+    /// assert_eq!(max_value, Some(10));
+    /// let lookup_table = DiscreteLogTable::new(0..=max_value.unwrap());
+    /// assert_eq!(receiver.secret().decrypt(weighted_sum, &lookup_table), Some(5));
+    /// ```
+    pub fn linear_combination(terms: &[(Self, u64)]) -> Self {
+        let coefficients: Vec<_> = terms
+            .iter()
+            .map(|&(_, coefficient)| G::Scalar::from(coefficient))
+            .collect();
+        let random_elements = terms
+            .iter()
+            .map(|(ciphertext, _)| ciphertext.random_element);
+        let blinded_elements = terms
+            .iter()
+            .map(|(ciphertext, _)| ciphertext.blinded_element);
+        Self {
+            random_element: G::vartime_multi_mul(&coefficients, random_elements),
+            blinded_element: G::vartime_multi_mul(&coefficients, blinded_elements),
+        }
+    }
+
+    /// Computes the maximum value encrypted in a [linear combination](Self::linear_combination())
+    /// given `terms` consisting of the maximum encrypted value and the coefficient
+    /// for each combined ciphertext. Assuming that combined ciphertexts encrypt values
+    /// from `0..=max_value`, the linear combination encrypts a value from `0..=bound`.
+    ///
+    /// Returns `None` if the bound overflows `u64`.
+    pub fn linear_combination_bound(terms: impl IntoIterator<Item = (u64, u64)>) -> Option<u64> {
+        terms
+            .into_iter()
+            .try_fold(0_u64, |acc, (max_value, coefficient)| {
+                acc.checked_add(max_value.checked_mul(coefficient)?)
+            })
+    }
+
     /// Serializes this ciphertext as two group elements (the random element,
     /// then the blinded value).
     pub fn to_bytes(self) -> Vec<u8> {
@@ -151,6 +247,84 @@ impl<G: Group> Ciphertext<G> {
         G::serialize_element(&self.blinded_element, &mut bytes[G::ELEMENT_SIZE..]);
         bytes
     }
+
+    /// Deserializes a ciphertext from bytes produced by [`Self::to_bytes()`].
+    /// Returns `None` if `bytes` do not represent a ciphertext.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 2 * G::ELEMENT_SIZE {
+            return None;
+        }
+        let (random_bytes, blinded_bytes) = bytes.split_at(G::ELEMENT_SIZE);
+        Some(Self {
+            random_element: G::deserialize_element(random_bytes)?,
+            blinded_element: G::deserialize_element(blinded_bytes)?,
+        })
+    }
+
+    /// Checks whether this ciphertext decrypts to the specified `candidate` value.
+    ///
+    /// Unlike decryption with a [`DiscreteLogTable`], this does not require building a table
+    /// of all possible values, which is convenient if the expected plaintext is known
+    /// in advance (e.g., when checking a receipt). The check is performed in constant time
+    /// w.r.t. `candidate` and the decrypted value.
+    ///
+    /// `decryptor` may be a [`SecretKey`] or a [`VerifiableDecryption`] (e.g., one combined
+    /// from shares in a threshold encryption scheme).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{group::Ristretto, Keypair};
+    /// # use rand::thread_rng;
+    /// let mut rng = thread_rng();
+    /// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+    /// let ciphertext = receiver.public().encrypt(42_u64, &mut rng);
+    /// assert!(ciphertext.verify_candidate(42, receiver.secret()));
+    /// assert!(!ciphertext.verify_candidate(41, receiver.secret()));
+    /// ```
+    pub fn verify_candidate<D: Decryptor<G> + ?Sized>(
+        &self,
+        candidate: u64,
+        decryptor: &D,
+    ) -> bool {
+        let decrypted = decryptor.decrypt_to_element(*self);
+        let expected = G::mul_generator(&G::Scalar::from(candidate));
+        G::ct_eq_elements(&decrypted, &expected).into()
+    }
+}
+
+/// Compares ciphertexts in constant time. This is useful if ciphertexts are derived
+/// from secret data, e.g., to check a re-encryption computed with a secret key.
+impl<G: Group> ConstantTimeEq for Ciphertext<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        G::ct_eq_elements(&self.random_element, &other.random_element)
+            & G::ct_eq_elements(&self.blinded_element, &other.blinded_element)
+    }
+}
+
+/// Means to decrypt a [`Ciphertext`]: either a [`SecretKey`], or a [`VerifiableDecryption`]
+/// (e.g., combined from decryption shares in a threshold encryption scheme).
+///
+/// This trait is sealed; it cannot be implemented outside the crate.
+pub trait Decryptor<G: Group>: crate::sealed::Sealed {
+    /// Decrypts the provided ciphertext and returns the produced group element.
+    fn decrypt_to_element(&self, encrypted: Ciphertext<G>) -> G::Element;
+}
+
+impl<G: Group> crate::sealed::Sealed for SecretKey<G> {}
+
+impl<G: Group> Decryptor<G> for SecretKey<G> {
+    fn decrypt_to_element(&self, encrypted: Ciphertext<G>) -> G::Element {
+        SecretKey::decrypt_to_element(self, encrypted)
+    }
+}
+
+impl<G: Group> crate::sealed::Sealed for VerifiableDecryption<G> {}
+
+impl<G: Group> Decryptor<G> for VerifiableDecryption<G> {
+    fn decrypt_to_element(&self, encrypted: Ciphertext<G>) -> G::Element {
+        VerifiableDecryption::decrypt_to_element(self, encrypted)
+    }
 }
 
 impl<G: Group> ops::Add for Ciphertext<G> {
@@ -218,6 +392,354 @@ impl<G: Group> ops::Neg for Ciphertext<G> {
     }
 }
 
+/// [`Ciphertext`] together with public associated data (a *tag*), such as a contest ID
+/// or the position of the ciphertext in a ballot.
+///
+/// The tag is not encrypted, and is not involved in ElGamal arithmetic. Instead, it is bound
+/// to zero-knowledge proofs about the ciphertext created via the `*_tagged` methods
+/// of [`PublicKey`], such as [`PublicKey::encrypt_bool_tagged()`]. A proof created
+/// for a tagged ciphertext does not verify for the same ciphertext with another tag,
+/// or for the bare ciphertext. Thus, proven ciphertexts cannot be transplanted
+/// between contexts undetected.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Keypair, TaggedCiphertext};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let (ciphertext, proof) = receiver
+///     .public()
+///     .encrypt_bool_tagged(true, b"contest #1", &mut rng);
+/// receiver.public().verify_bool_tagged(&ciphertext, &proof)?;
+///
+/// // The proof does not verify if the ciphertext is moved to another context.
+/// let transplanted = TaggedCiphertext::new(b"contest #2", *ciphertext.ciphertext());
+/// assert!(receiver.public().verify_bool_tagged(&transplanted, &proof).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TaggedCiphertext<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "BytesHelper"))]
+    tag: Vec<u8>,
+    ciphertext: Ciphertext<G>,
+}
+
+impl<G: Group> TaggedCiphertext<G> {
+    /// Attaches the `tag` to the specified `ciphertext`.
+    pub fn new(tag: impl AsRef<[u8]>, ciphertext: Ciphertext<G>) -> Self {
+        Self {
+            tag: tag.as_ref().to_vec(),
+            ciphertext,
+        }
+    }
+
+    /// Returns the tag of this ciphertext.
+    pub fn tag(&self) -> &[u8] {
+        &self.tag
+    }
+
+    /// Returns the ciphertext without the tag.
+    pub fn ciphertext(&self) -> &Ciphertext<G> {
+        &self.ciphertext
+    }
+
+    /// Splits this ciphertext into the tag and the bare ciphertext.
+    pub fn into_parts(self) -> (Vec<u8>, Ciphertext<G>) {
+        (self.tag, self.ciphertext)
+    }
+}
+
+/// [`Ciphertext`] bound to the [fingerprint](PublicKey::fingerprint()) of the key it is
+/// encrypted for.
+///
+/// Decrypting a ciphertext with a wrong key (or combining threshold decryption shares
+/// for a ciphertext encrypted for another key set) silently produces garbage. A bound
+/// ciphertext checks the key before giving out the bare ciphertext, so such mistakes
+/// are caught early with a [`KeyMismatchError`]. The binding is not cryptographic;
+/// it protects against accidental mismatches, not against an active adversary.
+///
+/// Bound ciphertexts can be added together and multiplied by scalars as long
+/// as they are bound to the same key.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, DiscreteLogTable, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let other = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = receiver.public().encrypt_bound(3_u64, &mut rng);
+/// let sum = ciphertext.checked_add(receiver.public().encrypt_bound(4_u64, &mut rng))?;
+///
+/// let lookup_table = DiscreteLogTable::new(0..10);
+/// assert_eq!(sum.decrypt(&receiver, &lookup_table)?, Some(7));
+/// assert!(sum.decrypt(&other, &lookup_table).is_err());
+/// // The bare ciphertext can be obtained after checking the key.
+/// let bare_ciphertext = sum.check_key(receiver.public())?;
+/// # assert_eq!(receiver.secret().decrypt(bare_ciphertext, &lookup_table), Some(7));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct BoundCiphertext<G: Group> {
+    key_fingerprint: KeyFingerprint,
+    ciphertext: Ciphertext<G>,
+}
+
+impl<G: Group> BoundCiphertext<G> {
+    /// Binds the `ciphertext` to the `receiver` key. The caller is responsible for
+    /// the ciphertext actually being encrypted for this key.
+    pub fn new(ciphertext: Ciphertext<G>, receiver: &PublicKey<G>) -> Self {
+        Self {
+            key_fingerprint: receiver.fingerprint(),
+            ciphertext,
+        }
+    }
+
+    /// Returns the fingerprint of the key this ciphertext is bound to.
+    pub fn key_fingerprint(&self) -> KeyFingerprint {
+        self.key_fingerprint
+    }
+
+    /// Returns the bare ciphertext without checking the key.
+    pub fn ciphertext_unchecked(&self) -> &Ciphertext<G> {
+        &self.ciphertext
+    }
+
+    /// Returns the bare ciphertext if this ciphertext is bound to `key`. For threshold
+    /// encryption, `key` is the shared key of the key set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this ciphertext is bound to another key.
+    pub fn check_key(&self, key: &PublicKey<G>) -> Result<Ciphertext<G>, KeyMismatchError> {
+        let actual = key.fingerprint();
+        if actual == self.key_fingerprint {
+            Ok(self.ciphertext)
+        } else {
+            Err(KeyMismatchError {
+                expected: self.key_fingerprint,
+                actual,
+            })
+        }
+    }
+
+    /// Decrypts this ciphertext with the `receiver` keypair, using the `lookup_table`
+    /// similarly to [`SecretKey::decrypt()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this ciphertext is bound to another key.
+    pub fn decrypt<D: DiscreteLogSolver<G> + ?Sized>(
+        &self,
+        receiver: &Keypair<G>,
+        lookup_table: &D,
+    ) -> Result<Option<u64>, KeyMismatchError> {
+        let ciphertext = self.check_key(receiver.public())?;
+        Ok(receiver.secret().decrypt(ciphertext, lookup_table))
+    }
+
+    /// Adds `other` ciphertext to this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ciphertexts are bound to different keys.
+    pub fn checked_add(self, other: Self) -> Result<Self, KeyMismatchError> {
+        if self.key_fingerprint == other.key_fingerprint {
+            Ok(Self {
+                key_fingerprint: self.key_fingerprint,
+                ciphertext: self.ciphertext + other.ciphertext,
+            })
+        } else {
+            Err(KeyMismatchError {
+                expected: self.key_fingerprint,
+                actual: other.key_fingerprint,
+            })
+        }
+    }
+}
+
+impl<G: Group> ops::Mul<&G::Scalar> for BoundCiphertext<G> {
+    type Output = Self;
+
+    fn mul(self, rhs: &G::Scalar) -> Self {
+        Self {
+            key_fingerprint: self.key_fingerprint,
+            ciphertext: self.ciphertext * rhs,
+        }
+    }
+}
+
+impl<G: Group> ops::Mul<u64> for BoundCiphertext<G> {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        Self {
+            key_fingerprint: self.key_fingerprint,
+            ciphertext: self.ciphertext * rhs,
+        }
+    }
+}
+
+/// Error returned when a [`BoundCiphertext`] is used with a key it is not bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyMismatchError {
+    expected: KeyFingerprint,
+    actual: KeyFingerprint,
+}
+
+impl KeyMismatchError {
+    /// Returns the fingerprint of the key the ciphertext is bound to.
+    pub fn expected(&self) -> KeyFingerprint {
+        self.expected
+    }
+
+    /// Returns the fingerprint of the key the ciphertext was used with.
+    pub fn actual(&self) -> KeyFingerprint {
+        self.actual
+    }
+}
+
+impl fmt::Display for KeyMismatchError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "ciphertext is bound to key {}, but is used with key {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyMismatchError {}
+
+/// [`Ciphertext`] sealed with a zero-knowledge proof of knowledge of the encrypted value,
+/// which is bound to the receiver key and a public *context* (e.g., the ID of the record
+/// in the escrow database).
+///
+/// Plain ElGamal ciphertexts are malleable: anyone can add an encrypted value
+/// to a ciphertext, or re-randomize it. This is essential for tallying, but is a liability
+/// if a ciphertext must be stored or transferred as is (e.g., in key escrow or credential
+/// storage). The proof in a sealed encryption is a Schnorr proof of knowledge
+/// of the randomness `r` such that `R = [r]G` (and thus of the encrypted value),
+/// with the challenge depending on the entire ciphertext and the context. This is
+/// the *signed ElGamal* construction, which is non-malleable in the random oracle model:
+/// a modified ciphertext or a ciphertext moved to another context does not verify, and
+/// creating a valid sealed encryption requires knowing the encrypted value.
+///
+/// Correspondingly, sealed encryptions do not support homomorphic operations, and the bare
+/// ciphertext is only given out after verification, e.g., via [`Self::open()`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, DiscreteLogTable, Keypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let sealed = receiver.public().encrypt_sealed(42_u64, b"record #5", &mut rng);
+///
+/// let lookup_table = DiscreteLogTable::new(0..100);
+/// let value = sealed.decrypt(&receiver, b"record #5", &lookup_table)?;
+/// assert_eq!(value, Some(42));
+/// // The encryption cannot be used in another context.
+/// assert!(sealed.decrypt(&receiver, b"record #6", &lookup_table).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SealedEncryption<G: Group> {
+    ciphertext: Ciphertext<G>,
+    proof: ProofOfPossession<G>,
+}
+
+impl<G: Group> SealedEncryption<G> {
+    fn transcript(
+        receiver: &PublicKey<G>,
+        context: &[u8],
+        ciphertext: &Ciphertext<G>,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::SEALED_ENCRYPTION);
+        transcript.append_element_bytes(b"K", receiver.as_bytes());
+        transcript.append_message(b"ctx", context);
+        transcript.append_element::<G>(b"B", &ciphertext.blinded_element);
+        transcript
+    }
+
+    pub(crate) fn new<R: CryptoRng + RngCore>(
+        receiver: &PublicKey<G>,
+        context: &[u8],
+        ciphertext: Ciphertext<G>,
+        randomness: SecretRandomness<G>,
+        rng: &mut R,
+    ) -> Self {
+        let random_element = PublicKey::from_element(ciphertext.random_element);
+        let mut transcript = Self::transcript(receiver, context, &ciphertext);
+        let proof = ProofOfPossession::from_keys(
+            core::iter::once(&randomness.into_secret_key()),
+            core::iter::once(&random_element),
+            &mut transcript,
+            rng,
+        );
+        Self { ciphertext, proof }
+    }
+
+    /// Returns the bare ciphertext **without** checking the proof.
+    pub fn ciphertext_unchecked(&self) -> &Ciphertext<G> {
+        &self.ciphertext
+    }
+
+    /// Verifies the proof for the `receiver` key and `context`, and returns the bare ciphertext,
+    /// e.g., to be decrypted by threshold decryption. The returned ciphertext should not
+    /// be transformed homomorphically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not verify, e.g., if the ciphertext was modified
+    /// or is used in another context.
+    pub fn open(
+        &self,
+        receiver: &PublicKey<G>,
+        context: &[u8],
+    ) -> Result<Ciphertext<G>, VerificationError> {
+        let random_element = PublicKey::from_element(self.ciphertext.random_element);
+        let mut transcript = Self::transcript(receiver, context, &self.ciphertext);
+        self.proof
+            .verify(core::iter::once(&random_element), &mut transcript)?;
+        Ok(self.ciphertext)
+    }
+
+    /// Verifies this encryption similarly to [`Self::open()`] and decrypts it
+    /// with the `receiver` keypair, using the `lookup_table`
+    /// similarly to [`SecretKey::decrypt()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof does not verify.
+    pub fn decrypt<D: DiscreteLogSolver<G> + ?Sized>(
+        &self,
+        receiver: &Keypair<G>,
+        context: &[u8],
+        lookup_table: &D,
+    ) -> Result<Option<u64>, VerificationError> {
+        let ciphertext = self.open(receiver.public(), context)?;
+        Ok(receiver.secret().decrypt(ciphertext, lookup_table))
+    }
+}
+
 /// Lookup table for discrete logarithms.
 ///
 /// For [`Ciphertext`]s to be partially homomorphic, the encrypted values must be
@@ -291,6 +813,72 @@ impl<G: Group> DiscreteLogTable<G> {
     }
 }
 
+/// Solver of discrete logs for decrypted group elements, i.e., a way to restore the encrypted
+/// value `x` from `[x]G`, where `G` is the group generator.
+///
+/// The trait is implemented by [`DiscreteLogTable`], [`KangarooDecrypter`]
+/// and (with the `mmap` crate feature) `MmapDiscreteLogTable`. It can also be implemented
+/// for external solvers, e.g., ones based on the baby-step giant-step algorithm.
+///
+/// [`KangarooDecrypter`]: crate::KangarooDecrypter
+pub trait DiscreteLogSolver<G: Group> {
+    /// Returns the discrete log of `decrypted_element`, or `None` if it is not found.
+    fn solve(&self, decrypted_element: &G::Element) -> Option<u64>;
+}
+
+impl<G: Group> DiscreteLogSolver<G> for DiscreteLogTable<G> {
+    fn solve(&self, decrypted_element: &G::Element) -> Option<u64> {
+        self.get(decrypted_element)
+    }
+}
+
+/// Random scalar used to create a [`Ciphertext`], obtained via
+/// [`PublicKey::encrypt_with_randomness()`] or [`CiphertextWithValue::secret_randomness()`].
+///
+/// Knowledge of the randomness `r` allows to construct custom zero-knowledge proofs about
+/// the ciphertext `(R, B) = ([r]G, [x]G + [r]K)` (e.g., via [`LogEqualityProof`]s),
+/// but also to decrypt the ciphertext without the receiver's secret key. Thus, the randomness
+/// must be kept secret; it is zeroized on drop.
+///
+/// [`LogEqualityProof`]: crate::LogEqualityProof
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::{Group, Ristretto}, Keypair};
+/// # use rand::thread_rng;
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let (ciphertext, randomness) = receiver.public().encrypt_with_randomness(5_u64, &mut rng);
+/// assert_eq!(
+///     *ciphertext.random_element(),
+///     Ristretto::mul_generator(randomness.expose_scalar())
+/// );
+/// ```
+#[derive(Clone)]
+pub struct SecretRandomness<G: Group>(SecretKey<G>);
+
+impl<G: Group> fmt::Debug for SecretRandomness<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SecretRandomness")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> SecretRandomness<G> {
+    /// Exposes the random scalar.
+    pub fn expose_scalar(&self) -> &G::Scalar {
+        self.0.expose_scalar()
+    }
+
+    /// Converts the randomness into a [`SecretKey`], e.g., to use it as a witness
+    /// in proofs accepting secret keys.
+    pub fn into_secret_key(self) -> SecretKey<G> {
+        self.0
+    }
+}
+
 /// [`Ciphertext`] together with the random scalar used to create it.
 #[derive(Debug, Clone)]
 #[doc(hidden)] // only public for benchmarking
@@ -300,6 +888,10 @@ pub struct ExtendedCiphertext<G: Group> {
 }
 
 impl<G: Group> ExtendedCiphertext<G> {
+    pub(crate) fn into_parts(self) -> (Ciphertext<G>, SecretRandomness<G>) {
+        (self.inner, SecretRandomness(self.random_scalar))
+    }
+
     /// Creates a ciphertext of `value` for the specified `receiver`.
     pub(crate) fn new<R: CryptoRng + RngCore>(
         value: G::Element,
@@ -419,6 +1011,11 @@ where
         &self.inner.inner
     }
 
+    /// Returns the random scalar used to create the ciphertext.
+    pub fn secret_randomness(&self) -> SecretRandomness<G> {
+        SecretRandomness(self.inner.random_scalar.clone())
+    }
+
     pub(crate) fn extended_ciphertext(&self) -> &ExtendedCiphertext<G> {
         &self.inner
     }
@@ -437,7 +1034,12 @@ mod tests {
     use rand::{thread_rng, Rng};
 
     use super::*;
-    use crate::{curve25519::scalar::Scalar as Curve25519Scalar, group::Ristretto, Keypair};
+    use merlin::Transcript;
+
+    use crate::{
+        curve25519::scalar::Scalar as Curve25519Scalar, group::Ristretto, Keypair,
+        LogEqualityProof, RangeDecomposition,
+    };
 
     #[test]
     fn ciphertext_addition() {
@@ -453,6 +1055,27 @@ mod tests {
         assert_eq!(decrypted, Ristretto::vartime_mul_generator(&sum.into()));
     }
 
+    #[test]
+    fn batch_encryption() {
+        let mut rng = thread_rng();
+        let (pk, sk) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        // Check batches both below and above the threshold for precomputing tables.
+        for len in [0_u64, 5, 20] {
+            let scalars: Vec<_> = (0..len).map(Curve25519Scalar::from).collect();
+            let elements: Vec<_> = scalars.iter().map(Ristretto::mul_generator).collect();
+            let key_multiples = Ristretto::batch_mul(pk.as_element(), &scalars);
+            for (scalar, key_multiple) in scalars.iter().zip(key_multiples) {
+                assert_eq!(key_multiple, pk.as_element() * scalar);
+            }
+
+            let ciphertexts = pk.encrypt_batch(0..len, &mut rng);
+            assert_eq!(ciphertexts.len(), elements.len());
+            for (ciphertext, element) in ciphertexts.into_iter().zip(elements) {
+                assert_eq!(sk.decrypt_to_element(ciphertext), element);
+            }
+        }
+    }
+
     #[test]
     fn ciphertext_mul_by_u64() {
         let mut rng = thread_rng();
@@ -504,4 +1127,194 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn tagged_ciphertext_proofs() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+
+        let (ciphertext, proof) = pk.encrypt_zero_tagged(b"test", &mut rng);
+        pk.verify_zero_tagged(&ciphertext, &proof).unwrap();
+        assert!(pk.verify_zero(*ciphertext.ciphertext(), &proof).is_err());
+        let other = TaggedCiphertext::new(b"other", *ciphertext.ciphertext());
+        assert!(pk.verify_zero_tagged(&other, &proof).is_err());
+
+        let (ciphertext, proof) = pk.encrypt_bool_tagged(false, b"test", &mut rng);
+        pk.verify_bool_tagged(&ciphertext, &proof).unwrap();
+        assert!(pk.verify_bool(*ciphertext.ciphertext(), &proof).is_err());
+        let (untagged_ciphertext, untagged_proof) = pk.encrypt_bool(false, &mut rng);
+        let tagged = TaggedCiphertext::new(b"", untagged_ciphertext);
+        assert!(pk.verify_bool_tagged(&tagged, &untagged_proof).is_err());
+
+        let range = RangeDecomposition::optimal(20).into();
+        let (ciphertext, proof) = pk.encrypt_range_tagged(&range, 13, b"test", &mut rng);
+        pk.verify_range_tagged(&range, &ciphertext, &proof).unwrap();
+        let (tag, inner) = ciphertext.into_parts();
+        assert_eq!(tag, b"test");
+        assert!(pk.verify_range(&range, inner, &proof).is_err());
+    }
+
+    #[test]
+    fn signed_range_proofs() {
+        let mut rng = thread_rng();
+        let (pk, sk) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let range = RangeDecomposition::optimal(20).into();
+        for value in [-10, -1, 0, 9] {
+            let (ciphertext, proof) = pk.encrypt_signed_range(&range, value, &mut rng);
+            pk.verify_signed_range(&range, ciphertext, &proof).unwrap();
+            assert!(pk.verify_range(&range, ciphertext, &proof).is_err());
+            let expected = Ristretto::scalar_from_i64(value).unwrap();
+            assert_eq!(
+                sk.decrypt_to_element(ciphertext),
+                Ristretto::vartime_mul_generator(&expected)
+            );
+        }
+
+        let (ciphertext, proof) = pk.encrypt_signed_range(&range, 3, &mut rng);
+        let shifted_ciphertext = ciphertext + Ciphertext::non_blinded(1_u64);
+        assert!(pk
+            .verify_signed_range(&range, shifted_ciphertext, &proof)
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "value is out of range")]
+    fn signed_range_proof_panics_for_out_of_range_value() {
+        let mut rng = thread_rng();
+        let (pk, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+        let range = RangeDecomposition::optimal(20).into();
+        pk.encrypt_signed_range(&range, 10, &mut rng);
+    }
+
+    #[test]
+    fn verifying_candidates_with_combined_decryption() {
+        use crate::sharing::{ActiveParticipant, Dealer, Params, PublicKeySet};
+
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+
+        let ciphertext = key_set.shared_key().encrypt(1_000_000_u64, &mut rng);
+        let shares = (1..3).map(|i| {
+            let secret_share = dealer.secret_share_for_participant(i);
+            let participant = ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap();
+            (i, participant.decrypt_share(ciphertext, &mut rng).0)
+        });
+        let decryption = params.combine_shares(shares).unwrap();
+
+        assert!(ciphertext.verify_candidate(1_000_000, &decryption));
+        assert!(!ciphertext.verify_candidate(999_999, &decryption));
+        assert!(!ciphertext.verify_candidate(0, &decryption));
+    }
+
+    #[test]
+    fn bound_ciphertext_arithmetic() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        assert_ne!(
+            receiver.public().fingerprint(),
+            other.public().fingerprint()
+        );
+
+        let ciphertext = receiver.public().encrypt_bound(2_u64, &mut rng) * 3;
+        let lookup_table = DiscreteLogTable::new(0..10);
+        assert_eq!(ciphertext.decrypt(&receiver, &lookup_table), Ok(Some(6)));
+
+        let other_ciphertext = other.public().encrypt_bound(1_u64, &mut rng);
+        let err = ciphertext.checked_add(other_ciphertext).unwrap_err();
+        assert_eq!(err.expected(), receiver.public().fingerprint());
+        assert_eq!(err.actual(), other.public().fingerprint());
+        let err = other_ciphertext.check_key(receiver.public()).unwrap_err();
+        assert_eq!(err.expected(), other.public().fingerprint());
+    }
+
+    #[test]
+    fn linear_combination_of_ciphertexts() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let terms: Vec<_> = (0..20)
+            .map(|_| {
+                let value = rng.gen_range(0..10_u64);
+                let coefficient = rng.gen_range(0..100_u64);
+                (
+                    receiver.public().encrypt(value, &mut rng),
+                    value,
+                    coefficient,
+                )
+            })
+            .collect();
+
+        let expected_value: u64 = terms.iter().map(|(_, value, coeff)| value * coeff).sum();
+        let expected = terms
+            .iter()
+            .fold(Ciphertext::zero(), |acc, &(ciphertext, _, coeff)| {
+                acc + ciphertext * coeff
+            });
+        let combination_terms: Vec<_> = terms.iter().map(|&(ct, _, coeff)| (ct, coeff)).collect();
+        let combination = Ciphertext::linear_combination(&combination_terms);
+        assert_eq!(combination.random_element, expected.random_element);
+        assert_eq!(combination.blinded_element, expected.blinded_element);
+        assert!(combination.verify_candidate(expected_value, receiver.secret()));
+
+        let bound = Ciphertext::<Ristretto>::linear_combination_bound(
+            terms.iter().map(|&(_, _, coeff)| (9, coeff)),
+        );
+        assert!(bound.unwrap() >= expected_value);
+        let overflowing_bound =
+            Ciphertext::<Ristretto>::linear_combination_bound([(u64::MAX, 1), (1, 1)]);
+        assert_eq!(overflowing_bound, None);
+    }
+
+    #[test]
+    fn encryption_randomness_allows_custom_proofs() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let (ciphertext, randomness) = receiver.public().encrypt_with_randomness(7_u64, &mut rng);
+        let dh_element = ciphertext.blinded_element - Ristretto::mul_generator(&7_u64.into());
+        let powers = (ciphertext.random_element, dh_element);
+
+        let proof = LogEqualityProof::new(
+            receiver.public(),
+            &randomness.into_secret_key(),
+            powers,
+            &mut Transcript::new(b"custom"),
+            &mut rng,
+        );
+        proof
+            .verify(receiver.public(), powers, &mut Transcript::new(b"custom"))
+            .unwrap();
+
+        let ciphertext = CiphertextWithValue::new(3_u64, receiver.public(), &mut rng);
+        let randomness = ciphertext.secret_randomness();
+        assert_eq!(
+            ciphertext.inner().random_element,
+            Ristretto::mul_generator(randomness.expose_scalar())
+        );
+    }
+
+    #[test]
+    fn sealed_encryption_is_not_malleable() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        let sealed = receiver.public().encrypt_sealed(5_u64, b"ctx", &mut rng);
+        let lookup_table = DiscreteLogTable::new(0..10);
+        let value = sealed.decrypt(&receiver, b"ctx", &lookup_table).unwrap();
+        assert_eq!(value, Some(5));
+        assert!(sealed.open(other.public(), b"ctx").is_err());
+
+        let mut shifted = sealed.clone();
+        shifted.ciphertext += receiver.public().encrypt(1_u64, &mut rng);
+        assert!(shifted.open(receiver.public(), b"ctx").is_err());
+        let mut shifted = sealed.clone();
+        shifted.ciphertext.blinded_element +=
+            Ristretto::mul_generator(&Curve25519Scalar::from(1_u64));
+        assert!(shifted.open(receiver.public(), b"ctx").is_err());
+        let mut rerandomized = sealed;
+        rerandomized.ciphertext += receiver.public().encrypt(0_u64, &mut rng);
+        assert!(rerandomized.open(receiver.public(), b"ctx").is_err());
+    }
 }
@@ -1,14 +1,23 @@
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "curve25519-dalek")]
+use subtle::{Choice, ConstantTimeEq};
 
 use core::convert::TryInto;
 
+#[cfg(feature = "curve25519-dalek")]
+use crate::curve25519::traits::BasepointTable;
 use crate::curve25519::{
     constants::{ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE},
-    edwards::{CompressedEdwardsY, EdwardsPoint},
+    edwards::{CompressedEdwardsY, EdwardsBasepointTable, EdwardsPoint},
     scalar::Scalar,
     traits::{Identity, IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
 };
-use crate::group::{ElementOps, Group, RandomBytesProvider, ScalarOps};
+use crate::{
+    alloc::Vec,
+    group::{
+        ElementOps, Group, PrecomputedMul, RandomBytesProvider, ScalarOps, BATCH_TABLE_THRESHOLD,
+    },
+};
 
 /// Prime-order subgroup of Curve25519 without any transforms performed for EC points.
 ///
@@ -69,6 +78,13 @@ impl ScalarOps for Curve25519Subgroup {
         let bytes: &[u8; 32] = buffer.try_into().expect("input has incorrect byte size");
         Scalar::from_canonical_bytes(*bytes)
     }
+
+    // `curve25519-dalek-ng` implements `ConstantTimeEq` from a fork of `subtle`, so we use
+    // the default implementation for it.
+    #[cfg(feature = "curve25519-dalek")]
+    fn ct_eq_scalars(lhs: &Self::Scalar, rhs: &Self::Scalar) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl ElementOps for Curve25519Subgroup {
@@ -106,9 +122,17 @@ impl ElementOps for Curve25519Subgroup {
             .decompress()
             .filter(EdwardsPoint::is_torsion_free)
     }
+
+    #[cfg(feature = "curve25519-dalek")]
+    fn ct_eq_elements(lhs: &Self::Element, rhs: &Self::Element) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl Group for Curve25519Subgroup {
+    // The group order is approximately `2^252`.
+    const SECURITY_BITS: usize = 126;
+
     #[cfg(feature = "curve25519-dalek")]
     fn mul_generator(k: &Scalar) -> Self::Element {
         k * ED25519_BASEPOINT_TABLE
@@ -128,6 +152,14 @@ impl Group for Curve25519Subgroup {
         EdwardsPoint::vartime_double_scalar_mul_basepoint(&zero, &EdwardsPoint::identity(), k)
     }
 
+    fn batch_mul(element: Self::Element, scalars: &[Scalar]) -> Vec<Self::Element> {
+        if scalars.len() < BATCH_TABLE_THRESHOLD {
+            return scalars.iter().map(|scalar| element * scalar).collect();
+        }
+        let table = EdwardsBasepointTable::create(&element);
+        scalars.iter().map(|scalar| scalar * &table).collect()
+    }
+
     fn multi_mul<'a, I, J>(scalars: I, elements: J) -> Self::Element
     where
         I: IntoIterator<Item = &'a Self::Scalar>,
@@ -153,6 +185,18 @@ impl Group for Curve25519Subgroup {
     }
 }
 
+impl PrecomputedMul for Curve25519Subgroup {
+    type Table = EdwardsBasepointTable;
+
+    fn precompute(element: &Self::Element) -> Self::Table {
+        EdwardsBasepointTable::create(element)
+    }
+
+    fn mul_precomputed(table: &Self::Table, k: &Scalar) -> Self::Element {
+        k * table
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -195,4 +239,18 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn batch_mul_with_precomputed_table() {
+        let mut rng = thread_rng();
+        let element =
+            Curve25519Subgroup::mul_generator(&Curve25519Subgroup::generate_scalar(&mut rng));
+        let scalars: Vec<_> = (0..2 * BATCH_TABLE_THRESHOLD)
+            .map(|_| Curve25519Subgroup::generate_scalar(&mut rng))
+            .collect();
+        let products = Curve25519Subgroup::batch_mul(element, &scalars);
+        for (scalar, product) in scalars.iter().zip(products) {
+            assert_eq!(product, element * scalar);
+        }
+    }
 }
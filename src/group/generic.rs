@@ -2,14 +2,15 @@ use elliptic_curve::{
     ff::PrimeField,
     generic_array::{typenum::Unsigned, GenericArray},
     sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
-    CurveArithmetic, Field, FieldBytesSize, Group as _, ProjectivePoint, Scalar,
+    subtle::{Choice, ConstantTimeEq},
+    CurveArithmetic, Field, FieldBytesSize, Group as _, PrimeCurve, ProjectivePoint, Scalar,
 };
 use rand_core::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 use core::marker::PhantomData;
 
-use super::{ElementOps, Group, ScalarOps};
+use super::{ElementOps, Group, PrecomputedMul, ScalarOps};
 
 /// Generic [`Group`] implementation for elliptic curves defined in terms of the traits
 /// from the [`elliptic-curve`] crate.
@@ -19,7 +20,44 @@ use super::{ElementOps, Group, ScalarOps};
 /// - Arithmetic operations required to be constant-time as per [`ScalarOps`] and [`ElementOps`]
 ///   contracts are indeed constant-time.
 ///
+/// # Suitability checks
+///
+/// Curves unsuitable for the protocols in this crate are rejected at compile time:
+///
+/// - The curve must have prime order, i.e., implement the [`PrimeCurve`] marker trait.
+///   Curves with a cofactor (e.g., Curve25519) require cofactor handling, which this
+///   implementation does not perform; such curves need a dedicated [`Group`] implementation
+///   (cf. [`Curve25519Subgroup`] and [`Ristretto`]).
+/// - The group order must have at least [`Self::MIN_ORDER_BITS`] bits. Using a curve
+///   with a smaller order fails compilation with a message mentioning the requirement.
+///
+/// For example, the following code does not compile since the toy curve (a wrapper around
+/// secp256k1 declaring a smaller scalar field) has a 160-bit order:
+///
+/// ```compile_fail
+/// # const TOY_NUM_BITS: u32 = 160;
+/// # include!("../../tests/fixtures/toy_curve.rs");
+/// # impl elliptic_curve::PrimeCurve for ToyCurve {}
+/// use elastic_elgamal::group::{Generic, ScalarOps};
+/// # fn main() {
+/// let scalar = Generic::<ToyCurve>::generate_scalar(&mut rand::thread_rng());
+/// # }
+/// ```
+///
+/// Likewise, a curve not marked as having prime order is not a [`Group`]:
+///
+/// ```compile_fail
+/// # const TOY_NUM_BITS: u32 = 256;
+/// # include!("../../tests/fixtures/toy_curve.rs");
+/// use elastic_elgamal::{group::Generic, Keypair};
+/// # fn main() {
+/// let keypair = Keypair::<Generic<ToyCurve>>::generate(&mut rand::thread_rng());
+/// # }
+/// ```
+///
 /// [`elliptic-curve`]: https://docs.rs/elliptic-curve/
+/// [`Curve25519Subgroup`]: crate::group::Curve25519Subgroup
+/// [`Ristretto`]: crate::group::Ristretto
 #[derive(Debug)]
 pub struct Generic<C>(PhantomData<C>);
 
@@ -31,6 +69,19 @@ impl<C> Clone for Generic<C> {
 
 impl<C> Copy for Generic<C> {}
 
+impl<C: CurveArithmetic> Generic<C> {
+    /// Minimum bit length of the group order, which corresponds to the 112-bit security level.
+    pub const MIN_ORDER_BITS: u32 = 224;
+
+    /// Fails const evaluation (and thus compilation) if the group order is too small.
+    /// Referenced from the methods used by all protocols, so that the check is performed
+    /// for any practical instantiation.
+    const ORDER_CHECK: () = assert!(
+        <Scalar<C> as PrimeField>::NUM_BITS >= Self::MIN_ORDER_BITS,
+        "group order of the curve is too small; at least 224 bits are required"
+    );
+}
+
 impl<C> ScalarOps for Generic<C>
 where
     C: CurveArithmetic,
@@ -41,6 +92,7 @@ where
     const SCALAR_SIZE: usize = <FieldBytesSize<C> as Unsigned>::USIZE;
 
     fn generate_scalar<R: CryptoRng + RngCore>(rng: &mut R) -> Self::Scalar {
+        let () = Self::ORDER_CHECK;
         Scalar::<C>::random(rng)
     }
 
@@ -56,11 +108,15 @@ where
         // For most curves, cloning will be resolved as a copy.
         Scalar::<C>::from_repr(GenericArray::from_slice(buffer).clone()).into()
     }
+
+    fn ct_eq_scalars(lhs: &Self::Scalar, rhs: &Self::Scalar) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl<C> ElementOps for Generic<C>
 where
-    C: CurveArithmetic,
+    C: CurveArithmetic + PrimeCurve,
     Scalar<C>: Zeroize,
     FieldBytesSize<C>: ModulusSize,
     ProjectivePoint<C>: ToEncodedPoint<C> + FromEncodedPoint<C>,
@@ -81,6 +137,7 @@ where
 
     #[inline]
     fn generator() -> Self::Element {
+        let () = Self::ORDER_CHECK;
         C::ProjectivePoint::generator()
     }
 
@@ -93,16 +150,45 @@ where
         let encoded_point = EncodedPoint::<C>::from_bytes(input).ok()?;
         ProjectivePoint::<C>::from_encoded_point(&encoded_point).into()
     }
+
+    fn ct_eq_elements(lhs: &Self::Element, rhs: &Self::Element) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl<C> Group for Generic<C>
 where
-    C: CurveArithmetic + 'static,
+    C: CurveArithmetic + PrimeCurve + 'static,
+    Scalar<C>: Zeroize,
+    FieldBytesSize<C>: ModulusSize,
+    ProjectivePoint<C>: ToEncodedPoint<C> + FromEncodedPoint<C>,
+{
+    /// Half of the bit length of the group order.
+    const SECURITY_BITS: usize = {
+        let () = Self::ORDER_CHECK;
+        <Scalar<C> as PrimeField>::NUM_BITS as usize / 2
+    };
+
+    // Other default implementations are fine.
+}
+
+/// No precomputation is performed; the "table" is the element itself.
+impl<C> PrecomputedMul for Generic<C>
+where
+    C: CurveArithmetic + PrimeCurve + 'static,
     Scalar<C>: Zeroize,
     FieldBytesSize<C>: ModulusSize,
     ProjectivePoint<C>: ToEncodedPoint<C> + FromEncodedPoint<C>,
 {
-    // Default implementations are fine.
+    type Table = Self::Element;
+
+    fn precompute(element: &Self::Element) -> Self::Table {
+        *element
+    }
+
+    fn mul_precomputed(table: &Self::Table, k: &Self::Scalar) -> Self::Element {
+        *table * k
+    }
 }
 
 #[cfg(test)]
@@ -113,6 +199,13 @@ mod tests {
 
     type K256 = Generic<k256::Secp256k1>;
 
+    /// Toy curve used in compile-fail doctests for [`Generic`], with a sufficiently large order.
+    mod toy_curve {
+        const TOY_NUM_BITS: u32 = 256;
+        include!("../../tests/fixtures/toy_curve.rs");
+        impl elliptic_curve::PrimeCurve for ToyCurve {}
+    }
+
     #[test]
     fn scalar_roundtrip() {
         let mut rng = thread_rng();
@@ -134,4 +227,54 @@ mod tests {
             assert_eq!(K256::deserialize_element(&buffer).unwrap(), point);
         }
     }
+
+    #[test]
+    fn constant_time_comparisons() {
+        let mut rng = thread_rng();
+        let scalar = K256::generate_scalar(&mut rng);
+        let other_scalar = K256::generate_scalar(&mut rng);
+        assert!(bool::from(K256::ct_eq_scalars(&scalar, &scalar)));
+        assert!(!bool::from(K256::ct_eq_scalars(&scalar, &other_scalar)));
+
+        let point = K256::mul_generator(&scalar);
+        let other_point = K256::mul_generator(&other_scalar);
+        assert!(bool::from(K256::ct_eq_elements(&point, &point)));
+        assert!(!bool::from(K256::ct_eq_elements(&point, &other_point)));
+        assert!(!bool::from(K256::ct_eq_elements(&point, &K256::identity())));
+    }
+
+    #[test]
+    fn security_level() {
+        let () = K256::ORDER_CHECK;
+        assert_eq!(K256::SECURITY_BITS, 128);
+        assert_eq!(K256::SECURITY_BITS, 4 * K256::SCALAR_SIZE);
+    }
+
+    #[test]
+    fn integer_conversions_with_big_endian_scalars() {
+        for value in [0, 1, 255, 256, u128::from(u64::MAX) + 1, u128::MAX] {
+            let scalar = K256::scalar_from_u128(value).unwrap();
+            assert_eq!(K256::scalar_to_u128(&scalar), Some(value));
+        }
+        for value in [0, -1, 42, i64::MIN, i64::MAX] {
+            let scalar = K256::scalar_from_i64(value).unwrap();
+            assert_eq!(K256::scalar_to_i64(&scalar), Some(value));
+        }
+        let scalar = K256::generate_scalar(&mut thread_rng());
+        assert_eq!(K256::scalar_to_u128(&scalar), None);
+    }
+
+    #[test]
+    fn toy_curve_is_valid_with_sufficient_order() {
+        use crate::{DiscreteLogTable, Keypair};
+
+        type ToyGroup = Generic<toy_curve::ToyCurve>;
+
+        let mut rng = thread_rng();
+        assert_eq!(ToyGroup::SECURITY_BITS, 128);
+        let keypair = Keypair::<ToyGroup>::generate(&mut rng);
+        let ciphertext = keypair.public().encrypt(5_u64, &mut rng);
+        let lookup_table = DiscreteLogTable::new(0..10);
+        assert_eq!(keypair.secret().decrypt(ciphertext, &lookup_table), Some(5));
+    }
 }
@@ -15,17 +15,29 @@
 use merlin::Transcript;
 use rand_chacha::ChaChaRng;
 use rand_core::{CryptoRng, RngCore, SeedableRng};
-use zeroize::Zeroize;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, Zeroizing};
 
 use core::{fmt, ops, str};
 
+use crate::alloc::{vec, Vec};
+
 #[cfg(any(feature = "curve25519-dalek", feature = "curve25519-dalek-ng"))]
 mod curve25519;
 mod generic;
 #[cfg(any(feature = "curve25519-dalek", feature = "curve25519-dalek-ng"))]
 mod ristretto;
+#[cfg(feature = "testkit")]
+mod test_group;
 
 pub use self::generic::Generic;
+#[cfg(feature = "testkit")]
+pub use self::test_group::{TestElement, TestGroup, TestScalar};
+
+/// Minimum number of scalars in [`Group::batch_mul()`] for which Curve25519-based groups
+/// precompute a multiplication table for the multiplied element.
+#[cfg(any(feature = "curve25519-dalek", feature = "curve25519-dalek-ng"))]
+const BATCH_TABLE_THRESHOLD: usize = 8;
 #[cfg(any(feature = "curve25519-dalek", feature = "curve25519-dalek-ng"))]
 pub use self::{curve25519::Curve25519Subgroup, ristretto::Ristretto};
 
@@ -123,6 +135,20 @@ pub trait ScalarOps {
     /// [`Self::SCALAR_SIZE`]. This method returns `None` if the buffer
     /// does not correspond to a representation of a valid scalar.
     fn deserialize_scalar(buffer: &[u8]) -> Option<Self::Scalar>;
+
+    /// Compares two scalars in constant time. Scalars derived from secrets should be compared
+    /// using this method rather than `==`.
+    ///
+    /// # Default implementation
+    ///
+    /// Serializes both scalars and compares the serialized bytes in constant time.
+    fn ct_eq_scalars(lhs: &Self::Scalar, rhs: &Self::Scalar) -> Choice {
+        let mut lhs_bytes = Zeroizing::new(vec![0_u8; Self::SCALAR_SIZE]);
+        Self::serialize_scalar(lhs, &mut lhs_bytes);
+        let mut rhs_bytes = Zeroizing::new(vec![0_u8; Self::SCALAR_SIZE]);
+        Self::serialize_scalar(rhs, &mut rhs_bytes);
+        lhs_bytes.ct_eq(&rhs_bytes)
+    }
 }
 
 /// Helper trait for [`Group`] that describes operations on group elements (i.e., EC points
@@ -158,6 +184,22 @@ pub trait ElementOps: ScalarOps {
     /// [`Self::ELEMENT_SIZE`]. This method returns `None` if the buffer
     /// does not correspond to a representation of a valid scalar.
     fn deserialize_element(buffer: &[u8]) -> Option<Self::Element>;
+
+    /// Compares two elements in constant time. Elements derived from secrets (e.g., decrypted
+    /// values) should be compared using this method rather than `==`.
+    ///
+    /// # Default implementation
+    ///
+    /// Serializes both elements and compares the serialized bytes in constant time.
+    /// Implementations should override this method if serialization is not constant-time
+    /// or does not support the identity element.
+    fn ct_eq_elements(lhs: &Self::Element, rhs: &Self::Element) -> Choice {
+        let mut lhs_bytes = vec![0_u8; Self::ELEMENT_SIZE];
+        Self::serialize_element(lhs, &mut lhs_bytes);
+        let mut rhs_bytes = vec![0_u8; Self::ELEMENT_SIZE];
+        Self::serialize_element(rhs, &mut rhs_bytes);
+        lhs_bytes.ct_eq(&rhs_bytes)
+    }
 }
 
 /// Prime-order group in which the discrete log problem and decisional / computational
@@ -174,11 +216,24 @@ pub trait ElementOps: ScalarOps {
 ///   of the [eponymous technique][ristretto].
 /// - [`Generic`] implementation defined in terms of traits from the [`elliptic-curve`] crate.
 ///   (For example, this means secp256k1 support via the [`k256`] crate.)
+/// - [`TestGroup`], a toy group with tiny order for exhaustive tests (only available
+///   with the `testkit` crate feature).
 ///
 /// [ristretto]: https://ristretto.group/
 /// [`elliptic-curve`]: https://docs.rs/elliptic-curve/
 /// [`k256`]: https://docs.rs/k256/
 pub trait Group: Copy + ScalarOps + ElementOps + 'static {
+    /// Approximate security level of the group in bits, i.e., the binary logarithm
+    /// of the number of group operations necessary to solve the discrete log problem.
+    /// This must not exceed half of the bit length of the group order (generic attacks
+    /// such as Pollard's rho need about the square root of the order operations).
+    ///
+    /// # Default implementation
+    ///
+    /// Returns half of the bit length of a serialized scalar, i.e., `4 * SCALAR_SIZE`.
+    /// This is accurate for groups with order close to `2^(8 * SCALAR_SIZE)`.
+    const SECURITY_BITS: usize = 4 * Self::SCALAR_SIZE;
+
     /// Multiplies the provided scalar by [`ElementOps::generator()`]. This operation must be
     /// constant-time.
     ///
@@ -202,6 +257,20 @@ pub trait Group: Copy + ScalarOps + ElementOps + 'static {
         Self::mul_generator(k)
     }
 
+    /// Multiplies `element` by each of the provided `scalars`, returning the products
+    /// in the same order. This operation must be constant-time.
+    ///
+    /// This is used in [batch encryption](crate::Ciphertext::new_batch()) to multiply
+    /// the receiver key by random scalars; the group implementation may precompute
+    /// a multiplication table for `element` shared among all scalars.
+    ///
+    /// # Default implementation
+    ///
+    /// Implemented using [`Mul`](ops::Mul) for every scalar.
+    fn batch_mul(element: Self::Element, scalars: &[Self::Scalar]) -> Vec<Self::Element> {
+        scalars.iter().map(|scalar| element * scalar).collect()
+    }
+
     /// Multiplies provided `scalars` by `elements`. This operation must be constant-time
     /// w.r.t. the given length of elements.
     ///
@@ -250,4 +319,139 @@ pub trait Group: Copy + ScalarOps + ElementOps + 'static {
     {
         Self::multi_mul(scalars, elements)
     }
+
+    /// Returns the maximum bit length of integers that are guaranteed to be less
+    /// than the group order, and thus can be converted to scalars without wrapping.
+    /// This is `2 * SECURITY_BITS - 1` (see the [`Self::SECURITY_BITS`] contract), so
+    /// the bound is conservative for groups with the order much greater
+    /// than `2^(2 * SECURITY_BITS)`.
+    fn max_integer_bits() -> u32 {
+        u32::try_from((2 * Self::SECURITY_BITS).saturating_sub(1)).unwrap_or(u32::MAX)
+    }
+
+    /// Converts an unsigned integer to a scalar. Returns `None` if the `value` exceeds
+    /// [`Self::max_integer_bits()`] and thus may wrap around the group order.
+    /// Unlike `Self::Scalar::from(value)`, this conversion is injective.
+    fn scalar_from_u64(value: u64) -> Option<Self::Scalar> {
+        Self::scalar_from_u128(value.into())
+    }
+
+    /// Converts an unsigned integer to a scalar; see [`Self::scalar_from_u64()`].
+    fn scalar_from_u128(value: u128) -> Option<Self::Scalar> {
+        if 128 - value.leading_zeros() > Self::max_integer_bits() {
+            return None;
+        }
+        // Truncation is intentional: we split the value into 64-bit limbs.
+        #[allow(clippy::cast_possible_truncation)]
+        let (low, high) = (value as u64, (value >> 64) as u64);
+        let mut scalar = Self::Scalar::from(low);
+        if high != 0 {
+            let limb_base = Self::Scalar::from(1_u64 << 32);
+            scalar = scalar + Self::Scalar::from(high) * limb_base * limb_base;
+        }
+        Some(scalar)
+    }
+
+    /// Converts a signed integer to a scalar. Negative values are mapped to negated scalars,
+    /// i.e., `-5` is mapped to `-Self::Scalar::from(5)`. Returns `None` if the bit length
+    /// of the absolute value is not less than [`Self::max_integer_bits()`]; this ensures that
+    /// positive and negative values are mapped to distinct scalars.
+    fn scalar_from_i64(value: i64) -> Option<Self::Scalar> {
+        let abs_value = value.unsigned_abs();
+        if 64 - abs_value.leading_zeros() >= Self::max_integer_bits() {
+            return None;
+        }
+        let abs_scalar = Self::Scalar::from(abs_value);
+        Some(if value < 0 { -abs_scalar } else { abs_scalar })
+    }
+
+    /// Converts a scalar to an unsigned integer. Returns `None` if the scalar does not
+    /// correspond to an integer fitting into `u64` and [`Self::max_integer_bits()`].
+    /// This is the inverse of [`Self::scalar_from_u64()`].
+    fn scalar_to_u64(scalar: &Self::Scalar) -> Option<u64> {
+        Self::scalar_to_u128(scalar).and_then(|value| u64::try_from(value).ok())
+    }
+
+    /// Converts a scalar to an unsigned integer. This is the inverse
+    /// of [`Self::scalar_from_u128()`].
+    ///
+    /// # Default implementation
+    ///
+    /// Assumes that scalars are serialized as little-endian or big-endian integers
+    /// (the byte order is determined by serializing scalar 1). The conversion result
+    /// is always checked by converting it back to a scalar, so if the assumption does not hold,
+    /// the method returns `None` rather than an incorrect value.
+    fn scalar_to_u128(scalar: &Self::Scalar) -> Option<u128> {
+        let mut bytes = Zeroizing::new(vec![0_u8; Self::SCALAR_SIZE]);
+        Self::serialize_scalar(&Self::Scalar::from(1_u64), &mut bytes);
+        let is_big_endian = match (bytes.first(), bytes.last()) {
+            (Some(1), _) => false,
+            (_, Some(1)) => true,
+            _ => return None,
+        };
+
+        Self::serialize_scalar(scalar, &mut bytes);
+        if is_big_endian {
+            bytes.reverse();
+        }
+        let limb_len = bytes.len().min(16);
+        if bytes[limb_len..].iter().any(|&byte| byte != 0) {
+            return None;
+        }
+        let mut value_bytes = [0_u8; 16];
+        value_bytes[..limb_len].copy_from_slice(&bytes[..limb_len]);
+        let value = u128::from_le_bytes(value_bytes);
+
+        let restored = Self::scalar_from_u128(value)?;
+        bool::from(Self::ct_eq_scalars(&restored, scalar)).then_some(value)
+    }
+
+    /// Converts a scalar to a signed integer. This is the inverse of [`Self::scalar_from_i64()`].
+    fn scalar_to_i64(scalar: &Self::Scalar) -> Option<i64> {
+        let (abs_value, is_negative) = match Self::scalar_to_u64(scalar) {
+            Some(value) => (value, false),
+            None => (Self::scalar_to_u64(&-*scalar)?, true),
+        };
+        if 64 - abs_value.leading_zeros() >= Self::max_integer_bits() {
+            return None;
+        }
+        if !is_negative {
+            return i64::try_from(abs_value).ok();
+        }
+        match i64::try_from(abs_value) {
+            Ok(abs_value) => Some(-abs_value),
+            Err(_) => (abs_value == i64::MIN.unsigned_abs()).then_some(i64::MIN),
+        }
+    }
+
+    /// Encodes an unsigned integer as a group element `[value]G`, where `G` is
+    /// the [group generator](ElementOps::generator()). Returns `None` in the same cases
+    /// as [`Self::scalar_from_u64()`], so that the encoding is injective.
+    /// The multiplication is constant-time.
+    fn element_from_u64(value: u64) -> Option<Self::Element> {
+        Self::scalar_from_u64(value).map(|scalar| Self::mul_generator(&scalar))
+    }
+
+    /// Encodes a signed integer as a group element `[value]G`; see [`Self::element_from_u64()`]
+    /// and [`Self::scalar_from_i64()`].
+    fn element_from_i64(value: i64) -> Option<Self::Element> {
+        Self::scalar_from_i64(value).map(|scalar| Self::mul_generator(&scalar))
+    }
+}
+
+/// [`Group`] supporting precomputed tables for multiplying a fixed element by scalars.
+///
+/// Precomputation is useful when the same element (e.g., a receiver key) is multiplied
+/// by many scalars over the application lifetime; see
+/// [`PrecomputedBases`](crate::PrecomputedBases).
+pub trait PrecomputedMul: Group {
+    /// Table of precomputed multiples of an element.
+    type Table: Clone;
+
+    /// Precomputes a multiplication table for `element`.
+    fn precompute(element: &Self::Element) -> Self::Table;
+
+    /// Multiplies the element the `table` was precomputed for by `k`. This operation
+    /// must be constant-time.
+    fn mul_precomputed(table: &Self::Table, k: &Self::Scalar) -> Self::Element;
 }
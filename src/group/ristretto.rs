@@ -1,14 +1,21 @@
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "curve25519-dalek")]
+use subtle::{Choice, ConstantTimeEq};
 
 use core::convert::TryInto;
 
 use crate::curve25519::{
     constants::{RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE},
-    ristretto::{CompressedRistretto, RistrettoPoint},
+    ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint},
     scalar::Scalar,
     traits::{Identity, IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
 };
-use crate::group::{ElementOps, Group, RandomBytesProvider, ScalarOps};
+use crate::{
+    alloc::Vec,
+    group::{
+        ElementOps, Group, PrecomputedMul, RandomBytesProvider, ScalarOps, BATCH_TABLE_THRESHOLD,
+    },
+};
 
 /// [Ristretto](https://ristretto.group/) transform of Curve25519, also known as ristretto255.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -58,6 +65,13 @@ impl ScalarOps for Ristretto {
         let bytes: &[u8; 32] = buffer.try_into().expect("input has incorrect byte size");
         Scalar::from_canonical_bytes(*bytes)
     }
+
+    // `curve25519-dalek-ng` implements `ConstantTimeEq` from a fork of `subtle`, so we use
+    // the default implementation for it.
+    #[cfg(feature = "curve25519-dalek")]
+    fn ct_eq_scalars(lhs: &Self::Scalar, rhs: &Self::Scalar) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl ElementOps for Ristretto {
@@ -90,9 +104,17 @@ impl ElementOps for Ristretto {
     fn deserialize_element(buffer: &[u8]) -> Option<Self::Element> {
         CompressedRistretto::from_slice(buffer).decompress()
     }
+
+    #[cfg(feature = "curve25519-dalek")]
+    fn ct_eq_elements(lhs: &Self::Element, rhs: &Self::Element) -> Choice {
+        lhs.ct_eq(rhs)
+    }
 }
 
 impl Group for Ristretto {
+    // The group order is approximately `2^252`.
+    const SECURITY_BITS: usize = 126;
+
     #[cfg(feature = "curve25519-dalek")]
     fn mul_generator(k: &Scalar) -> Self::Element {
         k * RISTRETTO_BASEPOINT_TABLE
@@ -112,6 +134,14 @@ impl Group for Ristretto {
         RistrettoPoint::vartime_double_scalar_mul_basepoint(&zero, &RistrettoPoint::identity(), k)
     }
 
+    fn batch_mul(element: Self::Element, scalars: &[Scalar]) -> Vec<Self::Element> {
+        if scalars.len() < BATCH_TABLE_THRESHOLD {
+            return scalars.iter().map(|scalar| element * scalar).collect();
+        }
+        let table = RistrettoBasepointTable::create(&element);
+        scalars.iter().map(|scalar| scalar * &table).collect()
+    }
+
     fn multi_mul<'a, I, J>(scalars: I, elements: J) -> Self::Element
     where
         I: IntoIterator<Item = &'a Self::Scalar>,
@@ -137,6 +167,18 @@ impl Group for Ristretto {
     }
 }
 
+impl PrecomputedMul for Ristretto {
+    type Table = RistrettoBasepointTable;
+
+    fn precompute(element: &Self::Element) -> Self::Table {
+        RistrettoBasepointTable::create(element)
+    }
+
+    fn mul_precomputed(table: &Self::Table, k: &Scalar) -> Self::Element {
+        k * table
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -189,4 +231,51 @@ mod tests {
             assert_ne!(keypair.public().as_bytes(), ed_keypair.public().as_bytes());
         }
     }
+
+    fn test_constant_time_comparisons<G: Group>() {
+        let mut rng = thread_rng();
+        let scalar = G::generate_scalar(&mut rng);
+        let other_scalar = G::generate_scalar(&mut rng);
+        assert!(bool::from(G::ct_eq_scalars(&scalar, &scalar)));
+        assert!(!bool::from(G::ct_eq_scalars(&scalar, &other_scalar)));
+
+        let point = G::mul_generator(&scalar);
+        // Same point with a different internal representation.
+        let same_point = point + point - point;
+        assert!(bool::from(G::ct_eq_elements(&point, &same_point)));
+        assert!(!bool::from(G::ct_eq_elements(
+            &point,
+            &G::mul_generator(&other_scalar)
+        )));
+        assert!(!bool::from(G::ct_eq_elements(&point, &G::identity())));
+        assert!(bool::from(G::ct_eq_elements(
+            &G::identity(),
+            &G::identity()
+        )));
+    }
+
+    #[test]
+    fn constant_time_comparisons() {
+        test_constant_time_comparisons::<Ristretto>();
+        test_constant_time_comparisons::<Curve25519Subgroup>();
+    }
+
+    #[test]
+    fn integer_conversions() {
+        assert_eq!(Ristretto::max_integer_bits(), 251);
+        for value in [0, 1, 255, 256, u128::from(u64::MAX) + 1, u128::MAX] {
+            let scalar = Ristretto::scalar_from_u128(value).unwrap();
+            assert_eq!(Ristretto::scalar_to_u128(&scalar), Some(value));
+        }
+        assert_eq!(Ristretto::scalar_from_u64(5), Some(Scalar::from(5_u64)),);
+        assert_eq!(Ristretto::scalar_from_i64(-5), Some(-Scalar::from(5_u64)));
+        for value in [0, -1, 42, i64::MIN, i64::MAX] {
+            let scalar = Ristretto::scalar_from_i64(value).unwrap();
+            assert_eq!(Ristretto::scalar_to_i64(&scalar), Some(value));
+        }
+        let big_value = Scalar::from(u64::MAX) + Scalar::from(1_u64);
+        assert_eq!(Ristretto::scalar_to_u64(&big_value), None);
+        assert_eq!(Ristretto::scalar_to_i64(&big_value), None);
+        assert_eq!(Ristretto::scalar_to_i64(&-big_value), None);
+    }
 }
@@ -0,0 +1,397 @@
+//! Toy group for exhaustive tests.
+
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+use core::ops;
+
+use crate::group::{ElementOps, Group, ScalarOps};
+
+/// Safe prime `p = 2q + 1` defining the field.
+const MODULUS: u64 = 2_039;
+/// Group order `q`.
+const ORDER: u64 = 1_019;
+
+const fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Toy prime-order group: the subgroup of quadratic residues in the multiplicative group
+/// of integers modulo the safe prime `p = 2039`. The group has order [`Self::ORDER`]` = 1019`
+/// and is generated by `4`.
+///
+/// The discrete log problem in this group is trivially solvable, so the group
+/// **must not** be used for anything but tests and experiments. Thanks to the tiny order,
+/// properties can be checked exhaustively over all scalars or elements, and soundness
+/// of proofs can be measured directly (a single challenge can be guessed with probability
+/// `1/1019`; see [`RepeatedLogEqualityProof`] for a way to amplify soundness).
+/// Repeated challenges are only supported for log equality proofs; other proofs,
+/// such as [`ProofOfPossession`] and [`RingProof`], retain the soundness error `1/1019`
+/// over this group, so they can only be used to check completeness.
+///
+/// Operations are implemented with plain integer arithmetic and are **not** constant-time.
+/// Scalars and elements are serialized as 2-byte little-endian integers.
+///
+/// [`RepeatedLogEqualityProof`]: crate::RepeatedLogEqualityProof
+/// [`ProofOfPossession`]: crate::ProofOfPossession
+/// [`RingProof`]: crate::RingProof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "testkit")))]
+pub struct TestGroup(());
+
+impl TestGroup {
+    /// Order of the group.
+    pub const ORDER: u64 = ORDER;
+}
+
+/// Scalar of the [`TestGroup`], i.e., an integer modulo [`TestGroup::ORDER`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "testkit")))]
+pub struct TestScalar(u64);
+
+impl TestScalar {
+    /// Returns the canonical integer representation of this scalar.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for TestScalar {
+    fn from(value: u64) -> Self {
+        Self(value % ORDER)
+    }
+}
+
+impl Zeroize for TestScalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ops::Neg for TestScalar {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self((ORDER - self.0) % ORDER)
+    }
+}
+
+impl ops::Add for TestScalar {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self((self.0 + rhs.0) % ORDER)
+    }
+}
+
+impl ops::Add<&Self> for TestScalar {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self {
+        self + *rhs
+    }
+}
+
+impl ops::Sub for TestScalar {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl ops::Mul for TestScalar {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0 % ORDER)
+    }
+}
+
+impl ops::Mul<&Self> for TestScalar {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self {
+        self * *rhs
+    }
+}
+
+/// Element of the [`TestGroup`], i.e., a quadratic residue modulo `2039`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "testkit")))]
+pub struct TestElement(u64);
+
+impl TestElement {
+    /// Returns the canonical integer representation of this element.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl ops::Add for TestElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0 % MODULUS)
+    }
+}
+
+impl ops::Neg for TestElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(pow_mod(self.0, MODULUS - 2, MODULUS))
+    }
+}
+
+impl ops::Sub for TestElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl ops::Mul<&TestScalar> for TestElement {
+    type Output = Self;
+
+    fn mul(self, rhs: &TestScalar) -> Self {
+        Self(pow_mod(self.0, rhs.0, MODULUS))
+    }
+}
+
+impl ScalarOps for TestGroup {
+    type Scalar = TestScalar;
+
+    const SCALAR_SIZE: usize = 2;
+
+    fn generate_scalar<R: CryptoRng + RngCore>(rng: &mut R) -> Self::Scalar {
+        // Use rejection sampling to get a uniform scalar.
+        loop {
+            let candidate = u64::from(rng.next_u32() & 0x03ff);
+            if candidate < ORDER {
+                return TestScalar(candidate);
+            }
+        }
+    }
+
+    fn invert_scalar(scalar: Self::Scalar) -> Self::Scalar {
+        TestScalar(pow_mod(scalar.0, ORDER - 2, ORDER))
+    }
+
+    fn serialize_scalar(scalar: &Self::Scalar, buffer: &mut [u8]) {
+        let value = u16::try_from(scalar.0).expect("scalar is reduced");
+        buffer.copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn deserialize_scalar(buffer: &[u8]) -> Option<Self::Scalar> {
+        let bytes: [u8; 2] = buffer.try_into().expect("input has incorrect byte size");
+        let value = u64::from(u16::from_le_bytes(bytes));
+        (value < ORDER).then_some(TestScalar(value))
+    }
+}
+
+impl ElementOps for TestGroup {
+    type Element = TestElement;
+
+    const ELEMENT_SIZE: usize = 2;
+
+    fn identity() -> Self::Element {
+        TestElement(1)
+    }
+
+    fn is_identity(element: &Self::Element) -> bool {
+        element.0 == 1
+    }
+
+    fn generator() -> Self::Element {
+        TestElement(4)
+    }
+
+    fn serialize_element(element: &Self::Element, buffer: &mut [u8]) {
+        let value = u16::try_from(element.0).expect("element is reduced");
+        buffer.copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn deserialize_element(buffer: &[u8]) -> Option<Self::Element> {
+        let bytes: [u8; 2] = buffer.try_into().expect("input has incorrect byte size");
+        let value = u64::from(u16::from_le_bytes(bytes));
+        // Quadratic residues are exactly the elements with `x^q = 1`.
+        let is_valid = value > 0 && value < MODULUS && pow_mod(value, ORDER, MODULUS) == 1;
+        is_valid.then_some(TestElement(value))
+    }
+}
+
+impl Group for TestGroup {
+    // The group order is approximately `2^10`.
+    const SECURITY_BITS: usize = 5;
+}
+
+#[cfg(test)]
+mod tests {
+    use merlin::Transcript;
+    use rand::thread_rng;
+    use rand_chacha::ChaChaRng;
+    use rand_core::SeedableRng;
+
+    use super::*;
+    use crate::{alloc::Vec, DiscreteLogTable, Keypair, RepeatedLogEqualityProof};
+
+    fn all_scalars() -> impl Iterator<Item = TestScalar> {
+        (0..ORDER).map(TestScalar)
+    }
+
+    fn all_elements() -> Vec<TestElement> {
+        let generator = TestGroup::generator();
+        all_scalars().map(|k| generator * &k).collect()
+    }
+
+    #[test]
+    fn scalar_field_laws() {
+        for k in all_scalars() {
+            assert_eq!(k + (-k), TestScalar::default());
+            if k != TestScalar::default() {
+                assert_eq!(k * TestGroup::invert_scalar(k), TestScalar::from(1));
+            }
+
+            let mut bytes = [0_u8; 2];
+            TestGroup::serialize_scalar(&k, &mut bytes);
+            assert_eq!(TestGroup::deserialize_scalar(&bytes), Some(k));
+        }
+        for value in ORDER..=u64::from(u16::MAX) {
+            let bytes = u16::try_from(value).unwrap().to_le_bytes();
+            assert_eq!(TestGroup::deserialize_scalar(&bytes), None);
+        }
+    }
+
+    #[test]
+    fn generator_spans_exactly_quadratic_residues() {
+        let mut elements = all_elements();
+        assert!(TestGroup::is_identity(&elements[0]));
+        elements.sort_unstable_by_key(|element| element.0);
+        elements.dedup();
+        assert_eq!(elements.len() as u64, ORDER);
+
+        let valid_encodings = (0..=u16::MAX)
+            .filter(|&value| TestGroup::deserialize_element(&value.to_le_bytes()).is_some())
+            .count();
+        assert_eq!(valid_encodings as u64, ORDER);
+
+        for &element in &elements {
+            assert!(TestGroup::is_identity(&(element - element)));
+            assert!(TestGroup::is_identity(
+                &(element * &TestScalar::from(ORDER - 1) - (-element))
+            ));
+            let mut bytes = [0_u8; 2];
+            TestGroup::serialize_element(&element, &mut bytes);
+            assert_eq!(TestGroup::deserialize_element(&bytes), Some(element));
+        }
+    }
+
+    #[test]
+    fn exhaustive_encryption_roundtrip() {
+        let mut rng = thread_rng();
+        let keypair = Keypair::<TestGroup>::generate(&mut rng);
+        let lookup_table = DiscreteLogTable::<TestGroup>::new(0..ORDER);
+        for value in 0..ORDER {
+            let ciphertext = keypair.public().encrypt(value, &mut rng);
+            assert_eq!(
+                keypair.secret().decrypt(ciphertext, &lookup_table),
+                Some(value)
+            );
+        }
+    }
+
+    /// Attempts to forge a proof for unequal discrete logs by guessing all challenges
+    /// in advance.
+    fn forge_proof(repetitions: usize, rng: &mut ChaChaRng) -> bool {
+        let log_base = Keypair::<TestGroup>::generate(rng).public().clone();
+        let generator = TestGroup::generator();
+        let powers = (generator, generator * &TestScalar::from(2));
+
+        let random_scalars = |rng: &mut _| -> Vec<_> {
+            (0..repetitions)
+                .map(|_| TestGroup::generate_scalar(rng))
+                .collect()
+        };
+        let mut bytes = Vec::new();
+        for (challenge, response) in random_scalars(rng).into_iter().zip(random_scalars(rng)) {
+            bytes.extend_from_slice(&u16::try_from(challenge.0).unwrap().to_le_bytes());
+            bytes.extend_from_slice(&u16::try_from(response.0).unwrap().to_le_bytes());
+        }
+        let proof = RepeatedLogEqualityProof::<TestGroup>::from_bytes(&bytes).unwrap();
+        proof
+            .verify(
+                &log_base,
+                powers,
+                repetitions,
+                &mut Transcript::new(b"forgery"),
+            )
+            .is_ok()
+    }
+
+    #[test]
+    fn repetitions_amplify_proof_soundness() {
+        const ATTEMPTS: usize = 10_000;
+
+        assert_eq!(
+            RepeatedLogEqualityProof::<TestGroup>::repetitions_for(10),
+            1
+        );
+        assert_eq!(
+            RepeatedLogEqualityProof::<TestGroup>::repetitions_for(30),
+            3
+        );
+
+        // The RNG is seeded so that the test is deterministic.
+        let mut rng = ChaChaRng::seed_from_u64(739);
+        // A single challenge is guessed with probability `1/1019`, so we expect ~10 successes.
+        let forged_count = (0..ATTEMPTS).filter(|_| forge_proof(1, &mut rng)).count();
+        assert!(forged_count > 0, "{forged_count}");
+        // With 3 repetitions, the success probability is ~`1e-9` per attempt.
+        let forged_count = (0..ATTEMPTS).filter(|_| forge_proof(3, &mut rng)).count();
+        assert_eq!(forged_count, 0);
+    }
+
+    #[test]
+    fn integer_conversions_respect_group_order() {
+        let max_value = (1_u64 << TestGroup::max_integer_bits()) - 1;
+        assert!(max_value < ORDER);
+        for value in 0..=max_value {
+            let scalar = TestGroup::scalar_from_u64(value).unwrap();
+            assert_eq!(TestGroup::scalar_to_u64(&scalar), Some(value));
+        }
+        assert_eq!(TestGroup::scalar_from_u64(max_value + 1), None);
+
+        // Signed values have 1 bit less to ensure that conversions are injective.
+        let max_signed_value = i64::try_from(max_value / 2).unwrap();
+        for value in -max_signed_value..=max_signed_value {
+            let scalar = TestGroup::scalar_from_i64(value).unwrap();
+            assert_eq!(TestGroup::scalar_to_i64(&scalar), Some(value));
+        }
+        assert_eq!(TestGroup::scalar_from_i64(max_signed_value + 1), None);
+        assert_eq!(TestGroup::scalar_from_i64(-max_signed_value - 1), None);
+        let scalar = TestScalar::from(u64::try_from(max_signed_value).unwrap() + 1);
+        assert_eq!(TestGroup::scalar_to_i64(&scalar), None);
+        assert_eq!(TestGroup::scalar_from_u128(u128::from(ORDER)), None);
+        assert_eq!(TestGroup::scalar_to_u64(&TestScalar(max_value + 1)), None);
+
+        let generator = TestGroup::generator();
+        assert_eq!(
+            TestGroup::element_from_u64(3),
+            Some(generator + generator + generator)
+        );
+        assert_eq!(TestGroup::element_from_i64(-1), Some(-generator));
+        assert_eq!(TestGroup::element_from_u64(max_value + 1), None);
+        assert_eq!(TestGroup::element_from_i64(max_signed_value + 1), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Stable hashes of public artifacts.
+
+use sha2::{Digest, Sha256};
+
+use core::fmt;
+
+use crate::{
+    app::{EncryptedChoice, ProveSum},
+    group::Group,
+    proto_labels,
+    sharing::PublicKeySet,
+    CandidateDecryption, Ciphertext, VerifiableDecryption,
+};
+
+/// Stable, domain-separated hash of a public artifact, such as a [`Ciphertext`],
+/// an [`EncryptedChoice`], a [`PublicKeySet`] or a decryption share.
+///
+/// Hashes are computed via `hash()` methods of the corresponding types. A hash is
+/// a SHA-256 digest over the [version](Self::VERSION) of the hashing scheme, the artifact type
+/// and the canonical byte representation of the artifact. Thus, hashes of artifacts
+/// of different types never coincide, and hashes remain stable across crate releases
+/// as long as the version does not change. Hashes can be used as database keys
+/// or cross-system references for artifacts.
+///
+/// Hashes do not depend on the group used by the artifact, so artifacts should only be
+/// compared within a single group.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Keypair};
+/// # use rand::thread_rng;
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = receiver.public().encrypt(5_u64, &mut rng);
+/// let hash = ciphertext.hash();
+/// assert_eq!(hash, ciphertext.hash());
+/// assert_ne!(hash, receiver.public().encrypt(5_u64, &mut rng).hash());
+/// println!("{hash}"); // hex-encoded hash
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectHash([u8; 32]);
+
+impl fmt::Debug for ObjectHash {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "ObjectHash({self})")
+    }
+}
+
+impl fmt::Display for ObjectHash {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(formatter, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ObjectHash {
+    /// Version of the hashing scheme. The version is incremented whenever hashes
+    /// of existing artifacts change.
+    pub const VERSION: u32 = 1;
+
+    /// Restores a hash from its bytes, e.g., loaded from a database.
+    pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns bytes of this hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Hasher producing [`ObjectHash`]es. All parts of the artifact are length-prefixed.
+pub(crate) struct ObjectHasher(Sha256);
+
+impl ObjectHasher {
+    pub(crate) fn new(object_type: &'static str) -> Self {
+        let mut hasher = Self(
+            Sha256::new()
+                .chain_update(proto_labels::OBJECT_HASH)
+                .chain_update(ObjectHash::VERSION.to_le_bytes()),
+        );
+        hasher.update(object_type.as_bytes());
+        hasher
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        self.0.update((bytes.len() as u64).to_le_bytes());
+        self.0.update(bytes);
+    }
+
+    pub(crate) fn update_u64(&mut self, value: u64) {
+        self.0.update(value.to_le_bytes());
+    }
+
+    pub(crate) fn finalize(self) -> ObjectHash {
+        ObjectHash(self.0.finalize().into())
+    }
+}
+
+impl<G: Group> Ciphertext<G> {
+    /// Computes the [`ObjectHash`] of this ciphertext.
+    pub fn hash(&self) -> ObjectHash {
+        let mut hasher = ObjectHasher::new("ciphertext");
+        hasher.update(&self.to_bytes());
+        hasher.finalize()
+    }
+}
+
+impl<G: Group> VerifiableDecryption<G> {
+    /// Computes the [`ObjectHash`] of this decryption share. The hash is the same as
+    /// for the corresponding [`CandidateDecryption`].
+    pub fn hash(&self) -> ObjectHash {
+        let mut hasher = ObjectHasher::new("decryption_share");
+        hasher.update(&self.to_bytes());
+        hasher.finalize()
+    }
+}
+
+impl<G: Group> CandidateDecryption<G> {
+    /// Computes the [`ObjectHash`] of this decryption share. The hash is the same as
+    /// for the corresponding [`VerifiableDecryption`].
+    pub fn hash(&self) -> ObjectHash {
+        self.into_unchecked().hash()
+    }
+}
+
+impl<G: Group, S: ProveSum<G>> EncryptedChoice<G, S> {
+    /// Computes the [`ObjectHash`] of this choice, covering ciphertexts for all options
+    /// and the proofs.
+    pub fn hash(&self) -> ObjectHash {
+        let mut hasher = ObjectHasher::new("encrypted_choice");
+        hasher.update_u64(self.len() as u64);
+        for ciphertext in self.choices_unchecked() {
+            hasher.update(&ciphertext.to_bytes());
+        }
+        hasher.update(&self.range_proof().to_bytes());
+        hasher.update(&S::proof_bytes(self.sum_proof()));
+        hasher.finalize()
+    }
+}
+
+impl<G: Group> PublicKeySet<G> {
+    /// Computes the [`ObjectHash`] of this key set, covering its params, epoch, shared key,
+    /// participant keys and identity keys (if any).
+    pub fn hash(&self) -> ObjectHash {
+        let mut hasher = ObjectHasher::new("public_key_set");
+        let params = self.params();
+        hasher.update_u64(params.shares as u64);
+        hasher.update_u64(params.threshold as u64);
+        hasher.update_u64(self.epoch().value());
+        hasher.update(self.shared_key().as_bytes());
+        for key in self.participant_keys() {
+            hasher.update(key.as_bytes());
+        }
+        hasher.update_u64(self.indexed_identity_keys().len() as u64);
+        for (_, key) in self.indexed_identity_keys() {
+            hasher.update(key.as_bytes());
+        }
+        hasher.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        app::ChoiceParams,
+        group::Ristretto,
+        sharing::{Dealer, Params},
+        Keypair,
+    };
+
+    #[test]
+    fn object_hashes_are_stable_and_domain_separated() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let ciphertext = receiver.public().encrypt(1_u64, &mut rng);
+        let restored = Ciphertext::<Ristretto>::from_bytes(&ciphertext.to_bytes()).unwrap();
+        assert_eq!(restored.hash(), ciphertext.hash());
+        assert_eq!(
+            ObjectHash::from_bytes(*ciphertext.hash().as_bytes()),
+            ciphertext.hash()
+        );
+
+        // A decryption share with the same bytes as a ciphertext half has a different hash.
+        let share = VerifiableDecryption::<Ristretto>::from_element(ciphertext.random_element);
+        let share_hash = share.hash();
+        assert_eq!(CandidateDecryption::from(share).hash(), share_hash);
+        let mut hasher = ObjectHasher::new("ciphertext");
+        hasher.update(&share.to_bytes());
+        assert_ne!(hasher.finalize(), share_hash);
+
+        let params = ChoiceParams::single(receiver.public().clone(), 3);
+        let choice = EncryptedChoice::single(&params, 0, &mut rng);
+        let (ciphertexts, proofs) = choice.clone().into_parts();
+        let restored = EncryptedChoice::from_parts(&params, ciphertexts, proofs).unwrap();
+        assert_eq!(restored.hash(), choice.hash());
+        let other_choice = EncryptedChoice::single(&params, 0, &mut rng);
+        assert_ne!(other_choice.hash(), choice.hash());
+
+        let key_params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(key_params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(key_params, public_poly, poly_proof).unwrap();
+        let next_key_set = key_set.clone().with_epoch(key_set.epoch().next());
+        assert_eq!(key_set.clone().hash(), key_set.hash());
+        assert_ne!(next_key_set.hash(), key_set.hash());
+    }
+}
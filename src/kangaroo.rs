@@ -0,0 +1,336 @@
+//! Pollard's kangaroo (lambda) method for discrete logarithms in an interval.
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use sha2::{Digest, Sha256};
+
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use crate::serde::{ElementHelper, VecHelper};
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    math::isqrt,
+    proto_labels, DiscreteLogSolver,
+};
+
+/// Max number of jumps in [`KangarooJumps`]; larger jumps do not make sense for `u64` values.
+/// This also ensures that jump distances `2^i` fit into `u128`.
+const MAX_JUMP_COUNT: usize = 64;
+/// Number of wild kangaroo runs (with different starting points) before giving up.
+const ATTEMPTS: u64 = 4;
+
+/// Precomputed jumps for [`KangarooDecrypter`]. Jumps are group elements `[2^i]G`
+/// for `i` in `0..n`; the jump taken from a certain element is determined by hashing
+/// this element.
+///
+/// Jump tables do not depend on the range of values being decrypted, only on its width,
+/// and can be shared among decrypters (e.g., via serialization).
+/// Since results of [`KangarooDecrypter`] are always checked before being returned,
+/// a malformed jump table (e.g., one deserialized from an untrusted source) cannot lead
+/// to incorrect decryption; it can only make decryption fail.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct KangarooJumps<G: Group> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "VecHelper::<ElementHelper<G>, 1>::serialize",
+            deserialize_with = "deserialize_jumps::<_, G>"
+        )
+    )]
+    elements: Vec<G::Element>,
+}
+
+/// Deserializes jump elements, checking that there are not too many of them.
+#[cfg(feature = "serde")]
+fn deserialize_jumps<'de, D, G>(deserializer: D) -> Result<Vec<G::Element>, D::Error>
+where
+    D: Deserializer<'de>,
+    G: Group,
+{
+    let elements = VecHelper::<ElementHelper<G>, 1>::deserialize(deserializer)?;
+    if elements.len() > MAX_JUMP_COUNT {
+        Err(D::Error::invalid_length(
+            elements.len(),
+            &"at most 64 jump elements",
+        ))
+    } else {
+        Ok(elements)
+    }
+}
+
+impl<G: Group> KangarooJumps<G> {
+    /// Creates a table with `count` jumps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero or exceeds 64.
+    pub fn new(count: usize) -> Self {
+        assert!(
+            (1..=MAX_JUMP_COUNT).contains(&count),
+            "jump count must be in 1..={MAX_JUMP_COUNT}"
+        );
+        let mut element = G::generator();
+        let mut elements = Vec::with_capacity(count);
+        for _ in 0..count {
+            elements.push(element);
+            element = element + element;
+        }
+        Self { elements }
+    }
+
+    /// Creates a table optimal for ranges with the specified `width`, i.e., one with
+    /// the mean jump close to `sqrt(width) / 2`.
+    pub fn for_width(width: u64) -> Self {
+        let target_mean = u128::from((isqrt(width) / 2).max(1));
+        let count = (1..=MAX_JUMP_COUNT)
+            .find(|&count| Self::mean_jump_for(count) >= target_mean)
+            .unwrap_or(MAX_JUMP_COUNT);
+        Self::new(count)
+    }
+
+    fn mean_jump_for(count: usize) -> u128 {
+        ((1_u128 << count) - 1) / count as u128
+    }
+
+    /// Returns the number of jumps in this table.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Checks whether this table is empty. This is never the case for tables created
+    /// via constructors.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn mean_jump(&self) -> u128 {
+        Self::mean_jump_for(self.elements.len()).max(1)
+    }
+
+    /// Selects a jump for the `element`, returning its distance and the jump element.
+    fn select(&self, element: &G::Element, buffer: &mut [u8]) -> (u128, G::Element) {
+        G::serialize_element(element, buffer);
+        let digest = Sha256::new()
+            .chain_update(proto_labels::KANGAROO_JUMP)
+            .chain_update(&*buffer)
+            .finalize();
+        // The slight bias of the index distribution is irrelevant for the kangaroo method.
+        let index = usize::from(digest[0]) % self.elements.len();
+        (1_u128 << index, self.elements[index])
+    }
+}
+
+/// Decrypter of values from a known range based on [Pollard's kangaroo method][kangaroo].
+///
+/// Unlike [`DiscreteLogTable`](crate::DiscreteLogTable), the decrypter does not store
+/// any per-value data; decryption takes `O(sqrt(w))` group operations, where `w` is
+/// the range width. Thus, it is suited for one-off decryptions of values from large ranges
+/// (e.g., `2^40` values), for which building a lookup table is wasteful.
+///
+/// Decryption is probabilistic: with a negligible probability (heuristically, less than `2^-40`),
+/// a value from the range may not be found. Values returned by
+/// [`Self::get()`] are always correct. Values outside the range are not found, and checking
+/// them takes several times longer than decrypting in-range values.
+///
+/// [kangaroo]: https://en.wikipedia.org/wiki/Pollard%27s_kangaroo_algorithm
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, KangarooDecrypter, KangarooJumps, Keypair};
+/// # use rand::thread_rng;
+/// let range = 1_000_000..1_010_000;
+/// let jumps = KangarooJumps::<Ristretto>::for_width(range.end - range.start);
+/// let decrypter = KangarooDecrypter::new(range, jumps);
+///
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = receiver.public().encrypt(1_005_000_u64, &mut rng);
+/// let element = receiver.secret().decrypt_to_element(ciphertext);
+/// assert_eq!(decrypter.get(&element), Some(1_005_000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KangarooDecrypter<G: Group> {
+    range: Range<u64>,
+    jumps: KangarooJumps<G>,
+    trap: G::Element,
+    /// Distance from `range.end` to `trap`.
+    trap_distance: u128,
+}
+
+impl<G: Group> DiscreteLogSolver<G> for KangarooDecrypter<G> {
+    fn solve(&self, decrypted_element: &G::Element) -> Option<u64> {
+        self.get(decrypted_element)
+    }
+}
+
+impl<G: Group> KangarooDecrypter<G> {
+    /// Creates a decrypter for the specified `range` of values using the provided jumps.
+    /// This runs a "tame" kangaroo, which takes approximately the same time
+    /// as a single decryption.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty or if `precomputed_jumps` is empty.
+    pub fn new(range: Range<u64>, precomputed_jumps: KangarooJumps<G>) -> Self {
+        assert!(range.start < range.end, "range is empty");
+        assert!(!precomputed_jumps.is_empty(), "jump table is empty");
+
+        // With the mean jump `m`, the wild kangaroo needs to travel approximately `m^2`
+        // along the tame trail to land on it. Jump tables oversized for the range would make
+        // this trail (and thus the tame walk) very long though; we cap the trail length
+        // by a multiple of the range width, which keeps the number of tame jumps small.
+        let mean_jump = precomputed_jumps.mean_jump();
+        let width = u128::from(range.end - range.start);
+        let trap_target = (mean_jump * mean_jump * 8).min((width + mean_jump) * 8);
+        let mut buffer = vec![0_u8; G::ELEMENT_SIZE];
+        let mut trap = G::vartime_mul_generator(&G::Scalar::from(range.end));
+        let mut trap_distance = 0_u128;
+        while trap_distance < trap_target {
+            let (distance, jump) = precomputed_jumps.select(&trap, &mut buffer);
+            trap = trap + jump;
+            trap_distance += distance;
+        }
+
+        Self {
+            range,
+            jumps: precomputed_jumps,
+            trap,
+            trap_distance,
+        }
+    }
+
+    /// Returns the range of values this decrypter works with.
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns jumps used by this decrypter.
+    pub fn jumps(&self) -> &KangarooJumps<G> {
+        &self.jumps
+    }
+
+    /// Gets the discrete log of `decrypted_element`, or `None` if it is not found
+    /// in the range of this decrypter.
+    pub fn get(&self, decrypted_element: &G::Element) -> Option<u64> {
+        if G::is_identity(decrypted_element) {
+            return self.range.contains(&0).then_some(0);
+        }
+
+        let mut buffer = vec![0_u8; G::ELEMENT_SIZE];
+        let width = u128::from(self.range.end - self.range.start);
+        let max_distance = width + self.trap_distance;
+        for shift in 0..ATTEMPTS {
+            // Shifting the starting point changes the path of the wild kangaroo.
+            let mut element = *decrypted_element + G::vartime_mul_generator(&shift.into());
+            let mut distance = 0_u128;
+            while distance <= max_distance {
+                if element == self.trap {
+                    let value = u128::from(self.range.end) + self.trap_distance
+                        - distance
+                        - u128::from(shift);
+                    return u64::try_from(value)
+                        .ok()
+                        .filter(|value| self.check(*value, decrypted_element));
+                }
+                let (jump_distance, jump) = self.jumps.select(&element, &mut buffer);
+                element = element + jump;
+                distance += jump_distance;
+            }
+        }
+        None
+    }
+
+    fn check(&self, value: u64, element: &G::Element) -> bool {
+        self.range.contains(&value) && G::vartime_mul_generator(&value.into()) == *element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, Rng};
+
+    use super::*;
+    use crate::group::Ristretto;
+
+    #[test]
+    fn jump_table_size() {
+        assert_eq!(KangarooJumps::<Ristretto>::for_width(1).len(), 1);
+        let jumps = KangarooJumps::<Ristretto>::for_width(1 << 40);
+        assert_eq!(jumps.len(), 24);
+        assert!(jumps.mean_jump() >= 1 << 19);
+    }
+
+    #[test]
+    fn kangaroo_decryption() {
+        let mut rng = thread_rng();
+        let range = (1 << 32)..((1 << 32) + 100_000);
+        let jumps = KangarooJumps::<Ristretto>::for_width(range.end - range.start);
+        let decrypter = KangarooDecrypter::new(range.clone(), jumps);
+
+        let boundary_values = [range.start, range.end - 1];
+        let random_values = (0..5).map(|_| rng.gen_range(range.clone()));
+        for value in boundary_values.into_iter().chain(random_values) {
+            let element = Ristretto::vartime_mul_generator(&value.into());
+            assert_eq!(decrypter.get(&element), Some(value));
+        }
+
+        for value in [0, range.start - 1, range.end, range.end + 10_000] {
+            let element = Ristretto::vartime_mul_generator(&value.into());
+            assert_eq!(decrypter.get(&element), None);
+        }
+    }
+
+    #[test]
+    fn kangaroo_decryption_with_zero() {
+        let jumps = KangarooJumps::<Ristretto>::for_width(100);
+        let decrypter = KangarooDecrypter::new(0..100, jumps);
+        for value in [0, 1, 42, 99] {
+            let element = Ristretto::vartime_mul_generator(&value.into());
+            assert_eq!(decrypter.get(&element), Some(value));
+        }
+    }
+
+    #[test]
+    fn decrypter_with_oversized_jump_table() {
+        let jumps = KangarooJumps::<Ristretto>::new(40);
+        let decrypter = KangarooDecrypter::new(0..100, jumps);
+        assert!(decrypter.trap_distance < 1 << 48);
+
+        // Decryption may fail because of the mismatched table, but it must terminate
+        // and not return incorrect values.
+        for value in [1, 42, 99, 100] {
+            let element = Ristretto::vartime_mul_generator(&value.into());
+            let output = decrypter.get(&element);
+            assert!(output.map_or(true, |output| output == value && value < 100));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn jump_table_serialization() {
+        let jumps = KangarooJumps::<Ristretto>::for_width(1_000);
+        let json = serde_json::to_string(&jumps).unwrap();
+        let restored: KangarooJumps<Ristretto> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.elements, jumps.elements);
+
+        let decrypter = KangarooDecrypter::new(500..1_500, restored);
+        let element = Ristretto::vartime_mul_generator(&777_u64.into());
+        assert_eq!(decrypter.get(&element), Some(777));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn jump_table_with_too_many_jumps_is_rejected() {
+        let mut jumps = KangarooJumps::<Ristretto>::new(MAX_JUMP_COUNT);
+        let extra_jumps = jumps.elements.clone();
+        jumps.elements.extend(extra_jumps);
+        let json = serde_json::to_string(&jumps).unwrap();
+        let err = serde_json::from_str::<KangarooJumps<Ristretto>>(&json).unwrap_err();
+        assert!(err.to_string().contains("at most 64 jump"), "{err}");
+    }
+}
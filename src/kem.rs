@@ -0,0 +1,293 @@
+//! Key encapsulation mechanism (KEM) based on ElGamal encryption.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use crate::serde::ElementHelper;
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    proto_labels,
+    sharing::{ActiveParticipant, Params, PublicKeySet},
+    CandidateDecryption, Ciphertext, LogEqualityProof, PublicKey, SecretKey, VerifiableDecryption,
+    VerificationError,
+};
+
+/// Encapsulated shared secret produced by [`PublicKey::encapsulate()`].
+///
+/// An encapsulation is the random element `R = [r]G` of an ElGamal ciphertext without
+/// the blinded element; the shared secret is derived from `R` and the Diffie–Hellman element
+/// `[r]K = [k]R`, where `K = [k]G` is the receiver key. Thus, the encapsulation can be
+/// decapsulated with the receiver's [`SecretKey`], or via any [`VerifiableDecryption`]
+/// of the corresponding [ciphertext](Self::to_ciphertext()) (e.g., one combined
+/// from threshold decryption shares).
+///
+/// The KEM provides no authentication of the sender; it is meant to be combined
+/// with an AEAD keyed by the [`SharedSecret`].
+///
+/// # Threshold decapsulation
+///
+/// If the receiver key is a [shared key](PublicKeySet::shared_key()) of a threshold committee,
+/// participants produce [decapsulation shares](ActiveParticipant::decapsulation_share())
+/// together with proofs of their correctness. A combiner
+/// [verifies](PublicKeySet::verify_decapsulation_share()) the shares
+/// and [combines](Params::combine_decapsulation_shares()) them to derive the shared secret.
+/// This allows a committee to jointly open data of arbitrary size (e.g., documents)
+/// encrypted with an AEAD, rather than only group-element plaintexts.
+///
+/// Decapsulation shares are not secret: anyone who collects shares from a threshold
+/// of participants can derive the shared secret. Thus, shares should be sent only
+/// to the parties authorized to decrypt the data.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Encapsulation, Keypair};
+/// # use rand::thread_rng;
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let (encapsulation, secret) = receiver.public().encapsulate(&mut rng);
+/// // Send `encapsulation` to the receiver together with the AEAD ciphertext
+/// // produced with `secret`.
+/// let bytes = encapsulation.to_bytes();
+/// let encapsulation = Encapsulation::<Ristretto>::from_bytes(&bytes).unwrap();
+/// let receiver_secret = receiver.secret().decapsulate(&encapsulation);
+/// assert_eq!(receiver_secret.as_bytes(), secret.as_bytes());
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct Encapsulation<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    random_element: G::Element,
+}
+
+impl<G: Group> Encapsulation<G> {
+    /// Returns the random element of this encapsulation.
+    pub fn random_element(&self) -> &G::Element {
+        &self.random_element
+    }
+
+    /// Converts this encapsulation to a ciphertext encrypting zero with no randomness
+    /// in the blinded element. Decryptions of this ciphertext (e.g., threshold decryption shares)
+    /// can be used to [decapsulate](VerifiableDecryption::decapsulate()) the shared secret.
+    pub fn to_ciphertext(&self) -> Ciphertext<G> {
+        Ciphertext {
+            random_element: self.random_element,
+            blinded_element: G::identity(),
+        }
+    }
+
+    /// Serializes this encapsulation into bytes.
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(&self.random_element, &mut bytes);
+        bytes
+    }
+
+    /// Deserializes an encapsulation from bytes. Returns `None` if `bytes` do not represent
+    /// a valid group element, or represent the group identity.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != G::ELEMENT_SIZE {
+            return None;
+        }
+        let random_element = G::deserialize_element(bytes)?;
+        if G::is_identity(&random_element) {
+            None
+        } else {
+            Some(Self { random_element })
+        }
+    }
+
+    fn derive_secret(&self, dh_element: &G::Element) -> SharedSecret {
+        let mut buffer = Zeroizing::new(vec![0_u8; G::ELEMENT_SIZE]);
+        G::serialize_element(&self.random_element, &mut buffer);
+        let mut hasher = Sha256::new()
+            .chain_update(proto_labels::KEM_SECRET)
+            .chain_update(&*buffer);
+        G::serialize_element(dh_element, &mut buffer);
+        hasher.update(&*buffer);
+        SharedSecret(hasher.finalize().into())
+    }
+}
+
+/// Shared secret produced by the ElGamal KEM; see [`Encapsulation`] for details.
+///
+/// The secret is a 32-byte SHA-256 digest suitable for use as an AEAD key (possibly
+/// after additional key derivation). It is zeroized on drop.
+pub struct SharedSecret([u8; SharedSecret::SIZE]);
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SharedSecret")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl SharedSecret {
+    /// Size of the shared secret in bytes.
+    pub const SIZE: usize = 32;
+
+    /// Exposes bytes of this secret.
+    pub fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+}
+
+impl<G: Group> PublicKey<G> {
+    /// Generates a shared secret and its encapsulation for this key.
+    pub fn encapsulate<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> (Encapsulation<G>, SharedSecret) {
+        let random_scalar = SecretKey::<G>::generate(rng);
+        let encapsulation = Encapsulation {
+            random_element: G::mul_generator(random_scalar.expose_scalar()),
+        };
+        let dh_element = self.as_element() * random_scalar.expose_scalar();
+        let secret = encapsulation.derive_secret(&dh_element);
+        (encapsulation, secret)
+    }
+}
+
+impl<G: Group> SecretKey<G> {
+    /// Decapsulates the shared secret from the `encapsulation`.
+    ///
+    /// Like decryption, this operation cannot fail; if the encapsulation targets
+    /// another receiver, the returned secret will be garbage (which will be detected
+    /// by the AEAD used in conjunction with the KEM).
+    pub fn decapsulate(&self, encapsulation: &Encapsulation<G>) -> SharedSecret {
+        let dh_element = encapsulation.random_element * self.expose_scalar();
+        encapsulation.derive_secret(&dh_element)
+    }
+}
+
+impl<G: Group> VerifiableDecryption<G> {
+    /// Decapsulates the shared secret from the `encapsulation`, assuming that this decryption
+    /// corresponds to [`Encapsulation::to_ciphertext()`]. In particular, this allows to
+    /// decapsulate secrets for a [shared key](crate::sharing::PublicKeySet::shared_key()).
+    pub fn decapsulate(&self, encapsulation: &Encapsulation<G>) -> SharedSecret {
+        encapsulation.derive_secret(self.as_element())
+    }
+}
+
+impl<G: Group> ActiveParticipant<G> {
+    /// Creates a decapsulation share for the `encapsulation` together with a proof
+    /// of its validity. This is equivalent to [decrypting](Self::decrypt_share())
+    /// [`Encapsulation::to_ciphertext()`].
+    pub fn decapsulation_share<R: CryptoRng + RngCore>(
+        &self,
+        encapsulation: &Encapsulation<G>,
+        rng: &mut R,
+    ) -> (VerifiableDecryption<G>, LogEqualityProof<G>) {
+        self.decrypt_share(encapsulation.to_ciphertext(), rng)
+    }
+}
+
+impl<G: Group> PublicKeySet<G> {
+    /// Verifies a candidate decapsulation share for the `encapsulation` provided
+    /// by a participant with the specified `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not correspond to a participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_decapsulation_share(
+        &self,
+        candidate_share: CandidateDecryption<G>,
+        encapsulation: &Encapsulation<G>,
+        index: usize,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, VerificationError> {
+        self.verify_share(candidate_share, encapsulation.to_ciphertext(), index, proof)
+    }
+}
+
+impl Params {
+    /// Combines verified decapsulation shares for the `encapsulation` and derives
+    /// the shared secret. The shares must be provided together with the 0-based indexes
+    /// of the participants they are coming from.
+    ///
+    /// Returns `None` if the number of shares is insufficient or any index in `shares`
+    /// is out of bounds.
+    pub fn combine_decapsulation_shares<G: Group>(
+        self,
+        encapsulation: &Encapsulation<G>,
+        shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
+    ) -> Option<SharedSecret> {
+        let combined = self.combine_shares(shares)?;
+        Some(combined.decapsulate(encapsulation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, Params, PublicKeySet},
+        Keypair,
+    };
+
+    #[test]
+    fn decapsulation_with_wrong_key() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        let (encapsulation, secret) = receiver.public().encapsulate(&mut rng);
+        let other_secret = other.secret().decapsulate(&encapsulation);
+        assert_ne!(other_secret.as_bytes(), secret.as_bytes());
+
+        let identity_bytes = [0_u8; 32];
+        assert!(Encapsulation::<Ristretto>::from_bytes(&identity_bytes).is_none());
+    }
+
+    #[test]
+    fn threshold_decapsulation() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let (encapsulation, secret) = key_set.shared_key().encapsulate(&mut rng);
+        let (other_encapsulation, _) = key_set.shared_key().encapsulate(&mut rng);
+
+        let shares = [0, 2].map(|i| {
+            let secret_share = dealer.secret_share_for_participant(i);
+            let participant = ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap();
+            let (share, proof) = participant.decapsulation_share(&encapsulation, &mut rng);
+            assert!(key_set
+                .verify_decapsulation_share(share.into(), &other_encapsulation, i, &proof)
+                .is_err());
+            let share = key_set
+                .verify_decapsulation_share(share.into(), &encapsulation, i, &proof)
+                .unwrap();
+            (i, share)
+        });
+        assert!(params
+            .combine_decapsulation_shares(&encapsulation, shares[..1].iter().copied())
+            .is_none());
+        let combined_secret = params
+            .combine_decapsulation_shares(&encapsulation, shares)
+            .unwrap();
+        assert_eq!(combined_secret.as_bytes(), secret.as_bytes());
+    }
+}
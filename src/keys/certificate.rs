@@ -0,0 +1,280 @@
+//! Certification of public keys by external authorities.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use crate::serde::BytesHelper;
+use crate::{
+    alloc::{Box, Vec},
+    group::Group,
+    proto_labels, PublicKey,
+};
+
+/// Verifier of signatures produced by an external certification authority (CA), e.g.,
+/// an organizational root key.
+///
+/// Signatures and CA keys are opaque to this crate; the signature scheme (e.g., Ed25519
+/// or ECDSA) is determined by the verifier implementation.
+pub trait CertificateVerifier {
+    /// Checks whether `signature` is a valid signature of the `message` under the `issuer` key.
+    fn verify(&self, issuer: &[u8], message: &[u8], signature: &[u8]) -> bool;
+}
+
+/// Single link of a [`CertificateChain`]: a signature of the issuer over the subject key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Certificate {
+    /// Bytes of the certified key. This is either a key of an intermediate authority,
+    /// or (for the last link in the chain) the certified [`PublicKey`].
+    #[cfg_attr(feature = "serde", serde(with = "BytesHelper"))]
+    pub subject: Vec<u8>,
+    /// Signature of the issuer over [`Self::signed_message()`] for the subject.
+    #[cfg_attr(feature = "serde", serde(with = "BytesHelper"))]
+    pub signature: Vec<u8>,
+}
+
+impl Certificate {
+    const DOMAIN_SEPARATOR: &'static [u8] = proto_labels::CERTIFICATE;
+
+    /// Returns the message that the issuer must sign to certify the `subject` key.
+    /// The message is domain-separated, so that certificate signatures cannot be confused
+    /// with other signatures made with the CA key.
+    pub fn signed_message(subject: &[u8]) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::DOMAIN_SEPARATOR.len() + 8 + subject.len());
+        message.extend_from_slice(Self::DOMAIN_SEPARATOR);
+        message.extend_from_slice(&(subject.len() as u64).to_le_bytes());
+        message.extend_from_slice(subject);
+        message
+    }
+}
+
+/// Chain of [`Certificate`]s leading from a root authority to a public key.
+///
+/// The first certificate is signed by the root key, and each following certificate
+/// is signed by the subject of the previous one. The subject of the last certificate
+/// is the certified key.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     group::Ristretto, Certificate, CertificateChain, CertificateVerifier, Keypair,
+/// # };
+/// # use rand::thread_rng;
+/// // Toy verifier for illustration; use a real signature scheme in practice.
+/// struct Concatenation;
+///
+/// impl CertificateVerifier for Concatenation {
+///     fn verify(&self, issuer: &[u8], message: &[u8], signature: &[u8]) -> bool {
+///         signature == [issuer, message].concat()
+///     }
+/// }
+///
+/// let keypair = Keypair::<Ristretto>::generate(&mut thread_rng());
+/// let root = b"root".to_vec();
+/// let subject = keypair.public().as_bytes().to_vec();
+/// let signature = [&root[..], &Certificate::signed_message(&subject)].concat();
+/// let chain = CertificateChain::new(vec![Certificate { subject, signature }]);
+/// assert!(keypair.public().verify_certificate(&chain, &root, &Concatenation).is_ok());
+/// assert!(keypair.public().verify_certificate(&chain, b"other", &Concatenation).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CertificateChain {
+    certificates: Vec<Certificate>,
+}
+
+impl CertificateChain {
+    /// Creates a chain from the `certificates` ordered from the root to the certified key.
+    pub fn new(certificates: Vec<Certificate>) -> Self {
+        Self { certificates }
+    }
+
+    /// Returns certificates in this chain ordered from the root to the certified key.
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// Verifies this chain for the `subject` key issued by the `root` key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain is empty, any of its signatures does not verify,
+    /// or if the chain certifies a key other than `subject`.
+    pub fn verify<V: CertificateVerifier + ?Sized>(
+        &self,
+        subject: &[u8],
+        root: &[u8],
+        verifier: &V,
+    ) -> Result<(), CertificationError> {
+        let last = self
+            .certificates
+            .last()
+            .ok_or(CertificationError::EmptyChain)?;
+        let mut issuer = root;
+        for (link, certificate) in self.certificates.iter().enumerate() {
+            let message = Certificate::signed_message(&certificate.subject);
+            if !verifier.verify(issuer, &message, &certificate.signature) {
+                return Err(CertificationError::InvalidSignature { link });
+            }
+            issuer = &certificate.subject;
+        }
+
+        if last.subject == subject {
+            Ok(())
+        } else {
+            Err(CertificationError::SubjectMismatch)
+        }
+    }
+}
+
+impl<G: Group> PublicKey<G> {
+    /// Verifies that this key is certified by the `root` authority via the provided `chain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same situations as [`CertificateChain::verify()`].
+    pub fn verify_certificate<V: CertificateVerifier + ?Sized>(
+        &self,
+        chain: &CertificateChain,
+        root: &[u8],
+        verifier: &V,
+    ) -> Result<(), CertificationError> {
+        chain.verify(self.as_bytes(), root, verifier)
+    }
+}
+
+/// Errors that can occur when verifying a [`CertificateChain`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CertificationError {
+    /// Certificate chain is empty.
+    EmptyChain,
+    /// Signature in the certificate with the specified 0-based index in the chain
+    /// does not verify.
+    InvalidSignature {
+        /// 0-based index of the certificate in the chain.
+        link: usize,
+    },
+    /// Certificate chain certifies a different key.
+    SubjectMismatch,
+    /// Certificate for the participant with the specified 0-based index is missing.
+    MissingCertificate {
+        /// 0-based index of the participant.
+        participant: usize,
+    },
+    /// Certificate chain for a participant is invalid.
+    Participant {
+        /// 0-based index of the participant.
+        index: usize,
+        /// Error verifying the chain.
+        error: Box<CertificationError>,
+    },
+}
+
+impl fmt::Display for CertificationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyChain => formatter.write_str("certificate chain is empty"),
+            Self::InvalidSignature { link } => write!(
+                formatter,
+                "signature in certificate #{link} of the chain does not verify"
+            ),
+            Self::SubjectMismatch => formatter.write_str("certificate chain certifies another key"),
+            Self::MissingCertificate { participant } => write!(
+                formatter,
+                "certificate for participant #{participant} is missing"
+            ),
+            Self::Participant { index, error } => write!(
+                formatter,
+                "certificate chain for participant #{index} is invalid: {error}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CertificationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Participant { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{alloc::vec, group::Ristretto, Keypair};
+
+    /// Insecure verifier for tests: a signature is the issuer key concatenated
+    /// with the message.
+    struct ConcatVerifier;
+
+    impl CertificateVerifier for ConcatVerifier {
+        fn verify(&self, issuer: &[u8], message: &[u8], signature: &[u8]) -> bool {
+            signature == [issuer, message].concat()
+        }
+    }
+
+    fn certify(issuer: &[u8], subject: &[u8]) -> Certificate {
+        Certificate {
+            subject: subject.to_vec(),
+            signature: [issuer, &Certificate::signed_message(subject)].concat(),
+        }
+    }
+
+    #[test]
+    fn certificate_chain_with_intermediate_authority() {
+        let key = Keypair::<Ristretto>::generate(&mut thread_rng())
+            .public()
+            .clone();
+        let chain = CertificateChain::new(vec![
+            certify(b"root", b"intermediate"),
+            certify(b"intermediate", key.as_bytes()),
+        ]);
+        key.verify_certificate(&chain, b"root", &ConcatVerifier)
+            .unwrap();
+
+        let err = key
+            .verify_certificate(&chain, b"other_root", &ConcatVerifier)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CertificationError::InvalidSignature { link: 0 }
+        ));
+
+        let other_key = Keypair::<Ristretto>::generate(&mut thread_rng())
+            .public()
+            .clone();
+        let err = other_key
+            .verify_certificate(&chain, b"root", &ConcatVerifier)
+            .unwrap_err();
+        assert!(matches!(err, CertificationError::SubjectMismatch));
+
+        // Skipping the intermediate authority breaks the chain.
+        let broken_chain = CertificateChain::new(vec![
+            certify(b"root", b"other_intermediate"),
+            certify(b"intermediate", key.as_bytes()),
+        ]);
+        let err = key
+            .verify_certificate(&broken_chain, b"root", &ConcatVerifier)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CertificationError::InvalidSignature { link: 1 }
+        ));
+
+        let err = key
+            .verify_certificate(&CertificateChain::new(vec![]), b"root", &ConcatVerifier)
+            .unwrap_err();
+        assert!(matches!(err, CertificationError::EmptyChain));
+    }
+}
@@ -2,13 +2,17 @@
 
 use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
 
 use core::iter;
 
 use crate::{
-    alloc::vec, encryption::ExtendedCiphertext, group::Group, Ciphertext, DiscreteLogTable,
-    LogEqualityProof, PreparedRange, PublicKey, RangeProof, RingProof, RingProofBuilder, SecretKey,
-    VerificationError,
+    alloc::{vec, Vec},
+    encryption::ExtendedCiphertext,
+    group::Group,
+    proto_labels, BoundCiphertext, Ciphertext, DiscreteLogSolver, LogEqualityProof, PreparedRange,
+    PublicKey, RangeProof, RingProof, RingProofBuilder, SealedEncryption, SecretKey,
+    SecretRandomness, TaggedCiphertext, VerificationError,
 };
 
 impl<G: Group> PublicKey<G> {
@@ -22,6 +26,66 @@ impl<G: Group> PublicKey<G> {
         ExtendedCiphertext::new(element, self, rng).inner
     }
 
+    /// Encrypts a value for this key and returns the ciphertext together with the randomness
+    /// used to create it. The randomness can be used to construct custom proofs
+    /// about the ciphertext.
+    pub fn encrypt_with_randomness<T, R: CryptoRng + RngCore>(
+        &self,
+        value: T,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, SecretRandomness<G>)
+    where
+        G::Scalar: From<T>,
+    {
+        let scalar = G::Scalar::from(value);
+        let element = G::mul_generator(&scalar);
+        self.encrypt_element_with_randomness(element, rng)
+    }
+
+    /// Encrypts multiple values for this key. This is equivalent to calling [`Self::encrypt()`]
+    /// for each value, but is more efficient since multiplications of the key are batched;
+    /// see [`Ciphertext::new_batch()`] for details.
+    pub fn encrypt_batch<T, R: CryptoRng + RngCore>(
+        &self,
+        values: impl IntoIterator<Item = T>,
+        rng: &mut R,
+    ) -> Vec<Ciphertext<G>>
+    where
+        G::Scalar: From<T>,
+    {
+        let scalars: Zeroizing<Vec<_>> =
+            Zeroizing::new(values.into_iter().map(G::Scalar::from).collect());
+        let elements: Vec<_> = scalars.iter().map(G::mul_generator).collect();
+        Ciphertext::new_batch(&elements, self, rng)
+    }
+
+    /// Encrypts a value for this key and [binds](BoundCiphertext) the ciphertext to the key.
+    pub fn encrypt_bound<T, R: CryptoRng + RngCore>(
+        &self,
+        value: T,
+        rng: &mut R,
+    ) -> BoundCiphertext<G>
+    where
+        G::Scalar: From<T>,
+    {
+        BoundCiphertext::new(self.encrypt(value, rng), self)
+    }
+
+    /// Encrypts a value for this key and [seals](SealedEncryption) the ciphertext
+    /// with a proof of knowledge bound to the `context`.
+    pub fn encrypt_sealed<T, R: CryptoRng + RngCore>(
+        &self,
+        value: T,
+        context: &[u8],
+        rng: &mut R,
+    ) -> SealedEncryption<G>
+    where
+        G::Scalar: From<T>,
+    {
+        let (ciphertext, randomness) = self.encrypt_with_randomness(value, rng);
+        SealedEncryption::new(self, context, ciphertext, randomness, rng)
+    }
+
     /// Encrypts a group element.
     pub fn encrypt_element<R: CryptoRng + RngCore>(
         &self,
@@ -31,25 +95,65 @@ impl<G: Group> PublicKey<G> {
         ExtendedCiphertext::new(value, self, rng).inner
     }
 
+    /// Encrypts a group element and returns the ciphertext together with the randomness
+    /// used to create it.
+    pub fn encrypt_element_with_randomness<R: CryptoRng + RngCore>(
+        &self,
+        value: G::Element,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, SecretRandomness<G>) {
+        ExtendedCiphertext::new(value, self, rng).into_parts()
+    }
+
     /// Encrypts zero value and provides a zero-knowledge proof of encryption correctness.
     pub fn encrypt_zero<R>(&self, rng: &mut R) -> (Ciphertext<G>, LogEqualityProof<G>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        self.encrypt_zero_with_mul(rng, |scalar| self.mul_bases(scalar))
+    }
+
+    /// Same as [`Self::encrypt_zero()`], but with `[x]G` and `[x]K` for scalars `x` computed
+    /// by `mul_bases` (e.g., using precomputed tables), where `K` is this key.
+    pub(crate) fn encrypt_zero_with_mul<R>(
+        &self,
+        rng: &mut R,
+        mul_bases: impl Fn(&G::Scalar) -> (G::Element, G::Element),
+    ) -> (Ciphertext<G>, LogEqualityProof<G>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut transcript = Transcript::new(proto_labels::ZERO_ENCRYPTION);
+        self.encrypt_zero_with_transcript(&mut transcript, rng, mul_bases)
+    }
+
+    fn mul_bases(&self, scalar: &G::Scalar) -> (G::Element, G::Element) {
+        (G::mul_generator(scalar), self.element * scalar)
+    }
+
+    fn encrypt_zero_with_transcript<R>(
+        &self,
+        transcript: &mut Transcript,
+        rng: &mut R,
+        mul_bases: impl Fn(&G::Scalar) -> (G::Element, G::Element),
+    ) -> (Ciphertext<G>, LogEqualityProof<G>)
     where
         R: CryptoRng + RngCore,
     {
         let random_scalar = SecretKey::<G>::generate(rng);
-        let random_element = G::mul_generator(&random_scalar.0);
-        let blinded_element = self.element * &random_scalar.0;
+        let (random_element, blinded_element) = mul_bases(&random_scalar.0);
         let ciphertext = Ciphertext {
             random_element,
             blinded_element,
         };
 
-        let proof = LogEqualityProof::new(
+        let proof = LogEqualityProof::new_with_mul(
             self,
             &random_scalar,
             (random_element, blinded_element),
-            &mut Transcript::new(b"zero_encryption"),
+            transcript,
             rng,
+            mul_bases,
         );
 
         (ciphertext, proof)
@@ -68,7 +172,7 @@ impl<G: Group> PublicKey<G> {
         proof.verify(
             self,
             (ciphertext.random_element, ciphertext.blinded_element),
-            &mut Transcript::new(b"zero_encryption"),
+            &mut Transcript::new(proto_labels::ZERO_ENCRYPTION),
         )
     }
 
@@ -83,13 +187,31 @@ impl<G: Group> PublicKey<G> {
         value: bool,
         rng: &mut R,
     ) -> (Ciphertext<G>, RingProof<G>) {
-        let mut transcript = Transcript::new(b"bool_encryption");
+        let (ciphertext, proof) = self.encrypt_bool_extended(value, rng);
+        (ciphertext.inner, proof)
+    }
+
+    pub(crate) fn encrypt_bool_extended<R: CryptoRng + RngCore>(
+        &self,
+        value: bool,
+        rng: &mut R,
+    ) -> (ExtendedCiphertext<G>, RingProof<G>) {
+        let mut transcript = Transcript::new(proto_labels::BOOL_ENCRYPTION);
+        self.encrypt_bool_with_transcript(value, &mut transcript, rng)
+    }
+
+    fn encrypt_bool_with_transcript<R: CryptoRng + RngCore>(
+        &self,
+        value: bool,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> (ExtendedCiphertext<G>, RingProof<G>) {
         let admissible_values = [G::identity(), G::generator()];
         let mut ring_responses = vec![G::Scalar::default(); 2];
-        let mut builder = RingProofBuilder::new(self, 1, &mut ring_responses, &mut transcript, rng);
+        let mut builder = RingProofBuilder::new(self, 1, &mut ring_responses, transcript, rng);
         let ciphertext = builder.add_value(&admissible_values, usize::from(value));
         let proof = RingProof::new(builder.build(), ring_responses);
-        (ciphertext.inner, proof)
+        (ciphertext, proof)
     }
 
     /// Verifies a proof of encryption correctness of a boolean value, which was presumably
@@ -106,13 +228,23 @@ impl<G: Group> PublicKey<G> {
         &self,
         ciphertext: Ciphertext<G>,
         proof: &RingProof<G>,
+    ) -> Result<(), VerificationError> {
+        let mut transcript = Transcript::new(proto_labels::BOOL_ENCRYPTION);
+        self.verify_bool_with_transcript(ciphertext, proof, &mut transcript)
+    }
+
+    fn verify_bool_with_transcript(
+        &self,
+        ciphertext: Ciphertext<G>,
+        proof: &RingProof<G>,
+        transcript: &mut Transcript,
     ) -> Result<(), VerificationError> {
         let admissible_values = [G::identity(), G::generator()];
         proof.verify(
             self,
             iter::once(&admissible_values as &[_]),
             iter::once(ciphertext),
-            &mut Transcript::new(b"bool_encryption"),
+            transcript,
         )
     }
 
@@ -131,7 +263,7 @@ impl<G: Group> PublicKey<G> {
         value: u64,
         rng: &mut R,
     ) -> (Ciphertext<G>, RangeProof<G>) {
-        let mut transcript = Transcript::new(b"ciphertext_range");
+        let mut transcript = Transcript::new(proto_labels::CIPHERTEXT_RANGE);
         let (ciphertext, proof) = RangeProof::new(self, range, value, &mut transcript, rng);
         (ciphertext.into(), proof)
     }
@@ -150,9 +282,196 @@ impl<G: Group> PublicKey<G> {
         ciphertext: Ciphertext<G>,
         proof: &RangeProof<G>,
     ) -> Result<(), VerificationError> {
-        let mut transcript = Transcript::new(b"ciphertext_range");
+        let mut transcript = Transcript::new(proto_labels::CIPHERTEXT_RANGE);
         proof.verify(self, range, ciphertext, &mut transcript)
     }
+
+    /// Returns the offset for signed values in `range`, i.e., half of its upper bound.
+    fn signed_offset(range: &PreparedRange<G>) -> u64 {
+        range.decomposition().upper_bound() / 2
+    }
+
+    /// Encrypts a signed `value` and provides a zero-knowledge proof that it lies in
+    /// the interval `[-offset, upper_bound - offset)`, where `upper_bound` is the upper bound
+    /// of the `range` and `offset = upper_bound / 2`. The value is encoded as
+    /// [`Group::scalar_from_i64()`] does, i.e., negative values are encrypted as negated
+    /// scalars.
+    ///
+    /// Internally, the proof is a [`RangeProof`] for `value + offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is out of the interval above.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{
+    /// #     group::{Group, Ristretto}, DiscreteLogTable, Keypair, RangeDecomposition,
+    /// # };
+    /// # use rand::thread_rng;
+    /// let mut rng = thread_rng();
+    /// let (receiver, secret) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+    /// // Values in `-5..5`.
+    /// let range = RangeDecomposition::optimal(10).into();
+    /// let (ciphertext, proof) = receiver.encrypt_signed_range(&range, -3, &mut rng);
+    /// receiver.verify_signed_range(&range, ciphertext, &proof).unwrap();
+    ///
+    /// let expected = Ristretto::vartime_mul_generator(&Ristretto::scalar_from_i64(-3).unwrap());
+    /// assert_eq!(secret.decrypt_to_element(ciphertext), expected);
+    /// ```
+    pub fn encrypt_signed_range<R: CryptoRng + RngCore>(
+        &self,
+        range: &PreparedRange<G>,
+        value: i64,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, RangeProof<G>) {
+        let offset = Self::signed_offset(range);
+        let shifted_value = i128::from(value) + i128::from(offset);
+        let shifted_value = u64::try_from(shifted_value)
+            .ok()
+            .filter(|&value| value < range.decomposition().upper_bound())
+            .expect("value is out of range");
+
+        let mut transcript = Transcript::new(proto_labels::SIGNED_CIPHERTEXT_RANGE);
+        let (ciphertext, proof) = RangeProof::new(self, range, shifted_value, &mut transcript, rng);
+        let ciphertext = Ciphertext::from(ciphertext) - Ciphertext::non_blinded(offset);
+        (ciphertext, proof)
+    }
+
+    /// Verifies `proof` that `ciphertext` encrypts a signed value lying in the interval
+    /// determined by `range`. The `proof` should be created with a call
+    /// to [`Self::encrypt_signed_range()`] with the same [`PreparedRange`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_signed_range(
+        &self,
+        range: &PreparedRange<G>,
+        ciphertext: Ciphertext<G>,
+        proof: &RangeProof<G>,
+    ) -> Result<(), VerificationError> {
+        let offset = Self::signed_offset(range);
+        let shifted_ciphertext = ciphertext + Ciphertext::non_blinded(offset);
+        let mut transcript = Transcript::new(proto_labels::SIGNED_CIPHERTEXT_RANGE);
+        proof.verify(self, range, shifted_ciphertext, &mut transcript)
+    }
+}
+
+impl<G: Group> PublicKey<G> {
+    fn tagged_transcript(label: &'static [u8], tag: &[u8]) -> Transcript {
+        let mut transcript = Transcript::new(label);
+        transcript.append_message(b"tag", tag);
+        transcript
+    }
+
+    /// Encrypts zero value and provides a zero-knowledge proof of encryption correctness
+    /// bound to the specified `tag`.
+    pub fn encrypt_zero_tagged<R>(
+        &self,
+        tag: &[u8],
+        rng: &mut R,
+    ) -> (TaggedCiphertext<G>, LogEqualityProof<G>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut transcript = Self::tagged_transcript(b"zero_encryption", tag);
+        let (ciphertext, proof) =
+            self.encrypt_zero_with_transcript(&mut transcript, rng, |scalar| {
+                self.mul_bases(scalar)
+            });
+        (TaggedCiphertext::new(tag, ciphertext), proof)
+    }
+
+    /// Verifies that `ciphertext` is an encryption of a zero value, with the proof bound
+    /// to the ciphertext tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_zero_tagged(
+        &self,
+        ciphertext: &TaggedCiphertext<G>,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<(), VerificationError> {
+        let inner = ciphertext.ciphertext();
+        proof.verify(
+            self,
+            (inner.random_element, inner.blinded_element),
+            &mut Self::tagged_transcript(b"zero_encryption", ciphertext.tag()),
+        )
+    }
+
+    /// Encrypts a boolean value (0 or 1) and provides a zero-knowledge proof of encryption
+    /// correctness bound to the specified `tag`.
+    ///
+    /// # Examples
+    ///
+    /// See [`TaggedCiphertext`] docs for an example of usage.
+    pub fn encrypt_bool_tagged<R: CryptoRng + RngCore>(
+        &self,
+        value: bool,
+        tag: &[u8],
+        rng: &mut R,
+    ) -> (TaggedCiphertext<G>, RingProof<G>) {
+        let mut transcript = Self::tagged_transcript(b"bool_encryption", tag);
+        let (ciphertext, proof) = self.encrypt_bool_with_transcript(value, &mut transcript, rng);
+        (TaggedCiphertext::new(tag, ciphertext.inner), proof)
+    }
+
+    /// Verifies a proof of encryption correctness of a boolean value, which was presumably
+    /// obtained via [`Self::encrypt_bool_tagged()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    ///
+    /// # Examples
+    ///
+    /// See [`TaggedCiphertext`] docs for an example of usage.
+    pub fn verify_bool_tagged(
+        &self,
+        ciphertext: &TaggedCiphertext<G>,
+        proof: &RingProof<G>,
+    ) -> Result<(), VerificationError> {
+        let mut transcript = Self::tagged_transcript(b"bool_encryption", ciphertext.tag());
+        self.verify_bool_with_transcript(*ciphertext.ciphertext(), proof, &mut transcript)
+    }
+
+    /// Encrypts `value` and provides a zero-knowledge proof that it lies in the specified `range`.
+    /// The proof is bound to the specified `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is out of `range`.
+    pub fn encrypt_range_tagged<R: CryptoRng + RngCore>(
+        &self,
+        range: &PreparedRange<G>,
+        value: u64,
+        tag: &[u8],
+        rng: &mut R,
+    ) -> (TaggedCiphertext<G>, RangeProof<G>) {
+        let mut transcript = Self::tagged_transcript(b"ciphertext_range", tag);
+        let (ciphertext, proof) = RangeProof::new(self, range, value, &mut transcript, rng);
+        (TaggedCiphertext::new(tag, ciphertext.into()), proof)
+    }
+
+    /// Verifies `proof` that `ciphertext` encrypts a value lying in `range`, with the proof
+    /// bound to the ciphertext tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_range_tagged(
+        &self,
+        range: &PreparedRange<G>,
+        ciphertext: &TaggedCiphertext<G>,
+        proof: &RangeProof<G>,
+    ) -> Result<(), VerificationError> {
+        let mut transcript = Self::tagged_transcript(b"ciphertext_range", ciphertext.tag());
+        proof.verify(self, range, *ciphertext.ciphertext(), &mut transcript)
+    }
 }
 
 impl<G: Group> SecretKey<G> {
@@ -168,14 +487,14 @@ impl<G: Group> SecretKey<G> {
 
     /// Decrypts the provided ciphertext and returns the original encrypted value.
     ///
-    /// `lookup_table` is used to find encrypted values based on the original decrypted
-    /// group element. That is, it must contain all valid plaintext values. If the value
-    /// is not in the table, this method will return `None`.
-    pub fn decrypt(
+    /// `lookup_table` (e.g., a [`DiscreteLogTable`](crate::DiscreteLogTable)) is used to find encrypted values based
+    /// on the original decrypted group element. That is, it must contain all valid plaintext
+    /// values. If the value is not in the table, this method will return `None`.
+    pub fn decrypt<D: DiscreteLogSolver<G> + ?Sized>(
         &self,
         encrypted: Ciphertext<G>,
-        lookup_table: &DiscreteLogTable<G>,
+        lookup_table: &D,
     ) -> Option<u64> {
-        lookup_table.get(&self.decrypt_to_element(encrypted))
+        lookup_table.solve(&self.decrypt_to_element(encrypted))
     }
 }
@@ -2,6 +2,8 @@
 
 use base64ct::{Base64UrlUnpadded, Encoding};
 use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
 use core::{fmt, ops};
@@ -9,9 +11,22 @@ use core::{fmt, ops};
 use crate::{
     alloc::{vec, Vec},
     group::Group,
+    proto_labels,
 };
 
+mod certificate;
 mod impls;
+mod precomputed;
+mod usage;
+
+pub use self::certificate::{
+    Certificate, CertificateChain, CertificateVerifier, CertificationError,
+};
+pub use self::precomputed::PrecomputedBases;
+pub use self::usage::{
+    EncryptionUsage, IdentityKeypair, IdentityPublicKey, IdentityUsage, KeyUsage, TypedKeypair,
+    TypedPublicKey,
+};
 
 /// Secret key for ElGamal encryption and related protocols. This is a thin wrapper around
 /// the [`Group`] scalar.
@@ -38,6 +53,14 @@ impl<G: Group> Drop for SecretKey<G> {
     }
 }
 
+/// Compares secret keys in constant time. Secret keys intentionally do not implement
+/// [`PartialEq`].
+impl<G: Group> ConstantTimeEq for SecretKey<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        G::ct_eq_scalars(&self.0, &other.0)
+    }
+}
+
 impl<G: Group> SecretKey<G> {
     pub(crate) fn new(scalar: G::Scalar) -> Self {
         SecretKey(scalar)
@@ -146,6 +169,13 @@ where
     }
 }
 
+/// Compares public keys in constant time, e.g., if keys are derived from secret data.
+impl<G: Group> ConstantTimeEq for PublicKey<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.bytes.ct_eq(&other.bytes)
+    }
+}
+
 impl<G: Group> PublicKey<G> {
     /// Deserializes a public key from bytes.
     ///
@@ -188,6 +218,51 @@ impl<G: Group> PublicKey<G> {
     pub fn as_element(&self) -> G::Element {
         self.element
     }
+
+    /// Computes a short fingerprint of this key.
+    pub fn fingerprint(&self) -> KeyFingerprint {
+        let digest = Sha256::new()
+            .chain_update(proto_labels::KEY_FINGERPRINT)
+            .chain_update(&self.bytes)
+            .finalize();
+        let mut bytes = [0_u8; KeyFingerprint::SIZE];
+        bytes.copy_from_slice(&digest[..KeyFingerprint::SIZE]);
+        KeyFingerprint(bytes)
+    }
+}
+
+/// Short fingerprint of a [`PublicKey`] (a truncated SHA-256 digest of the key).
+///
+/// Fingerprints are used to detect accidental mismatches between keys, e.g.,
+/// in [`BoundCiphertext`](crate::BoundCiphertext). They are too short to withstand
+/// deliberate collision attacks and should not be used as key identifiers in an adversarial
+/// setting.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyFingerprint(pub(crate) [u8; Self::SIZE]);
+
+impl fmt::Debug for KeyFingerprint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "KeyFingerprint({self})")
+    }
+}
+
+impl fmt::Display for KeyFingerprint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(formatter, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl KeyFingerprint {
+    /// Size of a fingerprint in bytes.
+    pub const SIZE: usize = 8;
+
+    /// Returns bytes of this fingerprint.
+    pub fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
 }
 
 impl<G: Group> From<&SecretKey<G>> for PublicKey<G> {
@@ -0,0 +1,235 @@
+//! Precomputed multiplication tables for bases used in encryption and proofs.
+
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use core::fmt;
+
+use super::{PublicKey, SecretKey};
+use crate::{alloc::Vec, group::PrecomputedMul, Ciphertext, LogEqualityProof};
+
+/// Precomputed multiplication tables for the bases used in encryption and proofs:
+/// the group generator, the receiver key, and optionally an auxiliary generator
+/// (e.g., the blinding base for Pedersen commitments).
+///
+/// Precomputing tables is relatively expensive, so an application should create bases
+/// once (e.g., at startup) and reuse them for all operations with the same receiver key.
+/// Operations performed via `PrecomputedBases` produce the same outputs
+/// as the corresponding [`PublicKey`] methods (e.g., [`Self::encrypt()`] is equivalent
+/// to [`PublicKey::encrypt()`]), so they can be verified in the usual way.
+///
+/// Tables are only used by the methods of this type; other proofs (e.g., [`RangeProof`]s
+/// and [`RingProof`]s created for [`EncryptedChoice`]) do not accept precomputed bases.
+/// Proof verification does not benefit from the tables either, since it uses variable-time
+/// multi-scalar multiplication.
+///
+/// [`RangeProof`]: crate::RangeProof
+/// [`RingProof`]: crate::RingProof
+/// [`EncryptedChoice`]: crate::app::EncryptedChoice
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, DiscreteLogTable, Keypair, PrecomputedBases};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let bases = PrecomputedBases::new(receiver.public());
+///
+/// let ciphertext = bases.encrypt(5_u64, &mut rng);
+/// let lookup_table = DiscreteLogTable::new(0..10);
+/// assert_eq!(receiver.secret().decrypt(ciphertext, &lookup_table), Some(5));
+///
+/// let (zero, proof) = bases.encrypt_zero(&mut rng);
+/// receiver.public().verify_zero(zero, &proof)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PrecomputedBases<G: PrecomputedMul> {
+    receiver: PublicKey<G>,
+    generator_table: G::Table,
+    receiver_table: G::Table,
+    auxiliary: Option<(G::Element, G::Table)>,
+}
+
+impl<G: PrecomputedMul> fmt::Debug for PrecomputedBases<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PrecomputedBases")
+            .field("receiver", &self.receiver)
+            .field(
+                "auxiliary_generator",
+                &self.auxiliary.as_ref().map(|(element, _)| element),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: PrecomputedMul> Clone for PrecomputedBases<G> {
+    fn clone(&self) -> Self {
+        Self {
+            receiver: self.receiver.clone(),
+            generator_table: self.generator_table.clone(),
+            receiver_table: self.receiver_table.clone(),
+            auxiliary: self.auxiliary.clone(),
+        }
+    }
+}
+
+impl<G: PrecomputedMul> PrecomputedBases<G> {
+    /// Precomputes tables for the group generator and the `receiver` key.
+    pub fn new(receiver: &PublicKey<G>) -> Self {
+        Self {
+            receiver: receiver.clone(),
+            generator_table: G::precompute(&G::generator()),
+            receiver_table: G::precompute(&receiver.as_element()),
+            auxiliary: None,
+        }
+    }
+
+    /// Additionally precomputes a table for the auxiliary `generator`.
+    #[must_use]
+    pub fn with_auxiliary_generator(mut self, generator: G::Element) -> Self {
+        self.auxiliary = Some((generator, G::precompute(&generator)));
+        self
+    }
+
+    /// Returns the receiver key.
+    pub fn receiver(&self) -> &PublicKey<G> {
+        &self.receiver
+    }
+
+    /// Returns the auxiliary generator, if one was provided.
+    pub fn auxiliary_generator(&self) -> Option<G::Element> {
+        self.auxiliary.as_ref().map(|(element, _)| *element)
+    }
+
+    /// Multiplies the group generator by `k`.
+    pub fn mul_generator(&self, k: &G::Scalar) -> G::Element {
+        G::mul_precomputed(&self.generator_table, k)
+    }
+
+    /// Multiplies the receiver key by `k`.
+    pub fn mul_receiver(&self, k: &G::Scalar) -> G::Element {
+        G::mul_precomputed(&self.receiver_table, k)
+    }
+
+    /// Multiplies the auxiliary generator by `k`. Returns `None` if the auxiliary generator
+    /// was not provided.
+    pub fn mul_auxiliary(&self, k: &G::Scalar) -> Option<G::Element> {
+        let (_, table) = self.auxiliary.as_ref()?;
+        Some(G::mul_precomputed(table, k))
+    }
+
+    /// Computes a Pedersen commitment `[value]G + [blinding]H` to `value`, where `H`
+    /// is the auxiliary generator. Returns `None` if the auxiliary generator was not provided.
+    pub fn commit(&self, value: &G::Scalar, blinding: &SecretKey<G>) -> Option<G::Element> {
+        let blinding_element = self.mul_auxiliary(blinding.expose_scalar())?;
+        Some(self.mul_generator(value) + blinding_element)
+    }
+
+    /// Encrypts `value` for the receiver key. This is equivalent to [`PublicKey::encrypt()`].
+    pub fn encrypt<T, R: CryptoRng + RngCore>(&self, value: T, rng: &mut R) -> Ciphertext<G>
+    where
+        G::Scalar: From<T>,
+    {
+        let scalar = Zeroizing::new(G::Scalar::from(value));
+        let element = self.mul_generator(&scalar);
+        self.encrypt_element(element, rng)
+    }
+
+    /// Encrypts a group element for the receiver key. This is equivalent
+    /// to [`PublicKey::encrypt_element()`].
+    pub fn encrypt_element<R: CryptoRng + RngCore>(
+        &self,
+        value: G::Element,
+        rng: &mut R,
+    ) -> Ciphertext<G> {
+        let random_scalar = SecretKey::<G>::generate(rng);
+        Ciphertext {
+            random_element: self.mul_generator(random_scalar.expose_scalar()),
+            blinded_element: value + self.mul_receiver(random_scalar.expose_scalar()),
+        }
+    }
+
+    /// Encrypts multiple values for the receiver key. This is equivalent to calling
+    /// [`Self::encrypt()`] for each value.
+    pub fn encrypt_batch<T, R: CryptoRng + RngCore>(
+        &self,
+        values: impl IntoIterator<Item = T>,
+        rng: &mut R,
+    ) -> Vec<Ciphertext<G>>
+    where
+        G::Scalar: From<T>,
+    {
+        values
+            .into_iter()
+            .map(|value| self.encrypt(value, rng))
+            .collect()
+    }
+
+    /// Encrypts zero value and provides a zero-knowledge proof of encryption correctness.
+    /// This is equivalent to [`PublicKey::encrypt_zero()`]; the proof can be verified
+    /// with [`PublicKey::verify_zero()`].
+    pub fn encrypt_zero<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+    ) -> (Ciphertext<G>, LogEqualityProof<G>) {
+        self.receiver.encrypt_zero_with_mul(rng, |scalar| {
+            (self.mul_generator(scalar), self.mul_receiver(scalar))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::{ElementOps, Group, Ristretto, ScalarOps},
+        DiscreteLogTable, Keypair,
+    };
+
+    #[test]
+    fn precomputed_bases_match_plain_operations() {
+        let mut rng = thread_rng();
+        let receiver = Keypair::<Ristretto>::generate(&mut rng);
+        let bases = PrecomputedBases::new(receiver.public());
+        let scalar = Ristretto::generate_scalar(&mut rng);
+        assert_eq!(
+            bases.mul_generator(&scalar),
+            Ristretto::mul_generator(&scalar)
+        );
+        assert_eq!(
+            bases.mul_receiver(&scalar),
+            receiver.public().as_element() * scalar
+        );
+        assert!(bases.mul_auxiliary(&scalar).is_none());
+
+        let blinding_base = Ristretto::mul_generator(&Ristretto::generate_scalar(&mut rng));
+        let bases = bases.with_auxiliary_generator(blinding_base);
+        assert_eq!(bases.auxiliary_generator(), Some(blinding_base));
+        let blinding = SecretKey::<Ristretto>::generate(&mut rng);
+        let commitment = bases.commit(&scalar, &blinding).unwrap();
+        let expected = Ristretto::multi_mul(
+            [&scalar, blinding.expose_scalar()],
+            [Ristretto::generator(), blinding_base],
+        );
+        assert_eq!(commitment, expected);
+
+        let lookup_table = DiscreteLogTable::new(0..5);
+        let ciphertexts = bases.encrypt_batch(0_u64..5, &mut rng);
+        for (i, ciphertext) in (0_u64..).zip(ciphertexts) {
+            let decrypted = receiver.secret().decrypt(ciphertext, &lookup_table);
+            assert_eq!(decrypted, Some(i));
+        }
+
+        let (zero, proof) = bases.encrypt_zero(&mut rng);
+        receiver.public().verify_zero(zero, &proof).unwrap();
+        let (one, _) = bases.encrypt_zero(&mut rng);
+        let one = one + receiver.public().encrypt(1_u64, &mut rng);
+        assert!(receiver.public().verify_zero(one, &proof).is_err());
+    }
+}
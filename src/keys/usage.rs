@@ -0,0 +1,235 @@
+//! Keys with a fixed usage.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use core::{fmt, marker::PhantomData};
+
+use super::{Keypair, PublicKey, PublicKeyConversionError, SecretKey};
+use crate::{alloc::vec, group::Group, proofs::TranscriptForGroup, proto_labels};
+
+/// Usage of a [`TypedKeypair`] or a [`TypedPublicKey`].
+///
+/// This trait is sealed; it is implemented for [`EncryptionUsage`] and [`IdentityUsage`].
+pub trait KeyUsage: crate::sealed::Sealed + 'static {
+    /// Label used for domain separation when [deriving](TypedKeypair::derive()) keys
+    /// with this usage.
+    const LABEL: &'static [u8];
+}
+
+/// [`KeyUsage`] for keys used to encrypt and decrypt data. This is the usage implied
+/// by an untyped [`Keypair`].
+#[derive(Debug)]
+pub enum EncryptionUsage {}
+
+impl crate::sealed::Sealed for EncryptionUsage {}
+
+impl KeyUsage for EncryptionUsage {
+    const LABEL: &'static [u8] = b"encryption";
+}
+
+/// [`KeyUsage`] for long-term identity keys used to sign protocol messages, such as
+/// the keys registered via
+/// [`PublicKeySet::with_identity_keys()`](crate::sharing::PublicKeySet::with_identity_keys()).
+#[derive(Debug)]
+pub enum IdentityUsage {}
+
+impl crate::sealed::Sealed for IdentityUsage {}
+
+impl KeyUsage for IdentityUsage {
+    const LABEL: &'static [u8] = b"identity";
+}
+
+/// Public key with a fixed [`KeyUsage`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct TypedPublicKey<G: Group, U: KeyUsage> {
+    inner: PublicKey<G>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _usage: PhantomData<fn() -> U>,
+}
+
+/// Public identity key.
+pub type IdentityPublicKey<G> = TypedPublicKey<G, IdentityUsage>;
+
+impl<G: Group, U: KeyUsage> fmt::Debug for TypedPublicKey<G, U> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_tuple("TypedPublicKey")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+impl<G: Group, U: KeyUsage> Clone for TypedPublicKey<G, U> {
+    fn clone(&self) -> Self {
+        Self::from_untyped(self.inner.clone())
+    }
+}
+
+impl<G: Group, U: KeyUsage> PartialEq for TypedPublicKey<G, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<G: Group, U: KeyUsage> TypedPublicKey<G, U> {
+    fn from_untyped(inner: PublicKey<G>) -> Self {
+        Self {
+            inner,
+            _usage: PhantomData,
+        }
+    }
+
+    /// Deserializes a public key from bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` has invalid byte size, does not represent a valid group element
+    /// or represents the group identity.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PublicKeyConversionError> {
+        PublicKey::from_bytes(bytes).map(Self::from_untyped)
+    }
+
+    /// Returns bytes representing the group element corresponding to this key.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+
+    /// Returns the untyped public key.
+    pub fn as_untyped(&self) -> &PublicKey<G> {
+        &self.inner
+    }
+}
+
+impl<G: Group> From<PublicKey<G>> for TypedPublicKey<G, EncryptionUsage> {
+    fn from(key: PublicKey<G>) -> Self {
+        Self::from_untyped(key)
+    }
+}
+
+/// Keypair with a fixed [`KeyUsage`].
+///
+/// Typed keypairs prevent a key generated for one purpose (e.g., a long-term identity key
+/// signing protocol messages) from being accidentally used for another purpose
+/// (e.g., encryption). Conversions between typed keypairs and untyped [`Keypair`]s
+/// are only provided for [`EncryptionUsage`]. To use a single secret for several purposes,
+/// [derive](Self::derive()) keys for each usage from it.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, IdentityKeypair, SecretKey};
+/// # use rand::thread_rng;
+/// let master_secret = SecretKey::<Ristretto>::generate(&mut thread_rng());
+/// let identity = IdentityKeypair::derive(&master_secret, b"participant #1");
+/// // Derivation is deterministic...
+/// let same_identity = IdentityKeypair::derive(&master_secret, b"participant #1");
+/// assert_eq!(identity.public(), same_identity.public());
+/// // ...and separated by the context.
+/// let other_identity = IdentityKeypair::derive(&master_secret, b"participant #2");
+/// assert_ne!(identity.public(), other_identity.public());
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct TypedKeypair<G: Group, U: KeyUsage> {
+    inner: Keypair<G>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _usage: PhantomData<fn() -> U>,
+}
+
+/// Identity keypair.
+pub type IdentityKeypair<G> = TypedKeypair<G, IdentityUsage>;
+
+impl<G: Group, U: KeyUsage> fmt::Debug for TypedKeypair<G, U> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TypedKeypair")
+            .field("public", self.inner.public())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group, U: KeyUsage> Clone for TypedKeypair<G, U> {
+    fn clone(&self) -> Self {
+        Self::from_untyped(self.inner.clone())
+    }
+}
+
+impl<G: Group, U: KeyUsage> TypedKeypair<G, U> {
+    fn from_untyped(inner: Keypair<G>) -> Self {
+        Self {
+            inner,
+            _usage: PhantomData,
+        }
+    }
+
+    /// Generates a random keypair.
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        Self::from_untyped(Keypair::generate(rng))
+    }
+
+    /// Deterministically derives a keypair from the `master` secret and the `context`.
+    /// Derivation is domain-separated by the [`KeyUsage`], so keys with different usages
+    /// derived from the same master secret and context are unrelated.
+    pub fn derive(master: &SecretKey<G>, context: &[u8]) -> Self {
+        let mut transcript = Transcript::new(proto_labels::KEY_DERIVATION);
+        transcript.append_message(b"usage", U::LABEL);
+        transcript.append_message(b"ctx", context);
+        let mut secret_bytes = Zeroizing::new(vec![0_u8; G::SCALAR_SIZE]);
+        G::serialize_scalar(master.expose_scalar(), &mut secret_bytes);
+        transcript.append_message(b"sk", &secret_bytes);
+
+        let secret = SecretKey::new(transcript.challenge_scalar::<G>(b"derived_sk"));
+        Self::from_untyped(Keypair::from(secret))
+    }
+
+    /// Returns the public part of this keypair.
+    pub fn public(&self) -> TypedPublicKey<G, U> {
+        TypedPublicKey::from_untyped(self.inner.public().clone())
+    }
+
+    /// Returns the untyped keypair.
+    pub(crate) fn as_untyped(&self) -> &Keypair<G> {
+        &self.inner
+    }
+}
+
+impl<G: Group> From<Keypair<G>> for TypedKeypair<G, EncryptionUsage> {
+    fn from(keypair: Keypair<G>) -> Self {
+        Self::from_untyped(keypair)
+    }
+}
+
+impl<G: Group> From<TypedKeypair<G, EncryptionUsage>> for Keypair<G> {
+    fn from(keypair: TypedKeypair<G, EncryptionUsage>) -> Self {
+        keypair.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::group::Ristretto;
+
+    #[test]
+    fn derivation_is_separated_by_usage() {
+        let master = SecretKey::<Ristretto>::generate(&mut thread_rng());
+        let identity = IdentityKeypair::derive(&master, b"test");
+        let encryption = TypedKeypair::<_, EncryptionUsage>::derive(&master, b"test");
+        assert_ne!(
+            identity.public().as_untyped(),
+            encryption.public().as_untyped()
+        );
+        assert_ne!(identity.public().as_untyped(), &PublicKey::from(&master));
+
+        let encryption = Keypair::from(encryption);
+        let restored = TypedKeypair::<_, EncryptionUsage>::derive(&master, b"test");
+        assert_eq!(encryption.public(), restored.public().as_untyped());
+    }
+}
@@ -15,6 +15,7 @@
 //!
 //! - [`Ciphertext`] provides ElGamal encryption. This and other protocols use
 //!   [`PublicKey`], [`SecretKey`] and [`Keypair`] to represent participants' keys.
+//!   [`TypedKeypair`] and [`TypedPublicKey`] restrict keys to a specific [`KeyUsage`].
 //! - Besides basic encryption, `PublicKey` also provides zero-knowledge proofs of
 //!   [zero encryption](PublicKey::encrypt_zero()) and of
 //!   [Boolean value encryption](PublicKey::encrypt_bool()). These are useful in higher-level
@@ -28,6 +29,10 @@
 //!   decryption.
 //! - [`dkg`] module implements distributed key generation using [Pedersen's scheme][pedersen-dkg]
 //!   with hash commitments.
+//! - [`two_party`] module provides a lightweight 2-of-2 threshold encryption scheme
+//!   with additive key shares, e.g., for a key co-held by a client and a server.
+//! - [`rng`] module provides deterministic and hedged derivation of RNGs for randomized
+//!   operations, protecting against randomness reuse (e.g., after a VM snapshot is restored).
 //! - [`app`] module provides higher-level protocols utilizing zero-knowledge proofs
 //!   and ElGamal encryption, such as provable encryption of m-of-n choice and a simple version
 //!   of [quadratic voting].
@@ -104,6 +109,53 @@
 //! should only be performed from a trusted source or in the presence of additional integrity
 //! checks.
 //!
+//! ## `rayon`
+//!
+//! *(off by default)*
+//!
+//! Enables parallel creation of range proofs in
+//! [`EncryptedChoice::new_parallel()`](crate::app::EncryptedChoice::new_parallel()) using
+//! the [`rayon`] thread pool. Requires the group scalars and elements to be thread-safe.
+//!
+//! ## `cbor`
+//!
+//! *(off by default)*
+//!
+//! Enables deterministic [CBOR] encoding for keys, ciphertexts and proofs, together with
+//! COSE-style wrappers, via the [`cbor`](crate::cbor) module.
+//!
+//! ## `authorization`
+//!
+//! *(off by default)*
+//!
+//! Enables [decryption policies](crate::sharing::DecryptionPolicy), which require threshold
+//! decryption to be approved by a quorum of requesters.
+//!
+//! ## `mmap`
+//!
+//! *(off by default; requires `std`)*
+//!
+//! Enables `MmapDiscreteLogTable`, a discrete log lookup table stored in a memory-mapped file.
+//! Such tables may exceed the available RAM and can be shared among processes.
+//!
+//! ## `testkit`
+//!
+//! *(off by default)*
+//!
+//! Enables the [`testkit`](crate::testkit) module with generators of test data,
+//! such as batches of valid and invalid encrypted ballots for load testing, and simulations
+//! of distributed key generation with corrupted participants. Also enables
+//! [`TestGroup`](crate::group::TestGroup), a toy group with tiny order for exhaustive tests.
+//! Ballots are generated in parallel if the `rayon` feature is enabled as well.
+//!
+//! ## `verifier`
+//!
+//! *(off by default; requires `std` and `serde`)*
+//!
+//! Builds the `verifier` binary, which verifies serialized election artifacts
+//! (the key set of talliers, encrypted ballots, decryption shares and mix-net audits) offline.
+//! Run `cargo run --features verifier --bin verifier -- sample` to see the expected input format.
+//!
 //! # Crate naming
 //!
 //! "Elastic" refers to pluggable backends, configurable params for threshold encryption,
@@ -125,8 +177,10 @@
 //! [`elliptic-curve`]: https://docs.rs/elliptic-curve/
 //! [`k256`]: https://docs.rs/k256/
 //! [`hashbrown`]: https://docs.rs/hashbrown/
+//! [`rayon`]: https://docs.rs/rayon/
 //! [docker-rng]: https://github.com/moby/moby/blob/master/pkg/namesgenerator/names-generator.go
 //! [quadratic voting]: https://en.wikipedia.org/wiki/Quadratic_voting
+//! [CBOR]: https://cbor.io/
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Documentation settings.
@@ -142,22 +196,43 @@
 )]
 
 pub mod app;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 mod decryption;
 pub mod dkg;
 mod encryption;
 pub mod group;
+mod hash;
+mod kangaroo;
+mod kem;
 mod keys;
+mod math;
+#[cfg(feature = "mmap")]
+mod mmap_table;
 mod proofs;
+pub mod proto_labels;
+pub mod rng;
 #[cfg(feature = "serde")]
 mod serde;
 pub mod sharing;
+#[cfg(feature = "testkit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testkit")))]
+pub mod testkit;
+mod text;
+pub mod two_party;
 
 // Polyfill for `alloc` types.
 mod alloc {
     #[cfg(not(feature = "std"))]
     extern crate alloc as std;
 
-    pub use std::{borrow::Cow, string::ToString, vec, vec::Vec};
+    pub use std::{
+        borrow::Cow,
+        boxed::Box,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
 
     #[cfg(all(not(feature = "std"), not(feature = "hashbrown")))]
     compile_error!(
@@ -189,14 +264,29 @@ mod sealed {
 
 pub use crate::{
     decryption::{CandidateDecryption, VerifiableDecryption},
-    encryption::{Ciphertext, CiphertextWithValue, DiscreteLogTable},
-    keys::{Keypair, PublicKey, PublicKeyConversionError, SecretKey},
+    encryption::{
+        BoundCiphertext, Ciphertext, CiphertextWithValue, Decryptor, DiscreteLogSolver,
+        DiscreteLogTable, KeyMismatchError, SealedEncryption, SecretRandomness, TaggedCiphertext,
+    },
+    hash::ObjectHash,
+    kangaroo::{KangarooDecrypter, KangarooJumps},
+    kem::{Encapsulation, SharedSecret},
+    keys::{
+        Certificate, CertificateChain, CertificateVerifier, CertificationError, EncryptionUsage,
+        IdentityKeypair, IdentityPublicKey, IdentityUsage, KeyFingerprint, KeyUsage, Keypair,
+        PrecomputedBases, PublicKey, PublicKeyConversionError, SecretKey, TypedKeypair,
+        TypedPublicKey,
+    },
     proofs::{
-        CommitmentEquivalenceProof, LogEqualityProof, PreparedRange, ProofOfPossession,
-        RangeDecomposition, RangeProof, RingProof, RingProofBuilder, SumOfSquaresProof,
-        VerificationError,
+        CoefficientPattern, CommitmentEquivalenceProof, LinearCombinationProof, LogEqualityProof,
+        PreparedRange, ProofOfPossession, RangeDecomposition, RangeProof, RepeatedLogEqualityProof,
+        RingProof, RingProofBuilder, SumOfSquaresProof, VerificationError,
     },
+    text::TextDecodingError,
 };
 
+#[cfg(feature = "mmap")]
+pub use crate::mmap_table::MmapDiscreteLogTable;
+
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
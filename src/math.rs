@@ -0,0 +1,47 @@
+//! Integer arithmetic helpers shared among modules.
+
+/// Integer square root of a `u64` number. Uses the digit-by-digit calculation method in base 2;
+/// see https://en.wikipedia.org/wiki/Methods_of_computing_square_roots#Binary_numeral_system_(base_2)
+pub(crate) fn isqrt(mut x: u64) -> u64 {
+    let mut root = 0_u64;
+    let mut power_of_4 = 1_u64 << 62;
+    while power_of_4 > x {
+        power_of_4 /= 4;
+    }
+    while power_of_4 > 0 {
+        if x >= root + power_of_4 {
+            x -= root + power_of_4;
+            root = root / 2 + power_of_4;
+        } else {
+            root /= 2;
+        }
+        power_of_4 /= 4;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isqrt_is_correct() {
+        let samples = (0..1_000).chain((0..1_000).map(|x| x * 1_000)).chain([
+            u64::MAX,
+            u64::MAX - 1,
+            1 << 63,
+            1 << 62,
+            (1 << 62) - 1,
+        ]);
+        for sample in samples {
+            let sqrt = isqrt(sample);
+            assert!(sqrt * sqrt <= sample, "sqrt({sample}) ?= {sqrt}");
+
+            let next_square = (sqrt + 1).checked_mul(sqrt + 1);
+            assert!(
+                next_square.map_or(true, |sq| sq > sample),
+                "sqrt({sample}) ?= {sqrt}"
+            );
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Memory-mapped discrete log lookup table.
+
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+
+use core::{fmt, marker::PhantomData};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{alloc::vec, group::Group, proto_labels, DiscreteLogSolver};
+
+const MAGIC: &[u8; 8] = b"EGDLOG01";
+const HEADER_SIZE: usize = 24;
+const ENTRY_SIZE: usize = 16;
+
+/// Lookup table for discrete logarithms stored in a memory-mapped file.
+///
+/// This is a drop-in alternative to [`DiscreteLogTable`](crate::DiscreteLogTable) for tables
+/// that are too large to keep in RAM, or that should be shared between processes
+/// (e.g., on machines tallying votes). The OS page cache is responsible for keeping
+/// the frequently accessed parts of the table in memory.
+///
+/// # File format
+///
+/// The table is stored as a sorted array of 16-byte entries, each consisting of a hash prefix
+/// of the group element `[x]G` and the value `x`. Hence, the table does not depend
+/// on the group element size. Lookups are performed via binary search; each candidate
+/// value is checked by recomputing `[x]G`, so hash collisions or a corrupted file cannot lead
+/// to an incorrect result (but can lead to a missed value).
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Keypair, MmapDiscreteLogTable};
+/// # use rand::thread_rng;
+/// # fn main() -> std::io::Result<()> {
+/// let path = std::env::temp_dir().join("elastic-elgamal-mmap-table-doctest.bin");
+/// // SAFETY: the table file is not modified by other processes.
+/// let lookup_table = unsafe { MmapDiscreteLogTable::<Ristretto>::create(&path, 0..1_000)? };
+///
+/// let mut rng = thread_rng();
+/// let receiver = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = receiver.public().encrypt(777_u64, &mut rng);
+/// let element = receiver.secret().decrypt_to_element(ciphertext);
+/// assert_eq!(lookup_table.get(&element), Some(777));
+/// # drop(lookup_table);
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MmapDiscreteLogTable<G: Group> {
+    mmap: Mmap,
+    len: usize,
+    _group: PhantomData<G>,
+}
+
+impl<G: Group> fmt::Debug for MmapDiscreteLogTable<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MmapDiscreteLogTable")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> DiscreteLogSolver<G> for MmapDiscreteLogTable<G> {
+    fn solve(&self, decrypted_element: &G::Element) -> Option<u64> {
+        self.get(decrypted_element)
+    }
+}
+
+impl<G: Group> MmapDiscreteLogTable<G> {
+    fn group_tag() -> [u8; 8] {
+        let mut generator_bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(&G::generator(), &mut generator_bytes);
+        let digest = Sha256::new()
+            .chain_update(proto_labels::DLOG_TABLE_GROUP)
+            .chain_update(&generator_bytes)
+            .finalize();
+        digest[..8].try_into().unwrap()
+    }
+
+    fn element_key(element: &G::Element) -> u64 {
+        let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(element, &mut bytes);
+        let digest = Sha256::new()
+            .chain_update(proto_labels::DLOG_TABLE_ENTRY)
+            .chain_update(&bytes)
+            .finalize();
+        u64::from_be_bytes(digest[..8].try_into().unwrap())
+    }
+
+    /// Creates a lookup table for the specified `values` and writes it to the file at `path`,
+    /// overwriting the file if it exists. The table is then mapped into memory.
+    ///
+    /// Table entries are sorted in memory before being written, so creation requires
+    /// 16 bytes of RAM per value; the table can be created once and then
+    /// [opened](Self::open()) by any number of processes.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors when writing or mapping the file.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::open()`].
+    pub unsafe fn create(
+        path: impl AsRef<Path>,
+        values: impl IntoIterator<Item = u64>,
+    ) -> io::Result<Self> {
+        let mut entries: Vec<_> = values
+            .into_iter()
+            .filter(|&value| value != 0)
+            .map(|value| {
+                let element = G::vartime_mul_generator(&G::Scalar::from(value));
+                (Self::element_key(&element), value)
+            })
+            .collect();
+        entries.sort_unstable();
+
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&Self::group_tag())?;
+        writer.write_all(&(entries.len() as u64).to_le_bytes())?;
+        for (key, value) in entries {
+            writer.write_all(&key.to_be_bytes())?;
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.into_inner()?.sync_all()?;
+
+        Self::open(path)
+    }
+
+    /// Opens a lookup table previously created with [`Self::create()`] and maps it into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors when opening or mapping the file, and an error with
+    /// [`InvalidData`](io::ErrorKind::InvalidData) kind if the file header is malformed
+    /// or the table was created for another group.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the table is in use (including
+    /// by other processes), since this is undefined behavior for memory-mapped files.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = Mmap::map(&file)?;
+
+        let invalid_data = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        if mmap.len() < HEADER_SIZE || &mmap[..8] != MAGIC {
+            return Err(invalid_data("file is not a discrete log lookup table"));
+        }
+        if mmap[8..16] != Self::group_tag() {
+            return Err(invalid_data("lookup table was created for another group"));
+        }
+        let mut len_bytes = [0_u8; 8];
+        len_bytes.copy_from_slice(&mmap[16..HEADER_SIZE]);
+        let len = usize::try_from(u64::from_le_bytes(len_bytes)).ok();
+        let expected_size = len
+            .and_then(|len| len.checked_mul(ENTRY_SIZE))
+            .and_then(|size| size.checked_add(HEADER_SIZE));
+        let len = match len {
+            Some(len) if expected_size == Some(mmap.len()) => len,
+            _ => return Err(invalid_data("lookup table size does not match its header")),
+        };
+
+        Ok(Self {
+            mmap,
+            len,
+            _group: PhantomData,
+        })
+    }
+
+    /// Returns the number of non-zero values in this table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether this table does not contain non-zero values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn entry(&self, index: usize) -> (u64, u64) {
+        let start = HEADER_SIZE + index * ENTRY_SIZE;
+        let entry = &self.mmap[start..start + ENTRY_SIZE];
+        let key = u64::from_be_bytes(entry[..8].try_into().unwrap());
+        let value = u64::from_le_bytes(entry[8..].try_into().unwrap());
+        (key, value)
+    }
+
+    /// Gets the discrete log of `decrypted_element`, or `None` if it is not present among `values`
+    /// stored in this table.
+    pub fn get(&self, decrypted_element: &G::Element) -> Option<u64> {
+        if G::is_identity(decrypted_element) {
+            return Some(0);
+        }
+
+        let key = Self::element_key(decrypted_element);
+        // Find the first entry with the `key` (entries with the same key are adjacent).
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid).0 < key {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        (low..self.len)
+            .map(|index| self.entry(index))
+            .take_while(|&(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+            .find(|&value| {
+                let element = G::vartime_mul_generator(&G::Scalar::from(value));
+                element == *decrypted_element
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, Rng};
+
+    use super::*;
+    use crate::{group::Ristretto, DiscreteLogTable};
+
+    struct TempPath(std::path::PathBuf);
+
+    impl TempPath {
+        fn new() -> Self {
+            let name = format!(
+                "elastic-elgamal-test-{:016x}.bin",
+                thread_rng().gen::<u64>()
+            );
+            Self(std::env::temp_dir().join(name))
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            std::fs::remove_file(&self.0).ok();
+        }
+    }
+
+    #[test]
+    fn mmap_table_is_consistent_with_in_memory_table() {
+        let path = TempPath::new();
+        let values = (0_u64..500).map(|i| i * 7);
+        let table = unsafe { MmapDiscreteLogTable::<Ristretto>::create(&path.0, values.clone()) };
+        let table = table.unwrap();
+        assert_eq!(table.len(), 499);
+        let reference_table = DiscreteLogTable::<Ristretto>::new(values);
+
+        for value in 0_u64..3_600 {
+            let element = Ristretto::vartime_mul_generator(&value.into());
+            assert_eq!(table.get(&element), reference_table.get(&element));
+        }
+
+        let reopened = unsafe { MmapDiscreteLogTable::<Ristretto>::open(&path.0) }.unwrap();
+        let element = Ristretto::vartime_mul_generator(&77_u64.into());
+        assert_eq!(reopened.get(&element), Some(77));
+    }
+
+    #[test]
+    fn opening_malformed_table() {
+        let path = TempPath::new();
+        std::fs::write(&path.0, b"not a table").unwrap();
+        let err = unsafe { MmapDiscreteLogTable::<Ristretto>::open(&path.0) }.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let table = unsafe { MmapDiscreteLogTable::<Ristretto>::create(&path.0, 0..10) };
+        drop(table.unwrap());
+        let mut bytes = std::fs::read(&path.0).unwrap();
+        bytes.truncate(bytes.len() - 1);
+        std::fs::write(&path.0, bytes).unwrap();
+        let err = unsafe { MmapDiscreteLogTable::<Ristretto>::open(&path.0) }.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
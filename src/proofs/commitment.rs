@@ -5,12 +5,14 @@ use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 #[cfg(feature = "serde")]
 use crate::serde::ScalarHelper;
 use crate::{
     group::Group,
     proofs::{TranscriptForGroup, VerificationError},
-    Ciphertext, CiphertextWithValue, PublicKey, SecretKey,
+    proto_labels, Ciphertext, CiphertextWithValue, PublicKey, SecretKey,
 };
 
 /// Zero-knowledge proof that an ElGamal ciphertext encrypts the same value as a Pedersen
@@ -145,7 +147,7 @@ impl<G: Group> CommitmentEquivalenceProof<G> {
             [G::generator(), commitment_blinding_base],
         );
 
-        transcript.start_proof(b"commitment_equivalence");
+        transcript.start_proof(proto_labels::COMMITMENT_EQUIVALENCE);
         transcript.append_element_bytes(b"K", receiver.as_bytes());
         transcript.append_element::<G>(b"R", &ciphertext.inner().random_element);
         transcript.append_element::<G>(b"B", &ciphertext.inner().blinded_element);
@@ -192,7 +194,7 @@ impl<G: Group> CommitmentEquivalenceProof<G> {
         commitment_blinding_base: G::Element,
         transcript: &mut Transcript,
     ) -> Result<(), VerificationError> {
-        transcript.start_proof(b"commitment_equivalence");
+        transcript.start_proof(proto_labels::COMMITMENT_EQUIVALENCE);
         transcript.append_element_bytes(b"K", receiver.as_bytes());
         transcript.append_element::<G>(b"R", &ciphertext.random_element);
         transcript.append_element::<G>(b"B", &ciphertext.blinded_element);
@@ -320,3 +322,29 @@ mod tests {
         assert!(matches!(err, VerificationError::ChallengeMismatch));
     }
 }
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for CommitmentEquivalenceProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for CommitmentEquivalenceProof<G> {
+    const CONTENT_TYPE: &'static str =
+        "application/x.elastic-elgamal.commitment-equivalence-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(4);
+        encoder.scalar::<G>(&self.challenge);
+        encoder.scalar::<G>(&self.randomness_response);
+        encoder.scalar::<G>(&self.value_response);
+        encoder.scalar::<G>(&self.commitment_response);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(4)?;
+        Ok(Self {
+            challenge: decoder.scalar::<G>()?,
+            randomness_response: decoder.scalar::<G>()?,
+            value_response: decoder.scalar::<G>()?,
+            commitment_response: decoder.scalar::<G>()?,
+        })
+    }
+}
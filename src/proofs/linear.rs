@@ -0,0 +1,296 @@
+//! [`LinearCombinationProof`] and related logic.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
+#[cfg(feature = "serde")]
+use crate::serde::{ScalarHelper, VecHelper};
+use crate::{
+    alloc::Vec,
+    group::Group,
+    proofs::{TranscriptForGroup, VerificationError},
+    proto_labels, SecretKey,
+};
+
+/// Zero-knowledge proof that a group element is a linear combination of public bases
+/// with secret coefficients known to the prover.
+///
+/// # Construction
+///
+/// The proof is a generalized Schnorr protocol (aka Okamoto protocol). Public parameters
+/// are bases `B_1, …, B_n` and the combined element `P`; the prover knows scalars
+/// `x_1, …, x_n` such that `P = [x_1]B_1 + … + [x_n]B_n`.
+///
+/// 1. **Commitment:** The prover generates random scalars `r_i` and sends to the verifier
+///   `R = [r_1]B_1 + … + [r_n]B_n`.
+/// 2. **Challenge:** The verifier sends to the prover random scalar `c`.
+/// 3. **Response:** The prover computes scalars `s_i = r_i + c*x_i` and sends them
+///   to the verifier.
+///
+/// The verification equation is
+///
+/// ```text
+/// [s_1]B_1 + … + [s_n]B_n ?= R + [c]P.
+/// ```
+///
+/// As with other proofs in this crate, the proof is made non-interactive via
+/// the Fiat–Shamir transform, and is represented as `(c, s_1, …, s_n)`; `R` is restored
+/// from the verification equation.
+///
+/// Bases do not need to be independent; e.g., several bases may coincide. Still, the proof
+/// only shows knowledge of *some* coefficients satisfying the relation, which are unique
+/// only if the discrete logs among bases are unknown to the prover.
+///
+/// # Implementation notes
+///
+/// - The proof is serialized as `n + 1` scalars.
+/// - Proof generation is constant-time. Verification is **not** constant-time.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     group::{ElementOps, Group, Ristretto, ScalarOps}, LinearCombinationProof, SecretKey,
+/// # };
+/// # use merlin::Transcript;
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let bases: Vec<_> = (0..3)
+///     .map(|_| Ristretto::mul_generator(&Ristretto::generate_scalar(&mut rng)))
+///     .collect();
+/// let coefficients: Vec<_> =
+///     (0..3).map(|_| SecretKey::<Ristretto>::generate(&mut rng)).collect();
+/// let point = Ristretto::multi_mul(
+///     coefficients.iter().map(SecretKey::expose_scalar),
+///     bases.iter().copied(),
+/// );
+///
+/// let proof = LinearCombinationProof::new(
+///     &bases,
+///     &coefficients,
+///     point,
+///     &mut Transcript::new(b"custom_proof"),
+///     &mut rng,
+/// );
+/// proof.verify(&bases, point, &mut Transcript::new(b"custom_proof"))?;
+///
+/// // The proof does not verify for another combined element.
+/// let other_point = point + Ristretto::generator();
+/// assert!(proof
+///     .verify(&bases, other_point, &mut Transcript::new(b"custom_proof"))
+///     .is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct LinearCombinationProof<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))]
+    challenge: G::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ScalarHelper<G>, 1>"))]
+    responses: Vec<G::Scalar>,
+}
+
+impl<G: Group> LinearCombinationProof<G> {
+    /// Creates a proof that `point` is equal to the linear combination of `bases`
+    /// with `coefficients`.
+    ///
+    /// It is **not** checked whether `point` actually equals the linear combination;
+    /// if this is not the case, the constructed proof will not [`verify`](Self::verify()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `coefficients` have differing lengths, or if they are empty.
+    pub fn new<R: CryptoRng + RngCore>(
+        bases: &[G::Element],
+        coefficients: &[SecretKey<G>],
+        point: G::Element,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Self {
+        assert!(!bases.is_empty(), "No bases provided");
+        assert_eq!(
+            bases.len(),
+            coefficients.len(),
+            "Number of bases differs from the number of coefficients"
+        );
+
+        Self::commit_to_statement(bases, point, transcript);
+        let random_scalars: Vec<_> = bases
+            .iter()
+            .map(|_| SecretKey::<G>::generate(rng))
+            .collect();
+        let random_element = G::multi_mul(
+            random_scalars.iter().map(SecretKey::expose_scalar),
+            bases.iter().copied(),
+        );
+        transcript.append_element::<G>(b"R", &random_element);
+
+        let challenge = transcript.challenge_scalar::<G>(b"c");
+        let responses = coefficients
+            .iter()
+            .zip(random_scalars)
+            .map(|(coefficient, mut randomness)| {
+                randomness += coefficient * &challenge;
+                *randomness.expose_scalar()
+            })
+            .collect();
+
+        Self {
+            challenge,
+            responses,
+        }
+    }
+
+    fn commit_to_statement(bases: &[G::Element], point: G::Element, transcript: &mut Transcript) {
+        transcript.start_proof(proto_labels::LINEAR_COMBINATION);
+        transcript.append_u64(b"n", bases.len() as u64);
+        for base in bases {
+            transcript.append_element::<G>(b"B", base);
+        }
+        transcript.append_element::<G>(b"P", &point);
+    }
+
+    /// Returns the number of bases / coefficients in the proven linear combination.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Checks whether the proof has no responses. This is always `false` for proofs
+    /// created with [`Self::new()`].
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Verifies this proof against the provided `bases` and the combined `point`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this proof does not verify.
+    pub fn verify(
+        &self,
+        bases: &[G::Element],
+        point: G::Element,
+        transcript: &mut Transcript,
+    ) -> Result<(), VerificationError> {
+        VerificationError::check_lengths("bases", self.responses.len(), bases.len())?;
+
+        Self::commit_to_statement(bases, point, transcript);
+        let neg_challenge = -self.challenge;
+        let random_element = G::vartime_multi_mul(
+            self.responses.iter().chain([&neg_challenge]),
+            bases.iter().copied().chain([point]),
+        );
+        transcript.append_element::<G>(b"R", &random_element);
+
+        let expected_challenge = transcript.challenge_scalar::<G>(b"c");
+        if expected_challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(VerificationError::ChallengeMismatch)
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for LinearCombinationProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for LinearCombinationProof<G> {
+    const CONTENT_TYPE: &'static str =
+        "application/x.elastic-elgamal.linear-combination-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.scalar::<G>(&self.challenge);
+        encoder.scalars::<G>(&self.responses);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        Ok(Self {
+            challenge: decoder.scalar::<G>()?,
+            responses: decoder.scalars::<G>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::group::{ElementOps, Ristretto, ScalarOps};
+
+    type SecretKey = crate::SecretKey<Ristretto>;
+
+    #[test]
+    fn linear_combination_proof_basics() {
+        let mut rng = thread_rng();
+        let bases: Vec<_> = (0..4)
+            .map(|_| Ristretto::mul_generator(&Ristretto::generate_scalar(&mut rng)))
+            .collect();
+        let coefficients: Vec<_> = (0..4).map(|_| SecretKey::generate(&mut rng)).collect();
+        let point = Ristretto::multi_mul(
+            coefficients.iter().map(SecretKey::expose_scalar),
+            bases.iter().copied(),
+        );
+
+        let proof = LinearCombinationProof::new(
+            &bases,
+            &coefficients,
+            point,
+            &mut Transcript::new(b"test_linear"),
+            &mut rng,
+        );
+        assert_eq!(proof.len(), 4);
+        proof
+            .verify(&bases, point, &mut Transcript::new(b"test_linear"))
+            .unwrap();
+
+        let mut shuffled_bases = bases.clone();
+        shuffled_bases.swap(0, 1);
+        let err = proof
+            .verify(&shuffled_bases, point, &mut Transcript::new(b"test_linear"))
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::ChallengeMismatch));
+
+        let err = proof
+            .verify(&bases[1..], point, &mut Transcript::new(b"test_linear"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::LenMismatch {
+                expected: 4,
+                actual: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn linear_combination_proof_with_wrong_coefficients() {
+        let mut rng = thread_rng();
+        let bases = [Ristretto::generator(), Ristretto::generator()];
+        let coefficients = [SecretKey::generate(&mut rng), SecretKey::generate(&mut rng)];
+        let point = Ristretto::mul_generator(&Ristretto::generate_scalar(&mut rng));
+
+        let proof = LinearCombinationProof::new(
+            &bases,
+            &coefficients,
+            point,
+            &mut Transcript::new(b"test_linear"),
+            &mut rng,
+        );
+        let err = proof
+            .verify(&bases, point, &mut Transcript::new(b"test_linear"))
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::ChallengeMismatch));
+    }
+}
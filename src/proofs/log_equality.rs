@@ -5,13 +5,15 @@ use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 #[cfg(feature = "serde")]
-use crate::serde::ScalarHelper;
+use crate::serde::{ScalarHelper, VecHelper};
 use crate::{
     alloc::{vec, Vec},
     group::Group,
     proofs::{TranscriptForGroup, VerificationError},
-    PublicKey, SecretKey,
+    proto_labels, PublicKey, SecretKey,
 };
 
 /// Zero-knowledge proof of equality of two discrete logarithms in different bases,
@@ -119,18 +121,24 @@ impl<G: Group> LogEqualityProof<G> {
         transcript: &mut Transcript,
         rng: &mut R,
     ) -> Self {
-        transcript.start_proof(b"log_eq");
-        transcript.append_element_bytes(b"K", log_base.as_bytes());
-        transcript.append_element::<G>(b"[r]G", &powers.0);
-        transcript.append_element::<G>(b"[r]K", &powers.1);
+        Self::new_with_mul(log_base, secret, powers, transcript, rng, |scalar| {
+            (G::mul_generator(scalar), log_base.as_element() * scalar)
+        })
+    }
 
+    /// Same as [`Self::new()`], but with `[x]G` and `[x]K` for the random scalar `x` computed
+    /// by `mul_bases` (e.g., using precomputed tables).
+    pub(crate) fn new_with_mul<R: CryptoRng + RngCore>(
+        log_base: &PublicKey<G>,
+        secret: &SecretKey<G>,
+        powers: (G::Element, G::Element),
+        transcript: &mut Transcript,
+        rng: &mut R,
+        mul_bases: impl FnOnce(&G::Scalar) -> (G::Element, G::Element),
+    ) -> Self {
         let random_scalar = SecretKey::<G>::generate(rng);
-        transcript.append_element::<G>(b"[x]G", &G::mul_generator(random_scalar.expose_scalar()));
-        transcript.append_element::<G>(
-            b"[x]K",
-            &(log_base.as_element() * random_scalar.expose_scalar()),
-        );
-        let challenge = transcript.challenge_scalar::<G>(b"c");
+        let commitments = mul_bases(random_scalar.expose_scalar());
+        let challenge = Self::challenge(log_base, powers, commitments, transcript);
         let response = challenge * secret.expose_scalar() + random_scalar.expose_scalar();
 
         Self {
@@ -139,6 +147,29 @@ impl<G: Group> LogEqualityProof<G> {
         }
     }
 
+    /// Computes the challenge given the commitments `[x]G` and `[x]K`.
+    pub(crate) fn challenge(
+        log_base: &PublicKey<G>,
+        powers: (G::Element, G::Element),
+        commitments: (G::Element, G::Element),
+        transcript: &mut Transcript,
+    ) -> G::Scalar {
+        transcript.start_proof(proto_labels::LOG_EQUALITY);
+        transcript.append_element_bytes(b"K", log_base.as_bytes());
+        transcript.append_element::<G>(b"[r]G", &powers.0);
+        transcript.append_element::<G>(b"[r]K", &powers.1);
+        transcript.append_element::<G>(b"[x]G", &commitments.0);
+        transcript.append_element::<G>(b"[x]K", &commitments.1);
+        transcript.challenge_scalar::<G>(b"c")
+    }
+
+    pub(crate) fn from_parts(challenge: G::Scalar, response: G::Scalar) -> Self {
+        Self {
+            challenge,
+            response,
+        }
+    }
+
     /// Verifies this proof.
     ///
     /// # Parameters
@@ -165,14 +196,7 @@ impl<G: Group> LogEqualityProof<G> {
             ),
         );
 
-        transcript.start_proof(b"log_eq");
-        transcript.append_element_bytes(b"K", log_base.as_bytes());
-        transcript.append_element::<G>(b"[r]G", &powers.0);
-        transcript.append_element::<G>(b"[r]K", &powers.1);
-        transcript.append_element::<G>(b"[x]G", &commitments.0);
-        transcript.append_element::<G>(b"[x]K", &commitments.1);
-        let expected_challenge = transcript.challenge_scalar::<G>(b"c");
-
+        let expected_challenge = Self::challenge(log_base, powers, commitments, transcript);
         if expected_challenge == self.challenge {
             Ok(())
         } else {
@@ -205,6 +229,233 @@ impl<G: Group> LogEqualityProof<G> {
     }
 }
 
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for LogEqualityProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for LogEqualityProof<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.log-equality-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.scalar::<G>(&self.challenge);
+        encoder.scalar::<G>(&self.response);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        Ok(Self {
+            challenge: decoder.scalar::<G>()?,
+            response: decoder.scalar::<G>()?,
+        })
+    }
+}
+
+/// [`LogEqualityProof`] with the sigma protocol repeated several times in parallel
+/// to amplify soundness.
+///
+/// A single [`LogEqualityProof`] can be forged with probability about `1/q`, where `q`
+/// is the group order. This is negligible for cryptographically sized groups, but not
+/// for toy groups used in teaching or experiments, such as `TestGroup` from the `testkit`
+/// crate feature. Repeating the protocol `k` times reduces the forgery probability to `1/q^k`.
+///
+/// # Construction
+///
+/// The prover generates `k` independent random scalars `x_i` and commits to all of them
+/// (i.e., to `X_G_i = [x_i]G` and `X_K_i = [x_i]K`) before deriving `k` challenges `c_i`
+/// from the [`Transcript`]. Since all challenges depend on all commitments, a malicious
+/// prover cannot grind repetitions one by one. Responses are `s_i = x_i + c_i * r`.
+///
+/// The proof is represented as `(c_i, s_i)` pairs, similarly to [`LogEqualityProof`];
+/// commitments are restored during verification. The number of repetitions is committed
+/// to the transcript, and the verifier must specify the expected number of repetitions.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Keypair, RepeatedLogEqualityProof};
+/// # use merlin::Transcript;
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let (log_base, _) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+/// let (power_g, discrete_log) = Keypair::<Ristretto>::generate(&mut rng).into_tuple();
+/// let power_k = log_base.as_element() * discrete_log.expose_scalar();
+///
+/// // For a large group, a single repetition is enough.
+/// let repetitions = RepeatedLogEqualityProof::<Ristretto>::repetitions_for(128);
+/// assert_eq!(repetitions, 1);
+/// let proof = RepeatedLogEqualityProof::new(
+///     &log_base,
+///     &discrete_log,
+///     (power_g.as_element(), power_k),
+///     repetitions,
+///     &mut Transcript::new(b"custom_proof"),
+///     &mut rng,
+/// );
+/// proof.verify(
+///     &log_base,
+///     (power_g.as_element(), power_k),
+///     repetitions,
+///     &mut Transcript::new(b"custom_proof"),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct RepeatedLogEqualityProof<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ScalarHelper<G>, 1>"))]
+    challenges: Vec<G::Scalar>,
+    #[cfg_attr(feature = "serde", serde(with = "VecHelper::<ScalarHelper<G>, 1>"))]
+    responses: Vec<G::Scalar>,
+}
+
+impl<G: Group> RepeatedLogEqualityProof<G> {
+    /// Returns the number of repetitions necessary to achieve soundness of `target_bits`,
+    /// i.e., for the forgery probability to not exceed `2^(-target_bits)`. Each repetition
+    /// is assumed to provide `2 * G::SECURITY_BITS` bits of soundness. The returned value
+    /// is at least 1.
+    pub fn repetitions_for(target_bits: usize) -> usize {
+        let bits_per_repetition = 2 * G::SECURITY_BITS;
+        ((target_bits + bits_per_repetition - 1) / bits_per_repetition).max(1)
+    }
+
+    /// Creates a new proof with the specified number of `repetitions`. Other parameters
+    /// are the same as for [`LogEqualityProof::new()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `repetitions` is zero.
+    pub fn new<R: CryptoRng + RngCore>(
+        log_base: &PublicKey<G>,
+        secret: &SecretKey<G>,
+        powers: (G::Element, G::Element),
+        repetitions: usize,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Self {
+        assert!(repetitions > 0, "number of repetitions must be positive");
+        let random_scalars: Vec<_> = (0..repetitions)
+            .map(|_| SecretKey::<G>::generate(rng))
+            .collect();
+        let commitments = random_scalars.iter().map(|scalar| {
+            (
+                G::mul_generator(scalar.expose_scalar()),
+                log_base.as_element() * scalar.expose_scalar(),
+            )
+        });
+        let challenges = Self::challenges(log_base, powers, commitments, repetitions, transcript);
+        let responses = challenges
+            .iter()
+            .zip(&random_scalars)
+            .map(|(challenge, scalar)| *challenge * secret.expose_scalar() + scalar.expose_scalar())
+            .collect();
+
+        Self {
+            challenges,
+            responses,
+        }
+    }
+
+    fn challenges(
+        log_base: &PublicKey<G>,
+        powers: (G::Element, G::Element),
+        commitments: impl Iterator<Item = (G::Element, G::Element)>,
+        repetitions: usize,
+        transcript: &mut Transcript,
+    ) -> Vec<G::Scalar> {
+        transcript.start_proof(proto_labels::REPEATED_LOG_EQUALITY);
+        transcript.append_element_bytes(b"K", log_base.as_bytes());
+        transcript.append_element::<G>(b"[r]G", &powers.0);
+        transcript.append_element::<G>(b"[r]K", &powers.1);
+        transcript.append_u64(b"k", repetitions as u64);
+        for (commitment_g, commitment_k) in commitments {
+            transcript.append_element::<G>(b"[x]G", &commitment_g);
+            transcript.append_element::<G>(b"[x]K", &commitment_k);
+        }
+        (0..repetitions)
+            .map(|_| transcript.challenge_scalar::<G>(b"c"))
+            .collect()
+    }
+
+    /// Returns the number of repetitions in this proof.
+    pub fn repetitions(&self) -> usize {
+        self.challenges.len()
+    }
+
+    /// Verifies this proof. Parameters are the same as for [`LogEqualityProof::verify()`],
+    /// plus the expected number of `repetitions`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this proof does not verify, or if it has an unexpected number
+    /// of repetitions.
+    pub fn verify(
+        &self,
+        log_base: &PublicKey<G>,
+        powers: (G::Element, G::Element),
+        repetitions: usize,
+        transcript: &mut Transcript,
+    ) -> Result<(), VerificationError> {
+        VerificationError::check_lengths("repetitions", repetitions, self.challenges.len())?;
+        VerificationError::check_lengths("responses", repetitions, self.responses.len())?;
+
+        let commitments =
+            self.challenges
+                .iter()
+                .zip(&self.responses)
+                .map(|(challenge, response)| {
+                    (
+                        G::vartime_double_mul_generator(&-*challenge, powers.0, response),
+                        G::vartime_multi_mul(
+                            &[-*challenge, *response],
+                            [powers.1, log_base.as_element()],
+                        ),
+                    )
+                });
+        let expected_challenges =
+            Self::challenges(log_base, powers, commitments, repetitions, transcript);
+        if expected_challenges == self.challenges {
+            Ok(())
+        } else {
+            Err(VerificationError::ChallengeMismatch)
+        }
+    }
+
+    /// Serializes this proof into bytes. The proof is serialized as `(c_i, s_i)` scalar pairs
+    /// for each repetition.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0_u8; 2 * G::SCALAR_SIZE * self.repetitions()];
+        let chunks = bytes.chunks_exact_mut(2 * G::SCALAR_SIZE);
+        for ((chunk, challenge), response) in chunks.zip(&self.challenges).zip(&self.responses) {
+            G::serialize_scalar(challenge, &mut chunk[..G::SCALAR_SIZE]);
+            G::serialize_scalar(response, &mut chunk[G::SCALAR_SIZE..]);
+        }
+        bytes
+    }
+
+    /// Attempts to parse the proof from `bytes`. Returns `None` if `bytes` do not represent
+    /// a well-formed proof with at least one repetition.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() || bytes.len() % (2 * G::SCALAR_SIZE) != 0 {
+            return None;
+        }
+
+        let mut challenges = Vec::with_capacity(bytes.len() / (2 * G::SCALAR_SIZE));
+        let mut responses = Vec::with_capacity(challenges.capacity());
+        for chunk in bytes.chunks_exact(2 * G::SCALAR_SIZE) {
+            challenges.push(G::deserialize_scalar(&chunk[..G::SCALAR_SIZE])?);
+            responses.push(G::deserialize_scalar(&chunk[G::SCALAR_SIZE..])?);
+        }
+        Some(Self {
+            challenges,
+            responses,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -239,4 +490,62 @@ mod tests {
                 .unwrap();
         }
     }
+
+    #[test]
+    fn repeated_log_equality_basics() {
+        let mut rng = thread_rng();
+        let log_base = Keypair::generate(&mut rng).public().clone();
+        let (generator_val, secret) = Keypair::generate(&mut rng).into_tuple();
+        let powers = (
+            generator_val.as_element(),
+            log_base.as_element() * secret.expose_scalar(),
+        );
+
+        for repetitions in 1..=3 {
+            let proof = RepeatedLogEqualityProof::new(
+                &log_base,
+                &secret,
+                powers,
+                repetitions,
+                &mut Transcript::new(b"testing_log_equality"),
+                &mut rng,
+            );
+            assert_eq!(proof.repetitions(), repetitions);
+            proof
+                .verify(
+                    &log_base,
+                    powers,
+                    repetitions,
+                    &mut Transcript::new(b"testing_log_equality"),
+                )
+                .unwrap();
+
+            let restored = RepeatedLogEqualityProof::<Ristretto>::from_bytes(&proof.to_bytes());
+            let restored = restored.unwrap();
+            restored
+                .verify(
+                    &log_base,
+                    powers,
+                    repetitions,
+                    &mut Transcript::new(b"testing_log_equality"),
+                )
+                .unwrap();
+
+            let err = proof
+                .verify(
+                    &log_base,
+                    powers,
+                    repetitions + 1,
+                    &mut Transcript::new(b"testing_log_equality"),
+                )
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                VerificationError::LenMismatch {
+                    collection: "repetitions",
+                    ..
+                }
+            ));
+        }
+    }
 }
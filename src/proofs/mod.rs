@@ -1,4 +1,14 @@
 //! Zero-knowledge proofs.
+//!
+//! All proofs in this module are standalone objects: they do not borrow from the statement
+//! they prove and (with the `serde` feature) can be serialized on their own. A proof
+//! is checked by a `verify` method taking the statement, e.g., [`LogEqualityProof::verify()`].
+//! Application-level artifacts bundling ciphertexts with proofs can be split into parts
+//! and restored, e.g., via [`EncryptedChoice::into_parts()`] and
+//! [`EncryptedChoice::from_parts()`].
+//!
+//! [`EncryptedChoice::into_parts()`]: crate::app::EncryptedChoice::into_parts()
+//! [`EncryptedChoice::from_parts()`]: crate::app::EncryptedChoice::from_parts()
 
 use merlin::Transcript;
 
@@ -7,9 +17,11 @@ use core::fmt;
 use crate::{
     alloc::vec,
     group::{Group, RandomBytesProvider},
+    proto_labels,
 };
 
 mod commitment;
+mod linear;
 mod log_equality;
 mod mul;
 mod possession;
@@ -18,9 +30,10 @@ mod ring;
 
 pub use self::{
     commitment::CommitmentEquivalenceProof,
-    log_equality::LogEqualityProof,
+    linear::LinearCombinationProof,
+    log_equality::{LogEqualityProof, RepeatedLogEqualityProof},
     mul::SumOfSquaresProof,
-    possession::ProofOfPossession,
+    possession::{CoefficientPattern, ProofOfPossession},
     range::{PreparedRange, RangeDecomposition, RangeProof},
     ring::{RingProof, RingProofBuilder},
 };
@@ -64,7 +77,9 @@ pub enum VerificationError {
     /// Restored challenge scalar does not match the one provided in the proof.
     ///
     /// This error most likely means that the proof itself is malformed, or that it was created
-    /// for a different context than it is being verified for.
+    /// for a different context than it is being verified for. Another possible cause is
+    /// a mismatch in [transcript labels](crate::proto_labels) between the prover and verifier;
+    /// to help diagnose this, the error description includes the labels version.
     ChallengeMismatch,
     /// A collection (e.g., number of responses in a [`RingProof`]) has a different size
     /// than expected.
@@ -78,6 +93,24 @@ pub enum VerificationError {
         /// Actual size of the collection.
         actual: usize,
     },
+    /// A coefficient of a public polynomial differs from the known value declared
+    /// in a [`CoefficientPattern`].
+    PatternMismatch {
+        /// 0-based index of the mismatching coefficient.
+        index: usize,
+    },
+    /// Identity key of the signer is unknown, e.g., because identity keys are not registered
+    /// in a [`PublicKeySet`](crate::sharing::PublicKeySet), or the signer index is out of bounds.
+    UnknownSigner {
+        /// 0-based index of the signer.
+        index: usize,
+    },
+    /// Participant index is out of bounds for the [`PublicKeySet`](crate::sharing::PublicKeySet)
+    /// the proof is verified against.
+    UnknownParticipant {
+        /// 0-based index of the participant.
+        index: usize,
+    },
 }
 
 impl VerificationError {
@@ -101,8 +134,12 @@ impl VerificationError {
 impl fmt::Display for VerificationError {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::ChallengeMismatch => formatter.write_str(
-                "restored challenge scalar does not match the one provided in the proof",
+            Self::ChallengeMismatch => write!(
+                formatter,
+                "restored challenge scalar does not match the one provided in the proof \
+                 (transcript labels v{}, elastic-elgamal v{})",
+                proto_labels::VERSION,
+                proto_labels::CRATE_VERSION
             ),
 
             Self::LenMismatch {
@@ -113,6 +150,20 @@ impl fmt::Display for VerificationError {
                 formatter,
                 "number of {collection} ({actual}) differs from expected ({expected})",
             ),
+
+            Self::PatternMismatch { index } => write!(
+                formatter,
+                "polynomial coefficient #{index} differs from the known value declared \
+                 in the pattern"
+            ),
+
+            Self::UnknownSigner { index } => {
+                write!(formatter, "identity key of signer #{index} is unknown")
+            }
+
+            Self::UnknownParticipant { index } => {
+                write!(formatter, "participant #{index} is not in the key set")
+            }
         }
     }
 }
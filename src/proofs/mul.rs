@@ -8,11 +8,13 @@ use zeroize::Zeroizing;
 
 use core::iter;
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 #[cfg(feature = "serde")]
 use crate::serde::{ScalarHelper, VecHelper};
 use crate::{
-    alloc::Vec, group::Group, proofs::TranscriptForGroup, Ciphertext, CiphertextWithValue,
-    PublicKey, SecretKey, VerificationError,
+    alloc::Vec, group::Group, proofs::TranscriptForGroup, proto_labels, Ciphertext,
+    CiphertextWithValue, PublicKey, SecretKey, VerificationError,
 };
 
 /// Zero-knowledge proof that an ElGamal-encrypted value is equal to a sum of squares
@@ -95,7 +97,7 @@ pub struct SumOfSquaresProof<G: Group> {
 
 impl<G: Group> SumOfSquaresProof<G> {
     fn initialize_transcript(transcript: &mut Transcript, receiver: &PublicKey<G>) {
-        transcript.start_proof(b"sum_of_squares");
+        transcript.start_proof(proto_labels::SUM_OF_SQUARES);
         transcript.append_element_bytes(b"K", receiver.as_bytes());
     }
 
@@ -299,6 +301,30 @@ impl<I: Iterator> Iterator for OddItems<I> {
     }
 }
 
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for SumOfSquaresProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for SumOfSquaresProof<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.sum-of-squares-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(3);
+        encoder.scalar::<G>(&self.challenge);
+        encoder.scalars::<G>(&self.ciphertext_responses);
+        encoder.scalar::<G>(&self.sum_response);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(3)?;
+        Ok(Self {
+            challenge: decoder.scalar::<G>()?,
+            ciphertext_responses: decoder.scalars::<G>()?,
+            sum_response: decoder.scalar::<G>()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
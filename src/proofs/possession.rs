@@ -5,13 +5,15 @@ use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 #[cfg(feature = "serde")]
 use crate::serde::{ScalarHelper, VecHelper};
 use crate::{
-    alloc::Vec,
+    alloc::{vec, Vec},
     group::Group,
     proofs::{TranscriptForGroup, VerificationError},
-    Keypair, PublicKey, SecretKey,
+    proto_labels, Keypair, PublicKey, SecretKey,
 };
 
 /// Zero-knowledge proof of possession of one or more secret scalars.
@@ -97,13 +99,22 @@ impl<G: Group> ProofOfPossession<G> {
         transcript: &mut Transcript,
         rng: &mut R,
     ) -> Self {
-        transcript.start_proof(b"multi_pop");
+        transcript.start_proof(proto_labels::PROOF_OF_POSSESSION);
         let mut key_count = 0;
         for public_key in public_keys {
             transcript.append_element_bytes(b"K", public_key.as_bytes());
             key_count += 1;
         }
+        Self::prove_committed(secrets, key_count, transcript, rng)
+    }
 
+    /// Creates a proof after public keys are committed to the `transcript`.
+    fn prove_committed<'a, R: CryptoRng + RngCore>(
+        secrets: impl Iterator<Item = &'a SecretKey<G>>,
+        key_count: usize,
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Self {
         let random_scalars: Vec<_> = (0..key_count)
             .map(|_| {
                 let randomness = SecretKey::<G>::generate(rng);
@@ -139,19 +150,24 @@ impl<G: Group> ProofOfPossession<G> {
         transcript: &mut Transcript,
     ) -> Result<(), VerificationError> {
         let mut key_count = 0;
-        transcript.start_proof(b"multi_pop");
+        transcript.start_proof(proto_labels::PROOF_OF_POSSESSION);
         for public_key in public_keys.clone() {
             transcript.append_element_bytes(b"K", public_key.as_bytes());
             key_count += 1;
         }
         VerificationError::check_lengths("public keys", self.responses.len(), key_count)?;
+        self.verify_committed(public_keys.map(PublicKey::as_element), transcript)
+    }
 
-        for (public_key, response) in public_keys.zip(&self.responses) {
-            let random_element = G::vartime_double_mul_generator(
-                &-self.challenge,
-                public_key.as_element(),
-                response,
-            );
+    /// Verifies this proof after public keys are committed to the `transcript`.
+    fn verify_committed(
+        &self,
+        public_elements: impl Iterator<Item = G::Element>,
+        transcript: &mut Transcript,
+    ) -> Result<(), VerificationError> {
+        for (public_element, response) in public_elements.zip(&self.responses) {
+            let random_element =
+                G::vartime_double_mul_generator(&-self.challenge, public_element, response);
             transcript.append_element::<G>(b"R", &random_element);
         }
 
@@ -164,12 +180,211 @@ impl<G: Group> ProofOfPossession<G> {
     }
 }
 
+impl<G: Group> ProofOfPossession<G> {
+    /// Creates a proof of possession for a polynomial with the coefficient `pattern`.
+    /// `secrets` are secret coefficients of the polynomial in the order they appear
+    /// in the pattern; known coefficients are not provided.
+    ///
+    /// The proof can be verified with [`Self::verify_pattern()`] against the public polynomial
+    /// (i.e., the coefficients multiplied by the group generator).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `secrets` differs from the number of secret coefficients
+    /// in the `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{
+    /// #     group::{ElementOps, Group, Ristretto}, CoefficientPattern, ProofOfPossession, SecretKey,
+    /// # };
+    /// # use merlin::Transcript;
+    /// # use rand::thread_rng;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut rng = thread_rng();
+    /// // Polynomial with the zero free term, as used in key refresh.
+    /// let pattern = CoefficientPattern::<Ristretto>::new(3).with_zero(0);
+    /// let secrets: Vec<_> =
+    ///     (0..2).map(|_| SecretKey::<Ristretto>::generate(&mut rng)).collect();
+    /// let proof = ProofOfPossession::for_pattern(
+    ///     &pattern,
+    ///     &secrets,
+    ///     &mut Transcript::new(b"refresh"),
+    ///     &mut rng,
+    /// );
+    ///
+    /// let public_poly: Vec<_> = [Ristretto::identity()]
+    ///     .into_iter()
+    ///     .chain(secrets.iter().map(|secret| Ristretto::mul_generator(secret.expose_scalar())))
+    ///     .collect();
+    /// proof.verify_pattern(&pattern, &public_poly, &mut Transcript::new(b"refresh"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_pattern<R: CryptoRng + RngCore>(
+        pattern: &CoefficientPattern<G>,
+        secrets: &[SecretKey<G>],
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> Self {
+        assert_eq!(
+            secrets.len(),
+            pattern.secret_count(),
+            "number of secrets differs from the number of secret coefficients in the pattern"
+        );
+
+        let mut secrets_iter = secrets.iter();
+        let elements = pattern.entries.iter().map(|entry| match entry {
+            Some(known) => *known,
+            None => G::mul_generator(secrets_iter.next().unwrap().expose_scalar()),
+        });
+        pattern.commit(elements, transcript);
+        Self::prove_committed(secrets.iter(), secrets.len(), transcript, rng)
+    }
+
+    /// Verifies this proof against the public polynomial with the coefficient `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `public_polynomial` does not match the `pattern` (i.e., has
+    /// a differing length, or a known coefficient with an unexpected value), or if this proof
+    /// does not verify.
+    pub fn verify_pattern(
+        &self,
+        pattern: &CoefficientPattern<G>,
+        public_polynomial: &[G::Element],
+        transcript: &mut Transcript,
+    ) -> Result<(), VerificationError> {
+        VerificationError::check_lengths(
+            "polynomial coefficients",
+            pattern.entries.len(),
+            public_polynomial.len(),
+        )?;
+        for (index, (entry, element)) in pattern.entries.iter().zip(public_polynomial).enumerate() {
+            if matches!(entry, Some(known) if known != element) {
+                return Err(VerificationError::PatternMismatch { index });
+            }
+        }
+        VerificationError::check_lengths(
+            "secret coefficients",
+            self.responses.len(),
+            pattern.secret_count(),
+        )?;
+
+        pattern.commit(public_polynomial.iter().copied(), transcript);
+        let secret_elements = pattern
+            .entries
+            .iter()
+            .zip(public_polynomial)
+            .filter_map(|(entry, element)| entry.is_none().then_some(*element));
+        self.verify_committed(secret_elements, transcript)
+    }
+}
+
+/// Pattern of polynomial coefficients for [`ProofOfPossession`]: each coefficient is either
+/// secret (knowledge of it is proven), or known to the verifier (e.g., fixed to zero).
+///
+/// Patterns are useful for protocols such as key refresh and resharing, in which
+/// polynomials have some coefficients fixed; see [`ProofOfPossession::for_pattern()`].
+#[derive(Debug, Clone)]
+pub struct CoefficientPattern<G: Group> {
+    /// Known coefficients multiplied by the group generator; `None` for secret coefficients.
+    entries: Vec<Option<G::Element>>,
+}
+
+impl<G: Group> CoefficientPattern<G> {
+    /// Creates a pattern with `len` coefficients, all of which are secret.
+    pub fn new(len: usize) -> Self {
+        Self {
+            entries: vec![None; len],
+        }
+    }
+
+    /// Declares the coefficient at `index` known and equal to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn with_known(mut self, index: usize, value: G::Scalar) -> Self {
+        self.entries[index] = Some(G::mul_generator(&value));
+        self
+    }
+
+    /// Declares the coefficient at `index` to be zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn with_zero(mut self, index: usize) -> Self {
+        self.entries[index] = Some(G::identity());
+        self
+    }
+
+    /// Returns the total number of coefficients in this pattern.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether this pattern is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Checks whether the coefficient at `index` is secret. Returns `false` if `index`
+    /// is out of bounds.
+    pub fn is_secret(&self, index: usize) -> bool {
+        matches!(self.entries.get(index), Some(None))
+    }
+
+    /// Returns the number of secret coefficients in this pattern.
+    pub fn secret_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.is_none()).count()
+    }
+
+    fn commit(&self, elements: impl Iterator<Item = G::Element>, transcript: &mut Transcript) {
+        transcript.start_proof(proto_labels::PATTERN_PROOF_OF_POSSESSION);
+        transcript.append_u64(b"len", self.entries.len() as u64);
+        for (entry, element) in self.entries.iter().zip(elements) {
+            let label: &'static [u8] = if entry.is_some() { b"k" } else { b"K" };
+            transcript.append_element::<G>(label, &element);
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for ProofOfPossession<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for ProofOfPossession<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.proof-of-possession+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.scalar::<G>(&self.challenge);
+        encoder.scalars::<G>(&self.responses);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        Ok(Self {
+            challenge: decoder.scalar::<G>()?,
+            responses: decoder.scalars::<G>()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
 
     use super::*;
-    use crate::group::Ristretto;
+    use crate::{
+        curve25519::scalar::Scalar as Scalar25519,
+        group::{ElementOps, Ristretto},
+    };
 
     type Keypair = crate::Keypair<Ristretto>;
 
@@ -185,4 +400,63 @@ mod tests {
             )
             .unwrap();
     }
+
+    #[test]
+    fn proof_of_possession_for_pattern() {
+        let mut rng = thread_rng();
+        let known_value = Scalar25519::from(5_u64);
+        let pattern = CoefficientPattern::<Ristretto>::new(4)
+            .with_zero(1)
+            .with_known(3, known_value);
+        assert_eq!(pattern.secret_count(), 2);
+        assert!(pattern.is_secret(0) && pattern.is_secret(2));
+        assert!(!pattern.is_secret(1) && !pattern.is_secret(4));
+
+        let secrets: Vec<_> = (0..2).map(|_| SecretKey::generate(&mut rng)).collect();
+        let proof = ProofOfPossession::for_pattern(
+            &pattern,
+            &secrets,
+            &mut Transcript::new(b"test_pattern_PoP"),
+            &mut rng,
+        );
+        let mut public_poly = vec![
+            Ristretto::mul_generator(secrets[0].expose_scalar()),
+            Ristretto::identity(),
+            Ristretto::mul_generator(secrets[1].expose_scalar()),
+            Ristretto::mul_generator(&known_value),
+        ];
+        proof
+            .verify_pattern(
+                &pattern,
+                &public_poly,
+                &mut Transcript::new(b"test_pattern_PoP"),
+            )
+            .unwrap();
+
+        let all_secret_pattern = CoefficientPattern::new(4);
+        let err = proof
+            .verify_pattern(
+                &all_secret_pattern,
+                &public_poly,
+                &mut Transcript::new(b"test_pattern_PoP"),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, VerificationError::LenMismatch { .. }),
+            "{err:?}"
+        );
+
+        public_poly[1] = Ristretto::generator();
+        let err = proof
+            .verify_pattern(
+                &pattern,
+                &public_poly,
+                &mut Transcript::new(b"test_pattern_PoP"),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, VerificationError::PatternMismatch { index: 1 }),
+            "{err:?}"
+        );
+    }
 }
@@ -9,12 +9,14 @@ use zeroize::Zeroizing;
 
 use core::{convert::TryFrom, fmt};
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 use crate::{
     alloc::{vec, HashMap, ToString, Vec},
     encryption::{CiphertextWithValue, ExtendedCiphertext},
     group::Group,
     proofs::{RingProof, RingProofBuilder, TranscriptForGroup},
-    Ciphertext, PublicKey, VerificationError,
+    proto_labels, Ciphertext, PublicKey, VerificationError,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -490,7 +492,7 @@ impl<G: Group> RangeProof<G> {
     ) -> Self {
         let value_indexes = range.decompose(*ciphertext.value());
         debug_assert_eq!(value_indexes.len(), range.admissible_values.len());
-        transcript.start_proof(b"encryption_range_proof");
+        transcript.start_proof(proto_labels::RANGE_PROOF);
         transcript.append_message(b"range", range.inner.to_string().as_bytes());
 
         let ring_responses_size = usize::try_from(range.inner.rings_size())
@@ -560,7 +562,7 @@ impl<G: Group> RangeProof<G> {
             range.admissible_values.len(),
         )?;
 
-        transcript.start_proof(b"encryption_range_proof");
+        transcript.start_proof(proto_labels::RANGE_PROOF);
         transcript.append_message(b"range", range.inner.to_string().as_bytes());
 
         let ciphertext_sum = self
@@ -579,6 +581,35 @@ impl<G: Group> RangeProof<G> {
     }
 }
 
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for RangeProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for RangeProof<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.range-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.array(self.partial_ciphertexts.len());
+        for ciphertext in &self.partial_ciphertexts {
+            ciphertext.encode(encoder);
+        }
+        self.inner.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        let ciphertext_count = decoder.array()?;
+        let partial_ciphertexts = (0..ciphertext_count)
+            .map(|_| Ciphertext::decode(decoder))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            partial_ciphertexts,
+            inner: RingProof::decode(decoder)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{thread_rng, Rng};
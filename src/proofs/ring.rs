@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 
 use core::{fmt, mem};
 
+#[cfg(feature = "cbor")]
+use crate::cbor::{CborEncoding, CborError, Decoder, Encoder};
 #[cfg(feature = "serde")]
 use crate::serde::{ScalarHelper, VecHelper};
 use crate::{
@@ -14,7 +16,7 @@ use crate::{
     encryption::ExtendedCiphertext,
     group::Group,
     proofs::{TranscriptForGroup, VerificationError},
-    Ciphertext, PublicKey, SecretKey,
+    proto_labels, Ciphertext, PublicKey, SecretKey,
 };
 
 /// An incomplete ring proving that the encrypted value is in the a priori known set of
@@ -87,7 +89,7 @@ impl<'a, G: Group> Ring<'a, G> {
         );
 
         let mut transcript = transcript.clone();
-        transcript.start_proof(b"ring_enc");
+        transcript.start_proof(proto_labels::RING_ENCRYPTION);
         transcript.append_message(b"enc", &ciphertext.inner.to_bytes());
         // NB: we don't add `admissible_values` to the transcript since we assume that
         // they are fixed in the higher-level protocol.
@@ -141,22 +143,27 @@ impl<'a, G: Group> Ring<'a, G> {
         transcript: &mut Transcript,
         rng: &mut R,
     ) -> G::Scalar {
-        debug_assert!(
-            rings.iter().enumerate().all(|(i, ring)| i == ring.index),
-            "Rings have bogus indexes"
-        );
+        let common_challenge = Self::common_challenge(&rings, transcript);
+        for ring in rings {
+            ring.finalize(log_base, common_challenge, rng);
+        }
+        common_challenge
+    }
 
-        for ring in &rings {
+    fn common_challenge<'r>(
+        rings: impl IntoIterator<Item = &'r Self>,
+        transcript: &mut Transcript,
+    ) -> G::Scalar
+    where
+        'a: 'r,
+    {
+        for (i, ring) in rings.into_iter().enumerate() {
+            debug_assert_eq!(i, ring.index, "Rings have bogus indexes");
             let commitments = &ring.terminal_commitments;
             transcript.append_element::<G>(b"R_G", &commitments.0);
             transcript.append_element::<G>(b"R_K", &commitments.1);
         }
-
-        let common_challenge = transcript.challenge_scalar::<G>(b"c");
-        for ring in rings {
-            ring.finalize(log_base, common_challenge, rng);
-        }
-        common_challenge
+        transcript.challenge_scalar::<G>(b"c")
     }
 
     fn finalize<R: CryptoRng + RngCore>(
@@ -273,6 +280,18 @@ impl<'a, G: Group> Ring<'a, G> {
 ///   and ring indexes, but not to the admissible values across the rings. This must be taken
 ///   care of in a higher-level protocol, and this is the case for protocols exposed by the crate.
 ///
+/// ## Batch verification
+///
+/// Unlike Schnorr-style proofs that carry their commitments, ring proofs cannot be verified
+/// in a batch by folding their equations into a single multi-scalar multiplication.
+/// In the compact form used here, a proof only contains the challenge `e_0`
+/// and the responses; each subsequent challenge `e_{j+1}` is derived by hashing
+/// the commitments `R_G(j)`, `R_K(j)` restored from the previous equation. Thus, the verifier
+/// must compute every commitment eagerly before it knows the scalars for the next equation,
+/// and there are no multiplications left to defer into an accumulator. Supporting batch
+/// verification would require a different proof format that includes all commitments,
+/// roughly tripling the proof size.
+///
 /// [`LogEqualityProof`]: crate::LogEqualityProof
 /// [Borromean ring signatures]: https://raw.githubusercontent.com/Blockstream/borromean_paper/master/borromean_draft_0.01_34241bb.pdf
 /// [ring]: https://link.springer.com/content/pdf/10.1007/3-540-36178-2_26.pdf
@@ -288,7 +307,7 @@ pub struct RingProof<G: Group> {
 
 impl<G: Group> RingProof<G> {
     fn initialize_transcript(transcript: &mut Transcript, receiver: &PublicKey<G>) {
-        transcript.start_proof(b"multi_ring_enc");
+        transcript.start_proof(proto_labels::MULTI_RING_ENCRYPTION);
         transcript.append_element_bytes(b"K", receiver.as_bytes());
     }
 
@@ -326,7 +345,7 @@ impl<G: Group> RingProof<G> {
             let mut commitments = (G::generator(), G::generator());
 
             let mut ring_transcript = initial_ring_transcript.clone();
-            ring_transcript.start_proof(b"ring_enc");
+            ring_transcript.start_proof(proto_labels::RING_ENCRYPTION);
             ring_transcript.append_message(b"enc", &ciphertext.to_bytes());
             ring_transcript.append_u64(b"i", ring_index as u64);
 
@@ -414,6 +433,84 @@ impl<G: Group> RingProof<G> {
     }
 }
 
+/// Parallel version of [`RingProofBuilder`] operating on all rings at once.
+#[cfg(feature = "rayon")]
+impl<G: Group> RingProof<G>
+where
+    G::Scalar: Send + Sync,
+    G::Element: Send + Sync,
+{
+    /// Encrypts `values` (each specified as admissible values and the index of the encrypted
+    /// value among them) and builds a proof for the produced ciphertexts, creating and finalizing
+    /// rings in parallel. The produced proof is indistinguishable from one produced
+    /// by a [`RingProofBuilder`].
+    ///
+    /// Since rings are created on different threads, each ring gets its own RNG seeded from `rng`.
+    pub(crate) fn build_parallel<R: CryptoRng + RngCore>(
+        receiver: &PublicKey<G>,
+        values: &[(&[G::Element], usize)],
+        transcript: &mut Transcript,
+        rng: &mut R,
+    ) -> (Self, Vec<ExtendedCiphertext<G>>) {
+        use rand_chacha::ChaChaRng;
+        use rand_core::SeedableRng;
+        use rayon::prelude::*;
+
+        Self::initialize_transcript(transcript, receiver);
+        let log_base = receiver.as_element();
+        let total_size: usize = values.iter().map(|(admissible, _)| admissible.len()).sum();
+        let mut ring_responses = vec![G::Scalar::default(); total_size];
+
+        let mut rest = ring_responses.as_mut_slice();
+        let ring_inputs: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(index, &(admissible_values, value_index))| {
+                let (responses, tail) = mem::take(&mut rest).split_at_mut(admissible_values.len());
+                rest = tail;
+                let mut seed = <ChaChaRng as SeedableRng>::Seed::default();
+                rng.fill_bytes(&mut seed);
+                (index, admissible_values, value_index, responses, seed)
+            })
+            .collect();
+
+        let ring_transcript = &*transcript;
+        let rings_with_rngs: Vec<_> = ring_inputs
+            .into_par_iter()
+            .map(|(index, admissible_values, value_index, responses, seed)| {
+                let mut ring_rng = ChaChaRng::from_seed(seed);
+                let ciphertext = ExtendedCiphertext::new(
+                    admissible_values[value_index],
+                    receiver,
+                    &mut ring_rng,
+                );
+                let partial_ring = Ring::new(
+                    index,
+                    log_base,
+                    ciphertext.clone(),
+                    admissible_values,
+                    value_index,
+                    ring_transcript,
+                    responses,
+                    &mut ring_rng,
+                );
+                (partial_ring, ciphertext, ring_rng)
+            })
+            .collect();
+
+        let common_challenge =
+            Ring::common_challenge(rings_with_rngs.iter().map(|(ring, ..)| ring), transcript);
+        let ciphertexts = rings_with_rngs
+            .into_par_iter()
+            .map(|(ring, ciphertext, mut ring_rng)| {
+                ring.finalize(log_base, common_challenge, &mut ring_rng);
+                ciphertext
+            })
+            .collect();
+        (Self::new(common_challenge, ring_responses), ciphertexts)
+    }
+}
+
 /// **NB.** Separate method calls of the builder depend on the position of the encrypted values
 /// within admissible ones. This means that if a proof is constructed with interruptions between
 /// method calls, there is a chance for an adversary to perform a timing attack.
@@ -506,6 +603,28 @@ impl<'a, G: Group, R: RngCore + CryptoRng> RingProofBuilder<'a, G, R> {
     }
 }
 
+#[cfg(feature = "cbor")]
+impl<G: Group> crate::sealed::Sealed for RingProof<G> {}
+
+#[cfg(feature = "cbor")]
+impl<G: Group> CborEncoding for RingProof<G> {
+    const CONTENT_TYPE: &'static str = "application/x.elastic-elgamal.ring-proof+cbor";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.array(2);
+        encoder.scalar::<G>(&self.common_challenge);
+        encoder.scalars::<G>(&self.ring_responses);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CborError> {
+        decoder.array_of_len(2)?;
+        Ok(Self {
+            common_challenge: decoder.scalar::<G>()?,
+            ring_responses: decoder.scalars::<G>()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{thread_rng, Rng};
@@ -0,0 +1,260 @@
+//! Labels used in [`merlin`] transcripts of zero-knowledge proofs and protocols
+//! implemented in this crate, and domain separators of SHA-256 digests
+//! (fingerprints, checksums, etc.) computed by the crate.
+//!
+//! All proofs in this crate are made non-interactive using the Fiat–Shamir transform,
+//! with the challenge derived from a transcript. A proof only verifies if the verifier
+//! constructs the transcript in the same way as the prover, including its labels.
+//! This module lists the labels so that they can be inspected (e.g., when implementing
+//! a verifier in another language) rather than copied from the source code.
+//!
+//! Labels are versioned as a whole via [`VERSION`]. The version is incremented whenever
+//! labels or the transcript layout change in an incompatible way, so that proofs created
+//! with another version will not verify. The version (together with [`CRATE_VERSION`])
+//! is included into the human-readable description of
+//! [`VerificationError::ChallengeMismatch`](crate::VerificationError::ChallengeMismatch)
+//! to simplify diagnosing such mismatches.
+//!
+//! Labels of proofs for which the caller supplies a transcript (e.g., [`LogEqualityProof`])
+//! are *domain separators* appended to that transcript; they do not replace
+//! application-specific labels of the caller's transcript.
+//!
+//! [`LogEqualityProof`]: crate::LogEqualityProof
+
+/// Version of the labels and transcript layout.
+pub const VERSION: u32 = 1;
+
+/// Version of this crate.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Domain separators for proofs.
+
+/// Domain separator for [`LogEqualityProof`](crate::LogEqualityProof)s.
+pub const LOG_EQUALITY: &[u8] = b"log_eq";
+/// Domain separator for [`RepeatedLogEqualityProof`](crate::RepeatedLogEqualityProof)s.
+pub const REPEATED_LOG_EQUALITY: &[u8] = b"rep_log_eq";
+/// Domain separator for a single ring in [`RingProof`](crate::RingProof)s.
+pub const RING_ENCRYPTION: &[u8] = b"ring_enc";
+/// Domain separator for [`RingProof`](crate::RingProof)s.
+pub const MULTI_RING_ENCRYPTION: &[u8] = b"multi_ring_enc";
+/// Domain separator for [`ProofOfPossession`](crate::ProofOfPossession)s.
+pub const PROOF_OF_POSSESSION: &[u8] = b"multi_pop";
+/// Domain separator for proofs of possession of a polynomial with
+/// a [`CoefficientPattern`](crate::CoefficientPattern).
+pub const PATTERN_PROOF_OF_POSSESSION: &[u8] = b"pattern_pop";
+/// Domain separator for [`SumOfSquaresProof`](crate::SumOfSquaresProof)s.
+pub const SUM_OF_SQUARES: &[u8] = b"sum_of_squares";
+/// Domain separator for [`LinearCombinationProof`](crate::LinearCombinationProof)s.
+pub const LINEAR_COMBINATION: &[u8] = b"linear_combination";
+/// Domain separator for [`CommitmentEquivalenceProof`](crate::CommitmentEquivalenceProof)s.
+pub const COMMITMENT_EQUIVALENCE: &[u8] = b"commitment_equivalence";
+/// Domain separator for [`RangeProof`](crate::RangeProof)s.
+pub const RANGE_PROOF: &[u8] = b"encryption_range_proof";
+/// Domain separator for [`EligibilityProof`](crate::app::EligibilityProof)s.
+pub const ELIGIBILITY: &[u8] = b"eligibility";
+/// Domain separator for decryptions with a custom key
+/// ([`VerifiableDecryption::new()`](crate::VerifiableDecryption::new())).
+pub const CUSTOM_KEY_DECRYPTION: &[u8] = b"decryption_with_custom_key";
+
+// Transcripts for encryption.
+
+/// Transcript for encryptions of zero with a proof.
+pub const ZERO_ENCRYPTION: &[u8] = b"zero_encryption";
+/// Transcript for encryptions of Boolean values with a proof.
+pub const BOOL_ENCRYPTION: &[u8] = b"bool_encryption";
+/// Transcript for encryptions of values in a range with a proof.
+pub const CIPHERTEXT_RANGE: &[u8] = b"ciphertext_range";
+/// Transcript for encryptions of signed values in a range with a proof.
+pub const SIGNED_CIPHERTEXT_RANGE: &[u8] = b"signed_ciphertext_range";
+/// Transcript for [`SealedEncryption`](crate::SealedEncryption)s.
+pub const SEALED_ENCRYPTION: &[u8] = b"sealed_encryption";
+/// Transcript for deriving typed keys from a master secret.
+pub const KEY_DERIVATION: &[u8] = b"elgamal_key_derivation";
+/// Transcript for deriving RNGs in [`DerivedRngSource`](crate::rng::DerivedRngSource).
+pub const RNG_DERIVATION: &[u8] = b"elgamal_rng_derivation";
+
+// Transcripts for applications.
+
+/// Transcript for sum proofs of single-choice [`EncryptedChoice`](crate::app::EncryptedChoice)s.
+pub const CHOICE_SUM: &[u8] = b"choice_encryption_sum";
+/// Transcript for sum proofs of [`EncryptedChoice`](crate::app::EncryptedChoice)s
+/// with an exact number of selected options.
+pub const CHOICE_EXACT_SUM: &[u8] = b"choice_encryption_exact_sum";
+/// Transcript for range proofs of [`EncryptedChoice`](crate::app::EncryptedChoice)s.
+pub const CHOICE_RANGES: &[u8] = b"encrypted_choice_ranges";
+/// Transcript for chunks of encrypted choices ([`ChoiceChunk`](crate::app::ChoiceChunk)).
+pub const CHOICE_CHUNKS: &[u8] = b"encrypted_choice_chunks";
+/// Transcript for [`EligibleChoice`](crate::app::EligibleChoice)s.
+pub const CHOICE_ELIGIBILITY: &[u8] = b"encrypted_choice_eligibility";
+/// Transcript for XOR operations on [`EncryptedBit`](crate::app::EncryptedBit)s.
+pub const BIT_XOR: &[u8] = b"encrypted_bit_xor";
+/// Transcript for variant proofs in [`QuadraticVotingBallot`](crate::app::QuadraticVotingBallot)s.
+pub const QUADRATIC_VOTING_VARIANT: &[u8] = b"quadratic_voting_variant";
+/// Transcript for credit range proofs in
+/// [`QuadraticVotingBallot`](crate::app::QuadraticVotingBallot)s.
+pub const QUADRATIC_VOTING_CREDIT_RANGE: &[u8] = b"quadratic_voting_credit_range";
+/// Transcript for credit equivalence proofs in
+/// [`QuadraticVotingBallot`](crate::app::QuadraticVotingBallot)s.
+pub const QUADRATIC_VOTING_CREDIT_EQUIVALENCE: &[u8] = b"quadratic_voting_credit_equiv";
+
+// Transcripts for threshold / multi-party protocols.
+
+/// Transcript for the key of the [two-party](crate::two_party) protocol.
+pub const TWO_PARTY_KEY: &[u8] = b"elgamal_two_party_key";
+/// Transcript for decryption shares in the [two-party](crate::two_party) protocol.
+pub const TWO_PARTY_DECRYPTION: &[u8] = b"elgamal_two_party_decryption";
+/// Transcript for binding the [DKG](crate::dkg) output to a VDF evaluation.
+pub const DKG_VDF: &[u8] = b"elgamal_dkg_vdf";
+/// Transcript for encrypting secret shares in the [aggregatable DKG](crate::dkg::AggregatableDkg).
+pub const AGGREGATABLE_DKG: &[u8] = b"elgamal_aggregatable_dkg";
+/// Transcript for [signed DKG messages](crate::dkg::SignedDkgMessage).
+pub const DKG_MESSAGE: &[u8] = b"elgamal_dkg_message";
+/// Transcript for complaints in the [aggregatable DKG](crate::dkg::AggregatableDkg).
+pub const DKG_COMPLAINT: &[u8] = b"elgamal_dkg_complaint";
+/// Transcript for proofs of possession of dealer (or DKG participant) polynomials.
+pub const SHARE_POLYNOMIAL: &[u8] = b"elgamal_share_poly";
+/// Transcript for proofs of possession of participant key shares.
+pub const PARTICIPANT_POP: &[u8] = b"elgamal_participant_pop";
+/// Transcript for threshold decryption shares.
+pub const DECRYPTION_SHARE: &[u8] = b"elgamal_decryption_share";
+/// Transcript for threshold decryption shares bound to a combination subset.
+pub const SUBSET_DECRYPTION_SHARE: &[u8] = b"elgamal_subset_decryption_share";
+/// Transcript for participant signatures made with identity keys.
+pub const PARTICIPANT_SIGNATURE: &[u8] = b"elgamal_participant_sig";
+/// Transcript for threshold re-encryption shares.
+pub const REENCRYPTION_SHARE: &[u8] = b"elgamal_reencryption_share";
+/// Transcript for signed digests of participant message logs.
+pub const MESSAGE_LOG: &[u8] = b"elgamal_message_log";
+/// Transcript for approvals of threshold decryption requests.
+pub const DECRYPTION_REQUEST: &[u8] = b"elgamal_decryption_request";
+/// Transcript for deriving test ciphertexts for liveness checks of threshold participants.
+pub const LIVENESS_CHECK: &[u8] = b"elgamal_liveness_check";
+
+// Domain separators for SHA-256 digests.
+
+/// Domain separator for [`ObjectHash`](crate::ObjectHash)es of serializable objects.
+pub const OBJECT_HASH: &[u8] = b"elastic_elgamal_object_hash";
+/// Domain separator for [`KeyFingerprint`](crate::KeyFingerprint)s of public keys.
+pub const KEY_FINGERPRINT: &[u8] = b"elastic_elgamal_key_fingerprint";
+/// Domain separator for messages signed in key certificates.
+pub const CERTIFICATE: &[u8] = b"elastic_elgamal_certificate";
+/// Domain separator for shared secrets derived in the [KEM](crate::PublicKey::encapsulate()).
+pub const KEM_SECRET: &[u8] = b"elastic_elgamal_kem";
+/// Domain separator for checksums in the text encoding of keys and ciphertexts.
+pub const TEXT_CHECKSUM: &[u8] = b"elastic_elgamal_text";
+/// Domain separator for selecting jumps in the kangaroo discrete log algorithm.
+pub const KANGAROO_JUMP: &[u8] = b"elastic_elgamal_kangaroo";
+/// Domain separator for group tags of memory-mapped discrete log tables.
+pub const DLOG_TABLE_GROUP: &[u8] = b"elastic_elgamal_dlog_group";
+/// Domain separator for entry keys of memory-mapped discrete log tables.
+pub const DLOG_TABLE_ENTRY: &[u8] = b"elastic_elgamal_dlog_entry";
+/// Domain separator for ciphertext lists in verifiable mixes.
+pub const MIX: &[u8] = b"elastic_elgamal_mix";
+/// Domain separator for the initial digest of a running tally.
+pub const RUNNING_TALLY: &[u8] = b"elastic_elgamal_running_tally";
+/// Domain separator for running tally updates.
+pub const TALLY_UPDATE: &[u8] = b"elastic_elgamal_tally_update";
+/// Domain separator for ballot box commitments in the `verifier` binary.
+pub const BALLOT_BOX: &[u8] = b"elastic_elgamal_ballot_box";
+/// Domain separator for VDF inputs in the [DKG](crate::dkg).
+pub const DKG_VDF_INPUT: &[u8] = b"elastic_elgamal_dkg_vdf";
+/// Domain separator for [`Generation`](crate::sharing::Generation)s of public key sets.
+pub const KEY_SET_GENERATION: &[u8] = b"elastic_elgamal_key_set_generation";
+/// Domain separator for [`KeySetFingerprint`](crate::sharing::KeySetFingerprint)s.
+pub const KEY_SET_FINGERPRINT: &[u8] = b"elastic_elgamal_key_set_fingerprint";
+/// Domain separator for digests of committee configurations.
+pub const COMMITTEE_CONFIG: &[u8] = b"elastic_elgamal_committee_config";
+/// Domain separator for checksums of secret share backups.
+pub const SHARE_BACKUP: &[u8] = b"elastic_elgamal_share_backup";
+/// Domain separator for the initial head of participant message logs.
+pub const LOG_HEAD: &[u8] = b"elastic_elgamal_log";
+/// Domain separator for messages in participant message logs.
+pub const LOG_MESSAGE: &[u8] = b"elastic_elgamal_log_message";
+/// Domain separator for entries in participant message logs.
+pub const LOG_ENTRY: &[u8] = b"elastic_elgamal_log_entry";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc::{ToString, Vec};
+
+    #[test]
+    fn labels_are_unique() {
+        let labels = [
+            LOG_EQUALITY,
+            REPEATED_LOG_EQUALITY,
+            RING_ENCRYPTION,
+            MULTI_RING_ENCRYPTION,
+            PROOF_OF_POSSESSION,
+            PATTERN_PROOF_OF_POSSESSION,
+            SUM_OF_SQUARES,
+            LINEAR_COMBINATION,
+            COMMITMENT_EQUIVALENCE,
+            RANGE_PROOF,
+            ELIGIBILITY,
+            CUSTOM_KEY_DECRYPTION,
+            ZERO_ENCRYPTION,
+            BOOL_ENCRYPTION,
+            CIPHERTEXT_RANGE,
+            SIGNED_CIPHERTEXT_RANGE,
+            SEALED_ENCRYPTION,
+            KEY_DERIVATION,
+            RNG_DERIVATION,
+            CHOICE_SUM,
+            CHOICE_EXACT_SUM,
+            CHOICE_RANGES,
+            CHOICE_CHUNKS,
+            CHOICE_ELIGIBILITY,
+            BIT_XOR,
+            QUADRATIC_VOTING_VARIANT,
+            QUADRATIC_VOTING_CREDIT_RANGE,
+            QUADRATIC_VOTING_CREDIT_EQUIVALENCE,
+            TWO_PARTY_KEY,
+            TWO_PARTY_DECRYPTION,
+            DKG_VDF,
+            AGGREGATABLE_DKG,
+            DKG_MESSAGE,
+            DKG_COMPLAINT,
+            SHARE_POLYNOMIAL,
+            PARTICIPANT_POP,
+            DECRYPTION_SHARE,
+            SUBSET_DECRYPTION_SHARE,
+            PARTICIPANT_SIGNATURE,
+            REENCRYPTION_SHARE,
+            MESSAGE_LOG,
+            DECRYPTION_REQUEST,
+            LIVENESS_CHECK,
+            OBJECT_HASH,
+            KEY_FINGERPRINT,
+            CERTIFICATE,
+            KEM_SECRET,
+            TEXT_CHECKSUM,
+            KANGAROO_JUMP,
+            DLOG_TABLE_GROUP,
+            DLOG_TABLE_ENTRY,
+            MIX,
+            RUNNING_TALLY,
+            TALLY_UPDATE,
+            BALLOT_BOX,
+            DKG_VDF_INPUT,
+            KEY_SET_GENERATION,
+            KEY_SET_FINGERPRINT,
+            COMMITTEE_CONFIG,
+            SHARE_BACKUP,
+            LOG_HEAD,
+            LOG_MESSAGE,
+            LOG_ENTRY,
+        ];
+        let mut unique_labels: Vec<_> = labels.to_vec();
+        unique_labels.sort_unstable();
+        unique_labels.dedup();
+        assert_eq!(unique_labels.len(), labels.len());
+    }
+
+    #[test]
+    fn challenge_mismatch_mentions_labels_version() {
+        let message = crate::VerificationError::ChallengeMismatch.to_string();
+        assert!(message.contains("transcript labels v1"), "{message}");
+        assert!(message.contains(CRATE_VERSION), "{message}");
+    }
+}
@@ -0,0 +1,262 @@
+//! Hardened derivation of randomness for encryption and proofs.
+//!
+//! All randomized operations in this crate accept an RNG implementing [`CryptoRng`] `+`
+//! [`RngCore`]. If the RNG state is duplicated (e.g., when a virtual machine is snapshotted
+//! and resumed several times, or a process is forked after seeding a user-space RNG),
+//! the same randomness may be reused for different messages. For ElGamal, this is fatal:
+//! two ciphertexts with the same random scalar reveal the difference of plaintexts,
+//! and proofs with a repeated nonce leak the secret.
+//!
+//! Forking cannot be reliably detected from within the process. Instead, [`RngSource`]s
+//! defined in this module derive an RNG for each operation from a secret key, a counter
+//! and the message being processed, similar to deterministic nonces in [RFC 6979].
+//! Even if the source state is duplicated, RNGs for different messages are independent.
+//! The [hedged](DerivedRngSource::hedged()) mode additionally mixes in fresh entropy, so that
+//! randomness is not fully deterministic if the same message is processed repeatedly.
+//!
+//! [RFC 6979]: https://www.rfc-editor.org/rfc/rfc6979
+//!
+//! # Examples
+//!
+//! ```
+//! # use elastic_elgamal::{group::Ristretto, rng::*, DiscreteLogTable, Keypair};
+//! # use rand::thread_rng;
+//! let keypair = Keypair::<Ristretto>::generate(&mut thread_rng());
+//! let mut source = DerivedRngSource::new([7; 32]).hedged(thread_rng());
+//!
+//! let value = 5_u64;
+//! let mut rng = source.rng_for(b"ballot", &value.to_le_bytes());
+//! let ciphertext = keypair.public().encrypt(value, &mut rng);
+//! let lookup_table = DiscreteLogTable::new(0..10);
+//! assert_eq!(keypair.secret().decrypt(ciphertext, &lookup_table), Some(5));
+//! ```
+
+use merlin::Transcript;
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, Error as RngError, RngCore, SeedableRng};
+use zeroize::Zeroizing;
+
+use core::fmt;
+
+use crate::{alloc::vec, group::Group, proto_labels, SecretKey};
+
+/// Source of RNGs for individual randomized operations.
+pub trait RngSource {
+    /// RNG produced by this source.
+    type Rng: CryptoRng + RngCore;
+
+    /// Creates an RNG for processing the `message` in the operation identified
+    /// by the `label` (e.g., encrypting a ballot). The message should contain all data
+    /// the operation depends on, such as the plaintext.
+    fn rng_for(&mut self, label: &[u8], message: &[u8]) -> Self::Rng;
+}
+
+/// RNG produced by [`DerivedRngSource`] and [`HedgedRngSource`].
+pub struct DerivedRng(ChaChaRng);
+
+impl fmt::Debug for DerivedRng {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("DerivedRng").finish_non_exhaustive()
+    }
+}
+
+impl RngCore for DerivedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for DerivedRng {}
+
+/// Deterministic [`RngSource`] deriving an RNG for each operation from a secret key,
+/// an operation counter and the processed message.
+///
+/// Sources can be derived [hierarchically](Self::child()), e.g., to obtain independent
+/// sources for each election from a single master key. The source is intentionally
+/// not [`Clone`]able: a cloned source would produce the same RNGs.
+pub struct DerivedRngSource {
+    key: Zeroizing<[u8; 32]>,
+    counter: u64,
+}
+
+impl fmt::Debug for DerivedRngSource {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("DerivedRngSource")
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DerivedRngSource {
+    /// Creates a source with the specified secret `key`.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key: Zeroizing::new(key),
+            counter: 0,
+        }
+    }
+
+    /// Derives a source from the `secret` key and the `context`.
+    pub fn from_secret<G: Group>(secret: &SecretKey<G>, context: &[u8]) -> Self {
+        let mut secret_bytes = Zeroizing::new(vec![0_u8; G::SCALAR_SIZE]);
+        G::serialize_scalar(secret.expose_scalar(), &mut secret_bytes);
+        let mut transcript = Transcript::new(proto_labels::RNG_DERIVATION);
+        transcript.append_message(b"op", b"from_secret");
+        transcript.append_message(b"sk", &secret_bytes);
+        transcript.append_message(b"ctx", context);
+        Self::from_transcript(&mut transcript)
+    }
+
+    fn from_transcript(transcript: &mut Transcript) -> Self {
+        let mut key = Zeroizing::new([0_u8; 32]);
+        transcript.challenge_bytes(b"key", &mut *key);
+        Self { key, counter: 0 }
+    }
+
+    /// Sets the operation counter, e.g., to resume the source from a persisted state.
+    #[must_use]
+    pub fn with_counter(mut self, counter: u64) -> Self {
+        self.counter = counter;
+        self
+    }
+
+    /// Returns the number of RNGs produced by this source (or the counter set
+    /// via [`Self::with_counter()`] plus the number of produced RNGs).
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Derives a child source with an independent key. Children with different `label`s
+    /// are independent from each other and from the parent.
+    #[must_use]
+    pub fn child(&self, label: &[u8]) -> Self {
+        let mut transcript = self.transcript(b"child");
+        transcript.append_message(b"label", label);
+        Self::from_transcript(&mut transcript)
+    }
+
+    /// Converts this source into a hedged one, which additionally mixes 32 bytes
+    /// from `entropy` into each derived RNG.
+    pub fn hedged<R: CryptoRng + RngCore>(self, entropy: R) -> HedgedRngSource<R> {
+        HedgedRngSource {
+            inner: self,
+            entropy,
+        }
+    }
+
+    fn transcript(&self, op: &'static [u8]) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::RNG_DERIVATION);
+        transcript.append_message(b"op", op);
+        transcript.append_message(b"key", &*self.key);
+        transcript
+    }
+
+    fn derive_rng(&mut self, label: &[u8], message: &[u8], entropy: &[u8]) -> DerivedRng {
+        let mut transcript = self.transcript(b"rng");
+        transcript.append_u64(b"ctr", self.counter);
+        transcript.append_message(b"label", label);
+        transcript.append_message(b"msg", message);
+        transcript.append_message(b"entropy", entropy);
+        self.counter += 1;
+
+        let mut seed = Zeroizing::new(<ChaChaRng as SeedableRng>::Seed::default());
+        transcript.challenge_bytes(b"seed", &mut *seed);
+        DerivedRng(ChaChaRng::from_seed(*seed))
+    }
+}
+
+impl RngSource for DerivedRngSource {
+    type Rng = DerivedRng;
+
+    fn rng_for(&mut self, label: &[u8], message: &[u8]) -> Self::Rng {
+        self.derive_rng(label, message, &[])
+    }
+}
+
+/// Hedged [`RngSource`] combining a [`DerivedRngSource`] with fresh entropy.
+///
+/// Derived RNGs are secure as long as either the key of the underlying source remains secret
+/// and messages or counters differ, or the entropy source is not compromised or duplicated.
+pub struct HedgedRngSource<R> {
+    inner: DerivedRngSource,
+    entropy: R,
+}
+
+impl<R> fmt::Debug for HedgedRngSource<R> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("HedgedRngSource")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R> HedgedRngSource<R> {
+    /// Returns the underlying deterministic source.
+    pub fn inner(&self) -> &DerivedRngSource {
+        &self.inner
+    }
+}
+
+impl<R: CryptoRng + RngCore> RngSource for HedgedRngSource<R> {
+    type Rng = DerivedRng;
+
+    fn rng_for(&mut self, label: &[u8], message: &[u8]) -> Self::Rng {
+        let mut entropy = Zeroizing::new([0_u8; 32]);
+        self.entropy.fill_bytes(&mut *entropy);
+        self.inner.derive_rng(label, message, &*entropy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    fn first_bytes(rng: &mut impl RngCore) -> [u8; 16] {
+        let mut bytes = [0_u8; 16];
+        rng.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn derived_rngs_depend_on_key_counter_and_message() {
+        let mut source = DerivedRngSource::new([1; 32]);
+        let first = first_bytes(&mut source.rng_for(b"test", b"message"));
+        assert_eq!(source.counter(), 1);
+        let second = first_bytes(&mut source.rng_for(b"test", b"message"));
+        assert_ne!(first, second);
+
+        // A duplicated source state produces the same RNG only for the same message.
+        let mut forked = DerivedRngSource::new([1; 32]);
+        assert_eq!(first_bytes(&mut forked.rng_for(b"test", b"message")), first);
+        let mut forked = DerivedRngSource::new([1; 32]);
+        assert_ne!(first_bytes(&mut forked.rng_for(b"test", b"other")), first);
+        let mut resumed = DerivedRngSource::new([1; 32]).with_counter(1);
+        assert_eq!(
+            first_bytes(&mut resumed.rng_for(b"test", b"message")),
+            second
+        );
+
+        let mut child = DerivedRngSource::new([1; 32]).child(b"child");
+        assert_ne!(first_bytes(&mut child.rng_for(b"test", b"message")), first);
+
+        let mut hedged = DerivedRngSource::new([1; 32]).hedged(thread_rng());
+        assert_ne!(first_bytes(&mut hedged.rng_for(b"test", b"message")), first);
+        assert_eq!(hedged.inner().counter(), 1);
+    }
+}
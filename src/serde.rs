@@ -13,7 +13,8 @@ use crate::{
     alloc::{vec, ToString, Vec},
     dkg::Opening,
     group::Group,
-    Keypair, PublicKey, SecretKey,
+    sharing::KeySetFingerprint,
+    KeyFingerprint, Keypair, ObjectHash, PublicKey, SecretKey,
 };
 
 fn serialize_bytes<S>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
@@ -104,6 +105,72 @@ impl<'de> Deserialize<'de> for Opening {
     }
 }
 
+impl Serialize for KeyFingerprint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyFingerprint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = deserialize_bytes(deserializer)?;
+        let bytes = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"8"))?;
+        Ok(Self(bytes))
+    }
+}
+
+impl Serialize for KeySetFingerprint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySetFingerprint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = deserialize_bytes(deserializer)?;
+        let bytes = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"16"))?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl Serialize for ObjectHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectHash {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = deserialize_bytes(deserializer)?;
+        let bytes = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"32"))?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
 impl<G: Group> Serialize for PublicKey<G> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -172,6 +239,26 @@ pub(crate) trait Helper: Serialize + DeserializeOwned {
     fn into_target(self) -> Self::Target;
 }
 
+/// Helper type to (de)serialize byte buffers.
+#[derive(Debug)]
+pub(crate) struct BytesHelper;
+
+impl BytesHelper {
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(bytes, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_bytes(deserializer)
+    }
+}
+
 /// Helper type to deserialize scalars.
 ///
 /// **NB.** Scalars are assumed to be public! Secret scalars must be serialized via `SecretKey`.
@@ -0,0 +1,515 @@
+//! Authorization of threshold decryption by a quorum of requesters.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::{fmt, iter};
+
+use crate::{
+    alloc::{String, Vec},
+    group::Group,
+    proofs::{ProofOfPossession, VerificationError},
+    proto_labels,
+    sharing::PublicKeySet,
+    Ciphertext, IdentityKeypair, IdentityPublicKey, PublicKey,
+};
+
+/// Policy requiring threshold decryption to be authorized by a quorum of requesters.
+///
+/// The policy lists identity keys of requesters (e.g., election administrators) and the number
+/// of them that must approve a [`DecryptionRequest`]. Once the policy is
+/// [attached](crate::sharing::ActiveParticipant::with_decryption_policy()) to a participant,
+/// the participant only produces decryption shares for requests carrying a quorum
+/// of valid approvals. Thus, no single requester can trigger decryption, even if they
+/// control the infrastructure delivering requests to participants.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     group::Ristretto, sharing::*, DiscreteLogTable, IdentityKeypair,
+/// # };
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let admins: Vec<_> = (0..3)
+///     .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+///     .collect();
+/// let policy = DecryptionPolicy::new(admins.iter().map(IdentityKeypair::public).collect(), 2);
+///
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participant = ActiveParticipant::new(
+///     key_set.clone(),
+///     0,
+///     dealer.secret_share_for_participant(0),
+/// )?;
+/// let participant = participant.with_decryption_policy(policy.clone());
+///
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let mut request = DecryptionRequest::new(&key_set, vec![ciphertext], "final tally");
+/// request.approve(&policy, &admins[0], &mut rng)?;
+/// // A single approval is insufficient.
+/// assert!(participant.decrypt_authorized(&request, &mut rng).is_err());
+/// request.approve(&policy, &admins[2], &mut rng)?;
+/// let shares = participant.decrypt_authorized(&request, &mut rng)?;
+/// assert_eq!(shares.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "", try_from = "DecryptionPolicyRepr<G>")
+)]
+pub struct DecryptionPolicy<G: Group> {
+    requesters: Vec<IdentityPublicKey<G>>,
+    quorum: usize,
+}
+
+/// Serialized form of a [`DecryptionPolicy`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct DecryptionPolicyRepr<G: Group> {
+    requesters: Vec<IdentityPublicKey<G>>,
+    quorum: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> TryFrom<DecryptionPolicyRepr<G>> for DecryptionPolicy<G> {
+    type Error = AuthorizationError;
+
+    fn try_from(repr: DecryptionPolicyRepr<G>) -> Result<Self, Self::Error> {
+        Self::check(&repr.requesters, repr.quorum)?;
+        Ok(Self {
+            requesters: repr.requesters,
+            quorum: repr.quorum,
+        })
+    }
+}
+
+impl<G: Group> DecryptionPolicy<G> {
+    /// Creates a policy with the specified requester identity keys and the number of requesters
+    /// that must approve a request. The same checks are performed when deserializing a policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quorum` is zero or exceeds the number of `requesters`, or if `requesters`
+    /// contain duplicate keys (which would allow a single requester to reach the quorum).
+    pub fn new(requesters: Vec<IdentityPublicKey<G>>, quorum: usize) -> Self {
+        if let Err(err) = Self::check(&requesters, quorum) {
+            panic!("{err}");
+        }
+        Self { requesters, quorum }
+    }
+
+    fn check(requesters: &[IdentityPublicKey<G>], quorum: usize) -> Result<(), AuthorizationError> {
+        if quorum == 0 || quorum > requesters.len() {
+            return Err(AuthorizationError::InvalidQuorum {
+                quorum,
+                requesters: requesters.len(),
+            });
+        }
+        for (i, requester) in requesters.iter().enumerate() {
+            let bytes = requester.as_bytes();
+            if let Some(j) = requesters[..i]
+                .iter()
+                .position(|other| other.as_bytes() == bytes)
+            {
+                return Err(AuthorizationError::DuplicateRequester {
+                    first: j,
+                    second: i,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns identity keys of requesters ordered by their 0-based indexes.
+    pub fn requesters(&self) -> &[IdentityPublicKey<G>] {
+        &self.requesters
+    }
+
+    /// Returns the number of requesters that must approve a request.
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+
+    fn commit(&self, transcript: &mut Transcript) {
+        transcript.append_u64(b"quorum", self.quorum as u64);
+        transcript.append_u64(b"n", self.requesters.len() as u64);
+        for requester in &self.requesters {
+            transcript.append_message(b"K", requester.as_bytes());
+        }
+    }
+
+    /// Verifies that the `request` is approved by a quorum of requesters and is addressed
+    /// to the specified `key_set`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request is addressed to another key set, contains an invalid
+    /// approval, or does not contain enough approvals.
+    pub fn verify(
+        &self,
+        key_set: &PublicKeySet<G>,
+        request: &DecryptionRequest<G>,
+    ) -> Result<(), AuthorizationError> {
+        if request.shared_key != *key_set.shared_key() {
+            return Err(AuthorizationError::KeySetMismatch);
+        }
+
+        let mut previous_index = None;
+        for (index, approval) in &request.approvals {
+            let index = *index;
+            if previous_index.map_or(false, |prev| prev >= index) {
+                return Err(AuthorizationError::DuplicateApproval(index));
+            }
+            previous_index = Some(index);
+
+            let requester = self
+                .requesters
+                .get(index)
+                .ok_or(AuthorizationError::UnknownRequester)?;
+            let mut transcript = request.approval_transcript(self, index);
+            approval
+                .verify(iter::once(requester.as_untyped()), &mut transcript)
+                .map_err(|err| AuthorizationError::InvalidApproval {
+                    requester: index,
+                    err,
+                })?;
+        }
+
+        let approvals = request.approvals.len();
+        if approvals < self.quorum {
+            return Err(AuthorizationError::InsufficientApprovals {
+                expected: self.quorum,
+                actual: approvals,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to decrypt ciphertexts with a threshold key, approved by requesters listed
+/// in a [`DecryptionPolicy`].
+///
+/// Approvals are signatures made with requester identity keys; they cover the policy,
+/// the shared key of the addressed [`PublicKeySet`], the request context
+/// and the ciphertexts. Hence, an approved request cannot be redirected to another committee
+/// or extended with additional ciphertexts.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct DecryptionRequest<G: Group> {
+    shared_key: PublicKey<G>,
+    context: String,
+    ciphertexts: Vec<Ciphertext<G>>,
+    approvals: Vec<(usize, ProofOfPossession<G>)>,
+}
+
+impl<G: Group> DecryptionRequest<G> {
+    /// Creates a request without approvals to decrypt `ciphertexts` encrypted
+    /// for the shared key of the `key_set`. The `context` describes the request
+    /// (e.g., an election identifier and the purpose of decryption).
+    pub fn new(
+        key_set: &PublicKeySet<G>,
+        ciphertexts: Vec<Ciphertext<G>>,
+        context: impl Into<String>,
+    ) -> Self {
+        Self {
+            shared_key: key_set.shared_key().clone(),
+            context: context.into(),
+            ciphertexts,
+            approvals: Vec::new(),
+        }
+    }
+
+    /// Returns the context of this request.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// Returns ciphertexts requested for decryption.
+    pub fn ciphertexts(&self) -> &[Ciphertext<G>] {
+        &self.ciphertexts
+    }
+
+    /// Returns 0-based indexes of requesters that have approved this request, in the ascending
+    /// order.
+    pub fn approved_by(&self) -> impl Iterator<Item = usize> + '_ {
+        self.approvals.iter().map(|(index, _)| *index)
+    }
+
+    fn approval_transcript(&self, policy: &DecryptionPolicy<G>, index: usize) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::DECRYPTION_REQUEST);
+        policy.commit(&mut transcript);
+        transcript.append_message(b"K", self.shared_key.as_bytes());
+        transcript.append_message(b"ctx", self.context.as_bytes());
+        transcript.append_u64(b"len", self.ciphertexts.len() as u64);
+        for ciphertext in &self.ciphertexts {
+            transcript.append_message(b"ct", &ciphertext.to_bytes());
+        }
+        transcript.append_u64(b"i", index as u64);
+        transcript
+    }
+
+    /// Approves this request on behalf of the requester with the specified `identity`.
+    /// If the requester has already approved the request, the previous approval is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `identity` is not listed among requesters in the `policy`.
+    pub fn approve<R: CryptoRng + RngCore>(
+        &mut self,
+        policy: &DecryptionPolicy<G>,
+        identity: &IdentityKeypair<G>,
+        rng: &mut R,
+    ) -> Result<(), AuthorizationError> {
+        let public = identity.public();
+        let index = policy
+            .requesters
+            .iter()
+            .position(|requester| *requester == public)
+            .ok_or(AuthorizationError::UnknownRequester)?;
+
+        let identity = identity.as_untyped();
+        let mut transcript = self.approval_transcript(policy, index);
+        let approval = ProofOfPossession::from_keys(
+            iter::once(identity.secret()),
+            iter::once(identity.public()),
+            &mut transcript,
+            rng,
+        );
+        match self.approvals.binary_search_by_key(&index, |(i, _)| *i) {
+            Ok(pos) => self.approvals[pos].1 = approval,
+            Err(pos) => self.approvals.insert(pos, (index, approval)),
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when authorizing a [`DecryptionRequest`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AuthorizationError {
+    /// The request is addressed to another key set.
+    KeySetMismatch,
+    /// Requester is not listed in the [`DecryptionPolicy`].
+    UnknownRequester,
+    /// Approvals are not ordered by the requester index, or the requester with
+    /// the specified 0-based index has approved the request several times.
+    DuplicateApproval(usize),
+    /// Approval by the requester with the specified 0-based index is invalid.
+    InvalidApproval {
+        /// 0-based index of the requester.
+        requester: usize,
+        /// Error verifying the approval.
+        err: VerificationError,
+    },
+    /// The request does not have enough approvals.
+    InsufficientApprovals {
+        /// Number of approvals required by the policy.
+        expected: usize,
+        /// Number of approvals in the request.
+        actual: usize,
+    },
+    /// Quorum of a [`DecryptionPolicy`] is zero or exceeds the number of requesters.
+    InvalidQuorum {
+        /// Quorum specified for the policy.
+        quorum: usize,
+        /// Number of requesters in the policy.
+        requesters: usize,
+    },
+    /// Requesters with the specified 0-based indexes in a [`DecryptionPolicy`]
+    /// have the same identity key.
+    DuplicateRequester {
+        /// Index of the first occurrence of the key.
+        first: usize,
+        /// Index of the repeated key.
+        second: usize,
+    },
+}
+
+impl fmt::Display for AuthorizationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeySetMismatch => formatter.write_str("request is addressed to another key set"),
+            Self::UnknownRequester => formatter.write_str("requester is not listed in the policy"),
+            Self::DuplicateApproval(requester) => write!(
+                formatter,
+                "approvals are not ordered or duplicated for requester #{requester}"
+            ),
+            Self::InvalidApproval { requester, err } => {
+                write!(
+                    formatter,
+                    "invalid approval by requester #{requester}: {err}"
+                )
+            }
+            Self::InsufficientApprovals { expected, actual } => write!(
+                formatter,
+                "insufficient number of approvals: expected at least {expected}, got {actual}"
+            ),
+            Self::InvalidQuorum { quorum, requesters } => write!(
+                formatter,
+                "invalid decryption policy: quorum {quorum} is not in 1..={requesters}"
+            ),
+            Self::DuplicateRequester { first, second } => write!(
+                formatter,
+                "invalid decryption policy: requesters #{first} and #{second} have the same key"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuthorizationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidApproval { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, Params},
+    };
+
+    #[test]
+    fn decryption_requires_quorum_of_approvals() {
+        let mut rng = thread_rng();
+        let admins: Vec<_> = (0..3)
+            .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let policy = DecryptionPolicy::new(admins.iter().map(IdentityKeypair::public).collect(), 2);
+
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))
+                .unwrap()
+                .with_decryption_policy(policy.clone());
+
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let mut request = DecryptionRequest::new(&key_set, vec![ciphertext], "test");
+        request.approve(&policy, &admins[1], &mut rng).unwrap();
+        request.approve(&policy, &admins[1], &mut rng).unwrap();
+        let err = participant
+            .decrypt_authorized(&request, &mut rng)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AuthorizationError::InsufficientApprovals {
+                expected: 2,
+                actual: 1
+            }
+        ));
+
+        let outsider = IdentityKeypair::generate(&mut rng);
+        let err = request.approve(&policy, &outsider, &mut rng).unwrap_err();
+        assert!(matches!(err, AuthorizationError::UnknownRequester));
+
+        request.approve(&policy, &admins[0], &mut rng).unwrap();
+        assert_eq!(request.approved_by().collect::<Vec<_>>(), [0, 1]);
+        let shares = participant.decrypt_authorized(&request, &mut rng).unwrap();
+        let (share, proof) = shares[0];
+        key_set
+            .verify_share(share.into(), ciphertext, 1, &proof)
+            .unwrap();
+
+        // Approvals do not transfer to a request with other ciphertexts.
+        let mut tampered = request.clone();
+        tampered.ciphertexts[0] = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let err = participant
+            .decrypt_authorized(&tampered, &mut rng)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AuthorizationError::InvalidApproval { requester: 0, .. }
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "requesters #0 and #1 have the same key")]
+    fn policy_with_duplicate_requesters_is_rejected() {
+        let admin = IdentityKeypair::<Ristretto>::generate(&mut thread_rng());
+        DecryptionPolicy::new(vec![admin.public(), admin.public()], 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn policy_deserialization_is_validated() {
+        let mut rng = thread_rng();
+        let admins: Vec<_> = (0..2)
+            .map(|_| IdentityKeypair::<Ristretto>::generate(&mut rng))
+            .collect();
+        let policy = DecryptionPolicy::new(admins.iter().map(IdentityKeypair::public).collect(), 2);
+        let json = serde_json::to_value(&policy).unwrap();
+        let restored: DecryptionPolicy<Ristretto> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.quorum(), 2);
+
+        let mut zero_quorum = json.clone();
+        zero_quorum["quorum"] = serde_json::json!(0);
+        let err = serde_json::from_value::<DecryptionPolicy<Ristretto>>(zero_quorum).unwrap_err();
+        assert!(err.to_string().contains("quorum 0"), "{err}");
+
+        let mut duplicates = json;
+        duplicates["requesters"][1] = duplicates["requesters"][0].clone();
+        let err = serde_json::from_value::<DecryptionPolicy<Ristretto>>(duplicates).unwrap_err();
+        assert!(err.to_string().contains("same key"), "{err}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn participant_policy_survives_serialization() {
+        let mut rng = thread_rng();
+        let admin = IdentityKeypair::<Ristretto>::generate(&mut rng);
+        let policy = DecryptionPolicy::new(vec![admin.public()], 1);
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set, 0, dealer.secret_share_for_participant(0))
+                .unwrap()
+                .with_decryption_policy(policy);
+
+        let json = serde_json::to_value(&participant).unwrap();
+        let restored: ActiveParticipant<Ristretto> = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.decryption_policy().unwrap().quorum(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "decryption policy")]
+    fn unauthorized_decryption_panics_with_policy() {
+        let mut rng = thread_rng();
+        let admin = IdentityKeypair::<Ristretto>::generate(&mut rng);
+        let policy = DecryptionPolicy::new(vec![admin.public()], 1);
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))
+                .unwrap()
+                .with_decryption_policy(policy);
+
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        participant.decrypt_share(ciphertext, &mut rng);
+    }
+}
@@ -0,0 +1,542 @@
+//! Printable backups of participant secret shares.
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use core::fmt;
+
+use super::{ActiveParticipant, PublicKeySet};
+use crate::{
+    alloc::{vec, String, Vec},
+    group::Group,
+    proto_labels, KeyFingerprint, SecretKey,
+};
+
+const VERSION: u8 = 1;
+const CHECKSUM_SIZE: usize = 4;
+/// Version + participant index + key fingerprint.
+const HEADER_SIZE: usize = 1 + 4 + KeyFingerprint::SIZE;
+const CHUNK_PREFIX: &str = "EGSB";
+
+const PROQUINT_CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const PROQUINT_VOWELS: &[u8; 4] = b"aiou";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Errors that can occur when restoring a [`ShareBackup`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum BackupError {
+    /// Backup contains an invalid word or char, or has an unexpected length.
+    MalformedEncoding,
+    /// Set of chunks is incomplete or contains inconsistent chunks.
+    InvalidChunks,
+    /// Backup checksum does not match its contents, e.g., because of a transcription error.
+    ChecksumMismatch,
+    /// Backup has an unsupported format version.
+    UnsupportedVersion(u8),
+    /// Backup does not correspond to the participant's public key share in the key set.
+    KeyMismatch,
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedEncoding => formatter.write_str("share backup encoding is malformed"),
+            Self::InvalidChunks => {
+                formatter.write_str("share backup chunks are incomplete or inconsistent")
+            }
+            Self::ChecksumMismatch => formatter.write_str("share backup checksum mismatch"),
+            Self::UnsupportedVersion(version) => {
+                write!(formatter, "unsupported share backup version: {version}")
+            }
+            Self::KeyMismatch => formatter.write_str(
+                "share backup does not correspond to the participant key share in the key set",
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BackupError {}
+
+/// Backup of a participant's secret share in a form suitable for paper storage.
+///
+/// A backup can be encoded either as [mnemonic words](Self::to_mnemonic()) or
+/// as [chunks](Self::to_chunks()) suitable for QR codes in the alphanumeric mode.
+/// In both cases, the encoded data includes:
+///
+/// - The 0-based participant index
+/// - The [fingerprint](crate::PublicKey::fingerprint()) of the participant's public key share
+/// - The secret share
+/// - A checksum detecting transcription errors
+///
+/// Because of the fingerprint, a backup can only be [restored](Self::restore()) against
+/// the key set it was created for; e.g., backups made before the shares are refreshed
+/// are rejected.
+///
+/// Mnemonic words are [proquints], i.e., pronounceable 5-letter words each encoding 16 bits.
+///
+/// [proquints]: https://arxiv.org/abs/0901.4016
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::{ActiveParticipant, Dealer, Params, PublicKeySet, ShareBackup}};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participant =
+///     ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))?;
+///
+/// let mnemonic = participant.backup().to_mnemonic();
+/// // Store the words on paper...
+/// let backup = ShareBackup::<Ristretto>::from_mnemonic(&mnemonic)?;
+/// let restored = backup.restore(key_set)?;
+/// assert_eq!(restored.index(), 1);
+/// assert_eq!(restored.public_key_share(), participant.public_key_share());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShareBackup<G: Group> {
+    index: usize,
+    key_fingerprint: KeyFingerprint,
+    secret_share: SecretKey<G>,
+}
+
+impl<G: Group> fmt::Debug for ShareBackup<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ShareBackup")
+            .field("index", &self.index)
+            .field("key_fingerprint", &self.key_fingerprint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> ShareBackup<G> {
+    pub(super) fn new(participant: &ActiveParticipant<G>) -> Self {
+        Self {
+            index: participant.index(),
+            key_fingerprint: participant.public_key_share().fingerprint(),
+            secret_share: participant.secret_share().clone(),
+        }
+    }
+
+    /// Returns the 0-based index of the participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the fingerprint of the participant's public key share.
+    pub fn key_fingerprint(&self) -> KeyFingerprint {
+        self.key_fingerprint
+    }
+
+    fn payload_size() -> usize {
+        HEADER_SIZE + G::SCALAR_SIZE + CHECKSUM_SIZE
+    }
+
+    /// Returns the max number of chunks produced by [`Self::to_chunks()`], i.e., the number
+    /// of chunks with a single data char each.
+    fn max_chunk_count() -> usize {
+        (Self::payload_size() * 8 + 4) / 5
+    }
+
+    fn checksum(data: &[u8]) -> [u8; CHECKSUM_SIZE] {
+        let digest = Sha256::new()
+            .chain_update(proto_labels::SHARE_BACKUP)
+            .chain_update(data)
+            .finalize();
+        let mut checksum = [0_u8; CHECKSUM_SIZE];
+        checksum.copy_from_slice(&digest[..CHECKSUM_SIZE]);
+        checksum
+    }
+
+    fn to_payload(&self) -> Zeroizing<Vec<u8>> {
+        let index = u32::try_from(self.index).expect("participant index does not fit into u32");
+        let mut payload = Zeroizing::new(vec![0_u8; Self::payload_size()]);
+        payload[0] = VERSION;
+        payload[1..5].copy_from_slice(&index.to_be_bytes());
+        payload[5..HEADER_SIZE].copy_from_slice(self.key_fingerprint.as_bytes());
+        let checksum_start = HEADER_SIZE + G::SCALAR_SIZE;
+        G::serialize_scalar(
+            self.secret_share.expose_scalar(),
+            &mut payload[HEADER_SIZE..checksum_start],
+        );
+        let checksum = Self::checksum(&payload[..checksum_start]);
+        payload[checksum_start..].copy_from_slice(&checksum);
+        payload
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<Self, BackupError> {
+        if payload.len() != Self::payload_size() {
+            return Err(BackupError::MalformedEncoding);
+        }
+        let checksum_start = HEADER_SIZE + G::SCALAR_SIZE;
+        if Self::checksum(&payload[..checksum_start])[..] != payload[checksum_start..] {
+            return Err(BackupError::ChecksumMismatch);
+        }
+        if payload[0] != VERSION {
+            return Err(BackupError::UnsupportedVersion(payload[0]));
+        }
+
+        let mut index_bytes = [0_u8; 4];
+        index_bytes.copy_from_slice(&payload[1..5]);
+        let index = usize::try_from(u32::from_be_bytes(index_bytes))
+            .map_err(|_| BackupError::MalformedEncoding)?;
+        let mut fingerprint = [0_u8; KeyFingerprint::SIZE];
+        fingerprint.copy_from_slice(&payload[5..HEADER_SIZE]);
+        let secret_share = SecretKey::from_bytes(&payload[HEADER_SIZE..checksum_start])
+            .ok_or(BackupError::MalformedEncoding)?;
+        Ok(Self {
+            index,
+            key_fingerprint: KeyFingerprint(fingerprint),
+            secret_share,
+        })
+    }
+
+    /// Encodes this backup as space-separated mnemonic words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the participant index does not fit into `u32`.
+    pub fn to_mnemonic(&self) -> Zeroizing<String> {
+        let mut payload = self.to_payload();
+        if payload.len() % 2 == 1 {
+            payload.push(0);
+        }
+        let mut mnemonic = Zeroizing::new(String::with_capacity(payload.len() * 3));
+        for (i, pair) in payload.chunks_exact(2).enumerate() {
+            if i > 0 {
+                mnemonic.push(' ');
+            }
+            push_proquint(&mut mnemonic, u16::from_be_bytes([pair[0], pair[1]]));
+        }
+        mnemonic
+    }
+
+    /// Decodes a backup from mnemonic words produced by [`Self::to_mnemonic()`].
+    /// Words may be separated by whitespace or dashes; the case of letters is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mnemonic is malformed or its checksum does not match.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, BackupError> {
+        let mut payload = Zeroizing::new(Vec::with_capacity(Self::payload_size() + 1));
+        let words = mnemonic
+            .split(|ch: char| ch.is_whitespace() || ch == '-')
+            .filter(|word| !word.is_empty());
+        for word in words {
+            let value = parse_proquint(word).ok_or(BackupError::MalformedEncoding)?;
+            payload.extend_from_slice(&value.to_be_bytes());
+        }
+
+        let has_padding = Self::payload_size() % 2 == 1;
+        if has_padding && (payload.len() != Self::payload_size() + 1 || payload.pop() != Some(0)) {
+            return Err(BackupError::MalformedEncoding);
+        }
+        Self::from_payload(&payload)
+    }
+
+    /// Encodes this backup as chunks containing at most `max_data_len` data chars each.
+    /// Chunks only contain chars from the alphanumeric mode of QR codes and have
+    /// the `EGSB:$index/$count:$data` format, where `$index` is 1-based.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_data_len` is zero, or if the participant index does not fit into `u32`.
+    pub fn to_chunks(&self, max_data_len: usize) -> Vec<Zeroizing<String>> {
+        assert!(max_data_len > 0, "chunk length must be positive");
+        let data = base32_encode(&self.to_payload());
+        let chunk_count = (data.len() + max_data_len - 1) / max_data_len;
+        data.as_bytes()
+            .chunks(max_data_len)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut encoded = Zeroizing::new(String::with_capacity(chunk.len() + 16));
+                encoded.push_str(CHUNK_PREFIX);
+                encoded.push(':');
+                push_decimal(&mut encoded, i + 1);
+                encoded.push('/');
+                push_decimal(&mut encoded, chunk_count);
+                encoded.push(':');
+                encoded.extend(chunk.iter().copied().map(char::from));
+                encoded
+            })
+            .collect()
+    }
+
+    /// Decodes a backup from chunks produced by [`Self::to_chunks()`]. Chunks may be supplied
+    /// in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the chunks is malformed, the set of chunks is incomplete
+    /// or inconsistent, or the backup checksum does not match.
+    pub fn from_chunks<'a>(chunks: impl IntoIterator<Item = &'a str>) -> Result<Self, BackupError> {
+        let mut parts: Vec<Option<&str>> = Vec::new();
+        for chunk in chunks {
+            let (index, count, data) =
+                parse_chunk(chunk.trim()).ok_or(BackupError::InvalidChunks)?;
+            if count > Self::max_chunk_count() {
+                return Err(BackupError::InvalidChunks);
+            }
+            if parts.is_empty() {
+                parts = vec![None; count];
+            } else if parts.len() != count {
+                return Err(BackupError::InvalidChunks);
+            }
+            match &mut parts[index - 1] {
+                Some(existing) if *existing != data => return Err(BackupError::InvalidChunks),
+                part => *part = Some(data),
+            }
+        }
+        if parts.is_empty() {
+            return Err(BackupError::InvalidChunks);
+        }
+
+        let mut data = Zeroizing::new(String::new());
+        for part in parts {
+            data.push_str(part.ok_or(BackupError::InvalidChunks)?);
+        }
+        let payload = base32_decode(&data).ok_or(BackupError::MalformedEncoding)?;
+        Self::from_payload(&payload)
+    }
+
+    /// Restores the participant state from this backup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backup does not correspond to the participant's public key share
+    /// in `key_set`.
+    pub fn restore(self, key_set: PublicKeySet<G>) -> Result<ActiveParticipant<G>, BackupError> {
+        let key_share = key_set
+            .participant_keys()
+            .get(self.index)
+            .ok_or(BackupError::KeyMismatch)?;
+        if key_share.fingerprint() != self.key_fingerprint {
+            return Err(BackupError::KeyMismatch);
+        }
+        ActiveParticipant::new(key_set, self.index, self.secret_share)
+            .map_err(|_| BackupError::KeyMismatch)
+    }
+}
+
+fn push_proquint(buffer: &mut String, value: u16) {
+    let consonant =
+        |shift: u16| char::from(PROQUINT_CONSONANTS[usize::from((value >> shift) & 15)]);
+    let vowel = |shift: u16| char::from(PROQUINT_VOWELS[usize::from((value >> shift) & 3)]);
+    buffer.extend([
+        consonant(12),
+        vowel(10),
+        consonant(6),
+        vowel(4),
+        consonant(0),
+    ]);
+}
+
+fn parse_proquint(word: &str) -> Option<u16> {
+    let word = word.as_bytes();
+    if word.len() != 5 {
+        return None;
+    }
+    let position = |alphabet: &[u8], ch: u8| {
+        let ch = ch.to_ascii_lowercase();
+        let position = alphabet.iter().position(|&c| c == ch)?;
+        u16::try_from(position).ok()
+    };
+    let mut value = 0_u16;
+    for (i, &ch) in word.iter().enumerate() {
+        value = if i % 2 == 0 {
+            (value << 4) | position(PROQUINT_CONSONANTS, ch)?
+        } else {
+            (value << 2) | position(PROQUINT_VOWELS, ch)?
+        };
+    }
+    Some(value)
+}
+
+fn push_decimal(buffer: &mut String, value: usize) {
+    use core::fmt::Write as _;
+    write!(buffer, "{value}").unwrap();
+}
+
+fn parse_chunk(chunk: &str) -> Option<(usize, usize, &str)> {
+    let mut parts = chunk.splitn(3, ':');
+    if parts.next()? != CHUNK_PREFIX {
+        return None;
+    }
+    let (index, count) = parts.next()?.split_once('/')?;
+    let index: usize = index.parse().ok()?;
+    let count: usize = count.parse().ok()?;
+    let data = parts.next()?;
+    let is_valid = (1..=count).contains(&index) && !data.is_empty();
+    is_valid.then_some((index, count, data))
+}
+
+fn base32_encode(bytes: &[u8]) -> Zeroizing<String> {
+    let mut encoded = Zeroizing::new(String::with_capacity((bytes.len() * 8 + 4) / 5));
+    let (mut buffer, mut bits) = (0_u16, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(char::from(
+                BASE32_ALPHABET[usize::from((buffer >> bits) & 31)],
+            ));
+        }
+    }
+    if bits > 0 {
+        encoded.push(char::from(
+            BASE32_ALPHABET[usize::from((buffer << (5 - bits)) & 31)],
+        ));
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Zeroizing<Vec<u8>>> {
+    let mut decoded = Zeroizing::new(Vec::with_capacity(encoded.len() * 5 / 8));
+    let (mut buffer, mut bits) = (0_u16, 0);
+    for ch in encoded.bytes() {
+        let ch = ch.to_ascii_uppercase();
+        let position = BASE32_ALPHABET.iter().position(|&c| c == ch)?;
+        buffer = (buffer << 5) | u16::try_from(position).ok()?;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push(u8::try_from((buffer >> bits) & 255).ok()?);
+        }
+    }
+    // Leftover bits must be zero padding.
+    (bits < 5 && buffer & ((1 << bits) - 1) == 0).then_some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{Dealer, Params},
+    };
+
+    fn create_participant() -> ActiveParticipant<Ristretto> {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        ActiveParticipant::new(key_set, 2, dealer.secret_share_for_participant(2)).unwrap()
+    }
+
+    #[test]
+    fn proquint_encoding() {
+        // Test vectors from the proquint spec (127.0.0.1 and 63.84.220.193).
+        for (value, expected) in [
+            (0x7f00, "lusab"),
+            (0x0001, "babad"),
+            (0x3f54, "gutih"),
+            (0xdcc1, "tugad"),
+        ] {
+            let mut word = String::new();
+            push_proquint(&mut word, value);
+            assert_eq!(word, expected);
+            assert_eq!(parse_proquint(expected), Some(value));
+        }
+        assert_eq!(parse_proquint("lusa"), None);
+        assert_eq!(parse_proquint("lusae"), None);
+    }
+
+    #[test]
+    fn base32_roundtrip() {
+        for len in 0_u8..12 {
+            let bytes: Vec<u8> = (0..len).map(|i| i.wrapping_mul(37)).collect();
+            let encoded = base32_encode(&bytes);
+            assert_eq!(encoded.len(), (usize::from(len) * 8 + 4) / 5);
+            assert_eq!(*base32_decode(&encoded).unwrap(), bytes);
+        }
+        assert_eq!(&*base32_encode(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn mnemonic_backup_roundtrip() {
+        let participant = create_participant();
+        let backup = participant.backup();
+        let mnemonic = backup.to_mnemonic();
+        assert_eq!(mnemonic.split(' ').count(), 25);
+
+        let restored = ShareBackup::<Ristretto>::from_mnemonic(&mnemonic.to_uppercase()).unwrap();
+        assert_eq!(restored.index(), 2);
+        assert_eq!(restored.key_fingerprint(), backup.key_fingerprint());
+        let restored = restored.restore(participant.key_set().clone()).unwrap();
+        assert_eq!(
+            restored.secret_share().expose_scalar(),
+            participant.secret_share().expose_scalar()
+        );
+    }
+
+    #[test]
+    fn mnemonic_with_typo() {
+        let participant = create_participant();
+        let mnemonic = participant.backup().to_mnemonic();
+        let mut words: Vec<_> = mnemonic.split(' ').map(String::from).collect();
+        words[7] = if words[7] == "babab" {
+            "babad"
+        } else {
+            "babab"
+        }
+        .into();
+        let err = ShareBackup::<Ristretto>::from_mnemonic(&words.join(" ")).unwrap_err();
+        assert!(matches!(err, BackupError::ChecksumMismatch));
+
+        words.pop();
+        let err = ShareBackup::<Ristretto>::from_mnemonic(&words.join("-")).unwrap_err();
+        assert!(matches!(err, BackupError::MalformedEncoding));
+    }
+
+    #[test]
+    fn chunked_backup_roundtrip() {
+        let participant = create_participant();
+        let chunks = participant.backup().to_chunks(30);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].starts_with("EGSB:1/3:"));
+
+        let reordered = [&chunks[2], &chunks[0], &chunks[1], &chunks[0]];
+        let backup =
+            ShareBackup::<Ristretto>::from_chunks(reordered.iter().map(|chunk| chunk.as_str()))
+                .unwrap();
+        backup.restore(participant.key_set().clone()).unwrap();
+
+        let err = ShareBackup::<Ristretto>::from_chunks(chunks[..2].iter().map(|c| c.as_str()))
+            .unwrap_err();
+        assert!(matches!(err, BackupError::InvalidChunks));
+
+        let chunks = participant.backup().to_chunks(1);
+        assert_eq!(chunks.len(), ShareBackup::<Ristretto>::max_chunk_count());
+        let backup =
+            ShareBackup::<Ristretto>::from_chunks(chunks.iter().map(|chunk| chunk.as_str()))
+                .unwrap();
+        backup.restore(participant.key_set().clone()).unwrap();
+
+        let err =
+            ShareBackup::<Ristretto>::from_chunks(["EGSB:1/18446744073709551615:A"]).unwrap_err();
+        assert!(matches!(err, BackupError::InvalidChunks));
+    }
+
+    #[test]
+    fn restoring_backup_for_another_key_set() {
+        let participant = create_participant();
+        let other_participant = create_participant();
+        let backup = participant.backup();
+        let err = backup
+            .restore(other_participant.key_set().clone())
+            .unwrap_err();
+        assert!(matches!(err, BackupError::KeyMismatch));
+    }
+}
@@ -0,0 +1,198 @@
+//! Conditional (adaptor-style) decryption shares.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    group::Group,
+    proofs::VerificationError,
+    sharing::{PublicKeySet, ReEncryptionProof, ReEncryptionShare},
+    Ciphertext, PublicKey, SecretKey, VerifiableDecryption,
+};
+
+/// Decryption share locked under a *condition* point `T = [t]G`. The share can only be
+/// [revealed](Self::reveal()) by the party knowing the scalar `t` (e.g., a payment preimage
+/// mapped to a scalar); before that, the share is hidden, but its validity can be
+/// [verified](PublicKeySet::verify_conditional_share()) by anyone.
+///
+/// This enables "decrypt only if paid" constructions on top of a threshold committee:
+/// a requester can check that `t` locked shares are valid before releasing a payment
+/// that publishes `t`, after which the shares are revealed and combined as usual.
+///
+/// # Construction
+///
+/// A conditional share is a [`ReEncryptionShare`] of the ciphertext to the condition
+/// point `T`: `(E_i, F_i) = ([k_i]G, D_i - [k_i]T)`, where `D_i = [s_i]R` is the ordinary
+/// decryption share. Knowing `t`, the share is revealed as `D_i = F_i + [t]E_i`.
+/// Correctness of the share is established with a [`ReEncryptionProof`].
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::*, DiscreteLogTable, Keypair};
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..3)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// // `condition` may be, e.g., derived from a payment preimage.
+/// let condition = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let locked_shares = participants[..2].iter().map(|participant| {
+///     let (share, proof) = participant.conditional_decrypt_share(
+///         ciphertext,
+///         condition.public(),
+///         &mut rng,
+///     );
+///     let index = participant.index();
+///     key_set
+///         .verify_conditional_share(&share, ciphertext, index, &proof)
+///         .map(|()| (index, share))
+/// });
+/// let locked_shares = locked_shares.collect::<Result<Vec<_>, _>>()?;
+///
+/// // Once the condition secret is published, shares can be revealed.
+/// let shares = locked_shares
+///     .into_iter()
+///     .map(|(i, share)| (i, share.reveal(condition.secret()).unwrap()));
+/// let combined = params.combine_shares(shares).unwrap();
+/// let lookup_table = DiscreteLogTable::new(0..10);
+/// assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(5));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ConditionalShare<G: Group> {
+    condition: PublicKey<G>,
+    locked_share: ReEncryptionShare<G>,
+}
+
+impl<G: Group> ConditionalShare<G> {
+    pub(super) fn new<R: CryptoRng + RngCore>(
+        key_set: &PublicKeySet<G>,
+        index: usize,
+        secret_share: &SecretKey<G>,
+        ciphertext: Ciphertext<G>,
+        condition: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (Self, ReEncryptionProof<G>) {
+        let (locked_share, proof) =
+            ReEncryptionProof::new(key_set, index, secret_share, ciphertext, condition, rng);
+        let share = Self {
+            condition: condition.clone(),
+            locked_share,
+        };
+        (share, proof)
+    }
+
+    /// Returns the condition point this share is locked under.
+    pub fn condition(&self) -> &PublicKey<G> {
+        &self.condition
+    }
+
+    /// Returns the locked share, which is a re-encryption of the decryption share
+    /// to the [condition](Self::condition()) point.
+    pub fn locked_share(&self) -> &ReEncryptionShare<G> {
+        &self.locked_share
+    }
+
+    /// Reveals the decryption share using the secret scalar `t` of the condition point.
+    /// Returns `None` if `condition_secret` does not correspond to the condition point.
+    ///
+    /// The revealed share is only correct if this share was verified beforehand
+    /// with [`PublicKeySet::verify_conditional_share()`].
+    pub fn reveal(&self, condition_secret: &SecretKey<G>) -> Option<VerifiableDecryption<G>> {
+        if PublicKey::from(condition_secret) != self.condition {
+            return None;
+        }
+        let dh_element = self.locked_share.blinded_element
+            + self.locked_share.random_element * condition_secret.expose_scalar();
+        Some(VerifiableDecryption::from_element(dh_element))
+    }
+}
+
+impl<G: Group> PublicKeySet<G> {
+    /// Verifies a [`ConditionalShare`] from the participant with the specified `index`
+    /// for the given `ciphertext`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not correspond to a participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_conditional_share(
+        &self,
+        share: &ConditionalShare<G>,
+        ciphertext: Ciphertext<G>,
+        index: usize,
+        proof: &ReEncryptionProof<G>,
+    ) -> Result<(), VerificationError> {
+        self.verify_reencryption_share(
+            &share.locked_share,
+            ciphertext,
+            &share.condition,
+            index,
+            proof,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, Params},
+        Keypair,
+    };
+
+    #[test]
+    fn conditional_share_is_bound_to_condition() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))
+                .unwrap();
+
+        let condition = Keypair::<Ristretto>::generate(&mut rng);
+        let other_condition = Keypair::<Ristretto>::generate(&mut rng);
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let (share, proof) =
+            participant.conditional_decrypt_share(ciphertext, condition.public(), &mut rng);
+        key_set
+            .verify_conditional_share(&share, ciphertext, 1, &proof)
+            .unwrap();
+
+        let mut forged_share = share.clone();
+        forged_share.condition = other_condition.public().clone();
+        assert!(key_set
+            .verify_conditional_share(&forged_share, ciphertext, 1, &proof)
+            .is_err());
+
+        assert!(share.reveal(other_condition.secret()).is_none());
+        let revealed = share.reveal(condition.secret()).unwrap();
+        let (expected, _) = participant.decrypt_share(ciphertext, &mut rng);
+        assert_eq!(revealed.to_bytes(), expected.to_bytes());
+    }
+}
@@ -0,0 +1,284 @@
+//! Declarative committee configuration.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use core::fmt;
+
+use crate::{
+    alloc::{Box, String, Vec},
+    group::Group,
+    proto_labels,
+    sharing::{Error, Params},
+    CertificateChain, CertificateVerifier, CertificationError, IdentityPublicKey,
+};
+
+/// Configuration of a single committee participant in a [`Config`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ParticipantConfig<G: Group> {
+    /// Long-term identity key of the participant.
+    pub identity_key: IdentityPublicKey<G>,
+    /// Application-defined label of the transport used to reach the participant
+    /// (e.g., a network address). Labels are not interpreted by this crate, but must be
+    /// unique within the committee.
+    pub transport: String,
+    /// Certificate chain for the identity key issued by an organizational root authority.
+    /// Committees can require all participants to be certified via
+    /// [`DkgSession::from_certified_config()`](crate::dkg::DkgSession::from_certified_config()).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub certificate: Option<CertificateChain>,
+}
+
+/// Declarative configuration of a threshold committee, e.g., loaded from a file
+/// shared among committee operators.
+///
+/// The configuration lists committee [`Params`] and participants in the order of their
+/// 0-based indexes, together with the context of the ceremony. It can be used to start
+/// a DKG session via [`DkgSession::from_config()`](crate::dkg::DkgSession::from_config());
+/// all participants should use the same configuration, which can be checked by comparing
+/// config [digests](Self::digest()).
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     group::Ristretto, sharing::{Config, ParticipantConfig, Params}, IdentityKeypair,
+/// # };
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let participants = (0..3).map(|i| ParticipantConfig {
+///     identity_key: IdentityKeypair::<Ristretto>::generate(&mut rng).public(),
+///     transport: format!("node-{i}.example.com:7000"),
+///     certificate: None,
+/// });
+/// let config = Config {
+///     params: Params::new(3, 2),
+///     context: "committee #1".to_owned(),
+///     participants: participants.collect(),
+/// };
+/// config.validate()?;
+///
+/// let mut invalid_config = config.clone();
+/// invalid_config.participants[2].transport = "node-0.example.com:7000".to_owned();
+/// assert!(invalid_config.validate().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Config<G: Group> {
+    /// Committee parameters.
+    pub params: Params,
+    /// Context of the ceremony (e.g., a committee or election identifier). The context
+    /// is used to initialize message logs of participants.
+    pub context: String,
+    /// Participants ordered by their 0-based indexes.
+    pub participants: Vec<ParticipantConfig<G>>,
+}
+
+impl<G: Group> Config<G> {
+    /// Checks the consistency of this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is inconsistent; see [`ConfigError`]
+    /// for possible reasons.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let Params { shares, threshold } = self.params;
+        if shares == 0 || threshold == 0 || threshold > shares {
+            return Err(ConfigError::InvalidParams);
+        }
+        self.params
+            .check_group::<G>()
+            .map_err(ConfigError::Params)?;
+        if self.participants.len() != shares {
+            return Err(ConfigError::ParticipantCountMismatch);
+        }
+        if self.context.is_empty() {
+            return Err(ConfigError::EmptyContext);
+        }
+
+        for (i, participant) in self.participants.iter().enumerate() {
+            if participant.transport.is_empty() {
+                return Err(ConfigError::EmptyTransport(i));
+            }
+            let previous = &self.participants[..i];
+            if previous
+                .iter()
+                .any(|other| other.identity_key == participant.identity_key)
+            {
+                return Err(ConfigError::DuplicateIdentityKey(i));
+            }
+            if previous
+                .iter()
+                .any(|other| other.transport == participant.transport)
+            {
+                return Err(ConfigError::DuplicateTransport(i));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the 0-based index of the participant with the specified identity key,
+    /// or `None` if there is no such participant.
+    pub fn index_of(&self, identity_key: &IdentityPublicKey<G>) -> Option<usize> {
+        self.participants
+            .iter()
+            .position(|participant| participant.identity_key == *identity_key)
+    }
+
+    /// Returns identity keys of all participants ordered by their indexes.
+    pub fn identity_keys(&self) -> Vec<IdentityPublicKey<G>> {
+        self.participants
+            .iter()
+            .map(|participant| participant.identity_key.clone())
+            .collect()
+    }
+
+    /// Returns certificate chains for identity keys of all participants ordered by their indexes,
+    /// or `None` if any participant is not certified.
+    pub fn certificates(&self) -> Option<Vec<CertificateChain>> {
+        self.participants
+            .iter()
+            .map(|participant| participant.certificate.clone())
+            .collect()
+    }
+
+    /// Checks that identity keys of all participants are certified by the `root` authority.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CertificationError::MissingCertificate`] if a participant does not have
+    /// a certificate, and [`CertificationError::Participant`] if a certificate chain
+    /// does not verify.
+    pub fn verify_certificates<V: CertificateVerifier + ?Sized>(
+        &self,
+        root: &[u8],
+        verifier: &V,
+    ) -> Result<(), CertificationError> {
+        for (index, participant) in self.participants.iter().enumerate() {
+            let chain = participant
+                .certificate
+                .as_ref()
+                .ok_or(CertificationError::MissingCertificate { participant: index })?;
+            participant
+                .identity_key
+                .as_untyped()
+                .verify_certificate(chain, root, verifier)
+                .map_err(|err| CertificationError::Participant {
+                    index,
+                    error: Box::new(err),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Computes a SHA-256 digest of this configuration. Operators can compare digests
+    /// to ensure that they use the same configuration. Certificates of participants
+    /// are covered by the digest if present.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new()
+            .chain_update(proto_labels::COMMITTEE_CONFIG)
+            .chain_update((self.params.shares as u64).to_le_bytes())
+            .chain_update((self.params.threshold as u64).to_le_bytes())
+            .chain_update((self.context.len() as u64).to_le_bytes())
+            .chain_update(self.context.as_bytes());
+        for participant in &self.participants {
+            let transport = participant.transport.as_bytes();
+            hasher = hasher
+                .chain_update(participant.identity_key.as_bytes())
+                .chain_update((transport.len() as u64).to_le_bytes())
+                .chain_update(transport);
+            if let Some(chain) = &participant.certificate {
+                hasher = hasher
+                    .chain_update(b"certificate")
+                    .chain_update((chain.certificates().len() as u64).to_le_bytes());
+                for certificate in chain.certificates() {
+                    for part in [&certificate.subject, &certificate.signature] {
+                        hasher = hasher
+                            .chain_update((part.len() as u64).to_le_bytes())
+                            .chain_update(part);
+                    }
+                }
+            }
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Errors that can occur when validating a [`Config`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// Threshold or number of shares in [`Params`] is invalid.
+    InvalidParams,
+    /// [`Params`] are not supported by the group.
+    Params(Error),
+    /// Number of participants differs from the number of shares in [`Params`].
+    ParticipantCountMismatch,
+    /// Ceremony context is empty.
+    EmptyContext,
+    /// Transport label of the participant with the specified 0-based index is empty.
+    EmptyTransport(usize),
+    /// Identity key of the participant with the specified 0-based index is used by
+    /// a previous participant.
+    DuplicateIdentityKey(usize),
+    /// Transport label of the participant with the specified 0-based index is used by
+    /// a previous participant.
+    DuplicateTransport(usize),
+    /// Identity key of the local participant is not present in the configuration.
+    UnknownIdentity,
+    /// Participant certificates do not verify against the root authority.
+    Certification(CertificationError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParams => {
+                formatter.write_str("threshold or number of shares in params is invalid")
+            }
+            Self::Params(err) => write!(formatter, "params are not supported: {err}"),
+            Self::ParticipantCountMismatch => formatter
+                .write_str("number of participants differs from the number of shares in params"),
+            Self::EmptyContext => formatter.write_str("ceremony context is empty"),
+            Self::EmptyTransport(participant) => write!(
+                formatter,
+                "transport label of participant #{participant} is empty"
+            ),
+            Self::DuplicateIdentityKey(participant) => write!(
+                formatter,
+                "identity key of participant #{participant} is used by a previous participant"
+            ),
+            Self::DuplicateTransport(participant) => write!(
+                formatter,
+                "transport label of participant #{participant} is used by a previous participant"
+            ),
+            Self::UnknownIdentity => {
+                formatter.write_str("local identity key is not present in the configuration")
+            }
+            Self::Certification(err) => {
+                write!(formatter, "participant certificates do not verify: {err}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Params(err) => Some(err),
+            Self::Certification(err) => Some(err),
+            _ => None,
+        }
+    }
+}
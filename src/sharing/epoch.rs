@@ -0,0 +1,136 @@
+//! Key set epochs.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+use crate::{group::Group, VerifiableDecryption};
+
+/// Epoch of a [`PublicKeySet`](crate::sharing::PublicKeySet), i.e., a monotonic counter
+/// distinguishing key sets of the same committee.
+///
+/// A committee running for a long time may refresh or reshare its key shares while keeping
+/// the shared key. Decryption shares produced before and after such an operation are
+/// incompatible, but nothing in the shares themselves reveals this. To catch such mistakes,
+/// the key set epoch is bound into all proofs produced by participants, and
+/// [`EpochShare`]s record the epoch they were verified in. As an exception,
+/// [`Self::INITIAL`] is not bound, so that proofs for key sets predating epochs remain valid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Epoch(u64);
+
+impl Epoch {
+    /// Initial epoch, which is used by key sets created by the dealer or via distributed
+    /// key generation.
+    pub const INITIAL: Self = Self(0);
+
+    /// Creates an epoch with the specified counter value.
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the counter value of this epoch.
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the epoch following this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter overflows.
+    #[must_use]
+    pub fn next(self) -> Self {
+        Self(self.0.checked_add(1).expect("epoch counter overflow"))
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "epoch #{}", self.0)
+    }
+}
+
+/// Generation of a [`PublicKeySet`](crate::sharing::PublicKeySet), which identifies
+/// the data that can be derived from the key set: its [`Epoch`] together with a digest
+/// of its params, shared key and participant keys.
+///
+/// Unlike the epoch, the generation does not need to be maintained by the committee:
+/// it is derived from public data when the key set is created and is available via
+/// [`PublicKeySet::generation()`](crate::sharing::PublicKeySet::generation()).
+///
+/// In this crate, the generation is checked by [`PreparedSubset`], which records it
+/// and rejects key sets of another generation (e.g., after the committee has refreshed
+/// or reshared its keys) with [`Error::StaleKeySet`]. Other data derived from a key set
+/// in applications can be invalidated in the same way.
+///
+/// [`PreparedSubset`]: crate::sharing::PreparedSubset
+/// [`Error::StaleKeySet`]: crate::sharing::Error::StaleKeySet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Generation {
+    epoch: Epoch,
+    digest: [u8; 32],
+}
+
+impl Generation {
+    pub(super) fn new(epoch: Epoch, digest: [u8; 32]) -> Self {
+        Self { epoch, digest }
+    }
+
+    /// Returns the epoch of the key set.
+    pub fn epoch(self) -> Epoch {
+        self.epoch
+    }
+
+    /// Returns the digest of the key set.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+}
+
+impl fmt::Display for Generation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} (key set digest ", self.epoch)?;
+        for byte in &self.digest[..4] {
+            write!(formatter, "{byte:02x}")?;
+        }
+        formatter.write_str("...)")
+    }
+}
+
+/// Decryption share verified against a [`PublicKeySet`](crate::sharing::PublicKeySet)
+/// in a certain [`Epoch`].
+///
+/// Produced by [`PublicKeySet::verify_epoch_share()`] and combined via
+/// [`PublicKeySet::combine_epoch_shares()`], which rejects shares from other epochs.
+///
+/// [`PublicKeySet::verify_epoch_share()`]: crate::sharing::PublicKeySet::verify_epoch_share()
+/// [`PublicKeySet::combine_epoch_shares()`]: crate::sharing::PublicKeySet::combine_epoch_shares()
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct EpochShare<G: Group> {
+    pub(super) epoch: Epoch,
+    pub(super) index: usize,
+    pub(super) share: VerifiableDecryption<G>,
+}
+
+impl<G: Group> EpochShare<G> {
+    /// Returns the epoch of the key set this share was verified against.
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    /// Returns the 0-based index of the participant that has produced this share.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the decryption share.
+    pub fn share(&self) -> VerifiableDecryption<G> {
+        self.share
+    }
+}
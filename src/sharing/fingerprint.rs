@@ -0,0 +1,227 @@
+//! Human-comparable fingerprints of `PublicKeySet`s.
+
+use sha2::{Digest, Sha256};
+
+use core::{fmt, str::FromStr};
+
+use super::PublicKeySet;
+use crate::{group::Group, proto_labels};
+
+/// Fingerprint of a [`PublicKeySet`] intended to be compared by humans, e.g., read aloud
+/// by trustees over the phone during a key generation ceremony.
+///
+/// The fingerprint is a truncated SHA-256 digest of the key set [`Params`](super::Params),
+/// shared key and participant keys. Unlike [`KeyFingerprint`](crate::KeyFingerprint),
+/// it is long enough (128 bits) to withstand deliberate attempts to find a key set
+/// with a matching fingerprint.
+///
+/// A fingerprint is displayed as 8 groups of 4 uppercase hex digits separated by dashes,
+/// e.g., `1A2B-3C4D-5E6F-7081-92A3-B4C5-D6E7-F809`. This format can be encoded in the compact
+/// alphanumeric mode of QR codes. Parsing a fingerprint via [`FromStr`] is case-insensitive
+/// and ignores dashes and whitespace.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::*};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+///
+/// let code = key_set.fingerprint().to_string();
+/// assert_eq!(code.len(), 39);
+/// // The code is read aloud to another trustee, who checks it against their key set.
+/// key_set.verify_fingerprint(&code.to_lowercase())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeySetFingerprint([u8; Self::SIZE]);
+
+impl fmt::Debug for KeySetFingerprint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "KeySetFingerprint({self})")
+    }
+}
+
+impl fmt::Display for KeySetFingerprint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.0.chunks(2).enumerate() {
+            if i > 0 {
+                formatter.write_str("-")?;
+            }
+            write!(formatter, "{:02X}{:02X}", group[0], group[1])?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeySetFingerprint {
+    type Err = FingerprintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut digits = s
+            .chars()
+            .filter(|&ch| ch != '-' && !ch.is_whitespace())
+            .map(|ch| ch.to_digit(16).ok_or(FingerprintError::InvalidFormat));
+
+        let mut bytes = [0_u8; Self::SIZE];
+        for byte in &mut bytes {
+            let (Some(hi), Some(lo)) = (digits.next(), digits.next()) else {
+                return Err(FingerprintError::InvalidFormat);
+            };
+            // `unwrap()` is safe: the value is less than 256
+            *byte = u8::try_from(hi? * 16 + lo?).unwrap();
+        }
+        if digits.next().is_some() {
+            return Err(FingerprintError::InvalidFormat);
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl KeySetFingerprint {
+    /// Size of a fingerprint in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Restores a fingerprint from its bytes.
+    pub const fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns bytes of this fingerprint.
+    pub fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+}
+
+impl<G: Group> PublicKeySet<G> {
+    /// Computes the [`KeySetFingerprint`] of this key set. The fingerprint covers the params,
+    /// shared key and participant keys; it does not depend on identity keys, certificates
+    /// or the key set epoch.
+    pub fn fingerprint(&self) -> KeySetFingerprint {
+        let params = self.params();
+        let mut hasher = Sha256::new()
+            .chain_update(proto_labels::KEY_SET_FINGERPRINT)
+            .chain_update((params.shares as u64).to_le_bytes())
+            .chain_update((params.threshold as u64).to_le_bytes())
+            .chain_update(self.shared_key().as_bytes());
+        for key in self.participant_keys() {
+            hasher.update(key.as_bytes());
+        }
+
+        let digest = hasher.finalize();
+        let mut bytes = [0_u8; KeySetFingerprint::SIZE];
+        bytes.copy_from_slice(&digest[..KeySetFingerprint::SIZE]);
+        KeySetFingerprint(bytes)
+    }
+
+    /// Checks that the `code` (e.g., read aloud by another trustee) is the fingerprint
+    /// of this key set. The code is parsed as described in [`KeySetFingerprint`] docs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the code cannot be parsed or does not match the fingerprint.
+    pub fn verify_fingerprint(&self, code: &str) -> Result<(), FingerprintError> {
+        let expected: KeySetFingerprint = code.parse()?;
+        if expected == self.fingerprint() {
+            Ok(())
+        } else {
+            Err(FingerprintError::Mismatch)
+        }
+    }
+}
+
+/// Errors that can occur when parsing or verifying a [`KeySetFingerprint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FingerprintError {
+    /// Fingerprint code contains non-hex chars or has an invalid number of digits.
+    InvalidFormat,
+    /// Fingerprint does not match the key set.
+    Mismatch,
+}
+
+impl fmt::Display for FingerprintError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::InvalidFormat => "invalid format of the key set fingerprint",
+            Self::Mismatch => "fingerprint does not match the key set",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FingerprintError {}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        alloc::ToString,
+        group::Ristretto,
+        sharing::{Dealer, Params},
+    };
+
+    #[test]
+    fn fingerprint_roundtrip() {
+        let fingerprint = KeySetFingerprint::from_bytes([
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
+            0x32, 0x10,
+        ]);
+        let code = fingerprint.to_string();
+        assert_eq!(code, "0123-4567-89AB-CDEF-FEDC-BA98-7654-3210");
+        assert_eq!(code.parse::<KeySetFingerprint>().unwrap(), fingerprint);
+
+        let spaced_code = "0123 4567 89ab cdef\nfedc ba98 7654 3210";
+        assert_eq!(
+            spaced_code.parse::<KeySetFingerprint>().unwrap(),
+            fingerprint
+        );
+
+        for invalid_code in [
+            "",
+            &code[..37],
+            &code[..38],
+            "0123-4567-89AB-CDEF-FEDC-BA98-7654-321X",
+        ] {
+            let err = invalid_code.parse::<KeySetFingerprint>().unwrap_err();
+            assert_eq!(err, FingerprintError::InvalidFormat);
+        }
+        let long_code = [&code, "00"].concat();
+        let err = long_code.parse::<KeySetFingerprint>().unwrap_err();
+        assert_eq!(err, FingerprintError::InvalidFormat);
+    }
+
+    #[test]
+    fn key_set_fingerprint_covers_keys() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+
+        let fingerprint = key_set.fingerprint();
+        let next_key_set = key_set.clone().with_epoch(key_set.epoch().next());
+        assert_eq!(next_key_set.fingerprint(), fingerprint);
+        key_set
+            .verify_fingerprint(&fingerprint.to_string())
+            .unwrap();
+
+        let other_dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = other_dealer.public_info();
+        let other_key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let err = other_key_set
+            .verify_fingerprint(&fingerprint.to_string())
+            .unwrap_err();
+        assert_eq!(err, FingerprintError::Mismatch);
+        let err = key_set.verify_fingerprint("not a fingerprint").unwrap_err();
+        assert_eq!(err, FingerprintError::InvalidFormat);
+    }
+}
@@ -3,27 +3,99 @@
 use merlin::Transcript;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use core::iter;
 
-use super::{lagrange_coefficients, Error, Params, PublicPolynomial};
+use super::{
+    lagrange_coefficients, Epoch, EpochShare, Error, Generation, Params, PublicPolynomial,
+};
 
 use crate::{
-    alloc::Vec,
+    alloc::{vec, Box, Vec},
     group::Group,
     proofs::{LogEqualityProof, ProofOfPossession, TranscriptForGroup, VerificationError},
-    CandidateDecryption, Ciphertext, PublicKey, VerifiableDecryption,
+    proto_labels,
+    sharing::{CombinationSubset, ReEncryptionProof, ReEncryptionShare, SignedDecryptionShare},
+    CandidateDecryption, CertificateChain, CertificateVerifier, CertificationError, Ciphertext,
+    IdentityPublicKey, PublicKey, VerifiableDecryption,
 };
 
 /// Full public information about the participants of a threshold ElGamal encryption scheme
 /// after all participants' commitments are collected.
+///
+/// Participants are identified by 0-based indexes. Participant keys are stored and serialized
+/// in the increasing index order, so the serialized form of a key set is canonical.
+/// Use [`Self::from_indexed_participants()`] to restore a key set from keys stored in
+/// an arbitrary order.
+///
+/// Participant keys are values of the public (Feldman commitment) polynomial at participant
+/// indexes. They are evaluated once when the key set is created (including at the end
+/// of [distributed key generation](crate::dkg)), so verifying decryption shares
+/// and other participant messages does not require evaluating the polynomial.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[cfg_attr(feature = "serde", serde(bound = "", try_from = "PublicKeySetRepr<G>"))]
 pub struct PublicKeySet<G: Group> {
     params: Params,
     shared_key: PublicKey<G>,
     participant_keys: Vec<PublicKey<G>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    identity_keys: Vec<IdentityPublicKey<G>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    certificates: Vec<CertificateChain>,
+    epoch: Epoch,
+    /// Cached generation; recomputed whenever the data it depends on changes.
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    generation: Generation,
+}
+
+/// Serialized form of a [`PublicKeySet`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct PublicKeySetRepr<G: Group> {
+    params: Params,
+    shared_key: PublicKey<G>,
+    participant_keys: Vec<PublicKey<G>>,
+    #[serde(default)]
+    identity_keys: Vec<IdentityPublicKey<G>>,
+    #[serde(default)]
+    certificates: Vec<CertificateChain>,
+    #[serde(default)]
+    epoch: Epoch,
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> TryFrom<PublicKeySetRepr<G>> for PublicKeySet<G> {
+    type Error = Error;
+
+    fn try_from(repr: PublicKeySetRepr<G>) -> Result<Self, Self::Error> {
+        let params = repr.params;
+        params.check_group::<G>()?;
+        let shares = params.shares;
+        if repr.participant_keys.len() != shares {
+            return Err(Error::ParticipantCountMismatch);
+        }
+        if !repr.identity_keys.is_empty() && repr.identity_keys.len() != shares {
+            return Err(Error::ParticipantCountMismatch);
+        }
+        if !repr.certificates.is_empty() && repr.identity_keys.len() != repr.certificates.len() {
+            return Err(Error::ParticipantCountMismatch);
+        }
+
+        let generation =
+            Self::compute_generation(params, repr.epoch, &repr.shared_key, &repr.participant_keys);
+        Ok(Self {
+            params,
+            shared_key: repr.shared_key,
+            participant_keys: repr.participant_keys,
+            identity_keys: repr.identity_keys,
+            certificates: repr.certificates,
+            epoch: repr.epoch,
+            generation,
+        })
+    }
 }
 
 impl<G: Group> PublicKeySet<G> {
@@ -32,11 +104,12 @@ impl<G: Group> PublicKeySet<G> {
         public_polynomial: &[G::Element],
         proof_of_possession: &ProofOfPossession<G>,
     ) -> Result<(), Error> {
+        params.check_group::<G>()?;
         if public_polynomial.len() != params.threshold {
             return Err(Error::MalformedDealerPolynomial);
         }
 
-        let mut transcript = Transcript::new(b"elgamal_share_poly");
+        let mut transcript = Transcript::new(proto_labels::SHARE_POLYNOMIAL);
         transcript.append_u64(b"n", params.shares as u64);
         transcript.append_u64(b"t", params.threshold as u64);
 
@@ -55,7 +128,8 @@ impl<G: Group> PublicKeySet<G> {
     ///
     /// # Errors
     ///
-    /// Returns an error if the information provided by the dealer is malformed.
+    /// Returns an error if the information provided by the dealer is malformed,
+    /// or if `params` are [not supported](Params::check_group()) by the group.
     ///
     /// [`Dealer`]: crate::sharing::Dealer
     pub fn new(
@@ -64,18 +138,34 @@ impl<G: Group> PublicKeySet<G> {
         proof_of_possession: &ProofOfPossession<G>,
     ) -> Result<Self, Error> {
         Self::validate(params, &public_polynomial, proof_of_possession)?;
-
         let public_poly = PublicPolynomial::<G>(public_polynomial);
+        Ok(Self::from_polynomial(params, &public_poly))
+    }
+
+    /// Creates a key set by evaluating the public polynomial at each participant index.
+    /// Unlike [`Self::from_participants()`], this does not need to check consistency of
+    /// participant keys, since they are derived from a single polynomial by construction
+    /// (see the `key_set/*` benchmarks in `benches/sharing.rs`).
+    pub(crate) fn from_polynomial(params: Params, public_poly: &PublicPolynomial<G>) -> Self {
+        debug_assert_eq!(public_poly.coefficients().len(), params.threshold);
         let shared_key = PublicKey::from_element(public_poly.value_at_zero());
-        let participant_keys = (0..params.shares)
-            .map(|idx| PublicKey::from_element(public_poly.value_at((idx as u64 + 1).into())))
-            .collect();
+        let participant_keys = public_poly
+            .values_at_indexes(params.shares)
+            .into_iter()
+            .map(PublicKey::from_element)
+            .collect::<Vec<_>>();
+        let generation =
+            Self::compute_generation(params, Epoch::INITIAL, &shared_key, &participant_keys);
 
-        Ok(Self {
+        Self {
             params,
             shared_key,
             participant_keys,
-        })
+            identity_keys: Vec::new(),
+            certificates: Vec::new(),
+            epoch: Epoch::INITIAL,
+            generation,
+        }
     }
 
     /// Creates a key set from the parameters and public keys of all participants.
@@ -84,11 +174,13 @@ impl<G: Group> PublicKeySet<G> {
     ///
     /// Returns an error if the number of keys in `participant_keys` does not match the number
     /// of participants in `params`, or if `participant_keys` are inconsistent (do not correspond
-    /// to a single shared key).
+    /// to a single shared key). Also returns an error if `params`
+    /// are [not supported](Params::check_group()) by the group.
     pub fn from_participants(
         params: Params,
         participant_keys: Vec<PublicKey<G>>,
     ) -> Result<Self, Error> {
+        params.check_group::<G>()?;
         if params.shares != participant_keys.len() {
             return Err(Error::ParticipantCountMismatch);
         }
@@ -137,13 +229,185 @@ impl<G: Group> PublicKeySet<G> {
             }
         }
 
+        let generation =
+            Self::compute_generation(params, Epoch::INITIAL, &shared_key, &participant_keys);
         Ok(Self {
             params,
             shared_key,
             participant_keys,
+            identity_keys: Vec::new(),
+            certificates: Vec::new(),
+            epoch: Epoch::INITIAL,
+            generation,
         })
     }
 
+    /// Creates a key set from the parameters and public keys of all participants provided
+    /// together with 0-based participant indexes. Unlike [`Self::from_participants()`],
+    /// keys may be supplied in any order (e.g., when read from unordered storage).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an index is out of bounds or repeated, if keys for some participants
+    /// are missing, or in the same cases as [`Self::from_participants()`].
+    pub fn from_indexed_participants(
+        params: Params,
+        participant_keys: impl IntoIterator<Item = (usize, PublicKey<G>)>,
+    ) -> Result<Self, Error> {
+        let mut keys: Vec<Option<PublicKey<G>>> = vec![None; params.shares];
+        for (index, key) in participant_keys {
+            match keys.get_mut(index) {
+                Some(slot @ None) => *slot = Some(key),
+                _ => return Err(Error::InvalidParticipantIndex(index)),
+            }
+        }
+        let keys = keys.into_iter().collect::<Option<Vec<_>>>();
+        let keys = keys.ok_or(Error::ParticipantCountMismatch)?;
+        Self::from_participants(params, keys)
+    }
+
+    /// Creates a key set from contributions of all participants of
+    /// [distributed key generation](crate::dkg) read from a `stream`, e.g., a public
+    /// bulletin board. Each contribution consists of the 0-based participant index,
+    /// the participant's public polynomial and the proof of possession for it
+    /// (i.e., the corresponding fields of [`PublicInfo`](crate::dkg::PublicInfo)).
+    ///
+    /// Contributions are verified in batches, and the shared public polynomial is accumulated
+    /// as the stream is read, so the stream does not need to fit into memory.
+    /// Unlike verifying contributions one by one, all invalid contributions are reported
+    /// at once. See [`Self::try_from_stream_parallel()`] for a version verifying each batch
+    /// in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParticipantIndex`] if a participant index is out of bounds
+    /// or repeated, and [`Error::InvalidContributions`] if any contributions are invalid.
+    /// Returns [`Error::ParticipantCountMismatch`] if contributions for some participants
+    /// are missing. Also returns an error if `params` are [not supported](Params::check_group())
+    /// by the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use elastic_elgamal::{dkg::*, group::Ristretto, sharing::{Params, PublicKeySet}};
+    /// # use rand::thread_rng;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let params = Params::new(3, 2);
+    /// let mut rng = thread_rng();
+    /// let participants: Vec<_> = (0..3)
+    ///     .map(|i| {
+    ///         ParticipantCollectingCommitments::<Ristretto>::new(params, i, &mut rng)
+    ///     })
+    ///     .collect();
+    /// let infos: Vec<_> = participants
+    ///     .iter()
+    ///     .map(|participant| {
+    ///         let mut participant = participant.clone();
+    ///         for (i, other) in participants.iter().enumerate() {
+    ///             participant.insert_commitment(i, other.commitment())?;
+    ///         }
+    ///         let participant = participant.finish_commitment_phase();
+    ///         Ok(participant.public_info().into_owned())
+    ///     })
+    ///     .collect::<Result<_, Error>>()?;
+    ///
+    /// let stream = infos.into_iter().enumerate().map(|(i, info)| {
+    ///     (i, info.polynomial, info.proof_of_possession.into_owned())
+    /// });
+    /// let key_set = PublicKeySet::try_from_stream(params, stream)?;
+    /// assert_eq!(key_set.participant_keys().len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_stream(
+        params: Params,
+        stream: impl IntoIterator<Item = (usize, Vec<G::Element>, ProofOfPossession<G>)>,
+    ) -> Result<Self, Error> {
+        Self::from_stream_with(params, stream, |batch| {
+            batch
+                .iter()
+                .map(|(_, polynomial, proof)| Self::validate(params, polynomial, proof).is_ok())
+                .collect()
+        })
+    }
+
+    /// Same as [`Self::try_from_stream()`], but verifies contributions in each batch
+    /// in parallel using the [`rayon`] thread pool.
+    ///
+    /// [`rayon`]: https://docs.rs/rayon/
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same situations as [`Self::try_from_stream()`].
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn try_from_stream_parallel(
+        params: Params,
+        stream: impl IntoIterator<Item = (usize, Vec<G::Element>, ProofOfPossession<G>)>,
+    ) -> Result<Self, Error>
+    where
+        G::Scalar: Send + Sync,
+        G::Element: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        Self::from_stream_with(params, stream, |batch| {
+            batch
+                .par_iter()
+                .map(|(_, polynomial, proof)| Self::validate(params, polynomial, proof).is_ok())
+                .collect()
+        })
+    }
+
+    /// Number of contributions verified at once in [`Self::try_from_stream()`].
+    const STREAM_BATCH_SIZE: usize = 64;
+
+    #[allow(clippy::type_complexity)] // not that complex
+    fn from_stream_with(
+        params: Params,
+        stream: impl IntoIterator<Item = (usize, Vec<G::Element>, ProofOfPossession<G>)>,
+        verify_batch: impl Fn(&[(usize, Vec<G::Element>, ProofOfPossession<G>)]) -> Vec<bool>,
+    ) -> Result<Self, Error> {
+        params.check_group::<G>()?;
+
+        let mut is_received = vec![false; params.shares];
+        let mut accumulated = PublicPolynomial::<G>::new(vec![G::identity(); params.threshold]);
+        let mut invalid_indexes = vec![];
+        let mut flush = |batch: &mut Vec<_>| {
+            let validity = verify_batch(batch);
+            for ((index, polynomial, _), is_valid) in batch.drain(..).zip(validity) {
+                if is_valid {
+                    accumulated += &PublicPolynomial::new(polynomial);
+                } else {
+                    invalid_indexes.push(index);
+                }
+            }
+        };
+
+        let mut batch = Vec::with_capacity(Self::STREAM_BATCH_SIZE);
+        for contribution in stream {
+            let index = contribution.0;
+            match is_received.get_mut(index) {
+                Some(flag @ false) => *flag = true,
+                _ => return Err(Error::InvalidParticipantIndex(index)),
+            }
+            batch.push(contribution);
+            if batch.len() == Self::STREAM_BATCH_SIZE {
+                flush(&mut batch);
+            }
+        }
+        flush(&mut batch);
+
+        if !invalid_indexes.is_empty() {
+            invalid_indexes.sort_unstable();
+            return Err(Error::InvalidContributions(invalid_indexes));
+        }
+        if is_received.contains(&false) {
+            return Err(Error::ParticipantCountMismatch);
+        }
+        Ok(Self::from_polynomial(params, &accumulated))
+    }
+
     /// Returns parameters for this scheme.
     pub fn params(&self) -> Params {
         self.params
@@ -165,9 +429,213 @@ impl<G: Group> PublicKeySet<G> {
         &self.participant_keys
     }
 
+    /// Iterates over participants' public keys together with their 0-based indexes.
+    /// Keys are yielded in the increasing index order.
+    pub fn indexed_participant_keys(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, &PublicKey<G>)> + '_ {
+        self.participant_keys.iter().enumerate()
+    }
+
+    /// Evaluates the public (Feldman commitment) polynomial at the point `x`. The value at zero
+    /// is the shared key, and the value at `i + 1` is the key of the participant with
+    /// the 0-based index `i`.
+    ///
+    /// The key set does not store polynomial coefficients, so the value is interpolated
+    /// from the keys of the first `t` participants. As with computing participant keys
+    /// from the polynomial, the interpolation is performed with a single multi-scalar
+    /// multiplication. The computation is not constant-time w.r.t. `x`.
+    pub fn commitment_evaluation(&self, x: G::Scalar) -> G::Element {
+        let threshold = self.params.threshold;
+        let points: Vec<_> = (1..=threshold as u64).map(G::Scalar::from).collect();
+        let mut denominators: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &point)| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold(G::Scalar::from(1), |acc, (_, &other)| acc * (point - other))
+            })
+            .collect();
+        G::invert_scalars(&mut denominators);
+
+        let coefficients: Vec<_> = denominators
+            .iter()
+            .enumerate()
+            .map(|(i, &denominator)| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold(denominator, |acc, (_, &other)| acc * (x - other))
+            })
+            .collect();
+        let keys = self.participant_keys[..threshold]
+            .iter()
+            .map(PublicKey::as_element);
+        G::vartime_multi_mul(&coefficients, keys)
+    }
+
+    /// Returns the public key of a participant with the specified 0-based `index`, which
+    /// may be out of bounds for this key set (e.g., to derive keys of participants added
+    /// during resharing or recovery).
+    pub fn participant_key_at(&self, index: usize) -> PublicKey<G> {
+        if let Some(key) = self.participant_keys.get(index) {
+            return key.clone();
+        }
+        let x = G::Scalar::from(index as u64 + 1);
+        PublicKey::from_element(self.commitment_evaluation(x))
+    }
+
+    /// Registers long-term identity keys of all participants. Participants use these keys
+    /// to sign protocol messages they emit, e.g., decryption shares; see
+    /// [`ActiveParticipant::with_identity()`](crate::sharing::ActiveParticipant::with_identity()).
+    ///
+    /// Previously attached [certificates](Self::with_certificates()) are removed, since
+    /// they certify the replaced identity keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of `identity_keys` does not match the number
+    /// of participants.
+    pub fn with_identity_keys(
+        mut self,
+        identity_keys: Vec<IdentityPublicKey<G>>,
+    ) -> Result<Self, Error> {
+        if identity_keys.len() != self.params.shares {
+            return Err(Error::ParticipantCountMismatch);
+        }
+        self.identity_keys = identity_keys;
+        self.certificates.clear();
+        Ok(self)
+    }
+
+    /// Attaches certificate chains for identity keys of all participants, ordered
+    /// by participant indexes. Chains are not verified by this method; use
+    /// [`Self::verify_certificates()`] to check them against a root authority.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if identity keys are not [registered](Self::with_identity_keys()),
+    /// or if the number of `certificates` does not match the number of participants.
+    pub fn with_certificates(mut self, certificates: Vec<CertificateChain>) -> Result<Self, Error> {
+        if self.identity_keys.is_empty() || certificates.len() != self.params.shares {
+            return Err(Error::ParticipantCountMismatch);
+        }
+        self.certificates = certificates;
+        Ok(self)
+    }
+
+    /// Returns the certificate chain for the identity key of a participant with
+    /// the specified `index`. Returns `None` if `index` is out of bounds, or if certificates
+    /// were not attached via [`Self::with_certificates()`].
+    pub fn certificate(&self, index: usize) -> Option<&CertificateChain> {
+        self.certificates.get(index)
+    }
+
+    /// Checks that identity keys of all participants are certified by the `root` authority.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CertificationError::MissingCertificate`] for the first participant without
+    /// an identity key or a certificate chain, and [`CertificationError::Participant`]
+    /// if a certificate chain does not verify.
+    pub fn verify_certificates<V: CertificateVerifier + ?Sized>(
+        &self,
+        root: &[u8],
+        verifier: &V,
+    ) -> Result<(), CertificationError> {
+        for index in 0..self.params.shares {
+            let key = self.identity_keys.get(index);
+            let chain = self.certificates.get(index);
+            let (Some(key), Some(chain)) = (key, chain) else {
+                return Err(CertificationError::MissingCertificate { participant: index });
+            };
+            key.as_untyped()
+                .verify_certificate(chain, root, verifier)
+                .map_err(|err| CertificationError::Participant {
+                    index,
+                    error: Box::new(err),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Returns the identity key of a participant with the specified `index`. Returns `None`
+    /// if `index` is out of bounds, or if identity keys were not registered
+    /// via [`Self::with_identity_keys()`].
+    pub fn identity_key(&self, index: usize) -> Option<&IdentityPublicKey<G>> {
+        self.identity_keys.get(index)
+    }
+
+    /// Iterates over participants' identity keys together with their 0-based indexes.
+    /// Keys are yielded in the increasing index order. If identity keys were not registered,
+    /// the iterator is empty.
+    pub fn indexed_identity_keys(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, &IdentityPublicKey<G>)> + '_ {
+        self.identity_keys.iter().enumerate()
+    }
+
+    /// Returns the epoch of this key set. Key sets are created in [`Epoch::INITIAL`];
+    /// use [`Self::with_epoch()`] to change it.
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    /// Sets the epoch of this key set. This should be used when the committee refreshes
+    /// or reshares its keys, so that decryption shares and other proofs from participants
+    /// cannot be mixed up between the old and new key sets. Usually, the new epoch is
+    /// the [next one](Epoch::next()) after the epoch of the previous key set.
+    ///
+    /// The epoch is bound into all participant proofs, so participants and verifiers must agree
+    /// on it. The only exception is [`Epoch::INITIAL`], for which participant proofs
+    /// are the same as for key sets without an epoch.
+    #[must_use]
+    pub fn with_epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = epoch;
+        self.generation =
+            Self::compute_generation(self.params, epoch, &self.shared_key, &self.participant_keys);
+        self
+    }
+
+    /// Returns the [`Generation`] of this key set. The generation changes whenever
+    /// the epoch, params or any of the participant keys change; identity keys do not affect it.
+    ///
+    /// The generation is computed once when the key set is created (or its epoch is changed),
+    /// so this method is cheap.
+    pub fn generation(&self) -> Generation {
+        self.generation
+    }
+
+    fn compute_generation(
+        params: Params,
+        epoch: Epoch,
+        shared_key: &PublicKey<G>,
+        participant_keys: &[PublicKey<G>],
+    ) -> Generation {
+        let mut hasher = Sha256::new()
+            .chain_update(proto_labels::KEY_SET_GENERATION)
+            .chain_update((params.shares as u64).to_le_bytes())
+            .chain_update((params.threshold as u64).to_le_bytes())
+            .chain_update(epoch.value().to_le_bytes())
+            .chain_update(shared_key.as_bytes());
+        for key in participant_keys {
+            hasher.update(key.as_bytes());
+        }
+        Generation::new(epoch, hasher.finalize().into())
+    }
+
     pub(super) fn commit(&self, transcript: &mut Transcript) {
         transcript.append_u64(b"n", self.params.shares as u64);
         transcript.append_u64(b"t", self.params.threshold as u64);
+        // The initial epoch is not bound so that proofs created before epochs were introduced
+        // remain valid.
+        if self.epoch != Epoch::INITIAL {
+            transcript.append_u64(b"epoch", self.epoch.value());
+        }
         transcript.append_element_bytes(b"K", self.shared_key.as_bytes());
     }
 
@@ -195,7 +663,7 @@ impl<G: Group> PublicKeySet<G> {
                 self.participant_keys.len()
             );
         });
-        let mut transcript = Transcript::new(b"elgamal_participant_pop");
+        let mut transcript = Transcript::new(proto_labels::PARTICIPANT_POP);
         self.commit(&mut transcript);
         transcript.append_u64(b"i", index as u64);
         proof.verify(iter::once(participant_key), &mut transcript)
@@ -206,7 +674,9 @@ impl<G: Group> PublicKeySet<G> {
     ///
     /// # Errors
     ///
-    /// Returns an error if the `proof` does not verify.
+    /// Returns an error if the `proof` does not verify. This includes the case when
+    /// the share was produced with a key set having another shared key, even if the key
+    /// of the participant is the same in both key sets.
     pub fn verify_share(
         &self,
         candidate_share: CandidateDecryption<G>,
@@ -216,10 +686,7 @@ impl<G: Group> PublicKeySet<G> {
     ) -> Result<VerifiableDecryption<G>, VerificationError> {
         let key_share = self.participant_keys[index].as_element();
         let dh_element = candidate_share.dh_element();
-        let mut transcript = Transcript::new(b"elgamal_decryption_share");
-        self.commit(&mut transcript);
-        transcript.append_u64(b"i", index as u64);
-
+        let mut transcript = self.share_transcript(index);
         proof.verify(
             &PublicKey::from_element(ciphertext.random_element),
             (key_share, dh_element),
@@ -227,6 +694,229 @@ impl<G: Group> PublicKeySet<G> {
         )?;
         Ok(VerifiableDecryption::from_element(dh_element))
     }
+
+    /// Verifies a candidate decryption share similarly to [`Self::verify_share()`], and
+    /// marks the verified share with the epoch of this key set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify. This includes the case when
+    /// the share was produced by a participant with a key set in another epoch.
+    pub fn verify_epoch_share(
+        &self,
+        candidate_share: CandidateDecryption<G>,
+        ciphertext: Ciphertext<G>,
+        index: usize,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<EpochShare<G>, VerificationError> {
+        let share = self.verify_share(candidate_share, ciphertext, index, proof)?;
+        Ok(EpochShare {
+            epoch: self.epoch,
+            index,
+            share,
+        })
+    }
+
+    /// Combines decryption shares verified via [`Self::verify_epoch_share()`]. Shares are
+    /// selected in the same way as in [`Params::combine_shares()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `shares` was verified in an epoch other than
+    /// the [epoch](Self::epoch()) of this key set, or if there are not enough shares.
+    pub fn combine_epoch_shares(
+        &self,
+        shares: impl IntoIterator<Item = EpochShare<G>>,
+    ) -> Result<VerifiableDecryption<G>, Error> {
+        let shares = shares
+            .into_iter()
+            .map(|share| {
+                if share.epoch == self.epoch {
+                    Ok((share.index, share.share))
+                } else {
+                    Err(Error::EpochMismatch {
+                        expected: self.epoch,
+                        actual: share.epoch,
+                    })
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.params
+            .combine_shares(shares)
+            .ok_or(Error::InsufficientShares)
+    }
+
+    pub(super) fn share_transcript(&self, index: usize) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::DECRYPTION_SHARE);
+        self.commit(&mut transcript);
+        transcript.append_u64(b"i", index as u64);
+        transcript
+    }
+
+    pub(super) fn subset_share_transcript(
+        &self,
+        subset: &CombinationSubset,
+        index: usize,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::SUBSET_DECRYPTION_SHARE);
+        self.commit(&mut transcript);
+        for &subset_index in subset.indexes() {
+            transcript.append_u64(b"j", subset_index as u64);
+        }
+        transcript.append_u64(b"i", index as u64);
+        transcript
+    }
+
+    /// Verifies a candidate decryption share for `ciphertext` produced for the `subset`
+    /// (i.e., premultiplied by the Lagrange coefficient) by a participant with
+    /// the specified `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `subset` does not match the parameters of this key set
+    /// (i.e., has a size other than the threshold or contains out-of-bounds indexes),
+    /// if `index` is not in the `subset`, or if the `proof` does not verify.
+    pub fn verify_subset_share(
+        &self,
+        candidate_share: CandidateDecryption<G>,
+        ciphertext: Ciphertext<G>,
+        subset: &CombinationSubset,
+        index: usize,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, Error> {
+        subset.check(self.params)?;
+        let coefficient = subset
+            .lagrange_coefficient::<G>(index)
+            .ok_or(Error::InvalidParticipantIndex(index))?;
+        let scaled_key = self.participant_keys[index].as_element() * &coefficient;
+        let dh_element = candidate_share.dh_element();
+        let mut transcript = self.subset_share_transcript(subset, index);
+
+        proof
+            .verify(
+                &PublicKey::from_element(ciphertext.random_element),
+                (scaled_key, dh_element),
+                &mut transcript,
+            )
+            .map_err(|err| Error::InvalidShare { index, err })?;
+        Ok(VerifiableDecryption::from_element(dh_element))
+    }
+
+    /// Verifies a share re-encrypting `ciphertext` to the `target` key provided by a participant
+    /// with the specified `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not correspond to a participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_reencryption_share(
+        &self,
+        share: &ReEncryptionShare<G>,
+        ciphertext: Ciphertext<G>,
+        target: &PublicKey<G>,
+        index: usize,
+        proof: &ReEncryptionProof<G>,
+    ) -> Result<(), VerificationError> {
+        proof.verify(self, index, share, ciphertext, target)
+    }
+
+    pub(super) fn signature_transcript(&self, index: usize, message: &[u8]) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::PARTICIPANT_SIGNATURE);
+        self.commit(&mut transcript);
+        transcript.append_u64(b"i", index as u64);
+        transcript.append_message(b"msg", message);
+        transcript
+    }
+
+    /// Verifies a `signature` of the `message` by a participant with the specified `index`.
+    /// The signature is checked against the participant's identity key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerificationError::UnknownSigner`] if identity keys are not registered,
+    /// or if `index` does not correspond to a participant. Returns other errors
+    /// if the `signature` does not verify.
+    pub fn verify_signature(
+        &self,
+        index: usize,
+        message: &[u8],
+        signature: &ProofOfPossession<G>,
+    ) -> Result<(), VerificationError> {
+        let identity_key = self
+            .identity_key(index)
+            .ok_or(VerificationError::UnknownSigner { index })?;
+        let mut transcript = self.signature_transcript(index, message);
+        signature.verify(iter::once(identity_key.as_untyped()), &mut transcript)
+    }
+
+    /// Verifies a signed decryption share for `ciphertext` provided by a participant
+    /// with the specified `index`. Both the signature and the proof of share correctness
+    /// are checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same situations as [`Self::verify_signature()`], and if
+    /// the share proof does not verify.
+    pub fn verify_signed_share(
+        &self,
+        signed_share: &SignedDecryptionShare<G>,
+        ciphertext: Ciphertext<G>,
+        index: usize,
+    ) -> Result<VerifiableDecryption<G>, VerificationError> {
+        let message = SignedDecryptionShare::signed_message(
+            ciphertext,
+            signed_share.share,
+            signed_share.proof,
+        );
+        self.verify_signature(index, &message, &signed_share.signature)?;
+        self.verify_share(signed_share.share, ciphertext, index, &signed_share.proof)
+    }
+
+    /// Returns the test ciphertext for a liveness check with the specified `challenge`
+    /// (e.g., a date or a random nonce chosen by the party organizing the check).
+    ///
+    /// The ciphertext is an encryption of zero with the randomness derived from the challenge
+    /// and this key set, so it is publicly known and cannot coincide with a real ciphertext.
+    /// Participants prove that they are able to decrypt by producing shares for the ciphertext
+    /// via [`ActiveParticipant::liveness_share()`](super::ActiveParticipant::liveness_share()).
+    /// Since the randomness is public, a liveness share reveals nothing about the secret share
+    /// of the participant beyond its public key; only the proof of the share correctness
+    /// requires the knowledge of the secret.
+    pub fn liveness_ciphertext(&self, challenge: &[u8]) -> Ciphertext<G> {
+        let mut transcript = Transcript::new(proto_labels::LIVENESS_CHECK);
+        self.commit(&mut transcript);
+        transcript.append_message(b"challenge", challenge);
+        let randomness = transcript.challenge_scalar::<G>(b"r");
+        Ciphertext {
+            random_element: G::mul_generator(&randomness),
+            blinded_element: self.shared_key.as_element() * &randomness,
+        }
+    }
+
+    /// Verifies a liveness share for the `challenge` provided by a participant
+    /// with the specified `index`. See [`Self::liveness_ciphertext()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not correspond to a participant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify, e.g., if the share was produced
+    /// for another challenge.
+    pub fn verify_liveness_share(
+        &self,
+        challenge: &[u8],
+        index: usize,
+        candidate_share: CandidateDecryption<G>,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, VerificationError> {
+        let ciphertext = self.liveness_ciphertext(challenge);
+        self.verify_share(candidate_share, ciphertext, index, proof)
+    }
 }
 
 #[cfg(test)]
@@ -235,8 +925,9 @@ mod tests {
 
     use super::*;
     use crate::{
-        group::{ElementOps, Ristretto},
-        sharing::Dealer,
+        group::{ElementOps, Ristretto, ScalarOps},
+        sharing::{ActiveParticipant, CombinationSubset, Dealer},
+        Keypair,
     };
 
     #[test]
@@ -247,8 +938,10 @@ mod tests {
         let dealer = Dealer::<Ristretto>::new(params, &mut rng);
         let (public_poly, _) = dealer.public_info();
         let public_poly = PublicPolynomial::<Ristretto>(public_poly);
-        let participant_keys: Vec<PublicKey<Ristretto>> = (1..=params.shares)
-            .map(|i| PublicKey::from_element(public_poly.value_at((i as u64).into())))
+        let participant_keys: Vec<PublicKey<Ristretto>> = public_poly
+            .values_at_indexes(params.shares)
+            .into_iter()
+            .map(PublicKey::from_element)
             .collect();
 
         // Check that `participant_keys` are computed correctly.
@@ -272,4 +965,281 @@ mod tests {
             assert!(matches!(err, Error::MalformedParticipantKeys));
         }
     }
+
+    #[test]
+    fn restoring_key_set_from_indexed_participant_keys() {
+        let mut rng = thread_rng();
+        let params = Params::new(5, 3);
+
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let indexed_keys: Vec<_> = key_set
+            .indexed_participant_keys()
+            .map(|(i, key)| (i, key.clone()))
+            .collect();
+
+        let restored =
+            PublicKeySet::from_indexed_participants(params, indexed_keys.iter().rev().cloned())
+                .unwrap();
+        assert_eq!(restored.shared_key(), key_set.shared_key());
+        assert_eq!(restored.participant_keys(), key_set.participant_keys());
+
+        let mut repeated_keys = indexed_keys.clone();
+        repeated_keys[1].0 = 0;
+        let err = PublicKeySet::from_indexed_participants(params, repeated_keys).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(0)), "{err:?}");
+
+        let mut out_of_bounds_keys = indexed_keys.clone();
+        out_of_bounds_keys[1].0 = 5;
+        let err = PublicKeySet::from_indexed_participants(params, out_of_bounds_keys).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(5)), "{err:?}");
+
+        let missing_keys = indexed_keys[1..].to_vec();
+        let err = PublicKeySet::from_indexed_participants(params, missing_keys).unwrap_err();
+        assert!(matches!(err, Error::ParticipantCountMismatch), "{err:?}");
+    }
+
+    #[test]
+    fn restoring_key_set_from_contribution_stream() {
+        let mut rng = thread_rng();
+        // Use more participants than the batch size to check batching.
+        let params = Params::new(PublicKeySet::<Ristretto>::STREAM_BATCH_SIZE + 6, 3);
+        let contributions: Vec<_> = (0..params.shares)
+            .map(|i| {
+                let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+                let (polynomial, proof) = dealer.public_info();
+                (i, polynomial, proof.clone())
+            })
+            .collect();
+        let expected_key = contributions
+            .iter()
+            .map(|(_, polynomial, _)| polynomial[0])
+            .fold(Ristretto::identity(), |acc, element| acc + element);
+
+        let key_set = PublicKeySet::try_from_stream(params, contributions.clone()).unwrap();
+        assert_eq!(key_set.shared_key().as_element(), expected_key);
+        #[cfg(feature = "rayon")]
+        {
+            let stream = contributions.iter().rev().cloned();
+            let parallel_key_set = PublicKeySet::try_from_stream_parallel(params, stream).unwrap();
+            assert_eq!(
+                parallel_key_set.participant_keys(),
+                key_set.participant_keys()
+            );
+        }
+
+        let mut invalid_contributions = contributions.clone();
+        invalid_contributions[1].2 = contributions[0].2.clone();
+        invalid_contributions[params.shares - 1].1.pop();
+        let err = PublicKeySet::try_from_stream(params, invalid_contributions).unwrap_err();
+        let expected_indexes = [1, params.shares - 1];
+        assert!(
+            matches!(&err, Error::InvalidContributions(indexes) if *indexes == expected_indexes),
+            "{err:?}"
+        );
+
+        let mut repeated_contributions = contributions.clone();
+        repeated_contributions[1].0 = 0;
+        let err = PublicKeySet::try_from_stream(params, repeated_contributions).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(0)), "{err:?}");
+
+        let missing_contributions = contributions[1..].to_vec();
+        let err = PublicKeySet::try_from_stream(params, missing_contributions).unwrap_err();
+        assert!(matches!(err, Error::ParticipantCountMismatch), "{err:?}");
+    }
+
+    #[test]
+    fn shares_from_other_epochs_are_rejected() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        assert_eq!(key_set.epoch(), Epoch::INITIAL);
+        let next_key_set = key_set.clone().with_epoch(key_set.epoch().next());
+
+        let participants: Vec<_> = (0..3)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(next_key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+        let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+        let shares: Vec<_> = participants
+            .iter()
+            .map(|participant| {
+                let (share, proof) = participant.decrypt_share(ciphertext, &mut rng);
+                let index = participant.index();
+                assert!(key_set
+                    .verify_epoch_share(share.into(), ciphertext, index, &proof)
+                    .is_err());
+                next_key_set
+                    .verify_epoch_share(share.into(), ciphertext, index, &proof)
+                    .unwrap()
+            })
+            .collect();
+
+        let combined = next_key_set
+            .combine_epoch_shares(shares.iter().copied())
+            .unwrap();
+        let expected = params
+            .combine_shares(shares.iter().map(|share| (share.index(), share.share())))
+            .unwrap();
+        assert_eq!(combined.as_element(), expected.as_element());
+
+        let err = key_set.combine_epoch_shares(shares.clone()).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::EpochMismatch { expected, actual }
+                    if expected == Epoch::INITIAL && actual == Epoch::new(1)
+            ),
+            "{err:?}"
+        );
+        let err = next_key_set
+            .combine_epoch_shares(shares.into_iter().take(1))
+            .unwrap_err();
+        assert!(matches!(err, Error::InsufficientShares), "{err:?}");
+    }
+
+    #[test]
+    fn generation_is_kept_up_to_date() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let restored =
+            PublicKeySet::from_participants(params, key_set.participant_keys().to_vec()).unwrap();
+        assert_eq!(restored.generation(), key_set.generation());
+
+        let next_key_set = key_set.clone().with_epoch(Epoch::new(1));
+        assert_eq!(next_key_set.generation().epoch(), Epoch::new(1));
+        assert_ne!(next_key_set.generation(), key_set.generation());
+        let initial_key_set = next_key_set.clone().with_epoch(Epoch::INITIAL);
+        assert_eq!(initial_key_set.generation(), key_set.generation());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&next_key_set).unwrap();
+            assert!(json.get("generation").is_none(), "{json:#}");
+            let restored: PublicKeySet<Ristretto> = serde_json::from_value(json).unwrap();
+            assert_eq!(restored.generation(), next_key_set.generation());
+        }
+    }
+
+    #[test]
+    fn shares_for_impostor_key_set_are_rejected() {
+        let mut rng = thread_rng();
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))
+                .unwrap();
+
+        // The impostor key set shares the participant key with the genuine one.
+        let other_key = Keypair::<Ristretto>::generate(&mut rng).into_tuple().0;
+        let impostor_keys = vec![key_set.participant_keys()[0].clone(), other_key];
+        let impostor_key_set = PublicKeySet::from_participants(params, impostor_keys).unwrap();
+        assert_ne!(
+            impostor_key_set.shared_key().as_bytes(),
+            key_set.shared_key().as_bytes()
+        );
+
+        let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+        let (share, proof) = participant.decrypt_share(ciphertext, &mut rng);
+        key_set
+            .verify_share(share.into(), ciphertext, 0, &proof)
+            .unwrap();
+        let err = impostor_key_set
+            .verify_share(share.into(), ciphertext, 0, &proof)
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::ChallengeMismatch));
+
+        let subset = CombinationSubset::new(params, vec![0, 1]).unwrap();
+        let (share, proof) = participant.decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+        key_set
+            .verify_subset_share(share.into(), ciphertext, &subset, 0, &proof)
+            .unwrap();
+        assert!(impostor_key_set
+            .verify_subset_share(share.into(), ciphertext, &subset, 0, &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn evaluating_commitment_polynomial() {
+        let mut rng = thread_rng();
+        let params = Params::new(5, 3);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let poly = PublicPolynomial::<Ristretto>(public_poly.clone());
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+
+        let zero = <Ristretto as ScalarOps>::Scalar::from(0_u64);
+        assert_eq!(
+            key_set.commitment_evaluation(zero),
+            key_set.shared_key().as_element()
+        );
+        for x in [1_u64, 4, 7, 100] {
+            let x = <Ristretto as ScalarOps>::Scalar::from(x);
+            assert_eq!(key_set.commitment_evaluation(x), poly.value_at(x));
+        }
+        for index in 0..8 {
+            let x = <Ristretto as ScalarOps>::Scalar::from(index as u64 + 1);
+            let key = key_set.participant_key_at(index);
+            assert_eq!(key.as_element(), poly.value_at(x));
+        }
+    }
+
+    #[test]
+    fn liveness_shares() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..3)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let challenge = b"health check #1";
+        let ciphertext = key_set.liveness_ciphertext(challenge);
+        assert_eq!(
+            ciphertext.to_bytes(),
+            key_set.liveness_ciphertext(challenge).to_bytes()
+        );
+        assert_ne!(
+            ciphertext.to_bytes(),
+            key_set.liveness_ciphertext(b"other").to_bytes()
+        );
+
+        let shares: Vec<_> = participants
+            .iter()
+            .map(|participant| {
+                let (share, proof) = participant.liveness_share(challenge, &mut rng);
+                let index = participant.index();
+                assert!(key_set
+                    .verify_liveness_share(b"other", index, share.into(), &proof)
+                    .is_err());
+                assert!(key_set
+                    .verify_liveness_share(challenge, (index + 1) % 3, share.into(), &proof)
+                    .is_err());
+                let share = key_set
+                    .verify_liveness_share(challenge, index, share.into(), &proof)
+                    .unwrap();
+                (index, share)
+            })
+            .collect();
+
+        // Liveness shares combine into the decryption of zero.
+        let combined = params.combine_shares(shares).unwrap();
+        let decrypted = combined.decrypt_to_element(ciphertext);
+        assert!(Ristretto::is_identity(&decrypted));
+    }
 }
@@ -0,0 +1,332 @@
+//! Tamper-evident log of protocol messages.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use core::iter;
+
+use crate::{
+    alloc::{String, ToString, Vec},
+    group::Group,
+    proofs::{ProofOfPossession, VerificationError},
+    proto_labels, IdentityKeypair, IdentityPublicKey,
+};
+
+/// Direction of a message recorded in a [`MessageLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MessageDirection {
+    /// Message sent by the log owner.
+    Sent,
+    /// Message received by the log owner.
+    Received,
+}
+
+impl MessageDirection {
+    fn as_byte(self) -> u8 {
+        match self {
+            Self::Sent => 0,
+            Self::Received => 1,
+        }
+    }
+}
+
+/// Entry in a [`MessageLog`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogEntry {
+    direction: MessageDirection,
+    peer: Option<usize>,
+    kind: String,
+    message_hash: [u8; 32],
+}
+
+impl LogEntry {
+    fn hash_message(message: &[u8]) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(proto_labels::LOG_MESSAGE)
+            .chain_update(message)
+            .finalize()
+            .into()
+    }
+
+    /// Returns the direction of the message.
+    pub fn direction(&self) -> MessageDirection {
+        self.direction
+    }
+
+    /// Returns the 0-based index of the participant the message was sent to or received from,
+    /// or `None` for broadcast messages sent by the log owner.
+    pub fn peer(&self) -> Option<usize> {
+        self.peer
+    }
+
+    /// Returns the application-defined kind of the message (e.g., `"dkg_polynomial"`).
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns the SHA-256 hash of the message.
+    pub fn message_hash(&self) -> &[u8; 32] {
+        &self.message_hash
+    }
+
+    /// Checks whether this entry corresponds to the specified `message`.
+    pub fn matches(&self, message: &[u8]) -> bool {
+        self.message_hash == Self::hash_message(message)
+    }
+
+    fn chain(&self, head: &[u8; 32], seq: u64) -> [u8; 32] {
+        let peer = self.peer.map_or(u64::MAX, |peer| peer as u64);
+        Sha256::new()
+            .chain_update(proto_labels::LOG_ENTRY)
+            .chain_update(head)
+            .chain_update(seq.to_le_bytes())
+            .chain_update([self.direction.as_byte()])
+            .chain_update(peer.to_le_bytes())
+            .chain_update((self.kind.len() as u64).to_le_bytes())
+            .chain_update(self.kind.as_bytes())
+            .chain_update(self.message_hash)
+            .finalize()
+            .into()
+    }
+}
+
+/// Hash-chained log of protocol messages (e.g., DKG messages or decryption shares)
+/// processed by a participant.
+///
+/// Each recorded message updates the log [head](Self::head()), which thus commits
+/// to all messages recorded so far and their order. A participant can
+/// [sign](Self::sign_digest()) the head with their identity key; in case of a dispute
+/// about who sent what during a ceremony, signed digests from all participants
+/// can be compared, and the logs (together with the messages) can be audited.
+///
+/// Logs only store message hashes, not messages themselves; message storage
+/// is the responsibility of the caller.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::{MessageDirection, MessageLog}, IdentityKeypair};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let identity = IdentityKeypair::<Ristretto>::generate(&mut rng);
+/// let mut log = MessageLog::new(b"ceremony #1");
+/// log.record(MessageDirection::Sent, None, "dkg_commitment", b"commitment bytes");
+/// log.record(MessageDirection::Received, Some(1), "dkg_commitment", b"other bytes");
+///
+/// let digest = log.sign_digest(&identity, &mut rng);
+/// digest.verify(&identity.public())?;
+/// assert_eq!(digest.head(), log.head());
+/// assert!(log.verify_chain(b"ceremony #1"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MessageLog {
+    entries: Vec<LogEntry>,
+    head: [u8; 32],
+}
+
+impl MessageLog {
+    /// Creates an empty log for the specified `context` (e.g., a ceremony identifier).
+    pub fn new(context: &[u8]) -> Self {
+        Self {
+            entries: Vec::new(),
+            head: Self::initial_head(context),
+        }
+    }
+
+    fn initial_head(context: &[u8]) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(proto_labels::LOG_HEAD)
+            .chain_update((context.len() as u64).to_le_bytes())
+            .chain_update(context)
+            .finalize()
+            .into()
+    }
+
+    /// Records a message in this log.
+    pub fn record(
+        &mut self,
+        direction: MessageDirection,
+        peer: Option<usize>,
+        kind: &str,
+        message: &[u8],
+    ) {
+        let entry = LogEntry {
+            direction,
+            peer,
+            kind: kind.to_string(),
+            message_hash: LogEntry::hash_message(message),
+        };
+        self.head = entry.chain(&self.head, self.entries.len() as u64);
+        self.entries.push(entry);
+    }
+
+    /// Returns recorded entries in the order of recording.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of recorded entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether this log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the current head of the hash chain.
+    pub fn head(&self) -> &[u8; 32] {
+        &self.head
+    }
+
+    /// Recomputes the hash chain starting from the specified `context` and checks that
+    /// it matches the head. This is useful to check logs obtained from an untrusted source
+    /// (e.g., deserialized). The log does not store its context, so a log created
+    /// for another context will not verify.
+    pub fn verify_chain(&self, context: &[u8]) -> bool {
+        let initial_head = Self::initial_head(context);
+        let head = self
+            .entries
+            .iter()
+            .zip(0_u64..)
+            .fold(initial_head, |head, (entry, seq)| entry.chain(&head, seq));
+        head == self.head
+    }
+
+    /// Signs the current head of this log with the `identity` key.
+    pub fn sign_digest<G: Group, R: CryptoRng + RngCore>(
+        &self,
+        identity: &IdentityKeypair<G>,
+        rng: &mut R,
+    ) -> SignedLogDigest<G> {
+        let len = self.entries.len() as u64;
+        let mut transcript = SignedLogDigest::<G>::transcript(len, &self.head);
+        let identity = identity.as_untyped();
+        let signature = ProofOfPossession::from_keys(
+            iter::once(identity.secret()),
+            iter::once(identity.public()),
+            &mut transcript,
+            rng,
+        );
+        SignedLogDigest {
+            len,
+            head: self.head,
+            signature,
+        }
+    }
+}
+
+/// [`MessageLog`] head signed with an identity key.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SignedLogDigest<G: Group> {
+    len: u64,
+    head: [u8; 32],
+    signature: ProofOfPossession<G>,
+}
+
+impl<G: Group> SignedLogDigest<G> {
+    fn transcript(len: u64, head: &[u8; 32]) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::MESSAGE_LOG);
+        transcript.append_u64(b"len", len);
+        transcript.append_message(b"head", head);
+        transcript
+    }
+
+    /// Returns the number of log entries at the time of signing.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Checks whether the log was empty at the time of signing.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the signed log head.
+    pub fn head(&self) -> &[u8; 32] {
+        &self.head
+    }
+
+    /// Verifies the signature of this digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature does not verify.
+    pub fn verify(&self, identity_key: &IdentityPublicKey<G>) -> Result<(), VerificationError> {
+        let mut transcript = Self::transcript(self.len, &self.head);
+        self.signature
+            .verify(iter::once(identity_key.as_untyped()), &mut transcript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::group::Ristretto;
+
+    #[test]
+    fn log_chain_detects_tampering() {
+        let mut log = MessageLog::new(b"test");
+        log.record(MessageDirection::Sent, None, "commitment", b"a");
+        log.record(MessageDirection::Received, Some(2), "share", b"b");
+        assert!(log.verify_chain(b"test"));
+        assert!(log.entries()[1].matches(b"b"));
+
+        let mut tampered = log.clone();
+        tampered.entries[1].peer = Some(1);
+        assert!(!tampered.verify_chain(b"test"));
+        let mut tampered = log.clone();
+        tampered.entries.swap(0, 1);
+        assert!(!tampered.verify_chain(b"test"));
+
+        let mut other_log = MessageLog::new(b"test");
+        other_log.record(MessageDirection::Sent, None, "commitment", b"a");
+        assert_ne!(other_log.head(), log.head());
+        other_log.record(MessageDirection::Received, Some(2), "share", b"b");
+        assert_eq!(other_log.head(), log.head());
+    }
+
+    #[test]
+    fn log_chain_is_bound_to_context() {
+        let mut log = MessageLog::new(b"test");
+        log.record(MessageDirection::Sent, None, "commitment", b"a");
+        assert!(log.verify_chain(b"test"));
+        assert!(!log.verify_chain(b"other"));
+
+        // A log re-rooted in another context must not verify for the original one.
+        let mut forged = MessageLog::new(b"other");
+        forged.record(MessageDirection::Sent, None, "commitment", b"a");
+        assert!(forged.verify_chain(b"other"));
+        assert!(!forged.verify_chain(b"test"));
+    }
+
+    #[test]
+    fn signed_digest_is_bound_to_head() {
+        let mut rng = thread_rng();
+        let identity = IdentityKeypair::<Ristretto>::generate(&mut rng);
+        let mut log = MessageLog::new(b"test");
+        log.record(MessageDirection::Sent, None, "commitment", b"a");
+        let mut digest = log.sign_digest(&identity, &mut rng);
+        digest.verify(&identity.public()).unwrap();
+
+        let other_identity = IdentityKeypair::<Ristretto>::generate(&mut rng);
+        assert!(digest.verify(&other_identity.public()).is_err());
+        digest.head[0] ^= 1;
+        assert!(digest.verify(&identity.public()).is_err());
+    }
+}
@@ -46,6 +46,40 @@
 //! polynomial together with the proof; participants should broadcast proof of knowledge of
 //! a secret share once they receive the share from the dealer.
 //!
+//! By default, authentication of protocol messages is delegated to the transport.
+//! Alternatively, long-term identity keys of participants can be registered in the
+//! [`PublicKeySet`]; participants then sign messages they emit (e.g., decryption shares
+//! via [`ActiveParticipant::decrypt_share_signed()`]), and receivers check signatures.
+//! Identity keys have a dedicated type ([`IdentityKeypair`](crate::IdentityKeypair)),
+//! so that they cannot be confused with key shares or other encryption keys.
+//!
+//! Participant proofs (e.g., proofs of decryption share correctness) are bound
+//! to the shared key of the [`PublicKeySet`] they were produced with, so that a share produced
+//! under another key set fails verification instead of yielding an incorrect decryption.
+//! Each [`PublicKeySet`] also has an [`Epoch`], which is bound into all participant proofs
+//! (other than for the initial epoch).
+//! If a long-running committee refreshes or reshares its keys, the new key set should
+//! be assigned the next epoch, so that decryption shares from different epochs are not mixed
+//! (see [`PublicKeySet::combine_epoch_shares()`]).
+//!
+//! Secret shares of participants can be backed up on paper via [`ShareBackup`]s,
+//! which encode a share as mnemonic words or as QR-friendly chunks with a checksum.
+//! To protect a share in use, a participant may [split](ActiveParticipant::split_share()) it
+//! between two devices; decryption then runs as a two-party computation between the devices
+//! (see [`SplitSecretShareBackend`]).
+//! Alternatively, a share may be kept by a remote service (e.g., sealed in a cloud KMS
+//! envelope) that only sees blinded ciphertexts; see [`RemoteShare`]. Shares from local
+//! and remote [`ShareBackend`]s are collected with retries and timeouts
+//! by a [`ShareAggregator`].
+//!
+//! A participant may also lock their decryption share under a condition point,
+//! so that the share is revealed only once the corresponding secret scalar is published
+//! (see [`ConditionalShare`]).
+//!
+//! Participants may record messages they send and receive (including DKG messages)
+//! in a hash-chained [`MessageLog`], and sign its head with their identity keys
+//! to simplify resolving disputes after a contested ceremony.
+//!
 //! # Distributed key generation
 //!
 //! Distributed key generation (DKG) differs from the approach implemented in this module
@@ -122,14 +156,50 @@ use crate::serde::{ElementHelper, VecHelper};
 
 use core::{cmp::Ordering, fmt, ops};
 
-use crate::{alloc::Vec, group::Group, proofs::VerificationError, VerifiableDecryption};
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    proofs::VerificationError,
+    Ciphertext, DiscreteLogSolver, VerifiableDecryption,
+};
 
+#[cfg(feature = "authorization")]
+mod authorization;
+mod backup;
+mod conditional;
+mod config;
+mod epoch;
+mod fingerprint;
 mod key_set;
+mod log;
 mod participant;
+mod reencryption;
+mod remote;
+mod split;
+mod subset;
 
+#[cfg(feature = "authorization")]
+#[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+pub use self::authorization::{AuthorizationError, DecryptionPolicy, DecryptionRequest};
 pub use self::{
+    backup::{BackupError, ShareBackup},
+    conditional::ConditionalShare,
+    config::{Config, ConfigError, ParticipantConfig},
+    epoch::{Epoch, EpochShare, Generation},
+    fingerprint::{FingerprintError, KeySetFingerprint},
     key_set::PublicKeySet,
-    participant::{ActiveParticipant, Dealer},
+    log::{LogEntry, MessageDirection, MessageLog, SignedLogDigest},
+    participant::{ActiveParticipant, Dealer, SignedDecryptionShare},
+    reencryption::{ReEncryptionProof, ReEncryptionShare},
+    remote::{
+        AggregatedDecryption, AggregationError, BlindedShareRequest, BoxFuture, RemoteShare,
+        RemoteShareError, RemoteShareHolder, RemoteShareTransport, ShareAggregator, ShareBackend,
+    },
+    split::{
+        SplitDecryption, SplitSecretShareBackend, SubShare, SubShareChallenge, SubShareCommitment,
+        SubShareNonce, SubShareResponse,
+    },
+    subset::{CombinationSubset, PreparedSubset},
 };
 
 /// Computes multipliers for the Lagrange polynomial interpolation based on the function value
@@ -175,6 +245,112 @@ fn lagrange_coefficients<G: Group>(indexes: &[usize]) -> (Vec<G::Scalar>, G::Sca
     (denominators, scale)
 }
 
+/// Computes Lagrange coefficients to evaluate the polynomial at the point corresponding
+/// to the 0-based participant index `target` based on its values at points corresponding
+/// to `indexes`. Unlike [`lagrange_coefficients()`], coefficients are not scaled.
+fn lagrange_coefficients_at<G: Group>(indexes: &[usize], target: usize) -> Vec<G::Scalar> {
+    let point = |index: usize| G::Scalar::from(index as u64 + 1);
+    let mut denominators: Vec<_> = indexes
+        .iter()
+        .map(|&index| {
+            indexes
+                .iter()
+                .filter(|&&other_index| other_index != index)
+                .fold(G::Scalar::from(1), |acc, &other_index| {
+                    acc * (point(index) - point(other_index))
+                })
+        })
+        .collect();
+    G::invert_scalars(&mut denominators);
+
+    indexes
+        .iter()
+        .zip(denominators)
+        .map(|(&index, denominator)| {
+            indexes
+                .iter()
+                .filter(|&&other_index| other_index != index)
+                .fold(denominator, |acc, &other_index| {
+                    acc * (point(target) - point(other_index))
+                })
+        })
+        .collect()
+}
+
+/// Returns positions of shares disagreeing with the polynomial interpolated from the shares
+/// at the `candidate` positions, or `None` if there are more than `max_errors` such shares.
+fn find_corrupted_shares<G: Group>(
+    candidate: &[usize],
+    indexes: &[usize],
+    elements: &[G::Element],
+    max_errors: usize,
+) -> Option<Vec<usize>> {
+    let candidate_indexes: Vec<_> = candidate.iter().map(|&pos| indexes[pos]).collect();
+    let candidate_elements: Vec<_> = candidate.iter().map(|&pos| elements[pos]).collect();
+    let mut corrupted = vec![];
+    for (pos, (&index, element)) in indexes.iter().zip(elements).enumerate() {
+        if candidate.contains(&pos) {
+            continue;
+        }
+        let coefficients = lagrange_coefficients_at::<G>(&candidate_indexes, index);
+        let expected = G::vartime_multi_mul(&coefficients, candidate_elements.iter().copied());
+        if expected != *element {
+            corrupted.push(pos);
+            if corrupted.len() > max_errors {
+                return None;
+            }
+        }
+    }
+    Some(corrupted)
+}
+
+/// Advances `combination` (a strictly increasing sequence of positions less than `bound`)
+/// to the next combination in the lexicographic order. Returns `false` if `combination`
+/// is the last one.
+fn next_combination(combination: &mut [usize], bound: usize) -> bool {
+    let len = combination.len();
+    for i in (0..len).rev() {
+        if combination[i] < bound - len + i {
+            combination[i] += 1;
+            for j in i + 1..len {
+                combination[j] = combination[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Computes multipliers for combining shares from participants with the given 0-based `indexes`
+/// in the same form as [`lagrange_coefficients()`].
+///
+/// If `indexes` are `0..k` (which is always the case if `threshold == shares`), the general
+/// computation is skipped. Indeed, the Lagrange coefficient for point `i` among points `1..=k`
+/// is the integer `(-1)^(i + 1) * C(k, i)`, so it can be computed without scalar inversions.
+fn combination_coefficients<G: Group>(indexes: &[usize]) -> (Vec<G::Scalar>, G::Scalar) {
+    let is_prefix = indexes.iter().enumerate().all(|(i, &index)| i == index);
+    if !is_prefix {
+        return lagrange_coefficients::<G>(indexes);
+    }
+
+    // Compute binomial coefficients `C(count, _)` as a row of Pascal's triangle.
+    let count = indexes.len();
+    let mut binomials = vec![G::Scalar::from(0); count + 1];
+    binomials[0] = G::Scalar::from(1);
+    for row in 1..=count {
+        for k in (1..=row).rev() {
+            binomials[k] = binomials[k] + binomials[k - 1];
+        }
+    }
+    let coefficients = binomials
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, binomial)| if i % 2 == 0 { -binomial } else { binomial })
+        .collect();
+    (coefficients, G::Scalar::from(1))
+}
+
 /// Structure representing public polynomial consisting of group elements.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -193,6 +369,16 @@ impl<G: Group> PublicPolynomial<G> {
         self.0[0]
     }
 
+    /// Adds `element` to the constant coefficient of this polynomial, which shifts
+    /// all its values by `element`.
+    pub(crate) fn shift(&mut self, element: G::Element) {
+        self.0[0] = self.0[0] + element;
+    }
+
+    pub(crate) fn coefficients(&self) -> &[G::Element] {
+        &self.0
+    }
+
     /// Computes value of this public polynomial at the specified point in variable time.
     pub(crate) fn value_at(&self, x: G::Scalar) -> G::Element {
         let mut val = G::Scalar::from(1_u64);
@@ -206,6 +392,15 @@ impl<G: Group> PublicPolynomial<G> {
 
         G::vartime_multi_mul(&scalars, self.0.iter().copied())
     }
+
+    /// Computes values of this public polynomial at participant indexes `1..=count`.
+    /// Each value is computed with a single multi-scalar multiplication, which is faster than
+    /// the alternatives (see the `poly/*` benchmarks in `benches/sharing.rs`).
+    pub(crate) fn values_at_indexes(&self, count: usize) -> Vec<G::Element> {
+        (1..=count as u64)
+            .map(|idx| self.value_at(idx.into()))
+            .collect()
+    }
 }
 
 impl<G: Group> ops::AddAssign<&Self> for PublicPolynomial<G> {
@@ -236,8 +431,68 @@ pub enum Error {
     /// Number of participants specified in [`Params`] does not match the number
     /// of provided public keys.
     ParticipantCountMismatch,
+    /// Serialized [`ActiveParticipant`] has a decryption policy attached, but the crate
+    /// is built without the `authorization` feature, so the policy cannot be enforced.
+    UnsupportedPolicy,
+    /// [`Params`] are invalid: the number of participants is zero or exceeds
+    /// [`Params::MAX_SHARES`], or the threshold is not in `1..=shares`.
+    InvalidParams,
     /// Participants' public keys do not correspond to a single shared key.
     MalformedParticipantKeys,
+    /// Identity keypair supplied to a participant does not match the identity key
+    /// registered in the [`PublicKeySet`].
+    IdentityKeyMismatch,
+    /// Participant index is out of bounds or repeated.
+    InvalidParticipantIndex(usize),
+    /// Number of participants in a [`CombinationSubset`] differs from the threshold.
+    SubsetSizeMismatch,
+    /// Decryption share was verified against a key set in another [`Epoch`].
+    EpochMismatch {
+        /// Epoch of the key set combining the shares.
+        expected: Epoch,
+        /// Epoch of the share.
+        actual: Epoch,
+    },
+    /// Number of provided decryption shares is less than the threshold (or, for
+    /// [`Params::combine_crosscheck()`], does not exceed it).
+    InsufficientShares,
+    /// More decryption shares are corrupted than [`Params::combine_robust()`] can correct.
+    UncorrectableShares,
+    /// Decryptions combined from two subsets of shares in [`Params::combine_crosscheck()`]
+    /// differ, i.e., some of the shares are inconsistent.
+    InconsistentShares {
+        /// 0-based participant indexes in the first subset, in the increasing order.
+        first: Vec<usize>,
+        /// 0-based participant indexes in the second subset, in the increasing order.
+        second: Vec<usize>,
+    },
+    /// Combined decryption does not correspond to a value that can be found by
+    /// the [`DiscreteLogSolver`].
+    UnknownPlaintext,
+    /// Number of participants specified in [`Params`] is not supported by the group;
+    /// see [`Params::max_shares()`].
+    TooManyShares {
+        /// Maximum supported number of participants.
+        max: usize,
+    },
+    /// [`PreparedSubset`] is used with a key set of another [`Generation`] than the one
+    /// it was prepared for.
+    StaleKeySet {
+        /// Generation the data was precomputed for.
+        expected: Generation,
+        /// Generation of the key set the data is used with.
+        actual: Generation,
+    },
+    /// Decryption share provided by a participant does not verify.
+    InvalidShare {
+        /// 0-based index of the participant.
+        index: usize,
+        /// Underlying verification error.
+        err: VerificationError,
+    },
+    /// Contributions of the specified participants to the shared key are invalid. Contains
+    /// 0-based participant indexes in the increasing order.
+    InvalidContributions(Vec<usize>),
 }
 
 impl fmt::Display for Error {
@@ -259,8 +514,59 @@ impl fmt::Display for Error {
                 "number of participants specified in `Params` does not match the number \
                  of provided public keys",
             ),
+            Self::UnsupportedPolicy => formatter.write_str(
+                "participant has a decryption policy, which requires the `authorization` \
+                 crate feature",
+            ),
+            Self::InvalidParams => formatter.write_str(
+                "invalid threshold scheme parameters: expected `0 < threshold <= shares` \
+                 and at most `Params::MAX_SHARES` shares",
+            ),
             Self::MalformedParticipantKeys => formatter
                 .write_str("participants' public keys do not correspond to a single shared key"),
+            Self::IdentityKeyMismatch => formatter.write_str(
+                "identity keypair does not match the identity key registered in the key set",
+            ),
+            Self::InvalidParticipantIndex(index) => {
+                write!(
+                    formatter,
+                    "participant index {index} is out of bounds or repeated"
+                )
+            }
+            Self::SubsetSizeMismatch => formatter
+                .write_str("number of participants in the subset differs from the threshold"),
+            Self::EpochMismatch { expected, actual } => write!(
+                formatter,
+                "decryption share is from {actual}, while the key set is in {expected}"
+            ),
+            Self::InsufficientShares => {
+                formatter.write_str("number of decryption shares is less than the threshold")
+            }
+            Self::UncorrectableShares => {
+                formatter.write_str("too many decryption shares are corrupted to correct them")
+            }
+            Self::InconsistentShares { first, second } => write!(
+                formatter,
+                "decryptions combined from shares of participants {first:?} and {second:?} differ"
+            ),
+            Self::UnknownPlaintext => formatter
+                .write_str("combined decryption does not correspond to a known plaintext value"),
+            Self::TooManyShares { max } => write!(
+                formatter,
+                "number of participants exceeds the maximum {max} supported by the group"
+            ),
+            Self::StaleKeySet { expected, actual } => write!(
+                formatter,
+                "data was precomputed for key set {expected}, but is used with key set {actual}"
+            ),
+            Self::InvalidShare { index, err } => write!(
+                formatter,
+                "decryption share from participant #{index} is invalid: {err}"
+            ),
+            Self::InvalidContributions(indexes) => write!(
+                formatter,
+                "contributions of participants {indexes:?} to the shared key are invalid"
+            ),
         }
     }
 }
@@ -269,15 +575,18 @@ impl fmt::Display for Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::InvalidDealerProof(err) => Some(err),
+            Self::InvalidDealerProof(err) | Self::InvalidShare { err, .. } => Some(err),
             _ => None,
         }
     }
 }
 
 /// Parameters of a threshold ElGamal encryption scheme.
+///
+/// Deserialized parameters are checked in the same way as in [`Self::new()`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ParamsRepr"))]
 pub struct Params {
     /// Total number of shares / participants.
     pub shares: usize,
@@ -285,56 +594,409 @@ pub struct Params {
     pub threshold: usize,
 }
 
+/// Serialized form of [`Params`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ParamsRepr {
+    shares: usize,
+    threshold: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ParamsRepr> for Params {
+    type Error = Error;
+
+    fn try_from(repr: ParamsRepr) -> Result<Self, Self::Error> {
+        let ParamsRepr { shares, threshold } = repr;
+        if shares == 0 || shares > Self::MAX_SHARES || threshold == 0 || threshold > shares {
+            Err(Error::InvalidParams)
+        } else {
+            Ok(Self { shares, threshold })
+        }
+    }
+}
+
 impl Params {
+    /// Maximum number of participants supported by the protocol regardless of the group.
+    /// Participant indexes are serialized as `u32` (e.g., in share backups), and the cost
+    /// of many operations (computing Lagrange coefficients, distributed key generation)
+    /// is quadratic in the number of participants, so the limit is set to `2^16 - 1`.
+    pub const MAX_SHARES: usize = 65_535;
+
     /// Creates new parameters.
     ///
+    /// Since parameters are not tied to a [`Group`], group-specific limits are checked
+    /// separately with [`Self::check_group()`] (which is called when creating a [`Dealer`]
+    /// or a [`PublicKeySet`]). Parameters that are valid, but provide weak guarantees
+    /// (e.g., `threshold == 1`) are flagged in the [`SecuritySummary`].
+    ///
     /// # Panics
     ///
-    /// Panics if `shares` is equal to zero or if `threshold` is not in `1..=shares`.
+    /// Panics if `shares` is equal to zero or exceeds [`Self::MAX_SHARES`], or if `threshold`
+    /// is not in `1..=shares`.
     pub const fn new(shares: usize, threshold: usize) -> Self {
         assert!(shares > 0);
+        assert!(shares <= Self::MAX_SHARES);
         assert!(threshold > 0 && threshold <= shares);
         Self { shares, threshold }
     }
 
-    /// Combines shares decrypting the specified `ciphertext`. The shares must be provided
-    /// together with the 0-based indexes of the participants they are coming from.
+    /// Checks whether all participants are necessary to restore the secret, i.e.,
+    /// `threshold == shares`.
     ///
-    /// Returns the combined decryption, or `None` if the number of shares is insufficient.
+    /// In this case, the scheme has no fault tolerance: if any participant is unavailable
+    /// or refuses to cooperate, ciphertexts cannot be decrypted. On the other hand,
+    /// shares are always combined from the same set of participants, which has
+    /// the following consequences:
     ///
-    /// # Panics
+    /// - Combining shares (e.g., in [`Self::combine_shares()`]) does not need to compute
+    ///   Lagrange coefficients, which are small integers for this set.
+    /// - The Lagrange-free flow with a [`CombinationSubset`] can be used without announcing
+    ///   the subset in advance; see [`CombinationSubset::unanimous()`].
+    pub const fn is_unanimous(self) -> bool {
+        self.threshold == self.shares
+    }
+
+    /// Returns the maximum number of participants supported by the group `G`. Participant
+    /// with 0-based index `i` is associated with the scalar `i + 1`; these scalars must
+    /// be distinct and non-zero, which is guaranteed if `i + 1` is less than
+    /// `2^G::max_integer_bits()`. The returned value is additionally capped
+    /// by [`Self::MAX_SHARES`], so it is only smaller than this limit for toy groups.
+    pub fn max_shares<G: Group>() -> usize {
+        1_usize
+            .checked_shl(G::max_integer_bits())
+            .map_or(Self::MAX_SHARES, |bound| (bound - 1).min(Self::MAX_SHARES))
+    }
+
+    /// Checks that these parameters are supported by the group `G`.
     ///
-    /// Panics if any index in `shares` exceeds the maximum participant's index as per `params`.
+    /// # Errors
+    ///
+    /// Returns an error if the number of participants exceeds [`Self::max_shares()`].
+    pub fn check_group<G: Group>(self) -> Result<(), Error> {
+        let max = Self::max_shares::<G>();
+        if self.shares > max {
+            Err(Error::TooManyShares { max })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a machine-readable summary of security-related parameters of a threshold scheme
+    /// with these parameters over the group `G`.
+    pub fn security_summary<G: Group>(self) -> SecuritySummary {
+        SecuritySummary {
+            security_bits: G::SECURITY_BITS,
+            scalar_size: G::SCALAR_SIZE,
+            element_size: G::ELEMENT_SIZE,
+            shares: self.shares,
+            threshold: self.threshold,
+            single_party_decryption: self.threshold == 1,
+            fault_tolerance: self.shares - self.threshold,
+        }
+    }
+
+    /// Combines shares decrypting the specified `ciphertext`. The shares must be provided
+    /// together with the 0-based indexes of the participants they are coming from.
+    ///
+    /// Returns the combined decryption, or `None` if the number of shares is insufficient
+    /// or any index in `shares` is out of bounds. Shares are selected and combined
+    /// as described in [`Self::select_shares()`], so the result does not depend on the order
+    /// of shares from distinct participants.
     pub fn combine_shares<G: Group>(
         self,
         shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
     ) -> Option<VerifiableDecryption<G>> {
-        let (indexes, shares): (Vec<_>, Vec<_>) = shares
+        let shares = shares
             .into_iter()
-            .take(self.threshold)
-            .map(|(index, share)| (index, *share.as_element()))
-            .unzip();
-        if shares.len() < self.threshold {
-            return None;
-        }
-        assert!(
-            indexes.iter().all(|&index| index < self.shares),
-            "Invalid share indexes {:?}; expected values in 0..{}",
-            indexes.iter().copied(),
-            self.shares
-        );
+            .map(|(index, share)| (index, *share.as_element()));
+        let (indexes, shares) = self.select_shares(shares)?;
 
-        let (denominators, scale) = lagrange_coefficients::<G>(&indexes);
+        let (denominators, scale) = combination_coefficients::<G>(&indexes);
         let restored_value = G::vartime_multi_mul(&denominators, shares);
         let dh_element = restored_value * &scale;
         Some(VerifiableDecryption::from_element(dh_element))
     }
+
+    /// Combines shares similarly to [`Self::combine_shares()`], cross-checking the result
+    /// on two subsets of shares. This provides cheap robustness when shares are not accompanied
+    /// by proofs (e.g., if they are received from a trusted, but possibly faulty channel).
+    ///
+    /// Shares are deduplicated by participant index and sorted by it. The first subset
+    /// consists of the [`threshold`](Self::threshold) shares with the smallest indexes,
+    /// and the second one of the shares with the largest indexes. Thus, the subsets are
+    /// disjoint if at least `2 * threshold` shares are provided, and differ as much as possible
+    /// otherwise. The decryption combined from the first subset is returned if it coincides
+    /// with the decryption combined from the second subset.
+    ///
+    /// A single faulty share is always detected unless it is contained in both subsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InsufficientShares`] if less than `threshold + 1` distinct shares
+    /// are provided, and [`Error::InconsistentShares`] if the two decryptions differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `shares` exceeds the maximum participant's index as per `params`.
+    pub fn combine_crosscheck<G: Group>(
+        self,
+        shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
+    ) -> Result<VerifiableDecryption<G>, Error> {
+        let mut distinct_shares: Vec<(usize, VerifiableDecryption<G>)> = vec![];
+        for (index, share) in shares {
+            assert!(
+                index < self.shares,
+                "Invalid share index {index}; expected values in 0..{}",
+                self.shares
+            );
+            if distinct_shares.iter().all(|&(other, _)| other != index) {
+                distinct_shares.push((index, share));
+            }
+        }
+        if distinct_shares.len() <= self.threshold {
+            return Err(Error::InsufficientShares);
+        }
+        distinct_shares.sort_unstable_by_key(|&(index, _)| index);
+
+        let first = &distinct_shares[..self.threshold];
+        let second = &distinct_shares[distinct_shares.len() - self.threshold..];
+        // `unwrap()`s are safe: both subsets have `threshold` distinct shares.
+        let first_decryption = self.combine_shares(first.iter().copied()).unwrap();
+        let second_decryption = self.combine_shares(second.iter().copied()).unwrap();
+        if first_decryption.as_element() == second_decryption.as_element() {
+            Ok(first_decryption)
+        } else {
+            Err(Error::InconsistentShares {
+                first: first.iter().map(|&(index, _)| index).collect(),
+                second: second.iter().map(|&(index, _)| index).collect(),
+            })
+        }
+    }
+
+    /// Combines shares similarly to [`Self::combine_shares()`], correcting up to
+    /// `(n - threshold) / 2` silently corrupted shares, where `n` is the number of distinct
+    /// provided shares. Returns the combined decryption together with 0-based indexes
+    /// of participants whose shares are corrupted (in the increasing order).
+    ///
+    /// # Construction
+    ///
+    /// Decryption shares are values of a polynomial of degree `threshold - 1` "in the exponent",
+    /// so they form a Reed–Solomon codeword, and corrupted shares are errors in it. Since
+    /// polynomial coefficients are hidden in the exponent, the linear system
+    /// of the Berlekamp–Welch decoder cannot be solved directly. Instead, the decoder searches
+    /// for a subset of `threshold` shares such that the polynomial interpolated from it
+    /// agrees with at least `n - (n - threshold) / 2` shares. Such a polynomial is unique,
+    /// so the decoding radius is the same as for Berlekamp–Welch.
+    ///
+    /// Candidate subsets are taken from the first `threshold + (n - threshold) / 2` shares,
+    /// among which at least `threshold` are correct. If there are no corrupted shares,
+    /// the first candidate is accepted; in general, the search may need to check
+    /// up to `C(threshold + e, e)` candidates, where `e` is the maximum number of errors.
+    /// Thus, this method is intended for committees of moderate size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InsufficientShares`] if the number of distinct shares is less than
+    /// the threshold, and [`Error::UncorrectableShares`] if too many shares are corrupted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index in `shares` exceeds the maximum participant's index as per `params`.
+    pub fn combine_robust<G: Group>(
+        self,
+        shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
+    ) -> Result<(VerifiableDecryption<G>, Vec<usize>), Error> {
+        let mut indexes = Vec::<usize>::new();
+        let mut elements = vec![];
+        for (index, share) in shares {
+            assert!(
+                index < self.shares,
+                "Invalid share index {index}; expected values in 0..{}",
+                self.shares
+            );
+            if !indexes.contains(&index) {
+                indexes.push(index);
+                elements.push(*share.as_element());
+            }
+        }
+        let count = indexes.len();
+        if count < self.threshold {
+            return Err(Error::InsufficientShares);
+        }
+        let max_errors = (count - self.threshold) / 2;
+
+        // Positions (rather than participant indexes) of shares in the candidate subset.
+        let mut candidate: Vec<_> = (0..self.threshold).collect();
+        loop {
+            let corrupted = find_corrupted_shares::<G>(&candidate, &indexes, &elements, max_errors);
+            if let Some(corrupted) = corrupted {
+                let selected = candidate.iter().map(|&pos| {
+                    let share = VerifiableDecryption::from_element(elements[pos]);
+                    (indexes[pos], share)
+                });
+                // `unwrap()` is safe: the candidate has `threshold` distinct shares.
+                let decryption = self.combine_shares(selected).unwrap();
+                let mut corrupted: Vec<_> = corrupted.into_iter().map(|pos| indexes[pos]).collect();
+                corrupted.sort_unstable();
+                return Ok((decryption, corrupted));
+            }
+            if !next_combination(&mut candidate, self.threshold + max_errors) {
+                return Err(Error::UncorrectableShares);
+            }
+        }
+    }
+
+    /// Combines shares decrypting the specified `ciphertext` similarly to [`Self::combine_shares()`],
+    /// and then decodes the decrypted value using the provided discrete log `solver`
+    /// (e.g., a [`DiscreteLogTable`](crate::DiscreteLogTable)).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any index in `shares` is out of bounds, if the number of shares
+    /// is insufficient, or if the decrypted value cannot be found by the `solver`.
+    pub fn combine_and_decode<G: Group, D: DiscreteLogSolver<G> + ?Sized>(
+        self,
+        ciphertext: Ciphertext<G>,
+        shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
+        solver: &D,
+    ) -> Result<u64, Error> {
+        let shares: Vec<_> = shares.into_iter().collect();
+        if let Some(&(index, _)) = shares.iter().find(|&&(index, _)| index >= self.shares) {
+            return Err(Error::InvalidParticipantIndex(index));
+        }
+        let decryption = self
+            .combine_shares(shares)
+            .ok_or(Error::InsufficientShares)?;
+        decryption
+            .decrypt(ciphertext, solver)
+            .ok_or(Error::UnknownPlaintext)
+    }
+
+    /// Combines shares re-encrypting the specified `ciphertext` to a target key. The shares must
+    /// be provided together with the 0-based indexes of the participants they are coming from.
+    ///
+    /// Returns the ciphertext encrypted for the target key, or `None` if the number of shares
+    /// is insufficient or any index in `shares` is out of bounds. See [`ReEncryptionShare`]
+    /// for more details. Shares are selected in the same way as for [`Self::combine_shares()`].
+    pub fn combine_reencryption_shares<G: Group>(
+        self,
+        ciphertext: Ciphertext<G>,
+        shares: impl IntoIterator<Item = (usize, ReEncryptionShare<G>)>,
+    ) -> Option<Ciphertext<G>> {
+        let (indexes, shares) = self.select_shares(shares)?;
+
+        let (denominators, scale) = combination_coefficients::<G>(&indexes);
+        let random_elements = shares.iter().map(|share| share.random_element);
+        let random_element = G::vartime_multi_mul(&denominators, random_elements) * &scale;
+        let blinded_elements = shares.iter().map(|share| share.blinded_element);
+        let dh_element = G::vartime_multi_mul(&denominators, blinded_elements) * &scale;
+        Some(Ciphertext {
+            random_element,
+            blinded_element: ciphertext.blinded_element - dh_element,
+        })
+    }
+
+    /// Selects shares to combine from `shares` provided together with 0-based participant indexes.
+    ///
+    /// The [`threshold`](Self::threshold) shares with the smallest distinct indexes are selected;
+    /// if a participant provides several shares, only the first one is considered. Thus,
+    /// the selection does not depend on the order of shares from distinct participants.
+    /// The selected shares are returned sorted by the participant index. Returns `None`
+    /// if the number of shares is insufficient, or if any index in `shares` exceeds
+    /// the maximum participant's index as per `params`.
+    pub fn select_shares<T>(
+        self,
+        shares: impl IntoIterator<Item = (usize, T)>,
+    ) -> Option<(Vec<usize>, Vec<T>)> {
+        let mut selected: Vec<_> = shares.into_iter().collect();
+        if selected.iter().any(|&(index, _)| index >= self.shares) {
+            return None;
+        }
+        // Stable sorting retains the first share provided by each participant.
+        selected.sort_by_key(|&(index, _)| index);
+        selected.dedup_by_key(|&mut (index, _)| index);
+
+        if selected.len() < self.threshold {
+            return None;
+        }
+        selected.truncate(self.threshold);
+        Some(selected.into_iter().unzip())
+    }
+}
+
+/// Machine-readable summary of security-related parameters of a threshold ElGamal scheme,
+/// returned by [`Params::security_summary()`]. Can be used for audit purposes, e.g.,
+/// by serializing it together with the public key set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SecuritySummary {
+    /// Security level of the group in bits ([`Group::SECURITY_BITS`]).
+    pub security_bits: usize,
+    /// Byte size of a serialized group scalar.
+    pub scalar_size: usize,
+    /// Byte size of a serialized group element.
+    pub element_size: usize,
+    /// Total number of shares / participants.
+    pub shares: usize,
+    /// Number of participants necessary to jointly restore the secret.
+    pub threshold: usize,
+    /// Whether a single participant can decrypt ciphertexts on their own (i.e., the threshold
+    /// is 1). In this case, the scheme provides no protection against a single
+    /// compromised participant.
+    pub single_party_decryption: bool,
+    /// Number of participants that may be unavailable without affecting decryption.
+    pub fault_tolerance: usize,
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::thread_rng;
+
     use super::*;
-    use crate::{curve25519::scalar::Scalar as Scalar25519, group::Ristretto};
+    use crate::{
+        curve25519::scalar::Scalar as Scalar25519,
+        group::{ElementOps, Ristretto},
+        DiscreteLogTable,
+    };
+
+    #[test]
+    fn security_summary_and_group_limits() {
+        let summary = Params::new(5, 1).security_summary::<Ristretto>();
+        assert_eq!(summary.security_bits, 126);
+        assert_eq!(summary.element_size, 32);
+        assert!(summary.single_party_decryption);
+        assert_eq!(summary.fault_tolerance, 4);
+        assert!(
+            !Params::new(5, 3)
+                .security_summary::<Ristretto>()
+                .single_party_decryption
+        );
+
+        assert_eq!(Params::max_shares::<Ristretto>(), Params::MAX_SHARES);
+        Params::new(Params::MAX_SHARES, 2)
+            .check_group::<Ristretto>()
+            .unwrap();
+    }
+
+    #[cfg(feature = "testkit")]
+    #[test]
+    fn params_exceeding_group_limits_are_rejected() {
+        use crate::group::TestGroup;
+
+        // The order of `TestGroup` is ~2^10, so scalars up to `2^9 - 1` are safe.
+        assert_eq!(Params::max_shares::<TestGroup>(), 511);
+        Params::new(511, 2).check_group::<TestGroup>().unwrap();
+        let err = Params::new(512, 2).check_group::<TestGroup>().unwrap_err();
+        assert!(matches!(err, Error::TooManyShares { max: 511 }), "{err:?}");
+    }
+
+    #[test]
+    #[should_panic(expected = "shares <= Self::MAX_SHARES")]
+    fn params_exceeding_protocol_limit_are_rejected() {
+        Params::new(Params::MAX_SHARES + 1, 2);
+    }
 
     #[test]
     fn lagrange_coeffs_are_computed_correctly() {
@@ -373,4 +1035,245 @@ mod tests {
         );
         assert_eq!(scale, Scalar25519::from(20_u32));
     }
+
+    #[test]
+    fn combination_coeffs_for_consecutive_indexes() {
+        for count in 1..=8 {
+            let indexes: Vec<_> = (0..count).collect();
+            let (coeffs, scale) = combination_coefficients::<Ristretto>(&indexes);
+            assert_eq!(scale, Scalar25519::from(1_u32));
+            let (expected_coeffs, expected_scale) = lagrange_coefficients::<Ristretto>(&indexes);
+            let expected_coeffs = expected_coeffs.into_iter().map(|d| d * expected_scale);
+            assert!(coeffs.into_iter().eq(expected_coeffs), "count = {count}");
+        }
+
+        // Non-consecutive indexes are processed in the general way.
+        let (coeffs, scale) = combination_coefficients::<Ristretto>(&[0, 2]);
+        assert_eq!((coeffs, scale), lagrange_coefficients::<Ristretto>(&[0, 2]));
+    }
+
+    #[test]
+    fn combining_shares_with_unanimous_params() {
+        use rand::thread_rng;
+
+        use crate::DiscreteLogTable;
+
+        let mut rng = thread_rng();
+        let params = Params::new(4, 4);
+        assert!(params.is_unanimous());
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..4)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let shares = participants.iter().rev().map(|participant| {
+            let (share, _) = participant.decrypt_share(ciphertext, &mut rng);
+            (participant.index(), share)
+        });
+        let lookup_table = DiscreteLogTable::new(0..5);
+        let decrypted = params.combine_and_decode(ciphertext, shares, &lookup_table);
+        assert_eq!(decrypted.unwrap(), 3);
+
+        let subset = CombinationSubset::unanimous(params).unwrap();
+        let subset_shares = participants.iter().map(|participant| {
+            let (share, proof) =
+                participant.decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+            let share = key_set
+                .verify_subset_share(
+                    share.into(),
+                    ciphertext,
+                    &subset,
+                    participant.index(),
+                    &proof,
+                )
+                .unwrap();
+            (participant.index(), share)
+        });
+        let combined = subset.combine_shares(subset_shares).unwrap();
+        assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(3));
+    }
+
+    #[test]
+    fn selecting_shares_for_combination() {
+        let params = Params::new(5, 3);
+        let mut shares = [(4, 'e'), (1, 'b'), (4, 'e'), (0, 'a'), (2, 'c')];
+        let (indexes, values) = params.select_shares(shares).unwrap();
+        assert_eq!(indexes, [0, 1, 2]);
+        assert_eq!(values, ['a', 'b', 'c']);
+
+        shares.reverse();
+        assert_eq!(params.select_shares(shares).unwrap(), (indexes, values));
+
+        // Only the first share from each participant is considered.
+        let shares = [(2, 'x'), (1, 'b'), (2, 'c'), (0, 'a')];
+        let (_, values) = params.select_shares(shares).unwrap();
+        assert_eq!(values, ['a', 'b', 'x']);
+
+        assert!(params
+            .select_shares([(1, 'b'), (1, 'b'), (3, 'd')])
+            .is_none());
+        assert!(params
+            .select_shares([(0, 'a'), (1, 'b'), (2, 'c'), (5, 'f')])
+            .is_none());
+    }
+
+    #[test]
+    fn combining_and_decoding_shares() {
+        use rand::thread_rng;
+
+        use crate::{DiscreteLogTable, KangarooDecrypter, KangarooJumps};
+
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let ciphertext = key_set.shared_key().encrypt(42_u64, &mut rng);
+        let shares: Vec<_> = [0, 2]
+            .into_iter()
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                let participant = ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap();
+                (i, participant.decrypt_share(ciphertext, &mut rng).0)
+            })
+            .collect();
+
+        let lookup_table = DiscreteLogTable::new(0..100);
+        let value = params
+            .combine_and_decode(ciphertext, shares.clone(), &lookup_table)
+            .unwrap();
+        assert_eq!(value, 42);
+        let kangaroo = KangarooDecrypter::new(0..1_000, KangarooJumps::for_width(1_000));
+        let value = params
+            .combine_and_decode(ciphertext, shares.clone(), &kangaroo)
+            .unwrap();
+        assert_eq!(value, 42);
+
+        let small_table = DiscreteLogTable::new(0..10);
+        let err = params
+            .combine_and_decode(ciphertext, shares.clone(), &small_table)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnknownPlaintext), "{err:?}");
+        let err = params
+            .combine_and_decode(ciphertext, shares[..1].to_vec(), &lookup_table)
+            .unwrap_err();
+        assert!(matches!(err, Error::InsufficientShares), "{err:?}");
+
+        let mut bogus_shares = shares.clone();
+        bogus_shares[0].0 = params.shares;
+        assert!(params.combine_shares(bogus_shares.clone()).is_none());
+        let err = params
+            .combine_and_decode(ciphertext, bogus_shares, &lookup_table)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidParticipantIndex(i) if i == params.shares),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn combining_shares_with_crosscheck() {
+        let mut rng = thread_rng();
+        let params = Params::new(5, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+        let mut shares: Vec<_> = (0..5)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                let participant = ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap();
+                (i, participant.decrypt_share(ciphertext, &mut rng).0)
+            })
+            .collect();
+
+        let lookup_table = DiscreteLogTable::new(0..10);
+        for count in 3..=5 {
+            let decryption = params
+                .combine_crosscheck(shares[..count].iter().copied())
+                .unwrap();
+            assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(5));
+        }
+        let err = params
+            .combine_crosscheck(shares[..2].iter().copied().chain([shares[0]]))
+            .unwrap_err();
+        assert!(matches!(err, Error::InsufficientShares), "{err:?}");
+
+        shares[3].1 = VerifiableDecryption::from_element(Ristretto::generator());
+        let err = params.combine_crosscheck(shares).unwrap_err();
+        match err {
+            Error::InconsistentShares { first, second } => {
+                assert_eq!(first, [0, 1]);
+                assert_eq!(second, [3, 4]);
+            }
+            _ => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn lagrange_coeffs_at_point() {
+        // f(x) = 3 + 2x; values at 1, 3 are 5, 9; value at 2 is 7.
+        let coeffs = lagrange_coefficients_at::<Ristretto>(&[0, 2], 1);
+        let value = coeffs[0] * Scalar25519::from(5_u32) + coeffs[1] * Scalar25519::from(9_u32);
+        assert_eq!(value, Scalar25519::from(7_u32));
+
+        let mut combination = [0, 1];
+        let mut combinations = vec![combination];
+        while next_combination(&mut combination, 4) {
+            combinations.push(combination);
+        }
+        assert_eq!(
+            combinations,
+            [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]
+        );
+    }
+
+    #[test]
+    fn combining_shares_with_error_correction() {
+        let mut rng = thread_rng();
+        let params = Params::new(7, 3);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+        let shares: Vec<_> = (0..7)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                let participant = ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap();
+                (i, participant.decrypt_share(ciphertext, &mut rng).0)
+            })
+            .collect();
+        let corrupted_share = VerifiableDecryption::from_element(Ristretto::generator());
+        let lookup_table = DiscreteLogTable::new(0..10);
+
+        let (decryption, corrupted) = params.combine_robust(shares.clone()).unwrap();
+        assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(5));
+        assert!(corrupted.is_empty());
+
+        // Up to `(7 - 3) / 2 = 2` corrupted shares can be corrected.
+        for bad_indexes in [[0, 1], [1, 5], [5, 6]] {
+            let mut shares = shares.clone();
+            for &i in &bad_indexes {
+                shares[i].1 = corrupted_share;
+            }
+            let (decryption, corrupted) = params.combine_robust(shares.into_iter().rev()).unwrap();
+            assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(5));
+            assert_eq!(corrupted, bad_indexes);
+        }
+
+        let mut shares = shares;
+        for share in &mut shares[..3] {
+            share.1 = corrupted_share;
+        }
+        let err = params.combine_robust(shares.clone()).unwrap_err();
+        assert!(matches!(err, Error::UncorrectableShares), "{err:?}");
+        let err = params.combine_robust(shares[..2].to_vec()).unwrap_err();
+        assert!(matches!(err, Error::InsufficientShares), "{err:?}");
+    }
 }
@@ -6,16 +6,23 @@
 use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use core::iter;
 
+#[cfg(feature = "authorization")]
+use crate::sharing::{AuthorizationError, DecryptionPolicy, DecryptionRequest};
 use crate::{
     alloc::Vec,
     group::Group,
     proofs::{LogEqualityProof, ProofOfPossession},
-    sharing::{Error, Params, PublicKeySet},
-    Ciphertext, Keypair, PublicKey, SecretKey, VerifiableDecryption,
+    proto_labels,
+    sharing::{
+        CombinationSubset, ConditionalShare, Error, Params, PublicKeySet, ReEncryptionProof,
+        ReEncryptionShare, ShareBackup, SplitSecretShareBackend, SubShare,
+    },
+    CandidateDecryption, Ciphertext, IdentityKeypair, Keypair, PublicKey, SecretKey,
+    VerifiableDecryption,
 };
 
 /// Dealer in a [Feldman verifiable secret sharing][feldman-vss] scheme.
@@ -32,12 +39,19 @@ pub struct Dealer<G: Group> {
 
 impl<G: Group> Dealer<G> {
     /// Instantiates a dealer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `params` are [not supported](Params::check_group()) by the group.
     pub fn new<R: CryptoRng + RngCore>(params: Params, rng: &mut R) -> Self {
+        if let Err(err) = params.check_group::<G>() {
+            panic!("invalid parameters: {err}");
+        }
         let polynomial: Vec<_> = (0..params.threshold)
             .map(|_| Keypair::<G>::generate(rng))
             .collect();
 
-        let mut transcript = Transcript::new(b"elgamal_share_poly");
+        let mut transcript = Transcript::new(proto_labels::SHARE_POLYNOMIAL);
         transcript.append_u64(b"n", params.shares as u64);
         transcript.append_u64(b"t", params.threshold as u64);
 
@@ -85,13 +99,103 @@ impl<G: Group> Dealer<G> {
 /// Personalized state of a participant of a threshold ElGamal encryption scheme
 /// once the participant receives the secret share from the [`Dealer`].
 /// At this point, the participant can produce [`VerifiableDecryption`]s.
+///
+/// The serialized form of a participant always contains the `policy` field, which is `null`
+/// if no decryption policy (available with the `authorization` crate feature) is attached.
+/// On deserialization, a missing policy field is an error, as is a policy in crate builds
+/// without the `authorization` feature; thus, a participant cannot silently lose its policy.
+/// The secret share and the identity keypair are checked in the same way as
+/// in [`Self::new()`] and [`Self::with_identity()`].
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "", try_from = "ActiveParticipantRepr<G>")
+)]
 pub struct ActiveParticipant<G: Group> {
     key_set: PublicKeySet<G>,
     index: usize,
     secret_share: SecretKey<G>,
+    identity: Option<IdentityKeypair<G>>,
+    #[cfg(feature = "authorization")]
+    policy: Option<DecryptionPolicy<G>>,
+}
+
+/// Serialized form of an [`ActiveParticipant`].
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(bound = "")]
+struct ActiveParticipantRef<'a, G: Group> {
+    key_set: &'a PublicKeySet<G>,
+    index: usize,
+    secret_share: &'a SecretKey<G>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    identity: Option<&'a IdentityKeypair<G>>,
+    #[cfg(feature = "authorization")]
+    policy: Option<&'a DecryptionPolicy<G>>,
+    #[cfg(not(feature = "authorization"))]
+    policy: Option<()>,
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> Serialize for ActiveParticipant<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ActiveParticipantRef {
+            key_set: &self.key_set,
+            index: self.index,
+            secret_share: &self.secret_share,
+            identity: self.identity.as_ref(),
+            #[cfg(feature = "authorization")]
+            policy: self.policy.as_ref(),
+            #[cfg(not(feature = "authorization"))]
+            policy: None,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialized form of an [`ActiveParticipant`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct ActiveParticipantRepr<G: Group> {
+    key_set: PublicKeySet<G>,
+    index: usize,
+    secret_share: SecretKey<G>,
+    #[serde(default)]
+    identity: Option<IdentityKeypair<G>>,
+    // `deserialize_with` makes the field required (by default, a missing `Option` is `None`).
+    #[cfg(feature = "authorization")]
+    #[serde(deserialize_with = "Option::deserialize")]
+    policy: Option<DecryptionPolicy<G>>,
+    #[cfg(not(feature = "authorization"))]
+    #[serde(deserialize_with = "Option::deserialize")]
+    policy: Option<serde::de::IgnoredAny>,
+}
+
+#[cfg(feature = "serde")]
+impl<G: Group> TryFrom<ActiveParticipantRepr<G>> for ActiveParticipant<G> {
+    type Error = Error;
+
+    fn try_from(repr: ActiveParticipantRepr<G>) -> Result<Self, Self::Error> {
+        #[cfg(not(feature = "authorization"))]
+        if repr.policy.is_some() {
+            return Err(Error::UnsupportedPolicy);
+        }
+        if repr.index >= repr.key_set.params().shares {
+            return Err(Error::InvalidParticipantIndex(repr.index));
+        }
+
+        let mut participant = Self::new(repr.key_set, repr.index, repr.secret_share)?;
+        if let Some(identity) = repr.identity {
+            participant = participant.with_identity(identity)?;
+        }
+        #[cfg(feature = "authorization")]
+        {
+            participant.policy = repr.policy;
+        }
+        Ok(participant)
+    }
 }
 
 impl<G: Group> ActiveParticipant<G> {
@@ -116,12 +220,33 @@ impl<G: Group> ActiveParticipant<G> {
                 key_set,
                 index,
                 secret_share,
+                identity: None,
+                #[cfg(feature = "authorization")]
+                policy: None,
             })
         } else {
             Err(Error::InvalidSecret)
         }
     }
 
+    /// Attaches a long-term identity keypair to this participant, which will be used to sign
+    /// protocol messages. The public part of the `identity` must be registered in the
+    /// [`PublicKeySet`] via [`PublicKeySet::with_identity_keys()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identity key for this participant is not registered
+    /// or differs from `identity.public()`.
+    pub fn with_identity(mut self, identity: IdentityKeypair<G>) -> Result<Self, Error> {
+        match self.key_set.identity_key(self.index) {
+            Some(key) if key.as_bytes() == identity.public().as_bytes() => {
+                self.identity = Some(identity);
+                Ok(self)
+            }
+            _ => Err(Error::IdentityKeyMismatch),
+        }
+    }
+
     /// Returns the public key set for the threshold ElGamal encryption scheme this participant
     /// is a part of.
     pub fn key_set(&self) -> &PublicKeySet<G> {
@@ -139,6 +264,26 @@ impl<G: Group> ActiveParticipant<G> {
         &self.secret_share
     }
 
+    /// Creates a [`ShareBackup`] of this participant's secret share, which can be printed
+    /// and later [restored](ShareBackup::restore()) given the key set.
+    pub fn backup(&self) -> ShareBackup<G> {
+        ShareBackup::new(self)
+    }
+
+    /// Splits the secret share of this participant into two [`SubShare`]s, which should be
+    /// stored on different devices. The returned backend can produce decryption shares
+    /// with the help of both devices; see [`SplitSecretShareBackend`] for details.
+    ///
+    /// The participant is consumed, so that the full secret share is dropped (and zeroized).
+    /// An [identity keypair](Self::with_identity()) attached to the participant is dropped
+    /// as well.
+    pub fn split_share<R: CryptoRng + RngCore>(
+        self,
+        rng: &mut R,
+    ) -> (SplitSecretShareBackend<G>, [SubShare<G>; 2]) {
+        SplitSecretShareBackend::new(&self, rng)
+    }
+
     /// Returns share of the public key for this participant.
     pub fn public_key_share(&self) -> &PublicKey<G> {
         &self.key_set.participant_keys()[self.index]
@@ -147,7 +292,7 @@ impl<G: Group> ActiveParticipant<G> {
     /// Generates a [`ProofOfPossession`] of the participant's
     /// [`secret_share`](Self::secret_share()).
     pub fn proof_of_possession<R: CryptoRng + RngCore>(&self, rng: &mut R) -> ProofOfPossession<G> {
-        let mut transcript = Transcript::new(b"elgamal_participant_pop");
+        let mut transcript = Transcript::new(proto_labels::PARTICIPANT_POP);
         self.key_set.commit(&mut transcript);
         transcript.append_u64(b"i", self.index as u64);
         ProofOfPossession::from_keys(
@@ -158,8 +303,74 @@ impl<G: Group> ActiveParticipant<G> {
         )
     }
 
+    /// Attaches a [`DecryptionPolicy`] to this participant. Once the policy is attached,
+    /// decryption shares can only be produced for approved [`DecryptionRequest`]s
+    /// via [`Self::decrypt_authorized()`]; other methods producing shares
+    /// (e.g., [`Self::decrypt_share()`]) panic.
+    #[cfg(feature = "authorization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    #[must_use]
+    pub fn with_decryption_policy(mut self, policy: DecryptionPolicy<G>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Returns the decryption policy attached to this participant, if any.
+    #[cfg(feature = "authorization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    pub fn decryption_policy(&self) -> Option<&DecryptionPolicy<G>> {
+        self.policy.as_ref()
+    }
+
+    /// Verifies the `request` against the attached [`DecryptionPolicy`] and, if it is
+    /// authorized, creates decryption shares for all requested ciphertexts together with
+    /// proofs of their validity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request is not authorized by the policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the decryption policy was not attached to this participant
+    /// via [`Self::with_decryption_policy()`].
+    #[cfg(feature = "authorization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "authorization")))]
+    #[allow(clippy::type_complexity)] // not that complex
+    pub fn decrypt_authorized<R: CryptoRng + RngCore>(
+        &self,
+        request: &DecryptionRequest<G>,
+        rng: &mut R,
+    ) -> Result<Vec<(VerifiableDecryption<G>, LogEqualityProof<G>)>, AuthorizationError> {
+        let policy = self
+            .policy
+            .as_ref()
+            .expect("decryption policy is not attached to the participant");
+        policy.verify(&self.key_set, request)?;
+        Ok(request
+            .ciphertexts()
+            .iter()
+            .map(|ciphertext| self.decrypt_share_unchecked(*ciphertext, rng))
+            .collect())
+    }
+
+    #[inline]
+    #[cfg_attr(not(feature = "authorization"), allow(clippy::unused_self))]
+    fn assert_unrestricted(&self) {
+        #[cfg(feature = "authorization")]
+        assert!(
+            self.policy.is_none(),
+            "decryption policy is attached to the participant; use `decrypt_authorized()`"
+        );
+    }
+
     /// Creates a [`VerifiableDecryption`] for the specified `ciphertext` together with a proof
     /// of its validity. `rng` is used to generate the proof.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a decryption policy is attached to this participant (requires
+    /// the `authorization` crate feature).
     pub fn decrypt_share<R>(
         &self,
         ciphertext: Ciphertext<G>,
@@ -168,12 +379,34 @@ impl<G: Group> ActiveParticipant<G> {
     where
         R: CryptoRng + RngCore,
     {
+        self.assert_unrestricted();
+        self.decrypt_share_unchecked(ciphertext, rng)
+    }
+
+    /// Creates a share for the liveness check with the specified `challenge`, together
+    /// with a proof of its validity. The share can be verified by other participants
+    /// via [`PublicKeySet::verify_liveness_share()`]; see
+    /// [`PublicKeySet::liveness_ciphertext()`] for details.
+    ///
+    /// Unlike [`Self::decrypt_share()`], this method can be used if a decryption policy
+    /// is attached to this participant, since liveness shares do not decrypt anything.
+    pub fn liveness_share<R: CryptoRng + RngCore>(
+        &self,
+        challenge: &[u8],
+        rng: &mut R,
+    ) -> (VerifiableDecryption<G>, LogEqualityProof<G>) {
+        let ciphertext = self.key_set.liveness_ciphertext(challenge);
+        self.decrypt_share_unchecked(ciphertext, rng)
+    }
+
+    fn decrypt_share_unchecked<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> (VerifiableDecryption<G>, LogEqualityProof<G>) {
         let dh_element = ciphertext.random_element * self.secret_share.expose_scalar();
         let our_public_key = self.public_key_share().as_element();
-        let mut transcript = Transcript::new(b"elgamal_decryption_share");
-        self.key_set.commit(&mut transcript);
-        transcript.append_u64(b"i", self.index as u64);
-
+        let mut transcript = self.key_set.share_transcript(self.index);
         let proof = LogEqualityProof::new(
             &PublicKey::from_element(ciphertext.random_element),
             &self.secret_share,
@@ -183,6 +416,174 @@ impl<G: Group> ActiveParticipant<G> {
         );
         (VerifiableDecryption::from_element(dh_element), proof)
     }
+
+    /// Creates a decryption share for the specified `ciphertext` premultiplied by the Lagrange
+    /// coefficient of this participant in the `subset`, together with a proof of its validity.
+    /// Such shares are combined by summing; see [`CombinationSubset`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `subset` does not match the parameters of the key set (i.e., has a size
+    /// other than the threshold or contains out-of-bounds indexes), if this participant
+    /// is not in the `subset`, or if a decryption policy is attached to this participant.
+    pub fn decrypt_share_for_subset<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        subset: &CombinationSubset,
+        rng: &mut R,
+    ) -> (VerifiableDecryption<G>, LogEqualityProof<G>) {
+        self.assert_unrestricted();
+        if let Err(err) = subset.check(self.key_set.params()) {
+            panic!("combination subset does not match key set: {err}");
+        }
+        let coefficient = subset
+            .lagrange_coefficient::<G>(self.index)
+            .expect("participant is not in the combination subset");
+        let scaled_share = &self.secret_share * &coefficient;
+        let dh_element = ciphertext.random_element * scaled_share.expose_scalar();
+        let scaled_key = self.public_key_share().as_element() * &coefficient;
+        let mut transcript = self.key_set.subset_share_transcript(subset, self.index);
+
+        let proof = LogEqualityProof::new(
+            &PublicKey::from_element(ciphertext.random_element),
+            &scaled_share,
+            (scaled_key, dh_element),
+            &mut transcript,
+            rng,
+        );
+        (VerifiableDecryption::from_element(dh_element), proof)
+    }
+
+    /// Creates a share re-encrypting the specified `ciphertext` to the `target` key together with
+    /// a proof of its validity. See [`ReEncryptionShare`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a decryption policy is attached to this participant.
+    pub fn reencrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        target: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (ReEncryptionShare<G>, ReEncryptionProof<G>) {
+        self.assert_unrestricted();
+        ReEncryptionProof::new(
+            &self.key_set,
+            self.index,
+            &self.secret_share,
+            ciphertext,
+            target,
+            rng,
+        )
+    }
+
+    /// Creates a decryption share for the specified `ciphertext` locked under the `condition`
+    /// point, together with a proof of its validity. See [`ConditionalShare`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a decryption policy is attached to this participant.
+    pub fn conditional_decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        condition: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (ConditionalShare<G>, ReEncryptionProof<G>) {
+        self.assert_unrestricted();
+        ConditionalShare::new(
+            &self.key_set,
+            self.index,
+            &self.secret_share,
+            ciphertext,
+            condition,
+            rng,
+        )
+    }
+
+    /// Signs the `message` with the identity key of this participant. The signature
+    /// can be verified using [`PublicKeySet::verify_signature()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the identity keypair was not attached to this participant
+    /// via [`Self::with_identity()`].
+    pub fn sign<R: CryptoRng + RngCore>(
+        &self,
+        message: &[u8],
+        rng: &mut R,
+    ) -> ProofOfPossession<G> {
+        let identity = self
+            .identity
+            .as_ref()
+            .expect("identity keypair is not attached to the participant")
+            .as_untyped();
+        let mut transcript = self.key_set.signature_transcript(self.index, message);
+        ProofOfPossession::from_keys(
+            iter::once(identity.secret()),
+            iter::once(identity.public()),
+            &mut transcript,
+            rng,
+        )
+    }
+
+    /// Creates a decryption share for the specified `ciphertext` similar to
+    /// [`Self::decrypt_share()`] and signs it with the identity key of this participant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the identity keypair was not attached to this participant
+    /// via [`Self::with_identity()`], or if a decryption policy is attached to this participant.
+    pub fn decrypt_share_signed<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> SignedDecryptionShare<G> {
+        let (share, proof) = self.decrypt_share(ciphertext, rng);
+        let share = CandidateDecryption::from(share);
+        let message = SignedDecryptionShare::signed_message(ciphertext, share, proof);
+        SignedDecryptionShare {
+            share,
+            proof,
+            signature: self.sign(&message, rng),
+        }
+    }
+}
+
+/// Decryption share signed by the identity key of the participant that has produced it.
+///
+/// Produced by [`ActiveParticipant::decrypt_share_signed()`] and verified by
+/// [`PublicKeySet::verify_signed_share()`]. The signature covers the decrypted ciphertext,
+/// the share and the proof of its correctness.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SignedDecryptionShare<G: Group> {
+    pub(super) share: CandidateDecryption<G>,
+    pub(super) proof: LogEqualityProof<G>,
+    pub(super) signature: ProofOfPossession<G>,
+}
+
+impl<G: Group> SignedDecryptionShare<G> {
+    pub(super) fn signed_message(
+        ciphertext: Ciphertext<G>,
+        share: CandidateDecryption<G>,
+        proof: LogEqualityProof<G>,
+    ) -> Vec<u8> {
+        let mut message = ciphertext.to_bytes();
+        message.extend_from_slice(&share.into_unchecked().to_bytes());
+        message.extend_from_slice(&proof.to_bytes());
+        message
+    }
+
+    /// Returns the unverified decryption share.
+    pub fn share(&self) -> CandidateDecryption<G> {
+        self.share
+    }
+
+    /// Returns the proof of correctness of the decryption share.
+    pub fn proof(&self) -> &LogEqualityProof<G> {
+        &self.proof
+    }
 }
 
 #[cfg(test)]
@@ -190,7 +591,57 @@ mod tests {
     use rand::thread_rng;
 
     use super::*;
-    use crate::{curve25519::scalar::Scalar as Scalar25519, group::Ristretto};
+    use crate::{curve25519::scalar::Scalar as Scalar25519, group::Ristretto, VerificationError};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn participant_serialization() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, public_poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, public_poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set, 1, dealer.secret_share_for_participant(1)).unwrap();
+
+        let json = serde_json::to_value(&participant).unwrap();
+        assert!(json["policy"].is_null(), "{json}");
+        let restored: ActiveParticipant<Ristretto> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.index(), 1);
+
+        let mut missing_policy = json.clone();
+        missing_policy.as_object_mut().unwrap().remove("policy");
+        serde_json::from_value::<ActiveParticipant<Ristretto>>(missing_policy).unwrap_err();
+
+        let mut wrong_share = json.clone();
+        wrong_share["secret_share"] = json["key_set"]["shared_key"].clone();
+        serde_json::from_value::<ActiveParticipant<Ristretto>>(wrong_share).unwrap_err();
+
+        let mut wrong_index = json;
+        wrong_index["index"] = serde_json::json!(3);
+        serde_json::from_value::<ActiveParticipant<Ristretto>>(wrong_index).unwrap_err();
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "authorization")))]
+    #[test]
+    fn participant_with_policy_is_rejected_without_authorization_feature() {
+        use serde::de::IntoDeserializer;
+
+        let mut rng = thread_rng();
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, public_poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, public_poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set, 0, dealer.secret_share_for_participant(0)).unwrap();
+
+        let mut json = serde_json::to_value(&participant).unwrap();
+        json["policy"] = serde_json::json!({ "requesters": [], "quorum": 1 });
+        let err = ActiveParticipant::<Ristretto>::deserialize(json.into_deserializer())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("authorization"), "{err}");
+    }
 
     #[test]
     fn shared_2_of_3_key() {
@@ -240,4 +691,67 @@ mod tests {
         let message = Ristretto::mul_generator(&Scalar25519::from(15_u64));
         assert_eq!(composite_dh_element, ciphertext.blinded_element - message);
     }
+
+    #[test]
+    fn signed_decryption_shares() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, public_poly_proof) = dealer.public_info();
+        let identities: Vec<_> = (0..3)
+            .map(|_| IdentityKeypair::generate(&mut rng))
+            .collect();
+        let identity_keys = identities.iter().map(IdentityKeypair::public);
+        let anonymous_key_set = PublicKeySet::new(params, public_poly, public_poly_proof).unwrap();
+        let key_set = anonymous_key_set
+            .clone()
+            .with_identity_keys(identity_keys.collect())
+            .unwrap();
+
+        let alice =
+            ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))
+                .unwrap();
+        let err = alice
+            .clone()
+            .with_identity(identities[1].clone())
+            .unwrap_err();
+        assert!(matches!(err, Error::IdentityKeyMismatch), "{err:?}");
+        let alice = alice.with_identity(identities[0].clone()).unwrap();
+
+        let ciphertext = key_set.shared_key().encrypt(15_u64, &mut rng);
+        let signed_share = alice.decrypt_share_signed(ciphertext, &mut rng);
+        let share = key_set
+            .verify_signed_share(&signed_share, ciphertext, 0)
+            .unwrap();
+        assert_eq!(
+            share.as_element(),
+            signed_share.share().into_unchecked().as_element()
+        );
+
+        // The signature is bound to the participant index and to the ciphertext.
+        assert!(key_set
+            .verify_signed_share(&signed_share, ciphertext, 1)
+            .is_err());
+        let other_ciphertext = key_set.shared_key().encrypt(15_u64, &mut rng);
+        assert!(key_set
+            .verify_signed_share(&signed_share, other_ciphertext, 0)
+            .is_err());
+
+        let signature = alice.sign(b"complaint", &mut rng);
+        key_set
+            .verify_signature(0, b"complaint", &signature)
+            .unwrap();
+        assert!(key_set.verify_signature(0, b"other", &signature).is_err());
+
+        // Signer indexes come from the network, so unknown signers must not lead to panics.
+        let err = key_set
+            .verify_signature(3, b"complaint", &signature)
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::UnknownSigner { index: 3 }));
+        let err = anonymous_key_set
+            .verify_signature(0, b"complaint", &signature)
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::UnknownSigner { index: 0 }));
+    }
 }
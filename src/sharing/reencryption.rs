@@ -0,0 +1,268 @@
+//! Threshold proxy re-encryption.
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::serde::{ElementHelper, ScalarHelper};
+use crate::{
+    group::Group,
+    proofs::{TranscriptForGroup, VerificationError},
+    proto_labels,
+    sharing::PublicKeySet,
+    Ciphertext, PublicKey, SecretKey,
+};
+
+/// Share of a ciphertext re-encrypted from the shared key of a threshold encryption scheme
+/// to a target [`PublicKey`].
+///
+/// # Construction
+///
+/// Given a ciphertext `(R, B) = ([r]G, [m]G + [r]K)` for the shared key `K = [x]G`
+/// and the target key `T`, a participant with the secret share `s_i` generates a random
+/// scalar `k_i` and outputs the share `(E_i, F_i) = ([k_i]G, [s_i]R - [k_i]T)`.
+/// `t` shares are combined using Lagrange interpolation into `(E, F) = ([k]G, [x]R - [k]T)`,
+/// which yields the re-encrypted ciphertext `(E, B - F) = ([k]G, [m]G + [k]T)`.
+///
+/// Each share is accompanied by a [`ReEncryptionProof`] that the share is produced
+/// using the participant's secret share. Besides the target, the decrypted value
+/// is not revealed to anyone, including participants.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{
+/// #     group::Ristretto, sharing::*, DiscreteLogTable, Keypair,
+/// # };
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..3)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let requester = Keypair::<Ristretto>::generate(&mut rng);
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let shares = participants[1..].iter().map(|participant| {
+///     let (share, proof) =
+///         participant.reencrypt_share(ciphertext, requester.public(), &mut rng);
+///     let index = participant.index();
+///     key_set
+///         .verify_reencryption_share(&share, ciphertext, requester.public(), index, &proof)
+///         .map(|()| (index, share))
+/// });
+/// let shares = shares.collect::<Result<Vec<_>, _>>()?;
+///
+/// let reencrypted = params.combine_reencryption_shares(ciphertext, shares).unwrap();
+/// let lookup_table = DiscreteLogTable::new(0..10);
+/// assert_eq!(requester.secret().decrypt(reencrypted, &lookup_table), Some(5));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ReEncryptionShare<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    pub(super) random_element: G::Element,
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    pub(super) blinded_element: G::Element,
+}
+
+/// Zero-knowledge proof of correctness of a [`ReEncryptionShare`].
+///
+/// # Construction
+///
+/// The proof is a standard Schnorr-style proof of knowledge of `(s_i, k_i)` such that
+/// `K_i = [s_i]G`, `E_i = [k_i]G` and `F_i = [s_i]R - [k_i]T`, where `K_i` is
+/// the participant's public key share; see [`ReEncryptionShare`] for the notation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ReEncryptionProof<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))]
+    challenge: G::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))]
+    share_response: G::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))]
+    randomness_response: G::Scalar,
+}
+
+impl<G: Group> ReEncryptionProof<G> {
+    fn transcript(
+        key_set: &PublicKeySet<G>,
+        index: usize,
+        ciphertext: &Ciphertext<G>,
+        target: &PublicKey<G>,
+        share: &ReEncryptionShare<G>,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::REENCRYPTION_SHARE);
+        key_set.commit(&mut transcript);
+        transcript.append_u64(b"i", index as u64);
+        transcript.append_element_bytes(b"T", target.as_bytes());
+        transcript.append_element::<G>(b"R", &ciphertext.random_element);
+        transcript.append_element::<G>(b"E", &share.random_element);
+        transcript.append_element::<G>(b"F", &share.blinded_element);
+        transcript
+    }
+
+    pub(super) fn new<R: CryptoRng + RngCore>(
+        key_set: &PublicKeySet<G>,
+        index: usize,
+        secret_share: &SecretKey<G>,
+        ciphertext: Ciphertext<G>,
+        target: &PublicKey<G>,
+        rng: &mut R,
+    ) -> (ReEncryptionShare<G>, Self) {
+        let randomness = SecretKey::<G>::generate(rng);
+        let share = ReEncryptionShare {
+            random_element: G::mul_generator(randomness.expose_scalar()),
+            blinded_element: ciphertext.random_element * secret_share.expose_scalar()
+                - target.as_element() * randomness.expose_scalar(),
+        };
+        let mut transcript = Self::transcript(key_set, index, &ciphertext, target, &share);
+
+        let share_commitment = SecretKey::<G>::generate(rng);
+        let randomness_commitment = SecretKey::<G>::generate(rng);
+        transcript
+            .append_element::<G>(b"[a]G", &G::mul_generator(share_commitment.expose_scalar()));
+        transcript.append_element::<G>(
+            b"[b]G",
+            &G::mul_generator(randomness_commitment.expose_scalar()),
+        );
+        transcript.append_element::<G>(
+            b"[a]R-[b]T",
+            &(ciphertext.random_element * share_commitment.expose_scalar()
+                - target.as_element() * randomness_commitment.expose_scalar()),
+        );
+
+        let challenge = transcript.challenge_scalar::<G>(b"c");
+        let share_response = share_commitment + secret_share * &challenge;
+        let randomness_response = randomness_commitment + randomness * &challenge;
+        let proof = Self {
+            challenge,
+            share_response: *share_response.expose_scalar(),
+            randomness_response: *randomness_response.expose_scalar(),
+        };
+        (share, proof)
+    }
+
+    pub(super) fn verify(
+        &self,
+        key_set: &PublicKeySet<G>,
+        index: usize,
+        share: &ReEncryptionShare<G>,
+        ciphertext: Ciphertext<G>,
+        target: &PublicKey<G>,
+    ) -> Result<(), VerificationError> {
+        let key_share = key_set.participant_keys()[index].as_element();
+        let mut transcript = Self::transcript(key_set, index, &ciphertext, target, share);
+
+        let neg_challenge = -self.challenge;
+        transcript.append_element::<G>(
+            b"[a]G",
+            &G::vartime_double_mul_generator(&neg_challenge, key_share, &self.share_response),
+        );
+        transcript.append_element::<G>(
+            b"[b]G",
+            &G::vartime_double_mul_generator(
+                &neg_challenge,
+                share.random_element,
+                &self.randomness_response,
+            ),
+        );
+        transcript.append_element::<G>(
+            b"[a]R-[b]T",
+            &G::vartime_multi_mul(
+                &[
+                    self.share_response,
+                    -self.randomness_response,
+                    neg_challenge,
+                ],
+                [
+                    ciphertext.random_element,
+                    target.as_element(),
+                    share.blinded_element,
+                ],
+            ),
+        );
+
+        let expected_challenge = transcript.challenge_scalar::<G>(b"c");
+        if expected_challenge == self.challenge {
+            Ok(())
+        } else {
+            Err(VerificationError::ChallengeMismatch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, Params},
+        DiscreteLogTable, Keypair,
+    };
+
+    #[test]
+    fn reencryption_shares_are_bound_to_context() {
+        let mut rng = thread_rng();
+        let params = Params::new(4, 3);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..4)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let target = Keypair::<Ristretto>::generate(&mut rng);
+        let other_target = Keypair::<Ristretto>::generate(&mut rng);
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+
+        let mut shares = vec![];
+        for participant in participants.iter().rev() {
+            let index = participant.index();
+            let (share, proof) = participant.reencrypt_share(ciphertext, target.public(), &mut rng);
+            key_set
+                .verify_reencryption_share(&share, ciphertext, target.public(), index, &proof)
+                .unwrap();
+            assert!(key_set
+                .verify_reencryption_share(&share, ciphertext, other_target.public(), index, &proof)
+                .is_err());
+            assert!(key_set
+                .verify_reencryption_share(&share, ciphertext, target.public(), index ^ 1, &proof)
+                .is_err());
+            shares.push((index, share));
+        }
+
+        assert!(params
+            .combine_reencryption_shares(ciphertext, shares[..2].to_vec())
+            .is_none());
+        let reencrypted = params
+            .combine_reencryption_shares(ciphertext, shares)
+            .unwrap();
+        let lookup_table = DiscreteLogTable::new(0..10);
+        assert_eq!(target.secret().decrypt(reencrypted, &lookup_table), Some(3));
+        assert_eq!(
+            other_target.secret().decrypt(reencrypted, &lookup_table),
+            None
+        );
+    }
+}
@@ -0,0 +1,841 @@
+//! Decryption with secret shares held by remote services, such as cloud KMS.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::{
+    convert::Infallible,
+    fmt,
+    future::{self, Future},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+#[cfg(feature = "serde")]
+use crate::serde::ElementHelper;
+use crate::{
+    alloc::{vec, Box, Vec},
+    group::Group,
+    proofs::{LogEqualityProof, VerificationError},
+    sharing::{
+        ActiveParticipant, PublicKeySet, SubShare, SubShareChallenge, SubShareCommitment,
+        SubShareNonce, SubShareResponse,
+    },
+    CandidateDecryption, Ciphertext, PublicKey, SecretKey, VerifiableDecryption,
+};
+
+/// Boxed future returned by [`ShareBackend`]s and [`RemoteShareTransport`]s.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Backend producing decryption shares of a single participant, possibly asynchronously.
+///
+/// The backend is implemented for [`ActiveParticipant`]s (the share is produced immediately)
+/// and for [`RemoteShare`]s (the share is produced by a remote service holding
+/// the secret share). Shares from backends are collected by a [`ShareAggregator`].
+pub trait ShareBackend<G: Group> {
+    /// Error produced by the backend.
+    type Error;
+
+    /// Returns 0-based index of the participant.
+    fn index(&self) -> usize;
+
+    /// Creates a decryption share for `ciphertext` together with a proof of its validity.
+    /// `rng` is only used before the returned future is first polled.
+    #[allow(clippy::type_complexity)] // not that complex
+    fn decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> BoxFuture<'_, Result<(VerifiableDecryption<G>, LogEqualityProof<G>), Self::Error>>;
+}
+
+/// Produces shares via [`ActiveParticipant::decrypt_share()`], so it panics if a decryption
+/// policy is attached to the participant.
+impl<G: Group> ShareBackend<G> for ActiveParticipant<G> {
+    type Error = Infallible;
+
+    fn index(&self) -> usize {
+        self.index()
+    }
+
+    fn decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> BoxFuture<'_, Result<(VerifiableDecryption<G>, LogEqualityProof<G>), Infallible>> {
+        let output = self.decrypt_share(ciphertext, rng);
+        Box::pin(future::ready(Ok(output)))
+    }
+}
+
+/// Request sent to a [`RemoteShareHolder`] by a [`RemoteShare`].
+///
+/// The request contains the random element of the decrypted ciphertext multiplied
+/// by a random blinding scalar, so that the holder cannot learn which ciphertext is being
+/// decrypted. Correspondingly, the holder returns blinded partial results, which are
+/// unblinded by the [`RemoteShare`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct BlindedShareRequest<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    blinded_element: G::Element,
+}
+
+impl<G: Group> BlindedShareRequest<G> {
+    /// Returns the blinded random element of the decrypted ciphertext.
+    pub fn blinded_element(&self) -> &G::Element {
+        &self.blinded_element
+    }
+}
+
+/// Secret share of a participant held by a remote service (e.g., sealed in a cloud KMS
+/// envelope and unsealed only within the service).
+///
+/// The holder takes part in a two-round protocol with a [`RemoteShare`]: it
+/// [commits](Self::commit()) to a partial decryption of a [`BlindedShareRequest`], and then
+/// [responds](Self::respond()) to the challenge computed by the [`RemoteShare`].
+/// Messages of the protocol are the same as for [`SubShare`]s.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct RemoteShareHolder<G: Group> {
+    share: SubShare<G>,
+}
+
+impl<G: Group> fmt::Debug for RemoteShareHolder<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RemoteShareHolder")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> RemoteShareHolder<G> {
+    /// Creates a holder for the specified secret share of a participant, e.g., obtained
+    /// via [`ActiveParticipant::secret_share()`].
+    pub fn new(secret_share: SecretKey<G>) -> Self {
+        Self {
+            share: SubShare {
+                secret: secret_share,
+            },
+        }
+    }
+
+    /// Computes the blinded partial decryption for the `request` and commits to the random
+    /// scalar for the proof of its correctness. The returned nonce must be retained
+    /// by the holder and passed to [`Self::respond()`].
+    pub fn commit<R: CryptoRng + RngCore>(
+        &self,
+        request: &BlindedShareRequest<G>,
+        rng: &mut R,
+    ) -> (SubShareCommitment<G>, SubShareNonce<G>) {
+        self.share.commit_to_element(request.blinded_element, rng)
+    }
+
+    /// Responds to the `challenge` issued by the [`RemoteShare`]. The `nonce` is consumed,
+    /// so it cannot be reused for another challenge.
+    pub fn respond(
+        &self,
+        nonce: SubShareNonce<G>,
+        challenge: &SubShareChallenge<G>,
+    ) -> SubShareResponse<G> {
+        self.share.respond(nonce, challenge)
+    }
+}
+
+/// Asynchronous callback delivering messages to a [`RemoteShareHolder`], e.g., via RPC
+/// to a function with access to the KMS key unsealing the share.
+///
+/// The transport is responsible for routing the challenge passed to [`Self::respond()`]
+/// to the same holder session as the preceding [`Self::commit()`] call, so that the holder
+/// can use the nonce retained after committing. Timeouts and retries are handled
+/// by the [`ShareAggregator`]; if the future returned by the transport is dropped,
+/// the session should be considered aborted.
+pub trait RemoteShareTransport<G: Group> {
+    /// Transport error.
+    type Error;
+
+    /// Sends the request to the holder and returns its [commitment](RemoteShareHolder::commit()).
+    fn commit(
+        &self,
+        request: BlindedShareRequest<G>,
+    ) -> BoxFuture<'_, Result<SubShareCommitment<G>, Self::Error>>;
+
+    /// Sends the challenge to the holder and returns its [response](RemoteShareHolder::respond()).
+    fn respond(
+        &self,
+        challenge: SubShareChallenge<G>,
+    ) -> BoxFuture<'_, Result<SubShareResponse<G>, Self::Error>>;
+}
+
+/// [`ShareBackend`] for a participant whose secret share is held by
+/// a [`RemoteShareHolder`] reachable via a [`RemoteShareTransport`].
+///
+/// The backend itself holds only public information. The ciphertext random element
+/// sent to the holder is blinded, and the blinded partial results returned by the holder
+/// are unblinded by the backend; the resulting decryption share and proof are
+/// indistinguishable from ones produced by [`ActiveParticipant`]s. The share is verified
+/// before being returned, so a holder with an incorrect share cannot influence decryption.
+#[derive(Debug, Clone)]
+pub struct RemoteShare<G: Group, T> {
+    key_set: PublicKeySet<G>,
+    index: usize,
+    transport: T,
+}
+
+impl<G: Group, T> RemoteShare<G, T> {
+    /// Creates a backend for the participant with the specified 0-based `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds for the participants in `key_set`.
+    pub fn new(key_set: PublicKeySet<G>, index: usize, transport: T) -> Self {
+        assert!(
+            index < key_set.participant_keys().len(),
+            "participant index {index} is out of bounds"
+        );
+        Self {
+            key_set,
+            index,
+            transport,
+        }
+    }
+
+    /// Returns the public key set for the threshold ElGamal encryption scheme the participant
+    /// is a part of.
+    pub fn key_set(&self) -> &PublicKeySet<G> {
+        &self.key_set
+    }
+
+    /// Returns share of the public key for the participant.
+    pub fn public_key_share(&self) -> &PublicKey<G> {
+        &self.key_set.participant_keys()[self.index]
+    }
+
+    /// Returns the transport used to reach the share holder.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<G: Group, T: RemoteShareTransport<G>> ShareBackend<G> for RemoteShare<G, T> {
+    type Error = RemoteShareError<T::Error>;
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> BoxFuture<'_, Result<(VerifiableDecryption<G>, LogEqualityProof<G>), Self::Error>> {
+        let blinding = SecretKey::<G>::generate(rng);
+        let request = BlindedShareRequest {
+            blinded_element: ciphertext.random_element * blinding.expose_scalar(),
+        };
+        let unblinding = SecretKey::<G>::new(G::invert_scalar(*blinding.expose_scalar()));
+
+        Box::pin(async move {
+            let commitment = self
+                .transport
+                .commit(request)
+                .await
+                .map_err(RemoteShareError::Transport)?;
+            let dh_element = commitment.partial_decryption * unblinding.expose_scalar();
+            let element_commitment = commitment.element_commitment * unblinding.expose_scalar();
+            let challenge = LogEqualityProof::<G>::challenge(
+                &PublicKey::from_element(ciphertext.random_element),
+                (self.public_key_share().as_element(), dh_element),
+                (commitment.generator_commitment, element_commitment),
+                &mut self.key_set.share_transcript(self.index),
+            );
+
+            let response = self
+                .transport
+                .respond(SubShareChallenge(challenge))
+                .await
+                .map_err(RemoteShareError::Transport)?;
+            let proof = LogEqualityProof::from_parts(challenge, response.0);
+            let share = VerifiableDecryption::from_element(dh_element);
+            let share = self
+                .key_set
+                .verify_share(
+                    CandidateDecryption::from(share),
+                    ciphertext,
+                    self.index,
+                    &proof,
+                )
+                .map_err(RemoteShareError::Verification)?;
+            Ok((share, proof))
+        })
+    }
+}
+
+/// Errors that can occur when obtaining a decryption share from a [`RemoteShare`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RemoteShareError<E> {
+    /// Error delivering a message to the share holder.
+    Transport(E),
+    /// Share produced by the holder does not verify, e.g., because the holder
+    /// has used an incorrect secret share.
+    Verification(VerificationError),
+}
+
+impl<E: fmt::Display> fmt::Display for RemoteShareError<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(formatter, "error reaching share holder: {err}"),
+            Self::Verification(err) => write!(formatter, "invalid remote share: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for RemoteShareError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(err) => Some(err),
+            Self::Verification(err) => Some(err),
+        }
+    }
+}
+
+/// Future resolving to the output of the wrapped future, or to `None` if the timer fires first.
+struct WithTimeout<'a, T, F> {
+    future: BoxFuture<'a, T>,
+    timer: Pin<Box<F>>,
+}
+
+impl<T, F: Future<Output = ()>> Future for WithTimeout<'_, T, F> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Poll::Ready(output) = this.future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        this.timer.as_mut().poll(cx).map(|()| None)
+    }
+}
+
+/// Aggregator collecting decryption shares from [`ShareBackend`]s with retry
+/// and timeout semantics.
+///
+/// The aggregator queries backends one by one until it has collected enough valid shares
+/// to decrypt a ciphertext. Each backend gets up to [`Self::with_attempts()`] attempts;
+/// an attempt fails if the backend returns an error, the share does not verify, or the share
+/// is not produced within [`Self::with_timeout()`]. Since the crate is not tied to
+/// an async runtime, timeouts are measured by the `sleep` function provided on creation
+/// (e.g., `tokio::time::sleep`); the future returned by the backend is dropped on timeout.
+///
+/// # Latency
+///
+/// Backends are queried sequentially rather than concurrently. Thus, each unavailable backend
+/// delays decryption by up to `attempts × timeout` (90 seconds with the default settings)
+/// before the next backend is queried, and these delays add up. If latency matters, place
+/// backends that are most likely to be available first, and / or reduce the number
+/// of attempts and the timeout.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::*, DiscreteLogTable};
+/// # use rand::thread_rng;
+/// # use std::{cell::RefCell, convert::Infallible, future::Future, sync::Arc, task};
+/// # use std::time::Duration;
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     struct Noop;
+/// #     impl task::Wake for Noop { fn wake(self: Arc<Self>) {} }
+/// #     let waker = Arc::new(Noop).into();
+/// #     let mut cx = task::Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let task::Poll::Ready(output) = future.as_mut().poll(&mut cx) { return output; }
+/// #     }
+/// # }
+/// // Transport delivering messages to a holder in the same process. In practice,
+/// // messages would be sent over the network to a service unsealing the share.
+/// struct LocalTransport {
+///     holder: RemoteShareHolder<Ristretto>,
+///     nonce: RefCell<Option<SubShareNonce<Ristretto>>>,
+/// }
+///
+/// impl RemoteShareTransport<Ristretto> for LocalTransport {
+///     type Error = Infallible;
+///
+///     fn commit(
+///         &self,
+///         request: BlindedShareRequest<Ristretto>,
+///     ) -> BoxFuture<'_, Result<SubShareCommitment<Ristretto>, Infallible>> {
+///         let (commitment, nonce) = self.holder.commit(&request, &mut thread_rng());
+///         *self.nonce.borrow_mut() = Some(nonce);
+///         Box::pin(async move { Ok(commitment) })
+///     }
+///
+///     fn respond(
+///         &self,
+///         challenge: SubShareChallenge<Ristretto>,
+///     ) -> BoxFuture<'_, Result<SubShareResponse<Ristretto>, Infallible>> {
+///         let nonce = self.nonce.borrow_mut().take().expect("no session");
+///         Box::pin(async move { Ok(self.holder.respond(nonce, &challenge)) })
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let backends: Vec<_> = (0..3)
+///     .map(|i| {
+///         let holder = RemoteShareHolder::new(dealer.secret_share_for_participant(i));
+///         let transport = LocalTransport { holder, nonce: RefCell::new(None) };
+///         RemoteShare::new(key_set.clone(), i, transport)
+///     })
+///     .collect();
+///
+/// // `sleep` would be provided by the async runtime (e.g., `tokio::time::sleep`).
+/// let sleep = |_: Duration| std::future::pending::<()>();
+/// let aggregator = ShareAggregator::new(key_set.clone(), sleep)
+///     .with_attempts(2)
+///     .with_timeout(Duration::from_secs(10));
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let output = block_on(aggregator.decrypt(ciphertext, &backends, &mut rng))?;
+/// assert_eq!(output.shares().len(), 2);
+/// let lookup_table = DiscreteLogTable::<Ristretto>::new(0..10);
+/// assert_eq!(output.decryption().decrypt(ciphertext, &lookup_table), Some(5));
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShareAggregator<G: Group, S> {
+    key_set: PublicKeySet<G>,
+    attempts: usize,
+    timeout: Duration,
+    sleep: S,
+}
+
+impl<G: Group, S> fmt::Debug for ShareAggregator<G, S>
+where
+    PublicKeySet<G>: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ShareAggregator")
+            .field("key_set", &self.key_set)
+            .field("attempts", &self.attempts)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G: Group, S> ShareAggregator<G, S> {
+    /// Default number of attempts for each backend.
+    pub const DEFAULT_ATTEMPTS: usize = 3;
+    /// Default timeout for a single attempt.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates an aggregator with the default retry policy. `sleep` must return a future
+    /// resolving after the specified duration.
+    pub fn new(key_set: PublicKeySet<G>, sleep: S) -> Self {
+        Self {
+            key_set,
+            attempts: Self::DEFAULT_ATTEMPTS,
+            timeout: Self::DEFAULT_TIMEOUT,
+            sleep,
+        }
+    }
+
+    /// Sets the maximum number of attempts to obtain a share from each backend.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attempts` is zero.
+    #[must_use]
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        assert!(attempts > 0, "number of attempts must be positive");
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets the timeout for a single attempt to obtain a share.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the public key set used to verify shares.
+    pub fn key_set(&self) -> &PublicKeySet<G> {
+        &self.key_set
+    }
+
+    /// Returns the maximum number of attempts for each backend.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns the timeout for a single attempt.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl<G, S, F> ShareAggregator<G, S>
+where
+    G: Group,
+    S: Fn(Duration) -> F,
+    F: Future<Output = ()>,
+{
+    /// Decrypts `ciphertext` by collecting verified shares from `backends` in order,
+    /// until the threshold number of shares is reached. Backends with duplicate
+    /// or out-of-bounds indexes are skipped. See the [type-level docs](Self#latency)
+    /// for the latency implications of querying backends sequentially.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fewer than the threshold number of backends have produced
+    /// a valid share within the allowed attempts.
+    pub async fn decrypt<B, R>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        backends: &[B],
+        rng: &mut R,
+    ) -> Result<AggregatedDecryption<G>, AggregationError>
+    where
+        B: ShareBackend<G>,
+        R: CryptoRng + RngCore,
+    {
+        let params = self.key_set.params();
+        let participant_count = self.key_set.participant_keys().len();
+        let mut shares: Vec<(usize, VerifiableDecryption<G>, LogEqualityProof<G>)> =
+            Vec::with_capacity(params.threshold);
+        let mut unavailable = vec![];
+
+        for backend in backends {
+            if shares.len() == params.threshold {
+                break;
+            }
+            let index = backend.index();
+            if index >= participant_count || shares.iter().any(|(i, ..)| *i == index) {
+                continue;
+            }
+            if let Some((share, proof)) = self.request_share(backend, ciphertext, rng).await {
+                shares.push((index, share, proof));
+            } else {
+                unavailable.push(index);
+            }
+        }
+
+        // Shares have distinct, in-bounds indexes, so combining only fails
+        // if the threshold is not reached.
+        let decryption =
+            params.combine_shares(shares.iter().map(|&(index, share, _)| (index, share)));
+        let Some(decryption) = decryption else {
+            return Err(AggregationError::InsufficientShares {
+                threshold: params.threshold,
+                unavailable,
+            });
+        };
+        Ok(AggregatedDecryption {
+            decryption,
+            shares,
+            unavailable,
+        })
+    }
+
+    async fn request_share<B, R>(
+        &self,
+        backend: &B,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> Option<(VerifiableDecryption<G>, LogEqualityProof<G>)>
+    where
+        B: ShareBackend<G>,
+        R: CryptoRng + RngCore,
+    {
+        let index = backend.index();
+        for _ in 0..self.attempts {
+            let attempt = WithTimeout {
+                future: backend.decrypt_share(ciphertext, rng),
+                timer: Box::pin((self.sleep)(self.timeout)),
+            };
+            let Some(Ok((share, proof))) = attempt.await else {
+                continue;
+            };
+            let candidate = CandidateDecryption::from(share);
+            if let Ok(share) = self
+                .key_set
+                .verify_share(candidate, ciphertext, index, &proof)
+            {
+                return Some((share, proof));
+            }
+        }
+        None
+    }
+}
+
+/// Output of [`ShareAggregator::decrypt()`].
+#[derive(Debug, Clone)]
+pub struct AggregatedDecryption<G: Group> {
+    decryption: VerifiableDecryption<G>,
+    shares: Vec<(usize, VerifiableDecryption<G>, LogEqualityProof<G>)>,
+    unavailable: Vec<usize>,
+}
+
+impl<G: Group> AggregatedDecryption<G> {
+    /// Returns the combined decryption.
+    pub fn decryption(&self) -> &VerifiableDecryption<G> {
+        &self.decryption
+    }
+
+    /// Returns verified shares used to obtain the decryption, together with participant
+    /// indexes and share proofs (e.g., to publish them for auditing).
+    pub fn shares(&self) -> &[(usize, VerifiableDecryption<G>, LogEqualityProof<G>)] {
+        &self.shares
+    }
+
+    /// Returns indexes of participants whose backends have failed to produce a valid share.
+    pub fn unavailable_participants(&self) -> &[usize] {
+        &self.unavailable
+    }
+}
+
+/// Errors that can occur in [`ShareAggregator::decrypt()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AggregationError {
+    /// Not enough backends have produced a valid share.
+    InsufficientShares {
+        /// Number of shares required for decryption.
+        threshold: usize,
+        /// Indexes of participants whose backends have failed to produce a valid share.
+        unavailable: Vec<usize>,
+    },
+}
+
+impl fmt::Display for AggregationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientShares {
+                threshold,
+                unavailable,
+            } => write!(
+                formatter,
+                "failed to collect {threshold} decryption shares; unavailable participants: \
+                 {unavailable:?}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AggregationError {}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use core::cell::{Cell, RefCell};
+    use std::{sync::Arc, task::Wake};
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{Dealer, Params},
+        DiscreteLogTable,
+    };
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Arc::new(NoopWaker).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Transport with a holder in the same process, which can hang or fail
+    /// on the first `failed_attempts` calls.
+    struct MockTransport {
+        holder: RemoteShareHolder<Ristretto>,
+        nonce: RefCell<Option<SubShareNonce<Ristretto>>>,
+        failed_attempts: usize,
+        hangs: bool,
+        attempts: Cell<usize>,
+    }
+
+    impl MockTransport {
+        fn new(secret_share: SecretKey<Ristretto>) -> Self {
+            Self {
+                holder: RemoteShareHolder::new(secret_share),
+                nonce: RefCell::new(None),
+                failed_attempts: 0,
+                hangs: false,
+                attempts: Cell::new(0),
+            }
+        }
+    }
+
+    impl RemoteShareTransport<Ristretto> for MockTransport {
+        type Error = &'static str;
+
+        fn commit(
+            &self,
+            request: BlindedShareRequest<Ristretto>,
+        ) -> BoxFuture<'_, Result<SubShareCommitment<Ristretto>, Self::Error>> {
+            let attempt = self.attempts.get();
+            self.attempts.set(attempt + 1);
+            if attempt < self.failed_attempts {
+                return if self.hangs {
+                    Box::pin(future::pending())
+                } else {
+                    Box::pin(future::ready(Err("service unavailable")))
+                };
+            }
+
+            let (commitment, nonce) = self.holder.commit(&request, &mut thread_rng());
+            *self.nonce.borrow_mut() = Some(nonce);
+            Box::pin(future::ready(Ok(commitment)))
+        }
+
+        fn respond(
+            &self,
+            challenge: SubShareChallenge<Ristretto>,
+        ) -> BoxFuture<'_, Result<SubShareResponse<Ristretto>, Self::Error>> {
+            let response = self
+                .nonce
+                .borrow_mut()
+                .take()
+                .map(|nonce| self.holder.respond(nonce, &challenge))
+                .ok_or("no session");
+            Box::pin(future::ready(response))
+        }
+    }
+
+    #[test]
+    fn remote_share_decryption() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))
+                .unwrap();
+        let transport = MockTransport::new(participant.secret_share().clone());
+        let remote_share = RemoteShare::new(key_set.clone(), 1, transport);
+
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let (share, proof) = block_on(ShareBackend::decrypt_share(
+            &remote_share,
+            ciphertext,
+            &mut rng,
+        ))
+        .unwrap();
+        let (expected_share, _) = participant.decrypt_share(ciphertext, &mut rng);
+        assert_eq!(share.as_element(), expected_share.as_element());
+        key_set
+            .verify_share(share.into(), ciphertext, 1, &proof)
+            .unwrap();
+
+        let wrong_transport = MockTransport::new(dealer.secret_share_for_participant(0));
+        let wrong_share = RemoteShare::new(key_set, 1, wrong_transport);
+        let err = block_on(ShareBackend::decrypt_share(
+            &wrong_share,
+            ciphertext,
+            &mut rng,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, RemoteShareError::Verification(_)));
+    }
+
+    #[test]
+    fn aggregator_retries_failed_backends() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let mut transports: Vec<_> = (0..3)
+            .map(|i| MockTransport::new(dealer.secret_share_for_participant(i)))
+            .collect();
+        // The first holder is down; the second one hangs on the first attempt.
+        transports[0].failed_attempts = usize::MAX;
+        transports[1].failed_attempts = 1;
+        transports[1].hangs = true;
+        let backends: Vec<_> = transports
+            .into_iter()
+            .enumerate()
+            .map(|(i, transport)| RemoteShare::new(key_set.clone(), i, transport))
+            .collect();
+
+        let aggregator = ShareAggregator::new(key_set.clone(), |_| future::ready(()));
+        let ciphertext = key_set.shared_key().encrypt(6_u64, &mut rng);
+        let output = block_on(aggregator.decrypt(ciphertext, &backends, &mut rng)).unwrap();
+
+        assert_eq!(output.unavailable_participants(), [0]);
+        let indexes: Vec<_> = output.shares().iter().map(|(i, ..)| *i).collect();
+        assert_eq!(indexes, [1, 2]);
+        let attempts: Vec<_> = backends
+            .iter()
+            .map(|b| b.transport().attempts.get())
+            .collect();
+        assert_eq!(attempts, [3, 2, 1]);
+        let lookup_table = DiscreteLogTable::<Ristretto>::new(0..10);
+        assert_eq!(
+            output.decryption().decrypt(ciphertext, &lookup_table),
+            Some(6)
+        );
+
+        let aggregator = aggregator.with_attempts(1);
+        let err = block_on(aggregator.decrypt(ciphertext, &backends[..2], &mut rng)).unwrap_err();
+        assert_eq!(
+            err,
+            AggregationError::InsufficientShares {
+                threshold: 2,
+                unavailable: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn aggregator_with_local_participants() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = [2, 2, 0]
+            .into_iter()
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let aggregator = ShareAggregator::new(key_set.clone(), |_| future::pending());
+        let ciphertext = key_set.shared_key().encrypt(2_u64, &mut rng);
+        let output = block_on(aggregator.decrypt(ciphertext, &participants, &mut rng)).unwrap();
+        let indexes: Vec<_> = output.shares().iter().map(|(i, ..)| *i).collect();
+        assert_eq!(indexes, [2, 0]);
+        assert!(output.unavailable_participants().is_empty());
+        let lookup_table = DiscreteLogTable::<Ristretto>::new(0..10);
+        assert_eq!(
+            output.decryption().decrypt(ciphertext, &lookup_table),
+            Some(2)
+        );
+    }
+}
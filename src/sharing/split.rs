@@ -0,0 +1,344 @@
+//! Splitting a participant's secret share among several devices.
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use crate::serde::{ElementHelper, ScalarHelper};
+use crate::{
+    group::Group,
+    proofs::{LogEqualityProof, VerificationError},
+    sharing::{ActiveParticipant, PublicKeySet},
+    CandidateDecryption, Ciphertext, PublicKey, SecretKey, VerifiableDecryption,
+};
+
+/// Part of a participant's secret share stored on a separate device.
+///
+/// A sub-share never leaves its device; instead, the device participates in a two-party
+/// computation orchestrated by [`SplitSecretShareBackend`]: it [commits](Self::commit())
+/// to a partial decryption, and then [responds](Self::respond()) to the challenge computed
+/// from both commitments.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SubShare<G: Group> {
+    pub(super) secret: SecretKey<G>,
+}
+
+impl<G: Group> fmt::Debug for SubShare<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("SubShare").finish_non_exhaustive()
+    }
+}
+
+impl<G: Group> SubShare<G> {
+    /// Computes the partial decryption of `ciphertext` and commits to the random scalar
+    /// for the proof of its correctness. The returned nonce must be kept on the device
+    /// and passed to [`Self::respond()`].
+    pub fn commit<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> (SubShareCommitment<G>, SubShareNonce<G>) {
+        self.commit_to_element(ciphertext.random_element, rng)
+    }
+
+    pub(super) fn commit_to_element<R: CryptoRng + RngCore>(
+        &self,
+        element: G::Element,
+        rng: &mut R,
+    ) -> (SubShareCommitment<G>, SubShareNonce<G>) {
+        let nonce = SecretKey::<G>::generate(rng);
+        let commitment = SubShareCommitment {
+            partial_decryption: element * self.secret.expose_scalar(),
+            generator_commitment: G::mul_generator(nonce.expose_scalar()),
+            element_commitment: element * nonce.expose_scalar(),
+        };
+        (commitment, SubShareNonce(nonce))
+    }
+
+    /// Responds to the `challenge` issued by the [`SplitSecretShareBackend`].
+    /// The `nonce` is consumed, so it cannot be reused for another challenge
+    /// (which would leak the sub-share).
+    #[allow(clippy::needless_pass_by_value)] // consuming `nonce` is intentional
+    pub fn respond(
+        &self,
+        nonce: SubShareNonce<G>,
+        challenge: &SubShareChallenge<G>,
+    ) -> SubShareResponse<G> {
+        let response = challenge.0 * self.secret.expose_scalar() + nonce.0.expose_scalar();
+        SubShareResponse(response)
+    }
+}
+
+/// Single-use secret produced by [`SubShare::commit()`].
+pub struct SubShareNonce<G: Group>(SecretKey<G>);
+
+impl<G: Group> fmt::Debug for SubShareNonce<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SubShareNonce")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Commitment sent by a device holding a [`SubShare`] to the [`SplitSecretShareBackend`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SubShareCommitment<G: Group> {
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    pub(super) partial_decryption: G::Element,
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    pub(super) generator_commitment: G::Element,
+    #[cfg_attr(feature = "serde", serde(with = "ElementHelper::<G>"))]
+    pub(super) element_commitment: G::Element,
+}
+
+/// Challenge sent by the [`SplitSecretShareBackend`] to devices holding [`SubShare`]s.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct SubShareChallenge<G: Group>(
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))] pub(super) G::Scalar,
+);
+
+/// Response of a device holding a [`SubShare`] to a [`SubShareChallenge`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent, bound = ""))]
+pub struct SubShareResponse<G: Group>(
+    #[cfg_attr(feature = "serde", serde(with = "ScalarHelper::<G>"))] pub(super) G::Scalar,
+);
+
+/// Participant state with the secret share split into two [`SubShare`]s (2-of-2 additive
+/// sharing), which are stored on different devices.
+///
+/// The backend itself holds only public information. Decryption shares produced via
+/// the backend are indistinguishable from ones produced by [`ActiveParticipant`]s
+/// and are verified in the same way; the full secret share is never reconstructed.
+/// Thus, compromising a single device does not leak the secret share.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::{ActiveParticipant, Dealer, Params, PublicKeySet}};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participant =
+///     ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))?;
+/// let (backend, [laptop_share, token_share]) = participant.split_share(&mut rng);
+///
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// // Each device commits to its partial decryption...
+/// let (laptop_commitment, laptop_nonce) = laptop_share.commit(ciphertext, &mut rng);
+/// let (token_commitment, token_nonce) = token_share.commit(ciphertext, &mut rng);
+/// // ...and responds to the challenge computed by the backend.
+/// let decryption = backend.start_decryption(ciphertext, [laptop_commitment, token_commitment]);
+/// let challenge = decryption.challenge();
+/// let responses = [
+///     laptop_share.respond(laptop_nonce, &challenge),
+///     token_share.respond(token_nonce, &challenge),
+/// ];
+/// let (share, proof) = decryption.finish(responses)?;
+/// key_set.verify_share(share.into(), ciphertext, 0, &proof)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SplitSecretShareBackend<G: Group> {
+    key_set: PublicKeySet<G>,
+    index: usize,
+}
+
+impl<G: Group> SplitSecretShareBackend<G> {
+    pub(super) fn new<R: CryptoRng + RngCore>(
+        participant: &ActiveParticipant<G>,
+        rng: &mut R,
+    ) -> (Self, [SubShare<G>; 2]) {
+        let first = SecretKey::<G>::generate(rng);
+        let second = participant.secret_share().clone() - first.clone();
+        let backend = Self {
+            key_set: participant.key_set().clone(),
+            index: participant.index(),
+        };
+        let sub_shares = [SubShare { secret: first }, SubShare { secret: second }];
+        (backend, sub_shares)
+    }
+
+    /// Returns the public key set for the threshold ElGamal encryption scheme the participant
+    /// is a part of.
+    pub fn key_set(&self) -> &PublicKeySet<G> {
+        &self.key_set
+    }
+
+    /// Returns 0-based index of the participant.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns share of the public key for the participant.
+    pub fn public_key_share(&self) -> &PublicKey<G> {
+        &self.key_set.participant_keys()[self.index]
+    }
+
+    /// Starts decrypting `ciphertext` given commitments from both devices.
+    pub fn start_decryption(
+        &self,
+        ciphertext: Ciphertext<G>,
+        commitments: [SubShareCommitment<G>; 2],
+    ) -> SplitDecryption<'_, G> {
+        let [first, second] = commitments;
+        let dh_element = first.partial_decryption + second.partial_decryption;
+        let proof_commitments = (
+            first.generator_commitment + second.generator_commitment,
+            first.element_commitment + second.element_commitment,
+        );
+        let challenge = LogEqualityProof::<G>::challenge(
+            &PublicKey::from_element(ciphertext.random_element),
+            (self.public_key_share().as_element(), dh_element),
+            proof_commitments,
+            &mut self.key_set.share_transcript(self.index),
+        );
+
+        SplitDecryption {
+            backend: self,
+            ciphertext,
+            dh_element,
+            challenge,
+        }
+    }
+
+    /// Creates a decryption share for `ciphertext` together with a proof of its validity
+    /// using [`SubShare`]s available locally. This is a shortcut for running
+    /// [`Self::start_decryption()`] and [`SplitDecryption::finish()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sub-shares do not correspond to the participant's key share.
+    pub fn decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        sub_shares: [&SubShare<G>; 2],
+        rng: &mut R,
+    ) -> Result<(VerifiableDecryption<G>, LogEqualityProof<G>), VerificationError> {
+        let (first_commitment, first_nonce) = sub_shares[0].commit(ciphertext, rng);
+        let (second_commitment, second_nonce) = sub_shares[1].commit(ciphertext, rng);
+        let decryption = self.start_decryption(ciphertext, [first_commitment, second_commitment]);
+        let challenge = decryption.challenge();
+        decryption.finish([
+            sub_shares[0].respond(first_nonce, &challenge),
+            sub_shares[1].respond(second_nonce, &challenge),
+        ])
+    }
+}
+
+/// Decryption in progress for a [`SplitSecretShareBackend`].
+#[derive(Debug)]
+pub struct SplitDecryption<'a, G: Group> {
+    backend: &'a SplitSecretShareBackend<G>,
+    ciphertext: Ciphertext<G>,
+    dh_element: G::Element,
+    challenge: G::Scalar,
+}
+
+impl<G: Group> SplitDecryption<'_, G> {
+    /// Returns the challenge to be sent to both devices.
+    pub fn challenge(&self) -> SubShareChallenge<G> {
+        SubShareChallenge(self.challenge)
+    }
+
+    /// Combines device responses into a decryption share and a proof of its validity.
+    /// The share is verified before being returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the combined proof does not verify, e.g., because a device
+    /// has used an incorrect sub-share or responded to another challenge.
+    pub fn finish(
+        self,
+        responses: [SubShareResponse<G>; 2],
+    ) -> Result<(VerifiableDecryption<G>, LogEqualityProof<G>), VerificationError> {
+        let response = responses[0].0 + responses[1].0;
+        let proof = LogEqualityProof::from_parts(self.challenge, response);
+        let share = VerifiableDecryption::from_element(self.dh_element);
+        let share = self.backend.key_set.verify_share(
+            CandidateDecryption::from(share),
+            self.ciphertext,
+            self.backend.index,
+            &proof,
+        )?;
+        Ok((share, proof))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{Dealer, Params},
+        DiscreteLogTable,
+    };
+
+    #[test]
+    fn split_share_decryption() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..2)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+        let (backend, [first, second]) = participants[1].clone().split_share(&mut rng);
+
+        let ciphertext = key_set.shared_key().encrypt(7_u64, &mut rng);
+        let (split_share, _) = backend
+            .decrypt_share(ciphertext, [&first, &second], &mut rng)
+            .unwrap();
+        let (share, _) = participants[1].decrypt_share(ciphertext, &mut rng);
+        assert_eq!(split_share.as_element(), share.as_element());
+
+        let (other_share, _) = participants[0].decrypt_share(ciphertext, &mut rng);
+        let combined = params
+            .combine_shares([(0, other_share), (1, split_share)])
+            .unwrap();
+        let lookup_table = DiscreteLogTable::<Ristretto>::new(0..10);
+        assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(7));
+    }
+
+    #[test]
+    fn split_share_decryption_with_wrong_sub_share() {
+        let mut rng = thread_rng();
+        let params = Params::new(2, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 0, dealer.secret_share_for_participant(0))
+                .unwrap();
+        let (backend, [first, _]) = participant.clone().split_share(&mut rng);
+        let (_, [other, _]) = participant.split_share(&mut rng);
+
+        let ciphertext = key_set.shared_key().encrypt(1_u64, &mut rng);
+        let err = backend
+            .decrypt_share(ciphertext, [&first, &other], &mut rng)
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::ChallengeMismatch));
+    }
+}
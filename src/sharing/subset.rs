@@ -0,0 +1,517 @@
+//! Lagrange-free share combination for an announced subset of participants.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{combination_coefficients, Error, Generation, Params, PublicKeySet};
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    CandidateDecryption, Ciphertext, LogEqualityProof, PublicKey, VerifiableDecryption,
+};
+
+/// Subset of exactly [`threshold`](Params::threshold) participants agreed upon in advance
+/// to produce decryption shares.
+///
+/// Once the subset is announced, each participant in it can multiply its decryption share
+/// by its Lagrange coefficient for the subset (see
+/// [`ActiveParticipant::decrypt_share_for_subset()`]). The combiner then only needs to sum
+/// the received shares via [`Self::combine_shares()`], which requires no scalar
+/// multiplications. This moves the combination work to participants, which allows to run
+/// the combiner on constrained hardware.
+///
+/// The downside is that the subset must be fixed before the shares are produced; if any
+/// participant from the subset drops off, the shares from the other participants cannot be
+/// reused with another subset.
+///
+/// [`ActiveParticipant::decrypt_share_for_subset()`]: crate::sharing::ActiveParticipant::decrypt_share_for_subset()
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::*, DiscreteLogTable};
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(4, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participants = (0..4)
+///     .map(|i| ActiveParticipant::new(
+///         key_set.clone(),
+///         i,
+///         dealer.secret_share_for_participant(i),
+///     ))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let subset = CombinationSubset::new(params, [3, 1])?;
+/// let shares = subset.indexes().iter().map(|&i| {
+///     let (share, proof) =
+///         participants[i].decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+///     key_set
+///         .verify_subset_share(share.into(), ciphertext, &subset, i, &proof)
+///         .map(|share| (i, share))
+/// });
+/// let shares = shares.collect::<Result<Vec<_>, _>>()?;
+///
+/// let combined = subset.combine_shares(shares).unwrap();
+/// let lookup_table = DiscreteLogTable::new(0..10);
+/// assert_eq!(combined.decrypt(ciphertext, &lookup_table), Some(5));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CombinationSubsetRepr"))]
+pub struct CombinationSubset {
+    indexes: Vec<usize>,
+}
+
+/// Serialized form of a [`CombinationSubset`] that is validated before conversion.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct CombinationSubsetRepr {
+    indexes: Vec<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CombinationSubsetRepr> for CombinationSubset {
+    type Error = Error;
+
+    fn try_from(repr: CombinationSubsetRepr) -> Result<Self, Self::Error> {
+        let mut indexes = repr.indexes;
+        indexes.sort_unstable();
+        if let Some(pair) = indexes.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(Error::InvalidParticipantIndex(pair[0]));
+        }
+        match indexes.last() {
+            None => Err(Error::SubsetSizeMismatch),
+            Some(&index) if index >= Params::MAX_SHARES => {
+                Err(Error::InvalidParticipantIndex(index))
+            }
+            Some(_) => Ok(Self { indexes }),
+        }
+    }
+}
+
+impl CombinationSubset {
+    /// Creates a subset from 0-based participant `indexes`, which may be specified
+    /// in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an index is out of bounds or repeated, or if the number
+    /// of indexes differs from the threshold in `params`.
+    pub fn new(params: Params, indexes: impl IntoIterator<Item = usize>) -> Result<Self, Error> {
+        let mut sorted_indexes = Vec::with_capacity(params.threshold);
+        for index in indexes {
+            if index >= params.shares || sorted_indexes.contains(&index) {
+                return Err(Error::InvalidParticipantIndex(index));
+            }
+            sorted_indexes.push(index);
+        }
+        if sorted_indexes.len() != params.threshold {
+            return Err(Error::SubsetSizeMismatch);
+        }
+        sorted_indexes.sort_unstable();
+        Ok(Self {
+            indexes: sorted_indexes,
+        })
+    }
+
+    /// Creates the subset of all participants. This subset is the only possible one
+    /// for [unanimous](Params::is_unanimous()) `params`, so participants may produce
+    /// shares for it without waiting for the subset to be announced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SubsetSizeMismatch`] if `params` are not unanimous.
+    pub fn unanimous(params: Params) -> Result<Self, Error> {
+        if params.is_unanimous() {
+            Ok(Self {
+                indexes: (0..params.shares).collect(),
+            })
+        } else {
+            Err(Error::SubsetSizeMismatch)
+        }
+    }
+
+    /// Checks that this subset is compatible with `params`. Subsets created via constructors
+    /// always are, but deserialized subsets are only checked for internal consistency.
+    pub(super) fn check(&self, params: Params) -> Result<(), Error> {
+        if self.indexes.len() != params.threshold {
+            return Err(Error::SubsetSizeMismatch);
+        }
+        match self.indexes.last() {
+            Some(&index) if index >= params.shares => Err(Error::InvalidParticipantIndex(index)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns 0-based indexes of participants in this subset in the increasing order.
+    pub fn indexes(&self) -> &[usize] {
+        &self.indexes
+    }
+
+    /// Checks whether this subset contains a participant with the specified 0-based `index`.
+    pub fn contains(&self, index: usize) -> bool {
+        self.indexes.binary_search(&index).is_ok()
+    }
+
+    /// Returns the Lagrange coefficient for the participant with the specified 0-based `index`,
+    /// or `None` if the participant is not in this subset.
+    pub fn lagrange_coefficient<G: Group>(&self, index: usize) -> Option<G::Scalar> {
+        let position = self.indexes.binary_search(&index).ok()?;
+        let (denominators, scale) = combination_coefficients::<G>(&self.indexes);
+        Some(denominators[position] * scale)
+    }
+
+    /// Combines decryption shares produced for this subset by summing them. The shares must be
+    /// provided together with the 0-based indexes of the participants they are coming from,
+    /// and must be verified beforehand (e.g., via
+    /// [`PublicKeySet::verify_subset_share()`](crate::sharing::PublicKeySet::verify_subset_share())).
+    ///
+    /// Returns `None` if a share is provided for a participant outside this subset, if a share
+    /// is repeated, or if shares for some participants in the subset are missing.
+    pub fn combine_shares<G: Group>(
+        &self,
+        shares: impl IntoIterator<Item = (usize, VerifiableDecryption<G>)>,
+    ) -> Option<VerifiableDecryption<G>> {
+        let mut is_provided = vec![false; self.indexes.len()];
+        let mut dh_element = G::identity();
+        for (index, share) in shares {
+            let position = self.indexes.binary_search(&index).ok()?;
+            if is_provided[position] {
+                return None;
+            }
+            is_provided[position] = true;
+            dh_element = dh_element + *share.as_element();
+        }
+
+        if is_provided.into_iter().all(|flag| flag) {
+            Some(VerifiableDecryption::from_element(dh_element))
+        } else {
+            None
+        }
+    }
+}
+
+/// [`CombinationSubset`] prepared for verifying decryption shares against a specific
+/// [`PublicKeySet`].
+///
+/// Verifying a share via [`PublicKeySet::verify_subset_share()`] requires scaling
+/// the participant key by its Lagrange coefficient. A prepared subset computes scaled keys
+/// for all participants in the subset once. Since scaled keys are derived from the key set,
+/// the prepared subset records the key set [`Generation`] and refuses to be used with
+/// a key set of another generation (e.g., after keys are refreshed).
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::*};
+/// # use rand::thread_rng;
+/// # use std::error::Error as StdError;
+/// # fn main() -> Result<(), Box<dyn StdError>> {
+/// let mut rng = thread_rng();
+/// let params = Params::new(3, 2);
+/// let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+/// let (public_poly, poly_proof) = dealer.public_info();
+/// let key_set = PublicKeySet::new(params, public_poly, poly_proof)?;
+/// let participant =
+///     ActiveParticipant::new(key_set.clone(), 2, dealer.secret_share_for_participant(2))?;
+///
+/// let subset = CombinationSubset::new(params, [0, 2])?;
+/// let prepared = PreparedSubset::new(&key_set, subset.clone())?;
+/// let ciphertext = key_set.shared_key().encrypt(5_u64, &mut rng);
+/// let (share, proof) = participant.decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+/// prepared.verify_share(&key_set, share.into(), ciphertext, 2, &proof)?;
+///
+/// // A key set in the next epoch has another generation.
+/// let next_key_set = key_set.with_epoch(Epoch::INITIAL.next());
+/// let err = prepared
+///     .verify_share(&next_key_set, share.into(), ciphertext, 2, &proof)
+///     .unwrap_err();
+/// assert!(matches!(err, Error::StaleKeySet { .. }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreparedSubset<G: Group> {
+    generation: Generation,
+    subset: CombinationSubset,
+    scaled_keys: Vec<G::Element>,
+}
+
+impl<G: Group> PreparedSubset<G> {
+    /// Prepares the `subset` for the specified `key_set`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `subset` does not match the parameters of the `key_set`,
+    /// i.e., has a size other than the threshold or contains out-of-bounds indexes.
+    pub fn new(key_set: &PublicKeySet<G>, subset: CombinationSubset) -> Result<Self, Error> {
+        subset.check(key_set.params())?;
+        let (denominators, scale) = combination_coefficients::<G>(&subset.indexes);
+        let scaled_keys = subset
+            .indexes
+            .iter()
+            .zip(denominators)
+            .map(|(&index, denominator)| {
+                key_set.participant_keys()[index].as_element() * &(denominator * scale)
+            })
+            .collect();
+        Ok(Self {
+            generation: key_set.generation(),
+            subset,
+            scaled_keys,
+        })
+    }
+
+    /// Returns the [`Generation`] of the key set this subset was prepared for.
+    pub fn generation(&self) -> Generation {
+        self.generation
+    }
+
+    /// Returns the prepared subset.
+    pub fn subset(&self) -> &CombinationSubset {
+        &self.subset
+    }
+
+    /// Checks that this subset was prepared for the `key_set`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::StaleKeySet`] if the key set has another generation.
+    pub fn check(&self, key_set: &PublicKeySet<G>) -> Result<(), Error> {
+        let actual = key_set.generation();
+        if actual == self.generation {
+            Ok(())
+        } else {
+            Err(Error::StaleKeySet {
+                expected: self.generation,
+                actual,
+            })
+        }
+    }
+
+    /// Verifies a candidate decryption share for `ciphertext` produced for the subset
+    /// by a participant with the specified `index`. This is equivalent to
+    /// [`PublicKeySet::verify_subset_share()`], but uses the precomputed scaled key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `key_set` has another generation than the one this subset
+    /// was prepared for, if `index` is not in the subset, or if the `proof` does not verify.
+    pub fn verify_share(
+        &self,
+        key_set: &PublicKeySet<G>,
+        candidate_share: CandidateDecryption<G>,
+        ciphertext: Ciphertext<G>,
+        index: usize,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, Error> {
+        self.check(key_set)?;
+        let position = self
+            .subset
+            .indexes
+            .binary_search(&index)
+            .map_err(|_| Error::InvalidParticipantIndex(index))?;
+        let dh_element = candidate_share.dh_element();
+        let mut transcript = key_set.subset_share_transcript(&self.subset, index);
+
+        proof
+            .verify(
+                &PublicKey::from_element(ciphertext.random_element),
+                (self.scaled_keys[position], dh_element),
+                &mut transcript,
+            )
+            .map_err(|err| Error::InvalidShare { index, err })?;
+        Ok(VerifiableDecryption::from_element(dh_element))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{
+        group::Ristretto,
+        sharing::{ActiveParticipant, Dealer, PublicKeySet},
+    };
+
+    #[test]
+    fn creating_subset_errors() {
+        let params = Params::new(5, 3);
+        let err = CombinationSubset::new(params, [0, 5, 1]).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(5)), "{err:?}");
+        let err = CombinationSubset::new(params, [0, 2, 2]).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(2)), "{err:?}");
+        let err = CombinationSubset::new(params, [0, 2]).unwrap_err();
+        assert!(matches!(err, Error::SubsetSizeMismatch), "{err:?}");
+
+        let subset = CombinationSubset::new(params, [4, 0, 2]).unwrap();
+        assert_eq!(subset.indexes(), [0, 2, 4]);
+        assert!(subset.contains(2));
+        assert!(!subset.contains(1));
+
+        let err = CombinationSubset::unanimous(params).unwrap_err();
+        assert!(matches!(err, Error::SubsetSizeMismatch), "{err:?}");
+        let subset = CombinationSubset::unanimous(Params::new(3, 3)).unwrap();
+        assert_eq!(subset.indexes(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn combining_subset_shares() {
+        let mut rng = thread_rng();
+        let params = Params::new(5, 3);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participants: Vec<_> = (0..5)
+            .map(|i| {
+                let secret_share = dealer.secret_share_for_participant(i);
+                ActiveParticipant::new(key_set.clone(), i, secret_share).unwrap()
+            })
+            .collect();
+
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let subset = CombinationSubset::new(params, [1, 2, 4]).unwrap();
+        let other_subset = CombinationSubset::new(params, [0, 2, 4]).unwrap();
+        let shares: Vec<_> = subset
+            .indexes()
+            .iter()
+            .map(|&i| {
+                let (share, proof) =
+                    participants[i].decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+                let verified = key_set
+                    .verify_subset_share(share.into(), ciphertext, &subset, i, &proof)
+                    .unwrap();
+                assert!(key_set
+                    .verify_subset_share(share.into(), ciphertext, &other_subset, 4, &proof)
+                    .is_err());
+                (i, verified)
+            })
+            .collect();
+
+        let expected = params
+            .combine_shares(subset.indexes().iter().map(|&i| {
+                let (share, _) = participants[i].decrypt_share(ciphertext, &mut rng);
+                (i, share)
+            }))
+            .unwrap();
+        let combined = subset.combine_shares(shares.iter().copied()).unwrap();
+        assert_eq!(combined.as_element(), expected.as_element());
+
+        assert!(subset.combine_shares(shares[..2].iter().copied()).is_none());
+        let repeated_shares = shares.iter().copied().chain([shares[0]]);
+        assert!(subset.combine_shares(repeated_shares).is_none());
+        assert!(other_subset.combine_shares(shares).is_none());
+    }
+
+    #[test]
+    fn prepared_subset_is_bound_to_key_set_generation() {
+        let mut rng = thread_rng();
+        let params = Params::new(3, 2);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))
+                .unwrap();
+        let other_dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = other_dealer.public_info();
+        let other_key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        assert_eq!(key_set.clone().generation(), key_set.generation());
+        assert_ne!(other_key_set.generation(), key_set.generation());
+
+        let subset = CombinationSubset::new(params, [1, 2]).unwrap();
+        let prepared = PreparedSubset::new(&key_set, subset.clone()).unwrap();
+        assert_eq!(prepared.generation(), key_set.generation());
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let (share, proof) = participant.decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+        let verified = prepared
+            .verify_share(&key_set, share.into(), ciphertext, 1, &proof)
+            .unwrap();
+        let expected = key_set
+            .verify_subset_share(share.into(), ciphertext, &subset, 1, &proof)
+            .unwrap();
+        assert_eq!(verified.as_element(), expected.as_element());
+
+        let err = prepared
+            .verify_share(&other_key_set, share.into(), ciphertext, 1, &proof)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::StaleKeySet { expected, .. } if expected == prepared.generation()),
+            "{err:?}"
+        );
+        let err = prepared
+            .verify_share(&key_set, share.into(), ciphertext, 0, &proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(0)), "{err:?}");
+        let err = prepared
+            .verify_share(&key_set, share.into(), ciphertext, 2, &proof)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidShare { index: 2, .. }),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn subset_deserialization_is_validated() {
+        let subset: CombinationSubset =
+            serde_json::from_str(r#"{ "indexes": [4, 0, 2] }"#).unwrap();
+        assert_eq!(subset.indexes(), [0, 2, 4]);
+        let json = serde_json::to_string(&subset).unwrap();
+        let restored: CombinationSubset = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, subset);
+
+        let err =
+            serde_json::from_str::<CombinationSubset>(r#"{ "indexes": [1, 0, 1] }"#).unwrap_err();
+        assert!(err.to_string().contains("participant index"), "{err}");
+        let err = serde_json::from_str::<CombinationSubset>(r#"{ "indexes": [] }"#).unwrap_err();
+        assert!(err.to_string().contains("threshold"), "{err}");
+        let err =
+            serde_json::from_str::<CombinationSubset>(r#"{ "indexes": [0, 100000] }"#).unwrap_err();
+        assert!(err.to_string().contains("participant index"), "{err}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn subset_is_checked_against_key_set() {
+        let mut rng = thread_rng();
+        let params = Params::new(5, 3);
+        let dealer = Dealer::<Ristretto>::new(params, &mut rng);
+        let (public_poly, poly_proof) = dealer.public_info();
+        let key_set = PublicKeySet::new(params, public_poly, poly_proof).unwrap();
+        let participant =
+            ActiveParticipant::new(key_set.clone(), 1, dealer.secret_share_for_participant(1))
+                .unwrap();
+        let subset = CombinationSubset::new(params, [1, 2, 4]).unwrap();
+        let ciphertext = key_set.shared_key().encrypt(3_u64, &mut rng);
+        let (share, proof) = participant.decrypt_share_for_subset(ciphertext, &subset, &mut rng);
+
+        let small_subset: CombinationSubset =
+            serde_json::from_str(r#"{ "indexes": [1, 2] }"#).unwrap();
+        let err = key_set
+            .verify_subset_share(share.into(), ciphertext, &small_subset, 1, &proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::SubsetSizeMismatch), "{err:?}");
+        let err = PreparedSubset::new(&key_set, small_subset).unwrap_err();
+        assert!(matches!(err, Error::SubsetSizeMismatch), "{err:?}");
+
+        let out_of_bounds_subset: CombinationSubset =
+            serde_json::from_str(r#"{ "indexes": [1, 2, 7] }"#).unwrap();
+        let err = key_set
+            .verify_subset_share(share.into(), ciphertext, &out_of_bounds_subset, 1, &proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(7)), "{err:?}");
+        let err = PreparedSubset::new(&key_set, out_of_bounds_subset).unwrap_err();
+        assert!(matches!(err, Error::InvalidParticipantIndex(7)), "{err:?}");
+    }
+}
@@ -0,0 +1,768 @@
+//! Utilities for testing and benchmarking applications built on top of this crate.
+//!
+//! The utilities are **not** meant to be used in production. In particular, randomness
+//! for generated data is derived from a user-supplied seed, so that generated ciphertexts
+//! are not secure.
+
+use rand_chacha::ChaCha8Rng;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use subtle::ConstantTimeEq;
+
+use core::fmt;
+
+use crate::{
+    alloc::{vec, Vec},
+    app::{ChoiceParams, EncryptedChoice, MultiChoice, ProveSum, SingleChoice},
+    dkg::{
+        Error as DkgError, ParticipantCollectingCommitments, ParticipantCollectingPolynomials,
+        ParticipantExchangingSecrets,
+    },
+    group::Group,
+    sharing::{ActiveParticipant, Params},
+    SecretKey,
+};
+
+/// Generator of [`EncryptedChoice`] ballots for load testing.
+///
+/// If the `rayon` crate feature is enabled, ballots are created in parallel using
+/// the [`rayon`](https://docs.rs/rayon/) thread pool.
+/// Each ballot uses a fast RNG (ChaCha8) derived from the generator seed and the ballot index,
+/// so the generated batch is fully determined by the seed.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{app::ChoiceParams, group::Ristretto, testkit::BallotGenerator, Keypair};
+/// # use rand::thread_rng;
+/// let receiver = Keypair::<Ristretto>::generate(&mut thread_rng());
+/// let params = ChoiceParams::single(receiver.public().clone(), 5);
+/// let batch = BallotGenerator::new(params.clone(), 42).generate(20, 3);
+/// assert_eq!(batch.ballots().len(), 23);
+///
+/// let invalid_count = batch
+///     .ballots()
+///     .iter()
+///     .filter(|ballot| ballot.verify(&params).is_err())
+///     .count();
+/// assert_eq!(invalid_count, 3);
+/// assert_eq!(batch.expected_tally().iter().sum::<u64>(), 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BallotGenerator<G: Group, S: ProveSum<G>> {
+    params: ChoiceParams<G, S>,
+    seed: u64,
+}
+
+impl<G: Group, S: ProveSum<G>> BallotGenerator<G, S>
+where
+    G::Scalar: Send + Sync,
+    G::Element: Send + Sync,
+    S: Send + Sync,
+    S::Proof: Send,
+{
+    /// Creates a generator with the specified parameters and RNG `seed`.
+    pub fn new(params: ChoiceParams<G, S>, seed: u64) -> Self {
+        Self { params, seed }
+    }
+
+    /// Returns the parameters of generated ballots.
+    pub fn params(&self) -> &ChoiceParams<G, S> {
+        &self.params
+    }
+
+    fn rng(&self, stream: u64) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(stream);
+        rng
+    }
+
+    fn generate_with<F>(
+        &self,
+        valid_count: usize,
+        invalid_count: usize,
+        select: F,
+    ) -> BallotBatch<G, S>
+    where
+        F: Fn(&mut ChaCha8Rng, usize) -> Vec<bool> + Sync,
+    {
+        let total_count = valid_count + invalid_count;
+        let mut is_invalid = vec![false; total_count];
+        is_invalid[..invalid_count].fill(true);
+        // Shuffle invalid ballots among valid ones (Fisher–Yates shuffle).
+        let mut rng = self.rng(u64::MAX);
+        for i in (1..total_count).rev() {
+            let j = rng.next_u64() % (i as u64 + 1);
+            is_invalid.swap(i, usize::try_from(j).unwrap());
+        }
+
+        let options_count = self.params.options_count();
+        #[cfg(feature = "rayon")]
+        let is_invalid_iter = is_invalid.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let is_invalid_iter = is_invalid.iter();
+        let ballots: Vec<_> = is_invalid_iter
+            .enumerate()
+            .map(|(i, &is_invalid)| {
+                let mut rng = self.rng(i as u64);
+                let choices = select(&mut rng, options_count);
+                let mut ballot = EncryptedChoice::new(&self.params, &choices, &mut rng);
+                if is_invalid {
+                    // Adding the group generator to an option ciphertext makes it
+                    // an encryption of 2 or 1, which invalidates the range proof.
+                    let option = usize::try_from(rng.next_u64() % options_count as u64).unwrap();
+                    let ciphertext = &mut ballot.choices_mut()[option];
+                    ciphertext.blinded_element = ciphertext.blinded_element + G::generator();
+                }
+                (ballot, choices)
+            })
+            .collect();
+
+        let mut expected_tally = vec![0; options_count];
+        let invalid_indexes = is_invalid
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &is_invalid)| is_invalid.then_some(i))
+            .collect();
+        let ballots = ballots
+            .into_iter()
+            .zip(&is_invalid)
+            .map(|((ballot, choices), &is_invalid)| {
+                if !is_invalid {
+                    for (total, flag) in expected_tally.iter_mut().zip(choices) {
+                        *total += u64::from(flag);
+                    }
+                }
+                ballot
+            })
+            .collect();
+
+        BallotBatch {
+            ballots,
+            invalid_indexes,
+            expected_tally,
+        }
+    }
+}
+
+impl<G: Group> BallotGenerator<G, SingleChoice>
+where
+    G::Scalar: Send + Sync,
+    G::Element: Send + Sync,
+{
+    /// Generates `valid_count` valid ballots (each selecting a random option)
+    /// and `invalid_count` invalid ballots placed at random positions in the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch size overflows `usize`.
+    pub fn generate(
+        &self,
+        valid_count: usize,
+        invalid_count: usize,
+    ) -> BallotBatch<G, SingleChoice> {
+        self.generate_with(valid_count, invalid_count, |rng, options_count| {
+            let selected = usize::try_from(rng.next_u64() % options_count as u64).unwrap();
+            (0..options_count).map(|i| i == selected).collect()
+        })
+    }
+}
+
+impl<G: Group> BallotGenerator<G, MultiChoice>
+where
+    G::Scalar: Send + Sync,
+    G::Element: Send + Sync,
+{
+    /// Generates `valid_count` valid ballots (each selecting a random subset of options)
+    /// and `invalid_count` invalid ballots placed at random positions in the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch size overflows `usize`.
+    pub fn generate(
+        &self,
+        valid_count: usize,
+        invalid_count: usize,
+    ) -> BallotBatch<G, MultiChoice> {
+        self.generate_with(valid_count, invalid_count, |rng, options_count| {
+            (0..options_count)
+                .map(|_| rng.next_u32() % 2 == 1)
+                .collect()
+        })
+    }
+}
+
+/// Batch of ballots produced by a [`BallotGenerator`].
+pub struct BallotBatch<G: Group, S: ProveSum<G>> {
+    ballots: Vec<EncryptedChoice<G, S>>,
+    invalid_indexes: Vec<usize>,
+    expected_tally: Vec<u64>,
+}
+
+impl<G: Group, S: ProveSum<G>> fmt::Debug for BallotBatch<G, S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("BallotBatch")
+            .field("ballots_len", &self.ballots.len())
+            .field("invalid_indexes", &self.invalid_indexes)
+            .field("expected_tally", &self.expected_tally)
+            .finish()
+    }
+}
+
+impl<G: Group, S: ProveSum<G>> BallotBatch<G, S> {
+    /// Returns generated ballots.
+    pub fn ballots(&self) -> &[EncryptedChoice<G, S>] {
+        &self.ballots
+    }
+
+    /// Returns 0-based indexes of invalid ballots in increasing order.
+    pub fn invalid_indexes(&self) -> &[usize] {
+        &self.invalid_indexes
+    }
+
+    /// Returns the tally of valid ballots, i.e., the number of votes for each option.
+    pub fn expected_tally(&self) -> &[u64] {
+        &self.expected_tally
+    }
+
+    /// Converts this batch into ballots.
+    pub fn into_ballots(self) -> Vec<EncryptedChoice<G, S>> {
+        self.ballots
+    }
+}
+
+/// Stage of the [distributed key generation](crate::dkg) protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DkgStage {
+    /// Participants exchange commitments to their public key shares.
+    Commitments,
+    /// Participants exchange public polynomials and openings of the commitments.
+    Polynomials,
+    /// Participants exchange secret shares.
+    SecretShares,
+}
+
+/// Misbehavior of a corrupted participant in a [`DkgSimulation`]. Apart from the fault,
+/// corrupted participants follow the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DkgFault {
+    /// Sends no messages to honest participants starting from the specified stage.
+    Withhold(DkgStage),
+    /// Sends two different commitments to each honest participant.
+    EquivocateCommitment,
+    /// Publishes a public polynomial not matching the commitment.
+    MismatchedPolynomial,
+    /// Sends random secret shares to honest participants.
+    InvalidSecretShares,
+}
+
+impl DkgFault {
+    fn withholds(self, stage: DkgStage) -> bool {
+        matches!(self, Self::Withhold(start) if start <= stage)
+    }
+}
+
+/// Reason of a [`DkgComplaint`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DkgComplaintReason {
+    /// The accused participant has not sent the message expected on the stage.
+    Missing,
+    /// A message from the accused participant was rejected.
+    Rejected(DkgError),
+}
+
+/// Complaint raised by an honest participant in a [`DkgSimulation`].
+#[derive(Debug)]
+pub struct DkgComplaint {
+    complainant: usize,
+    accused: usize,
+    stage: DkgStage,
+    reason: DkgComplaintReason,
+}
+
+impl DkgComplaint {
+    fn new(
+        complainant: usize,
+        accused: usize,
+        stage: DkgStage,
+        reason: DkgComplaintReason,
+    ) -> Self {
+        Self {
+            complainant,
+            accused,
+            stage,
+            reason,
+        }
+    }
+
+    /// Pushes complaints about `missing` messages, unless the recipient has already
+    /// complained about a rejected message from the same sender.
+    fn push_missing(
+        complaints: &mut Vec<Self>,
+        complainant: usize,
+        stage: DkgStage,
+        missing: impl Iterator<Item = usize>,
+    ) {
+        for accused in missing {
+            let has_complained = complaints.iter().any(|complaint| {
+                complaint.complainant == complainant
+                    && complaint.accused == accused
+                    && complaint.stage == stage
+            });
+            if !has_complained {
+                let reason = DkgComplaintReason::Missing;
+                complaints.push(Self::new(complainant, accused, stage, reason));
+            }
+        }
+    }
+
+    /// Returns the 0-based index of the honest participant raising the complaint.
+    pub fn complainant(&self) -> usize {
+        self.complainant
+    }
+
+    /// Returns the 0-based index of the accused participant.
+    pub fn accused(&self) -> usize {
+        self.accused
+    }
+
+    /// Returns the stage on which the complaint was raised.
+    pub fn stage(&self) -> DkgStage {
+        self.stage
+    }
+
+    /// Returns the reason of the complaint.
+    pub fn reason(&self) -> &DkgComplaintReason {
+        &self.reason
+    }
+}
+
+/// Simulation of the [distributed key generation](crate::dkg) protocol with
+/// corrupted participants, allowing to demonstrate and regression-test how misbehavior
+/// is detected.
+///
+/// All participants are simulated in the same process, with messages delivered
+/// synchronously. Honest participants check received messages and raise [`DkgComplaint`]s
+/// against participants sending missing or invalid messages. Corrupted participants
+/// collude, i.e., deliver genuine messages to each other. Since the DKG protocol
+/// in this crate has no recovery from misbehavior, the protocol is aborted after the first
+/// stage with complaints, and all accused participants are considered disqualified.
+/// Randomness for all participants is derived from the simulation seed.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, sharing::Params, testkit::*};
+/// let params = Params::new(4, 3);
+/// let outcome = DkgSimulation::new(params, 42)
+///     .corrupt(1, DkgFault::InvalidSecretShares)
+///     .corrupt(3, DkgFault::Withhold(DkgStage::SecretShares))
+///     .run::<Ristretto>();
+/// assert_eq!(outcome.aborted_at(), Some(DkgStage::SecretShares));
+/// assert_eq!(outcome.disqualified(), [1, 3]);
+/// // Each of 2 honest participants complains about each corrupted one.
+/// assert_eq!(outcome.complaints().len(), 4);
+///
+/// // Without faults, all participants complete the protocol.
+/// let outcome = DkgSimulation::new(params, 42).run::<Ristretto>();
+/// assert!(outcome.aborted_at().is_none());
+/// assert_eq!(outcome.participants().len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DkgSimulation {
+    params: Params,
+    seed: u64,
+    faults: Vec<Option<DkgFault>>,
+}
+
+impl DkgSimulation {
+    /// Creates a simulation with the specified parameters and RNG `seed`. Initially,
+    /// all participants are honest.
+    pub fn new(params: Params, seed: u64) -> Self {
+        Self {
+            params,
+            seed,
+            faults: vec![None; params.shares],
+        }
+    }
+
+    /// Corrupts the participant with the specified 0-based `index`, so that it commits
+    /// the `fault`. If the participant is already corrupted, its fault is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn corrupt(mut self, index: usize, fault: DkgFault) -> Self {
+        assert!(
+            index < self.params.shares,
+            "participant index {index} is out of bounds"
+        );
+        self.faults[index] = Some(fault);
+        self
+    }
+
+    /// Returns the fault of the participant with the specified 0-based `index`,
+    /// or `None` if the participant is honest or `index` is out of bounds.
+    pub fn fault(&self, index: usize) -> Option<DkgFault> {
+        self.faults.get(index).copied().flatten()
+    }
+
+    /// Runs the simulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an honest participant fails to complete the protocol after receiving
+    /// valid messages from all participants. This indicates a bug in the DKG implementation.
+    pub fn run<G: Group>(&self) -> DkgOutcome<G> {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut complaints = vec![];
+
+        let mut participants: Vec<_> = (0..self.params.shares)
+            .map(|i| ParticipantCollectingCommitments::<G>::new(self.params, i, &mut rng))
+            .collect();
+        self.exchange_commitments(&mut participants, &mut complaints);
+        if !complaints.is_empty() {
+            return DkgOutcome::aborted(DkgStage::Commitments, complaints);
+        }
+
+        let mut participants: Vec<_> = participants
+            .into_iter()
+            .map(ParticipantCollectingCommitments::finish_commitment_phase)
+            .collect();
+        self.exchange_polynomials(&mut participants, &mut complaints);
+        if !complaints.is_empty() {
+            return DkgOutcome::aborted(DkgStage::Polynomials, complaints);
+        }
+
+        let mut participants: Vec<_> = participants
+            .into_iter()
+            .map(ParticipantCollectingPolynomials::finish_polynomials_phase)
+            .collect();
+        self.exchange_secret_shares(&mut participants, &mut complaints, &mut rng);
+        if !complaints.is_empty() {
+            return DkgOutcome::aborted(DkgStage::SecretShares, complaints);
+        }
+
+        let participants = participants
+            .into_iter()
+            .filter(|participant| self.faults[participant.index()].is_none())
+            .map(|participant| {
+                participant
+                    .complete()
+                    .expect("DKG failed after all messages were verified")
+            })
+            .collect();
+        DkgOutcome {
+            complaints,
+            aborted_at: None,
+            participants,
+        }
+    }
+
+    /// Returns senders whose messages are delivered to `recipient` on the `stage`,
+    /// together with the sender fault.
+    fn senders(
+        &self,
+        recipient: usize,
+        stage: DkgStage,
+    ) -> impl Iterator<Item = (usize, Option<DkgFault>)> + '_ {
+        (0..self.params.shares).filter_map(move |sender| {
+            if sender == recipient {
+                return None;
+            }
+            let fault = if self.faults[recipient].is_some() {
+                None // corrupted participants collude
+            } else {
+                self.faults[sender]
+            };
+            let is_withheld = fault.map_or(false, |fault| fault.withholds(stage));
+            (!is_withheld).then_some((sender, fault))
+        })
+    }
+
+    fn exchange_commitments<G: Group>(
+        &self,
+        participants: &mut [ParticipantCollectingCommitments<G>],
+        complaints: &mut Vec<DkgComplaint>,
+    ) {
+        const STAGE: DkgStage = DkgStage::Commitments;
+
+        let commitments: Vec<_> = participants
+            .iter()
+            .map(ParticipantCollectingCommitments::commitment)
+            .collect();
+        for participant in participants {
+            let recipient = participant.index();
+            for (sender, fault) in self.senders(recipient, STAGE) {
+                let mut messages = vec![commitments[sender]];
+                if fault == Some(DkgFault::EquivocateCommitment) {
+                    let mut conflicting = commitments[sender];
+                    conflicting[0] ^= 1;
+                    messages.push(conflicting);
+                }
+                for message in messages {
+                    if let Err(err) = participant.insert_commitment(sender, message) {
+                        let reason = DkgComplaintReason::Rejected(err);
+                        complaints.push(DkgComplaint::new(recipient, sender, STAGE, reason));
+                    }
+                }
+            }
+            DkgComplaint::push_missing(
+                complaints,
+                recipient,
+                STAGE,
+                participant.missing_commitments(),
+            );
+        }
+    }
+
+    fn exchange_polynomials<G: Group>(
+        &self,
+        participants: &mut [ParticipantCollectingPolynomials<G>],
+        complaints: &mut Vec<DkgComplaint>,
+    ) {
+        const STAGE: DkgStage = DkgStage::Polynomials;
+
+        let infos: Vec<_> = participants
+            .iter()
+            .map(|participant| participant.public_info().into_owned())
+            .collect();
+        for participant in participants {
+            let recipient = participant.index();
+            for (sender, fault) in self.senders(recipient, STAGE) {
+                let mut info = infos[sender].clone();
+                if fault == Some(DkgFault::MismatchedPolynomial) {
+                    info.polynomial[0] = info.polynomial[0] + G::generator();
+                }
+                if let Err(err) = participant.insert_public_polynomial(sender, info) {
+                    let reason = DkgComplaintReason::Rejected(err);
+                    complaints.push(DkgComplaint::new(recipient, sender, STAGE, reason));
+                }
+            }
+            let missing = participant.missing_public_polynomials();
+            DkgComplaint::push_missing(complaints, recipient, STAGE, missing);
+        }
+    }
+
+    fn exchange_secret_shares<G: Group>(
+        &self,
+        participants: &mut [ParticipantExchangingSecrets<G>],
+        complaints: &mut Vec<DkgComplaint>,
+        rng: &mut ChaCha8Rng,
+    ) {
+        const STAGE: DkgStage = DkgStage::SecretShares;
+
+        let shares: Vec<Vec<_>> = participants
+            .iter()
+            .map(|sender| {
+                (0..self.params.shares)
+                    .map(|i| sender.secret_share_for_participant(i))
+                    .collect()
+            })
+            .collect();
+        for participant in participants {
+            let recipient = participant.index();
+            for (sender, fault) in self.senders(recipient, STAGE) {
+                let share = if fault == Some(DkgFault::InvalidSecretShares) {
+                    SecretKey::generate(rng)
+                } else {
+                    shares[sender][recipient].clone()
+                };
+                if let Err(err) = participant.insert_secret_share(sender, share) {
+                    let reason = DkgComplaintReason::Rejected(err);
+                    complaints.push(DkgComplaint::new(recipient, sender, STAGE, reason));
+                }
+            }
+            DkgComplaint::push_missing(complaints, recipient, STAGE, participant.missing_shares());
+        }
+    }
+}
+
+/// Outcome of a [`DkgSimulation`].
+#[derive(Debug)]
+pub struct DkgOutcome<G: Group> {
+    complaints: Vec<DkgComplaint>,
+    aborted_at: Option<DkgStage>,
+    participants: Vec<ActiveParticipant<G>>,
+}
+
+impl<G: Group> DkgOutcome<G> {
+    fn aborted(stage: DkgStage, complaints: Vec<DkgComplaint>) -> Self {
+        Self {
+            complaints,
+            aborted_at: Some(stage),
+            participants: vec![],
+        }
+    }
+
+    /// Returns complaints raised by honest participants, ordered by the complainant index.
+    pub fn complaints(&self) -> &[DkgComplaint] {
+        &self.complaints
+    }
+
+    /// Returns the stage on which the protocol was aborted, or `None` if the protocol
+    /// has completed.
+    pub fn aborted_at(&self) -> Option<DkgStage> {
+        self.aborted_at
+    }
+
+    /// Returns 0-based indexes of disqualified participants (i.e., participants accused
+    /// in at least one complaint) in increasing order.
+    pub fn disqualified(&self) -> Vec<usize> {
+        let mut indexes: Vec<_> = self.complaints.iter().map(|c| c.accused).collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes
+    }
+
+    /// Returns honest participants that have completed the protocol, ordered by their index.
+    /// If the protocol was aborted, returns an empty slice.
+    pub fn participants(&self) -> &[ActiveParticipant<G>] {
+        &self.participants
+    }
+
+    /// Converts this outcome into honest participants that have completed the protocol.
+    pub fn into_participants(self) -> Vec<ActiveParticipant<G>> {
+        self.participants
+    }
+}
+
+/// Asserts that two values are equal using a constant-time comparison
+/// ([`ConstantTimeEq`]). Unlike [`assert_eq!`], this works for types that intentionally
+/// do not implement [`PartialEq`], such as [`SecretKey`]s.
+///
+/// # Panics
+///
+/// Panics if the values are not equal.
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, testkit::assert_ct_eq, Keypair};
+/// # use rand::thread_rng;
+/// let keypair = Keypair::<Ristretto>::generate(&mut thread_rng());
+/// let (public_key, secret_key) = keypair.clone().into_tuple();
+/// assert_ct_eq(&secret_key, keypair.secret());
+/// assert_ct_eq(&public_key, keypair.public());
+/// ```
+#[track_caller]
+pub fn assert_ct_eq<T: ConstantTimeEq + fmt::Debug + ?Sized>(lhs: &T, rhs: &T) {
+    assert!(
+        bool::from(lhs.ct_eq(rhs)),
+        "values are not equal: {lhs:?} vs {rhs:?}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{group::Ristretto, Ciphertext, DiscreteLogTable, Keypair};
+
+    #[test]
+    fn constant_time_equality() {
+        let mut rng = thread_rng();
+        let keypair = Keypair::<Ristretto>::generate(&mut rng);
+        let other = Keypair::<Ristretto>::generate(&mut rng);
+        assert_ct_eq(keypair.secret(), &keypair.secret().clone());
+        assert!(!bool::from(keypair.secret().ct_eq(other.secret())));
+        assert!(!bool::from(keypair.public().ct_eq(other.public())));
+
+        let ciphertext = keypair.public().encrypt(3_u64, &mut rng);
+        assert_ct_eq(&ciphertext, &ciphertext.clone());
+        let other_ciphertext = keypair.public().encrypt(3_u64, &mut rng);
+        assert!(!bool::from(ciphertext.ct_eq(&other_ciphertext)));
+    }
+
+    #[test]
+    fn generating_multi_choice_ballots() {
+        let (receiver, secret) = Keypair::<Ristretto>::generate(&mut thread_rng()).into_tuple();
+        let params = ChoiceParams::multi(receiver, 4);
+        let generator = BallotGenerator::new(params.clone(), 1);
+        let batch = generator.generate(15, 5);
+        assert_eq!(batch.ballots().len(), 20);
+        assert_eq!(batch.invalid_indexes().len(), 5);
+
+        let mut totals = vec![Ciphertext::zero(); 4];
+        for (i, ballot) in batch.ballots().iter().enumerate() {
+            let is_invalid = batch.invalid_indexes().contains(&i);
+            match ballot.verify(&params) {
+                Ok(choices) => {
+                    assert!(!is_invalid);
+                    for (total, &choice) in totals.iter_mut().zip(choices) {
+                        *total += choice;
+                    }
+                }
+                Err(_) => assert!(is_invalid),
+            }
+        }
+        let lookup_table = DiscreteLogTable::new(0..=15);
+        let tally: Vec<_> = totals
+            .into_iter()
+            .map(|total| secret.decrypt(total, &lookup_table).unwrap())
+            .collect();
+        assert_eq!(tally, batch.expected_tally());
+
+        let same_batch = generator.generate(15, 5);
+        assert_eq!(same_batch.invalid_indexes(), batch.invalid_indexes());
+        assert_eq!(
+            same_batch.ballots()[0].choices_unchecked()[0].to_bytes(),
+            batch.ballots()[0].choices_unchecked()[0].to_bytes()
+        );
+    }
+
+    #[test]
+    fn dkg_simulation_reports_faults_on_each_stage() {
+        let params = Params::new(5, 3);
+        let outcome = DkgSimulation::new(params, 7)
+            .corrupt(0, DkgFault::EquivocateCommitment)
+            .corrupt(2, DkgFault::Withhold(DkgStage::Commitments))
+            .run::<Ristretto>();
+        assert_eq!(outcome.aborted_at(), Some(DkgStage::Commitments));
+        assert_eq!(outcome.disqualified(), [0, 2]);
+        assert_eq!(outcome.complaints().len(), 6);
+        for complaint in outcome.complaints() {
+            assert!(DkgSimulation::new(params, 7)
+                .fault(complaint.complainant())
+                .is_none());
+            match (complaint.accused(), complaint.reason()) {
+                (0, DkgComplaintReason::Rejected(DkgError::Equivocation(0)))
+                | (2, DkgComplaintReason::Missing) => { /* OK */ }
+                other => panic!("unexpected complaint: {other:?}"),
+            }
+        }
+
+        let outcome = DkgSimulation::new(params, 7)
+            .corrupt(4, DkgFault::MismatchedPolynomial)
+            .run::<Ristretto>();
+        assert_eq!(outcome.aborted_at(), Some(DkgStage::Polynomials));
+        assert_eq!(outcome.disqualified(), [4]);
+        assert!(outcome.complaints().iter().all(|complaint| matches!(
+            complaint.reason(),
+            DkgComplaintReason::Rejected(DkgError::InvalidCommitment)
+        )));
+
+        let outcome = DkgSimulation::new(params, 7)
+            .corrupt(1, DkgFault::Withhold(DkgStage::SecretShares))
+            .corrupt(3, DkgFault::InvalidSecretShares)
+            .run::<Ristretto>();
+        assert_eq!(outcome.aborted_at(), Some(DkgStage::SecretShares));
+        assert_eq!(outcome.disqualified(), [1, 3]);
+        assert!(outcome.participants().is_empty());
+
+        let outcome = DkgSimulation::new(params, 7).run::<Ristretto>();
+        assert!(outcome.complaints().is_empty());
+        let participants = outcome.into_participants();
+        assert_eq!(participants.len(), 5);
+        let shared_key = participants[0].key_set().shared_key();
+        assert!(participants
+            .iter()
+            .all(|participant| participant.key_set().shared_key() == shared_key));
+    }
+}
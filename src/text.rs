@@ -0,0 +1,249 @@
+//! Human-readable text encoding for keys, ciphertexts and decryption shares.
+
+use sha2::{Digest, Sha256};
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+    alloc::{vec, Vec},
+    group::Group,
+    proto_labels, CandidateDecryption, Ciphertext, PublicKey, VerifiableDecryption,
+};
+
+/// Byte size of the checksum in the text encoding.
+const CHECKSUM_SIZE: usize = 4;
+
+fn checksum(prefix: &str, bytes: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let digest = Sha256::new()
+        .chain_update(proto_labels::TEXT_CHECKSUM)
+        .chain_update(prefix.as_bytes())
+        .chain_update(bytes)
+        .finalize();
+    let mut checksum = [0_u8; CHECKSUM_SIZE];
+    checksum.copy_from_slice(&digest[..CHECKSUM_SIZE]);
+    checksum
+}
+
+fn write_hex(formatter: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(formatter, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, TextDecodingError> {
+    fn decode_digit(digit: u8) -> Result<u8, TextDecodingError> {
+        match digit {
+            b'0'..=b'9' => Ok(digit - b'0'),
+            b'a'..=b'f' => Ok(digit - b'a' + 10),
+            b'A'..=b'F' => Ok(digit - b'A' + 10),
+            _ => Err(TextDecodingError::InvalidHex),
+        }
+    }
+
+    if hex.len() % 2 != 0 {
+        return Err(TextDecodingError::InvalidHex);
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((decode_digit(pair[0])? << 4) | decode_digit(pair[1])?))
+        .collect()
+}
+
+/// Writes `bytes` in the text encoding with the specified `prefix`.
+fn write_encoded(formatter: &mut fmt::Formatter<'_>, prefix: &str, bytes: &[u8]) -> fmt::Result {
+    write!(formatter, "{prefix}:")?;
+    write_hex(formatter, bytes)?;
+    formatter.write_str(":")?;
+    write_hex(formatter, &checksum(prefix, bytes))
+}
+
+/// Decodes bytes from the text encoding with the specified `prefix`.
+fn decode(s: &str, prefix: &str) -> Result<Vec<u8>, TextDecodingError> {
+    let payload = s
+        .strip_prefix(prefix)
+        .and_then(|s| s.strip_prefix(':'))
+        .ok_or(TextDecodingError::UnexpectedPrefix)?;
+    let (hex, checksum_hex) = payload
+        .split_once(':')
+        .ok_or(TextDecodingError::MissingChecksum)?;
+    let bytes = decode_hex(hex)?;
+    let expected_checksum = decode_hex(checksum_hex)?;
+    if expected_checksum == checksum(prefix, &bytes) {
+        Ok(bytes)
+    } else {
+        Err(TextDecodingError::ChecksumMismatch)
+    }
+}
+
+/// Errors that can occur when parsing objects from their text encoding.
+///
+/// # Text encoding
+///
+/// [`PublicKey`]s, [`Ciphertext`]s and decryption shares ([`VerifiableDecryption`]s
+/// and [`CandidateDecryption`]s) implement [`Display`](fmt::Display) and [`FromStr`]
+/// using the following human-readable encoding, which is the same for all [`Group`]s:
+///
+/// ```text
+/// {prefix}:{hex-encoded bytes}:{hex-encoded checksum}
+/// ```
+///
+/// Here, `prefix` identifies the object type (`pk` for public keys, `ct` for ciphertexts
+/// and `ds` for decryption shares), and bytes are the same as produced by the `to_bytes()`
+/// / `as_bytes()` method of the object. The checksum is the first 4 bytes of
+/// the SHA-256 digest of the prefix and bytes; it allows to catch truncation
+/// or accidental modification of the encoded object (but not a deliberate one).
+///
+/// # Examples
+///
+/// ```
+/// # use elastic_elgamal::{group::Ristretto, Ciphertext, Keypair, PublicKey};
+/// # use rand::thread_rng;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = thread_rng();
+/// let keypair = Keypair::<Ristretto>::generate(&mut rng);
+/// let encoded_key = keypair.public().to_string();
+/// assert!(encoded_key.starts_with("pk:"));
+/// let key: PublicKey<Ristretto> = encoded_key.parse()?;
+/// assert_eq!(key, *keypair.public());
+///
+/// let ciphertext = key.encrypt(5_u64, &mut rng);
+/// let encoded = ciphertext.to_string();
+/// let parsed: Ciphertext<Ristretto> = encoded.parse()?;
+/// assert_eq!(parsed.to_bytes(), ciphertext.to_bytes());
+/// // Truncation is detected.
+/// let truncated = &encoded[..encoded.len() - 1];
+/// assert!(truncated.parse::<Ciphertext<Ristretto>>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextDecodingError {
+    /// The string does not start with the prefix expected for the object type.
+    UnexpectedPrefix,
+    /// The string does not contain a checksum.
+    MissingChecksum,
+    /// The string contains an invalid hex encoding.
+    InvalidHex,
+    /// The checksum does not match the encoded bytes.
+    ChecksumMismatch,
+    /// Encoded bytes do not represent a valid object.
+    InvalidValue,
+}
+
+impl fmt::Display for TextDecodingError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::UnexpectedPrefix => "string does not start with the expected prefix",
+            Self::MissingChecksum => "string does not contain a checksum",
+            Self::InvalidHex => "invalid hex encoding",
+            Self::ChecksumMismatch => "checksum does not match the encoded bytes",
+            Self::InvalidValue => "encoded bytes do not represent a valid object",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TextDecodingError {}
+
+const PUBLIC_KEY_PREFIX: &str = "pk";
+const CIPHERTEXT_PREFIX: &str = "ct";
+const DECRYPTION_PREFIX: &str = "ds";
+
+impl<G: Group> fmt::Display for PublicKey<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_encoded(formatter, PUBLIC_KEY_PREFIX, self.as_bytes())
+    }
+}
+
+impl<G: Group> FromStr for PublicKey<G> {
+    type Err = TextDecodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode(s, PUBLIC_KEY_PREFIX)?;
+        Self::from_bytes(&bytes).map_err(|_| TextDecodingError::InvalidValue)
+    }
+}
+
+impl<G: Group> fmt::Display for Ciphertext<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_encoded(formatter, CIPHERTEXT_PREFIX, &self.to_bytes())
+    }
+}
+
+impl<G: Group> FromStr for Ciphertext<G> {
+    type Err = TextDecodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode(s, CIPHERTEXT_PREFIX)?;
+        Self::from_bytes(&bytes).ok_or(TextDecodingError::InvalidValue)
+    }
+}
+
+impl<G: Group> fmt::Display for VerifiableDecryption<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_encoded(formatter, DECRYPTION_PREFIX, &self.to_bytes())
+    }
+}
+
+impl<G: Group> fmt::Display for CandidateDecryption<G> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = vec![0_u8; G::ELEMENT_SIZE];
+        G::serialize_element(&self.dh_element(), &mut bytes);
+        write_encoded(formatter, DECRYPTION_PREFIX, &bytes)
+    }
+}
+
+/// Decryption shares are parsed as [`CandidateDecryption`]s since they need to be verified
+/// before use.
+impl<G: Group> FromStr for CandidateDecryption<G> {
+    type Err = TextDecodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode(s, DECRYPTION_PREFIX)?;
+        Self::from_bytes(&bytes).ok_or(TextDecodingError::InvalidValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{alloc::ToString, group::Ristretto, Keypair};
+
+    #[test]
+    fn text_encoding_roundtrip_and_errors() {
+        let mut rng = thread_rng();
+        let keypair = Keypair::<Ristretto>::generate(&mut rng);
+        let ciphertext = keypair.public().encrypt(3_u64, &mut rng);
+        let decryption =
+            VerifiableDecryption::<Ristretto>::from_element(*ciphertext.random_element());
+
+        let encoded_share = decryption.to_string();
+        assert!(encoded_share.starts_with("ds:"), "{encoded_share}");
+        let share: CandidateDecryption<Ristretto> = encoded_share.parse().unwrap();
+        assert_eq!(share.to_string(), encoded_share);
+
+        let encoded_key = keypair.public().to_string();
+        // Element (32 bytes) + checksum (4 bytes) in hex, plus the prefix and separators.
+        assert_eq!(encoded_key.len(), 3 + 64 + 1 + 8);
+        assert_eq!(
+            encoded_key.parse::<Ciphertext<Ristretto>>().unwrap_err(),
+            TextDecodingError::UnexpectedPrefix
+        );
+        let mut corrupted = encoded_key.clone().into_bytes();
+        corrupted[10] = if corrupted[10] == b'0' { b'1' } else { b'0' };
+        let corrupted = crate::alloc::String::from_utf8(corrupted).unwrap();
+        assert_eq!(
+            corrupted.parse::<PublicKey<Ristretto>>().unwrap_err(),
+            TextDecodingError::ChecksumMismatch
+        );
+        let (payload, _) = encoded_key.rsplit_once(':').unwrap();
+        assert_eq!(
+            payload.parse::<PublicKey<Ristretto>>().unwrap_err(),
+            TextDecodingError::MissingChecksum
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Two-party (2-of-2) threshold encryption with additive key shares.
+//!
+//! This is a lightweight alternative to the [`sharing`](crate::sharing) and [`dkg`](crate::dkg)
+//! modules for the common case when a key is co-held by two parties (e.g., a client
+//! and a server), and both of them are required to decrypt. Since the threshold is equal
+//! to the number of parties, there is no need for polynomials or Lagrange interpolation:
+//! the shared secret is the sum of the parties' secrets, `x = x_c + x_s`.
+//!
+//! # Protocol
+//!
+//! 1. **Key generation.** Each party generates a keypair and sends its public key share
+//!   together with a [`ProofOfPossession`] to the other party ([`KeyShareMessage`]).
+//!   The proof prevents a rogue-key attack, in which a party would choose its key share
+//!   based on the other party's share to control the shared key. The shared public key
+//!   is the sum of the public key shares. This is a single message per party, which can be
+//!   exchanged in parallel.
+//! 2. **Decryption.** A party sends its decryption share `[x_i]R` for a ciphertext `(R, B)`
+//!   together with a [`LogEqualityProof`] of its correctness. The receiving party verifies
+//!   the share and adds its own share to obtain the full decryption. Thus, co-decryption
+//!   requires a single message.
+//!
+//! Unlike in the [`dkg`](crate::dkg) module, parties do not commit to their key shares
+//! before exchanging them, so the party sending its message last may bias the distribution
+//! of the shared key (but cannot learn the corresponding secret). If this is a concern,
+//! the messages should be exchanged via an intermediary that only reveals them
+//! once both are received.
+//!
+//! # Examples
+//!
+//! ```
+//! # use elastic_elgamal::{group::Ristretto, two_party::*, DiscreteLogTable};
+//! # use rand::thread_rng;
+//! # use std::error::Error as StdError;
+//! # fn main() -> Result<(), Box<dyn StdError>> {
+//! let mut rng = thread_rng();
+//! let client = KeyGeneration::<Ristretto>::new(Role::Client, &mut rng);
+//! let server = KeyGeneration::<Ristretto>::new(Role::Server, &mut rng);
+//! let client_message = client.message(&mut rng);
+//! let server_message = server.message(&mut rng);
+//! let client = client.finalize(&server_message)?;
+//! let server = server.finalize(&client_message)?;
+//! assert_eq!(client.shared_key(), server.shared_key());
+//!
+//! let ciphertext = client.shared_key().encrypt(5_u64, &mut rng);
+//! // The server sends its decryption share to the client...
+//! let (share, proof) = server.decrypt_share(ciphertext, &mut rng);
+//! // ...which the client verifies and combines with its own share.
+//! let decryption = client.decrypt(ciphertext, share.into(), &proof)?;
+//! let lookup_table = DiscreteLogTable::new(0..10);
+//! assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(5));
+//! # Ok(())
+//! # }
+//! ```
+
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+
+use crate::{
+    group::Group, proofs::VerificationError, proto_labels, CandidateDecryption, Ciphertext,
+    Keypair, LogEqualityProof, ProofOfPossession, PublicKey, VerifiableDecryption,
+};
+
+/// Role of a party in the two-party protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Role {
+    /// Client party.
+    Client,
+    /// Server party.
+    Server,
+}
+
+impl Role {
+    /// Returns the role of the other party.
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            Self::Client => Self::Server,
+            Self::Server => Self::Client,
+        }
+    }
+
+    fn as_u64(self) -> u64 {
+        match self {
+            Self::Client => 0,
+            Self::Server => 1,
+        }
+    }
+}
+
+/// Errors that can occur during two-party key generation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The other party has the same [`Role`] as this party.
+    RoleMismatch,
+    /// Proof of possession for the public key share of the other party does not verify.
+    InvalidProofOfPossession(VerificationError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RoleMismatch => {
+                formatter.write_str("the other party has the same role as this party")
+            }
+            Self::InvalidProofOfPossession(err) => write!(
+                formatter,
+                "proof of possession for the other party's key share is invalid: {err}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidProofOfPossession(err) => Some(err),
+            Self::RoleMismatch => None,
+        }
+    }
+}
+
+/// Message sent by a party during [`KeyGeneration`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct KeyShareMessage<G: Group> {
+    role: Role,
+    public_key: PublicKey<G>,
+    proof: ProofOfPossession<G>,
+}
+
+impl<G: Group> KeyShareMessage<G> {
+    /// Returns the role of the party that has produced this message.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Returns the public key share of the party.
+    pub fn public_key(&self) -> &PublicKey<G> {
+        &self.public_key
+    }
+}
+
+fn key_transcript(role: Role) -> Transcript {
+    let mut transcript = Transcript::new(proto_labels::TWO_PARTY_KEY);
+    transcript.append_u64(b"role", role.as_u64());
+    transcript
+}
+
+/// State of a party during two-party key generation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct KeyGeneration<G: Group> {
+    role: Role,
+    keypair: Keypair<G>,
+}
+
+impl<G: Group> KeyGeneration<G> {
+    /// Generates a key share for a party with the specified `role`.
+    pub fn new<R: CryptoRng + RngCore>(role: Role, rng: &mut R) -> Self {
+        Self {
+            role,
+            keypair: Keypair::generate(rng),
+        }
+    }
+
+    /// Returns the role of this party.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Creates a message for the other party.
+    pub fn message<R: CryptoRng + RngCore>(&self, rng: &mut R) -> KeyShareMessage<G> {
+        let proof = ProofOfPossession::new(
+            core::slice::from_ref(&self.keypair),
+            &mut key_transcript(self.role),
+            rng,
+        );
+        KeyShareMessage {
+            role: self.role,
+            public_key: self.keypair.public().clone(),
+            proof,
+        }
+    }
+
+    /// Completes key generation using the message from the other party.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the other party has the same role, or if its proof of possession
+    /// does not verify.
+    pub fn finalize(self, other: &KeyShareMessage<G>) -> Result<TwoPartyKey<G>, Error> {
+        if other.role == self.role {
+            return Err(Error::RoleMismatch);
+        }
+        other
+            .proof
+            .verify(
+                core::iter::once(&other.public_key),
+                &mut key_transcript(other.role),
+            )
+            .map_err(Error::InvalidProofOfPossession)?;
+
+        let shared_key = self.keypair.public().clone() + other.public_key.clone();
+        Ok(TwoPartyKey {
+            role: self.role,
+            keypair: self.keypair,
+            other_key: other.public_key.clone(),
+            shared_key,
+        })
+    }
+}
+
+/// Key share of a party in the two-party threshold encryption scheme, obtained
+/// after [`KeyGeneration`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TwoPartyKey<G: Group> {
+    role: Role,
+    keypair: Keypair<G>,
+    other_key: PublicKey<G>,
+    shared_key: PublicKey<G>,
+}
+
+impl<G: Group> TwoPartyKey<G> {
+    /// Returns the role of this party.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Returns the shared public key, for which ciphertexts should be encrypted.
+    pub fn shared_key(&self) -> &PublicKey<G> {
+        &self.shared_key
+    }
+
+    /// Returns the public key share of this party.
+    pub fn public_key_share(&self) -> &PublicKey<G> {
+        self.keypair.public()
+    }
+
+    /// Returns the public key share of the other party.
+    pub fn other_key_share(&self) -> &PublicKey<G> {
+        &self.other_key
+    }
+
+    fn decryption_transcript(&self, role: Role) -> Transcript {
+        let mut transcript = Transcript::new(proto_labels::TWO_PARTY_DECRYPTION);
+        transcript.append_message(b"K", self.shared_key.as_bytes());
+        transcript.append_u64(b"role", role.as_u64());
+        transcript
+    }
+
+    /// Creates a decryption share for the specified `ciphertext` together with a proof
+    /// of its validity. The share should be sent to the other party, which can use it
+    /// in [`Self::decrypt()`].
+    pub fn decrypt_share<R: CryptoRng + RngCore>(
+        &self,
+        ciphertext: Ciphertext<G>,
+        rng: &mut R,
+    ) -> (VerifiableDecryption<G>, LogEqualityProof<G>) {
+        let dh_element = ciphertext.random_element * self.keypair.secret().expose_scalar();
+        let proof = LogEqualityProof::new(
+            &PublicKey::from_element(ciphertext.random_element),
+            self.keypair.secret(),
+            (self.keypair.public().as_element(), dh_element),
+            &mut self.decryption_transcript(self.role),
+            rng,
+        );
+        (VerifiableDecryption::from_element(dh_element), proof)
+    }
+
+    /// Verifies a decryption share for `ciphertext` provided by the other party.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn verify_share(
+        &self,
+        share: CandidateDecryption<G>,
+        ciphertext: Ciphertext<G>,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, VerificationError> {
+        let dh_element = share.dh_element();
+        proof.verify(
+            &PublicKey::from_element(ciphertext.random_element),
+            (self.other_key.as_element(), dh_element),
+            &mut self.decryption_transcript(self.role.other()),
+        )?;
+        Ok(VerifiableDecryption::from_element(dh_element))
+    }
+
+    /// Verifies a decryption share for `ciphertext` provided by the other party and combines it
+    /// with the share of this party, thus obtaining the full decryption.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `proof` does not verify.
+    pub fn decrypt(
+        &self,
+        ciphertext: Ciphertext<G>,
+        other_share: CandidateDecryption<G>,
+        proof: &LogEqualityProof<G>,
+    ) -> Result<VerifiableDecryption<G>, VerificationError> {
+        let other_share = self.verify_share(other_share, ciphertext, proof)?;
+        let our_element = ciphertext.random_element * self.keypair.secret().expose_scalar();
+        Ok(VerifiableDecryption::from_element(
+            our_element + *other_share.as_element(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::{group::Ristretto, DiscreteLogTable};
+
+    #[test]
+    fn two_party_key_generation_and_decryption() {
+        let mut rng = thread_rng();
+        let client = KeyGeneration::<Ristretto>::new(Role::Client, &mut rng);
+        let server = KeyGeneration::<Ristretto>::new(Role::Server, &mut rng);
+        let client_message = client.message(&mut rng);
+        let server_message = server.message(&mut rng);
+
+        let other_client = KeyGeneration::<Ristretto>::new(Role::Client, &mut rng);
+        let err = other_client.finalize(&client_message).unwrap_err();
+        assert!(matches!(err, Error::RoleMismatch), "{err:?}");
+        let mut forged_message = server_message.clone();
+        forged_message.public_key = client_message.public_key.clone();
+        let err = client.clone().finalize(&forged_message).unwrap_err();
+        assert!(matches!(err, Error::InvalidProofOfPossession(_)), "{err:?}");
+
+        let client = client.finalize(&server_message).unwrap();
+        let server = server.finalize(&client_message).unwrap();
+        assert_eq!(client.shared_key(), server.shared_key());
+        assert_eq!(client.other_key_share(), server.public_key_share());
+
+        let lookup_table = DiscreteLogTable::new(0..10);
+        let ciphertext = client.shared_key().encrypt(7_u64, &mut rng);
+        let (share, proof) = server.decrypt_share(ciphertext, &mut rng);
+        let decryption = client.decrypt(ciphertext, share.into(), &proof).unwrap();
+        assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(7));
+
+        // The server's share cannot be passed off as the client's one.
+        assert!(server
+            .verify_share(share.into(), ciphertext, &proof)
+            .is_err());
+        let (share, proof) = client.decrypt_share(ciphertext, &mut rng);
+        let decryption = server.decrypt(ciphertext, share.into(), &proof).unwrap();
+        assert_eq!(decryption.decrypt(ciphertext, &lookup_table), Some(7));
+    }
+}
@@ -0,0 +1,399 @@
+// Toy elliptic curve for compile-time checks of `Generic` curves, included into doctests.
+//
+// The curve delegates all arithmetic to secp256k1, but declares `TOY_NUM_BITS` as the bit length
+// of its scalar field. `TOY_NUM_BITS` must be defined by the including code, as well as
+// the `PrimeCurve` implementation for `ToyCurve` if necessary.
+
+use core::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, ShrAssign, Sub, SubAssign},
+};
+use elliptic_curve::{
+    bigint::U256,
+    ff::{Field, PrimeField},
+    group,
+    ops::{Invert, LinearCombination, MulByGenerator, Reduce},
+    rand_core::RngCore,
+    scalar::{FromUintUnchecked, IsHigh},
+    sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    zeroize::DefaultIsZeroes,
+    Curve, CurveArithmetic, FieldBytes, FieldBytesEncoding, ScalarPrimitive,
+};
+use k256::{AffinePoint, ProjectivePoint, Scalar, Secp256k1};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ToyCurve;
+
+impl Curve for ToyCurve {
+    type FieldBytesSize = <Secp256k1 as Curve>::FieldBytesSize;
+    type Uint = U256;
+    const ORDER: U256 = Secp256k1::ORDER;
+}
+
+impl FieldBytesEncoding<ToyCurve> for U256 {}
+
+impl CurveArithmetic for ToyCurve {
+    type AffinePoint = AffinePoint;
+    type ProjectivePoint = ToyPoint;
+    type Scalar = ToyScalar;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd)]
+pub struct ToyScalar(Scalar);
+
+macro_rules! impl_ops {
+    ($ty:ident, $rhs:ident) => {
+        impl Add<$rhs> for $ty {
+            type Output = Self;
+            fn add(self, rhs: $rhs) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+        impl Add<&$rhs> for $ty {
+            type Output = Self;
+            fn add(self, rhs: &$rhs) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+        impl Sub<$rhs> for $ty {
+            type Output = Self;
+            fn sub(self, rhs: $rhs) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+        impl Sub<&$rhs> for $ty {
+            type Output = Self;
+            fn sub(self, rhs: &$rhs) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+        impl AddAssign<$rhs> for $ty {
+            fn add_assign(&mut self, rhs: $rhs) {
+                self.0 += rhs.0;
+            }
+        }
+        impl AddAssign<&$rhs> for $ty {
+            fn add_assign(&mut self, rhs: &$rhs) {
+                self.0 += rhs.0;
+            }
+        }
+        impl SubAssign<$rhs> for $ty {
+            fn sub_assign(&mut self, rhs: $rhs) {
+                self.0 -= rhs.0;
+            }
+        }
+        impl SubAssign<&$rhs> for $ty {
+            fn sub_assign(&mut self, rhs: &$rhs) {
+                self.0 -= rhs.0;
+            }
+        }
+        impl Mul<ToyScalar> for $ty {
+            type Output = Self;
+            fn mul(self, rhs: ToyScalar) -> Self {
+                Self(self.0 * rhs.0)
+            }
+        }
+        impl Mul<&ToyScalar> for $ty {
+            type Output = Self;
+            fn mul(self, rhs: &ToyScalar) -> Self {
+                Self(self.0 * rhs.0)
+            }
+        }
+        impl MulAssign<ToyScalar> for $ty {
+            fn mul_assign(&mut self, rhs: ToyScalar) {
+                self.0 *= rhs.0;
+            }
+        }
+        impl MulAssign<&ToyScalar> for $ty {
+            fn mul_assign(&mut self, rhs: &ToyScalar) {
+                self.0 *= rhs.0;
+            }
+        }
+        impl Neg for $ty {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+        impl Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|x| x.0).sum())
+            }
+        }
+        impl<'a> Sum<&'a $ty> for $ty {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                Self(iter.map(|x| x.0).sum())
+            }
+        }
+        impl ConditionallySelectable for $ty {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                Self(ConditionallySelectable::conditional_select(&a.0, &b.0, choice))
+            }
+        }
+        impl ConstantTimeEq for $ty {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+        impl DefaultIsZeroes for $ty {}
+    };
+}
+
+impl_ops!(ToyScalar, ToyScalar);
+impl_ops!(ToyPoint, ToyPoint);
+
+impl Product for ToyScalar {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|x| x.0).product())
+    }
+}
+
+impl<'a> Product<&'a ToyScalar> for ToyScalar {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        Self(iter.map(|x| x.0).product())
+    }
+}
+
+impl AsRef<ToyScalar> for ToyScalar {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl From<u64> for ToyScalar {
+    fn from(value: u64) -> Self {
+        Self(Scalar::from(value))
+    }
+}
+
+impl Field for ToyScalar {
+    const ZERO: Self = Self(Scalar::ZERO);
+    const ONE: Self = Self(Scalar::ONE);
+
+    fn random(rng: impl RngCore) -> Self {
+        Self(Scalar::random(rng))
+    }
+
+    fn square(&self) -> Self {
+        Self(self.0.square())
+    }
+
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        Field::invert(&self.0).map(Self)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        let (is_square, root) = Scalar::sqrt_ratio(&num.0, &div.0);
+        (is_square, Self(root))
+    }
+}
+
+impl PrimeField for ToyScalar {
+    type Repr = FieldBytes<ToyCurve>;
+
+    const MODULUS: &'static str = Scalar::MODULUS;
+    const NUM_BITS: u32 = TOY_NUM_BITS;
+    const CAPACITY: u32 = TOY_NUM_BITS - 1;
+    const TWO_INV: Self = Self(Scalar::TWO_INV);
+    const MULTIPLICATIVE_GENERATOR: Self = Self(Scalar::MULTIPLICATIVE_GENERATOR);
+    const S: u32 = Scalar::S;
+    const ROOT_OF_UNITY: Self = Self(Scalar::ROOT_OF_UNITY);
+    const ROOT_OF_UNITY_INV: Self = Self(Scalar::ROOT_OF_UNITY_INV);
+    const DELTA: Self = Self(Scalar::DELTA);
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        Scalar::from_repr(repr).map(Self)
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        self.0.to_repr()
+    }
+
+    fn is_odd(&self) -> Choice {
+        self.0.is_odd()
+    }
+}
+
+impl From<ScalarPrimitive<ToyCurve>> for ToyScalar {
+    fn from(value: ScalarPrimitive<ToyCurve>) -> Self {
+        Self(Scalar::from_uint_unchecked(*value.as_uint()))
+    }
+}
+
+impl From<ToyScalar> for ScalarPrimitive<ToyCurve> {
+    fn from(value: ToyScalar) -> Self {
+        ScalarPrimitive::new(value.into()).unwrap()
+    }
+}
+
+impl From<ToyScalar> for FieldBytes<ToyCurve> {
+    fn from(value: ToyScalar) -> Self {
+        value.0.to_repr()
+    }
+}
+
+impl From<ToyScalar> for U256 {
+    fn from(value: ToyScalar) -> Self {
+        value.0.into()
+    }
+}
+
+impl FromUintUnchecked for ToyScalar {
+    type Uint = U256;
+
+    fn from_uint_unchecked(uint: U256) -> Self {
+        Self(Scalar::from_uint_unchecked(uint))
+    }
+}
+
+impl Invert for ToyScalar {
+    type Output = CtOption<Self>;
+
+    fn invert(&self) -> CtOption<Self> {
+        Field::invert(self)
+    }
+}
+
+impl IsHigh for ToyScalar {
+    fn is_high(&self) -> Choice {
+        self.0.is_high()
+    }
+}
+
+impl Reduce<U256> for ToyScalar {
+    type Bytes = FieldBytes<ToyCurve>;
+
+    fn reduce(n: U256) -> Self {
+        Self(<Scalar as Reduce<U256>>::reduce(n))
+    }
+
+    fn reduce_bytes(bytes: &Self::Bytes) -> Self {
+        Self(<Scalar as Reduce<U256>>::reduce_bytes(bytes))
+    }
+}
+
+impl ShrAssign<usize> for ToyScalar {
+    fn shr_assign(&mut self, rhs: usize) {
+        self.0 >>= rhs;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToyPoint(ProjectivePoint);
+
+impl Add<AffinePoint> for ToyPoint {
+    type Output = Self;
+    fn add(self, rhs: AffinePoint) -> Self {
+        Self(self.0 + rhs)
+    }
+}
+
+impl Add<&AffinePoint> for ToyPoint {
+    type Output = Self;
+    fn add(self, rhs: &AffinePoint) -> Self {
+        Self(self.0 + rhs)
+    }
+}
+
+impl Sub<AffinePoint> for ToyPoint {
+    type Output = Self;
+    fn sub(self, rhs: AffinePoint) -> Self {
+        Self(self.0 - rhs)
+    }
+}
+
+impl Sub<&AffinePoint> for ToyPoint {
+    type Output = Self;
+    fn sub(self, rhs: &AffinePoint) -> Self {
+        Self(self.0 - rhs)
+    }
+}
+
+impl AddAssign<AffinePoint> for ToyPoint {
+    fn add_assign(&mut self, rhs: AffinePoint) {
+        self.0 += rhs;
+    }
+}
+
+impl AddAssign<&AffinePoint> for ToyPoint {
+    fn add_assign(&mut self, rhs: &AffinePoint) {
+        self.0 += rhs;
+    }
+}
+
+impl SubAssign<AffinePoint> for ToyPoint {
+    fn sub_assign(&mut self, rhs: AffinePoint) {
+        self.0 -= rhs;
+    }
+}
+
+impl SubAssign<&AffinePoint> for ToyPoint {
+    fn sub_assign(&mut self, rhs: &AffinePoint) {
+        self.0 -= rhs;
+    }
+}
+
+impl group::Group for ToyPoint {
+    type Scalar = ToyScalar;
+
+    fn random(rng: impl RngCore) -> Self {
+        Self(ProjectivePoint::random(rng))
+    }
+
+    fn identity() -> Self {
+        Self(ProjectivePoint::IDENTITY)
+    }
+
+    fn generator() -> Self {
+        Self(ProjectivePoint::GENERATOR)
+    }
+
+    fn is_identity(&self) -> Choice {
+        self.0.is_identity()
+    }
+
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+}
+
+impl group::Curve for ToyPoint {
+    type AffineRepr = AffinePoint;
+
+    fn to_affine(&self) -> AffinePoint {
+        self.0.to_affine()
+    }
+}
+
+impl From<AffinePoint> for ToyPoint {
+    fn from(point: AffinePoint) -> Self {
+        Self(point.into())
+    }
+}
+
+impl From<ToyPoint> for AffinePoint {
+    fn from(point: ToyPoint) -> Self {
+        point.0.into()
+    }
+}
+
+impl LinearCombination for ToyPoint {}
+
+impl MulByGenerator for ToyPoint {}
+
+impl ToEncodedPoint<ToyCurve> for ToyPoint {
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<ToyCurve> {
+        self.0.to_encoded_point(compress)
+    }
+}
+
+impl FromEncodedPoint<ToyCurve> for ToyPoint {
+    fn from_encoded_point(point: &EncodedPoint<ToyCurve>) -> CtOption<Self> {
+        ProjectivePoint::from_encoded_point(point).map(Self)
+    }
+}
@@ -18,7 +18,7 @@ fn complete_dkg<G: Group, R: RngCore + CryptoRng>(params: Params, rng: &mut R) {
     for (i, participant) in participants.iter_mut().enumerate() {
         for (j, &commitment) in commitments.iter().enumerate() {
             if i != j {
-                participant.insert_commitment(j, commitment);
+                participant.insert_commitment(j, commitment).unwrap();
             }
         }
     }
@@ -8,8 +8,9 @@ use rand_core::{RngCore, SeedableRng};
 use elastic_elgamal::{
     app::{ChoiceParams, EncryptedChoice, QuadraticVotingBallot, QuadraticVotingParams},
     group::{Generic, Group, Ristretto},
-    Ciphertext, CiphertextWithValue, CommitmentEquivalenceProof, Keypair, RangeDecomposition,
-    SecretKey, SumOfSquaresProof,
+    sharing::PublicKeySet,
+    Ciphertext, CiphertextWithValue, CommitmentEquivalenceProof, Keypair, LogEqualityProof,
+    ProofOfPossession, RangeDecomposition, SecretKey, SumOfSquaresProof, VerifiableDecryption,
 };
 use merlin::Transcript;
 
@@ -189,6 +190,31 @@ fn test_commitment_equivalence_snapshot<G: Group + Named>(blinding_base: G::Elem
     assert_yaml_snapshot!(full_name, commitment_with_proof);
 }
 
+/// Checks that participant proofs produced before key set epochs were introduced
+/// (i.e., by elastic-elgamal 0.3.0) still verify.
+fn test_pre_epoch_shares<G: Group + Named>() {
+    let path = format!(
+        "{}/tests/fixtures/pre-epoch-shares-{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        G::NAME
+    );
+    let fixture = std::fs::read_to_string(path).unwrap();
+    let fixture: serde_json::Value = serde_json::from_str(&fixture).unwrap();
+    let field = |name: &str| fixture[name].clone();
+
+    let key_set: PublicKeySet<G> = serde_json::from_value(field("key_set")).unwrap();
+    let participant_proof: ProofOfPossession<G> =
+        serde_json::from_value(field("participant_proof")).unwrap();
+    key_set.verify_participant(1, &participant_proof).unwrap();
+
+    let ciphertext: Ciphertext<G> = serde_json::from_value(field("ciphertext")).unwrap();
+    let share: VerifiableDecryption<G> = serde_json::from_value(field("share")).unwrap();
+    let share_proof: LogEqualityProof<G> = serde_json::from_value(field("share_proof")).unwrap();
+    key_set
+        .verify_share(share.into(), ciphertext, 1, &share_proof)
+        .unwrap();
+}
+
 mod ristretto {
     use super::*;
     use elastic_elgamal::group::ElementOps;
@@ -259,6 +285,11 @@ mod ristretto {
         let blinding_base = Ristretto::deserialize_element(BLINDING_BASE).unwrap();
         test_commitment_equivalence_snapshot::<Ristretto>(blinding_base);
     }
+
+    #[test]
+    fn pre_epoch_shares() {
+        test_pre_epoch_shares::<Ristretto>();
+    }
 }
 
 mod k256 {
@@ -337,4 +368,9 @@ mod k256 {
         };
         test_commitment_equivalence_snapshot::<K256>(blinding_base);
     }
+
+    #[test]
+    fn pre_epoch_shares() {
+        test_pre_epoch_shares::<K256>();
+    }
 }